@@ -17,6 +17,8 @@ echo libm
 cargo check --no-default-features --features libm
 echo micromath
 cargo check --no-default-features --features micromath
+echo uom
+cargo check --no-default-features --features uom
 echo alloc devices
 cargo check --no-default-features --features alloc,devices
 echo alloc dim_check_debug
@@ -27,6 +29,8 @@ echo alloc libm
 cargo check --no-default-features --features alloc,libm
 echo alloc micromath
 cargo check --no-default-features --features alloc,micromath
+echo alloc uom
+cargo check --no-default-features --features alloc,uom
 echo std devices
 cargo check --no-default-features --features std,devices
 echo std dim_check_debug
@@ -37,6 +41,8 @@ echo std libm
 cargo check --no-default-features --features std,libm
 echo std micromath
 cargo check --no-default-features --features std,micromath
+echo std uom
+cargo check --no-default-features --features std,uom
 echo devices dim_check_debug
 cargo check --no-default-features --features devices,dim_check_debug
 echo devices dim_check_release
@@ -45,16 +51,26 @@ echo devices libm
 cargo check --no-default-features --features devices,libm
 echo devices micromath
 cargo check --no-default-features --features devices,micromath
+echo devices uom
+cargo check --no-default-features --features devices,uom
 echo dim_check_debug libm
 cargo check --no-default-features --features dim_check_debug,libm
 echo dim_check_debug micromath
 cargo check --no-default-features --features dim_check_debug,micromath
+echo dim_check_debug uom
+cargo check --no-default-features --features dim_check_debug,uom
 echo dim_check_release libm
 cargo check --no-default-features --features dim_check_release,libm
 echo dim_check_release micromath
 cargo check --no-default-features --features dim_check_release,micromath
+echo dim_check_release uom
+cargo check --no-default-features --features dim_check_release,uom
 echo libm micromath
 cargo check --no-default-features --features libm,micromath
+echo libm uom
+cargo check --no-default-features --features libm,uom
+echo micromath uom
+cargo check --no-default-features --features micromath,uom
 echo alloc devices dim_check_debug
 cargo check --no-default-features --features alloc,devices,dim_check_debug
 echo alloc devices dim_check_release
@@ -63,16 +79,26 @@ echo alloc devices libm
 cargo check --no-default-features --features alloc,devices,libm
 echo alloc devices micromath
 cargo check --no-default-features --features alloc,devices,micromath
+echo alloc devices uom
+cargo check --no-default-features --features alloc,devices,uom
 echo alloc dim_check_debug libm
 cargo check --no-default-features --features alloc,dim_check_debug,libm
 echo alloc dim_check_debug micromath
 cargo check --no-default-features --features alloc,dim_check_debug,micromath
+echo alloc dim_check_debug uom
+cargo check --no-default-features --features alloc,dim_check_debug,uom
 echo alloc dim_check_release libm
 cargo check --no-default-features --features alloc,dim_check_release,libm
 echo alloc dim_check_release micromath
 cargo check --no-default-features --features alloc,dim_check_release,micromath
+echo alloc dim_check_release uom
+cargo check --no-default-features --features alloc,dim_check_release,uom
 echo alloc libm micromath
 cargo check --no-default-features --features alloc,libm,micromath
+echo alloc libm uom
+cargo check --no-default-features --features alloc,libm,uom
+echo alloc micromath uom
+cargo check --no-default-features --features alloc,micromath,uom
 echo std devices dim_check_debug
 cargo check --no-default-features --features std,devices,dim_check_debug
 echo std devices dim_check_release
@@ -81,67 +107,185 @@ echo std devices libm
 cargo check --no-default-features --features std,devices,libm
 echo std devices micromath
 cargo check --no-default-features --features std,devices,micromath
+echo std devices uom
+cargo check --no-default-features --features std,devices,uom
 echo std dim_check_debug libm
 cargo check --no-default-features --features std,dim_check_debug,libm
 echo std dim_check_debug micromath
 cargo check --no-default-features --features std,dim_check_debug,micromath
+echo std dim_check_debug uom
+cargo check --no-default-features --features std,dim_check_debug,uom
 echo std dim_check_release libm
 cargo check --no-default-features --features std,dim_check_release,libm
 echo std dim_check_release micromath
 cargo check --no-default-features --features std,dim_check_release,micromath
+echo std dim_check_release uom
+cargo check --no-default-features --features std,dim_check_release,uom
 echo std libm micromath
 cargo check --no-default-features --features std,libm,micromath
+echo std libm uom
+cargo check --no-default-features --features std,libm,uom
+echo std micromath uom
+cargo check --no-default-features --features std,micromath,uom
 echo devices dim_check_debug libm
 cargo check --no-default-features --features devices,dim_check_debug,libm
 echo devices dim_check_debug micromath
 cargo check --no-default-features --features devices,dim_check_debug,micromath
+echo devices dim_check_debug uom
+cargo check --no-default-features --features devices,dim_check_debug,uom
 echo devices dim_check_release libm
 cargo check --no-default-features --features devices,dim_check_release,libm
 echo devices dim_check_release micromath
 cargo check --no-default-features --features devices,dim_check_release,micromath
+echo devices dim_check_release uom
+cargo check --no-default-features --features devices,dim_check_release,uom
 echo devices libm micromath
 cargo check --no-default-features --features devices,libm,micromath
+echo devices libm uom
+cargo check --no-default-features --features devices,libm,uom
+echo devices micromath uom
+cargo check --no-default-features --features devices,micromath,uom
 echo dim_check_debug libm micromath
 cargo check --no-default-features --features dim_check_debug,libm,micromath
+echo dim_check_debug libm uom
+cargo check --no-default-features --features dim_check_debug,libm,uom
+echo dim_check_debug micromath uom
+cargo check --no-default-features --features dim_check_debug,micromath,uom
 echo dim_check_release libm micromath
 cargo check --no-default-features --features dim_check_release,libm,micromath
+echo dim_check_release libm uom
+cargo check --no-default-features --features dim_check_release,libm,uom
+echo dim_check_release micromath uom
+cargo check --no-default-features --features dim_check_release,micromath,uom
+echo libm micromath uom
+cargo check --no-default-features --features libm,micromath,uom
 echo alloc devices dim_check_debug libm
 cargo check --no-default-features --features alloc,devices,dim_check_debug,libm
 echo alloc devices dim_check_debug micromath
 cargo check --no-default-features --features alloc,devices,dim_check_debug,micromath
+echo alloc devices dim_check_debug uom
+cargo check --no-default-features --features alloc,devices,dim_check_debug,uom
 echo alloc devices dim_check_release libm
 cargo check --no-default-features --features alloc,devices,dim_check_release,libm
 echo alloc devices dim_check_release micromath
 cargo check --no-default-features --features alloc,devices,dim_check_release,micromath
+echo alloc devices dim_check_release uom
+cargo check --no-default-features --features alloc,devices,dim_check_release,uom
 echo alloc devices libm micromath
 cargo check --no-default-features --features alloc,devices,libm,micromath
+echo alloc devices libm uom
+cargo check --no-default-features --features alloc,devices,libm,uom
+echo alloc devices micromath uom
+cargo check --no-default-features --features alloc,devices,micromath,uom
 echo alloc dim_check_debug libm micromath
 cargo check --no-default-features --features alloc,dim_check_debug,libm,micromath
+echo alloc dim_check_debug libm uom
+cargo check --no-default-features --features alloc,dim_check_debug,libm,uom
+echo alloc dim_check_debug micromath uom
+cargo check --no-default-features --features alloc,dim_check_debug,micromath,uom
 echo alloc dim_check_release libm micromath
 cargo check --no-default-features --features alloc,dim_check_release,libm,micromath
+echo alloc dim_check_release libm uom
+cargo check --no-default-features --features alloc,dim_check_release,libm,uom
+echo alloc dim_check_release micromath uom
+cargo check --no-default-features --features alloc,dim_check_release,micromath,uom
+echo alloc libm micromath uom
+cargo check --no-default-features --features alloc,libm,micromath,uom
 echo std devices dim_check_debug libm
 cargo check --no-default-features --features std,devices,dim_check_debug,libm
 echo std devices dim_check_debug micromath
 cargo check --no-default-features --features std,devices,dim_check_debug,micromath
+echo std devices dim_check_debug uom
+cargo check --no-default-features --features std,devices,dim_check_debug,uom
 echo std devices dim_check_release libm
 cargo check --no-default-features --features std,devices,dim_check_release,libm
 echo std devices dim_check_release micromath
 cargo check --no-default-features --features std,devices,dim_check_release,micromath
+echo std devices dim_check_release uom
+cargo check --no-default-features --features std,devices,dim_check_release,uom
 echo std devices libm micromath
 cargo check --no-default-features --features std,devices,libm,micromath
+echo std devices libm uom
+cargo check --no-default-features --features std,devices,libm,uom
+echo std devices micromath uom
+cargo check --no-default-features --features std,devices,micromath,uom
 echo std dim_check_debug libm micromath
 cargo check --no-default-features --features std,dim_check_debug,libm,micromath
+echo std dim_check_debug libm uom
+cargo check --no-default-features --features std,dim_check_debug,libm,uom
+echo std dim_check_debug micromath uom
+cargo check --no-default-features --features std,dim_check_debug,micromath,uom
 echo std dim_check_release libm micromath
 cargo check --no-default-features --features std,dim_check_release,libm,micromath
+echo std dim_check_release libm uom
+cargo check --no-default-features --features std,dim_check_release,libm,uom
+echo std dim_check_release micromath uom
+cargo check --no-default-features --features std,dim_check_release,micromath,uom
+echo std libm micromath uom
+cargo check --no-default-features --features std,libm,micromath,uom
 echo devices dim_check_debug libm micromath
 cargo check --no-default-features --features devices,dim_check_debug,libm,micromath
+echo devices dim_check_debug libm uom
+cargo check --no-default-features --features devices,dim_check_debug,libm,uom
+echo devices dim_check_debug micromath uom
+cargo check --no-default-features --features devices,dim_check_debug,micromath,uom
 echo devices dim_check_release libm micromath
 cargo check --no-default-features --features devices,dim_check_release,libm,micromath
+echo devices dim_check_release libm uom
+cargo check --no-default-features --features devices,dim_check_release,libm,uom
+echo devices dim_check_release micromath uom
+cargo check --no-default-features --features devices,dim_check_release,micromath,uom
+echo devices libm micromath uom
+cargo check --no-default-features --features devices,libm,micromath,uom
+echo dim_check_debug libm micromath uom
+cargo check --no-default-features --features dim_check_debug,libm,micromath,uom
+echo dim_check_release libm micromath uom
+cargo check --no-default-features --features dim_check_release,libm,micromath,uom
 echo alloc devices dim_check_debug libm micromath
 cargo check --no-default-features --features alloc,devices,dim_check_debug,libm,micromath
+echo alloc devices dim_check_debug libm uom
+cargo check --no-default-features --features alloc,devices,dim_check_debug,libm,uom
+echo alloc devices dim_check_debug micromath uom
+cargo check --no-default-features --features alloc,devices,dim_check_debug,micromath,uom
 echo alloc devices dim_check_release libm micromath
 cargo check --no-default-features --features alloc,devices,dim_check_release,libm,micromath
+echo alloc devices dim_check_release libm uom
+cargo check --no-default-features --features alloc,devices,dim_check_release,libm,uom
+echo alloc devices dim_check_release micromath uom
+cargo check --no-default-features --features alloc,devices,dim_check_release,micromath,uom
+echo alloc devices libm micromath uom
+cargo check --no-default-features --features alloc,devices,libm,micromath,uom
+echo alloc dim_check_debug libm micromath uom
+cargo check --no-default-features --features alloc,dim_check_debug,libm,micromath,uom
+echo alloc dim_check_release libm micromath uom
+cargo check --no-default-features --features alloc,dim_check_release,libm,micromath,uom
 echo std devices dim_check_debug libm micromath
 cargo check --no-default-features --features std,devices,dim_check_debug,libm,micromath
+echo std devices dim_check_debug libm uom
+cargo check --no-default-features --features std,devices,dim_check_debug,libm,uom
+echo std devices dim_check_debug micromath uom
+cargo check --no-default-features --features std,devices,dim_check_debug,micromath,uom
 echo std devices dim_check_release libm micromath
 cargo check --no-default-features --features std,devices,dim_check_release,libm,micromath
+echo std devices dim_check_release libm uom
+cargo check --no-default-features --features std,devices,dim_check_release,libm,uom
+echo std devices dim_check_release micromath uom
+cargo check --no-default-features --features std,devices,dim_check_release,micromath,uom
+echo std devices libm micromath uom
+cargo check --no-default-features --features std,devices,libm,micromath,uom
+echo std dim_check_debug libm micromath uom
+cargo check --no-default-features --features std,dim_check_debug,libm,micromath,uom
+echo std dim_check_release libm micromath uom
+cargo check --no-default-features --features std,dim_check_release,libm,micromath,uom
+echo devices dim_check_debug libm micromath uom
+cargo check --no-default-features --features devices,dim_check_debug,libm,micromath,uom
+echo devices dim_check_release libm micromath uom
+cargo check --no-default-features --features devices,dim_check_release,libm,micromath,uom
+echo alloc devices dim_check_debug libm micromath uom
+cargo check --no-default-features --features alloc,devices,dim_check_debug,libm,micromath,uom
+echo alloc devices dim_check_release libm micromath uom
+cargo check --no-default-features --features alloc,devices,dim_check_release,libm,micromath,uom
+echo std devices dim_check_debug libm micromath uom
+cargo check --no-default-features --features std,devices,dim_check_debug,libm,micromath,uom
+echo std devices dim_check_release libm micromath uom
+cargo check --no-default-features --features std,devices,dim_check_release,libm,micromath,uom
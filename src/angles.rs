@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Utilities for working with angles in radians. Angles that are allowed to grow without bound, such
+//!as a heading integrated from a gyroscope, are easy to compare incorrectly; the functions here
+//!bring them back into a standard range and find the shortest way between them.
+use core::f32::consts::{PI, TAU};
+///Wrap an angle in radians to the range [-π, π).
+#[inline]
+pub fn wrap_to_pi(angle: f32) -> f32 {
+    let wrapped = wrap_to_2pi(angle + PI) - PI;
+    //Floating point error can put this just outside of the range.
+    if wrapped < -PI {
+        -PI
+    } else {
+        wrapped
+    }
+}
+///Wrap an angle in radians to the range [0, 2π).
+#[inline]
+pub fn wrap_to_2pi(angle: f32) -> f32 {
+    let wrapped = angle % TAU;
+    let wrapped = if wrapped < 0.0 {
+        wrapped + TAU
+    } else {
+        wrapped
+    };
+    //Adding 2π to a very small negative number can round to exactly 2π.
+    if wrapped >= TAU {
+        0.0
+    } else {
+        wrapped
+    }
+}
+///Get the signed angle in radians of the shortest arc from `from` to `to`. The result is in the
+///range [-π, π), so adding it to `from` will always result in an angle equivalent to `to`
+///without going the long way around.
+#[inline]
+pub fn shortest_arc(from: f32, to: f32) -> f32 {
+    wrap_to_pi(to - from)
+}
+///Get the absolute angular distance in radians between two angles along the shortest arc. The
+///result is in the range [0, π].
+#[inline]
+pub fn angular_distance(a: f32, b: f32) -> f32 {
+    let arc = shortest_arc(a, b);
+    if arc < 0.0 {
+        -arc
+    } else {
+        arc
+    }
+}
+///A range that angles can be normalized to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AngleRange {
+    ///[-π, π). See [`wrap_to_pi`].
+    NegativePiToPi,
+    ///[0, 2π). See [`wrap_to_2pi`].
+    ZeroToTwoPi,
+}
+impl AngleRange {
+    ///Wrap an angle in radians to this range.
+    #[inline]
+    pub fn wrap(self, angle: f32) -> f32 {
+        match self {
+            Self::NegativePiToPi => wrap_to_pi(angle),
+            Self::ZeroToTwoPi => wrap_to_2pi(angle),
+        }
+    }
+}
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Kinematics of two-link planar arms, the most common arm geometry on small competition robots.
+//![`ArmKinematics2`] converts between joint angles and the position of the end of the arm, and the
+//!streams here turn a target position into position commands for each joint. Lengths and positions
+//!are in millimeters with the shoulder at the origin, and angles are in radians, counterclockwise
+//!positive. The shoulder angle is measured from the positive x axis and the elbow angle from the
+//!direction of the first link, so an elbow angle of 0 means the arm is straight.
+//!
+//![`ArmGravityModel2`] and [`ArmGravityFeedforward`] compute the torque each joint needs to hold
+//!the arm up against gravity, for adding to the output of each joint's controller. For these, the
+//!positive y axis must point up.
+use crate::*;
+///Which of the two solutions for the joint angles to use when going from a position to joint
+///angles. Most positions can be reached with the elbow on either side of the line from the
+///shoulder to the target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElbowConfiguration {
+    ///The elbow is on the counterclockwise side of the line from the shoulder to the target, which
+    ///is above it when reaching in the positive x direction. The elbow angle is negative.
+    Up,
+    ///The elbow is on the clockwise side of the line from the shoulder to the target, which is below
+    ///it when reaching in the positive x direction. The elbow angle is positive.
+    Down,
+}
+///The lengths of the links of a two-link planar arm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArmKinematics2 {
+    ///The distance from the shoulder to the elbow.
+    pub upper_length: f32,
+    ///The distance from the elbow to the end of the arm.
+    pub lower_length: f32,
+}
+impl ArmKinematics2 {
+    ///Constructor for [`ArmKinematics2`].
+    pub const fn new(upper_length: f32, lower_length: f32) -> Self {
+        Self {
+            upper_length: upper_length,
+            lower_length: lower_length,
+        }
+    }
+    ///Get the position of the end of the arm from the shoulder and elbow angles.
+    pub fn forward(&self, shoulder: f32, elbow: f32) -> (f32, f32) {
+        let lower_angle = shoulder + elbow;
+        (
+            self.upper_length * cosf(shoulder) + self.lower_length * cosf(lower_angle),
+            self.upper_length * sinf(shoulder) + self.lower_length * sinf(lower_angle),
+        )
+    }
+    ///Returns true if the end of the arm can be placed at a position.
+    pub fn can_reach(&self, x: f32, y: f32) -> bool {
+        let distance_squared = x * x + y * y;
+        let max = self.upper_length + self.lower_length;
+        let min = self.upper_length - self.lower_length;
+        distance_squared <= max * max && distance_squared >= min * min
+    }
+    ///Get the shoulder and elbow angles that place the end of the arm at a position, or `None` if
+    ///it is out of reach.
+    pub fn inverse(&self, x: f32, y: f32, elbow: ElbowConfiguration) -> Option<(f32, f32)> {
+        if !self.can_reach(x, y) {
+            return None;
+        }
+        let (upper, lower) = (self.upper_length, self.lower_length);
+        let cos_elbow = (x * x + y * y - upper * upper - lower * lower) / (2.0 * upper * lower);
+        //Rounding can put this just outside of [-1, 1] at the edges of the reachable area.
+        let cos_elbow = cos_elbow.clamp(-1.0, 1.0);
+        let sin_elbow = sqrtf(1.0 - cos_elbow * cos_elbow);
+        let sin_elbow = match elbow {
+            ElbowConfiguration::Up => -sin_elbow,
+            ElbowConfiguration::Down => sin_elbow,
+        };
+        let elbow_angle = atan2f(sin_elbow, cos_elbow);
+        let shoulder_angle = atan2f(y, x) - atan2f(lower * sin_elbow, upper + lower * cos_elbow);
+        Some((shoulder_angle, elbow_angle))
+    }
+}
+///A stream computing the shoulder and elbow angles, in that order, that place the end of an arm at
+///the target position from its input. If the target is out of reach or the input returns
+///`Ok(None)`, this returns `Ok(None)`.
+pub struct ArmInverseKinematicsStream<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> {
+    target: Reference<G>,
+    arm: ArmKinematics2,
+    elbow: ElbowConfiguration,
+    phantom_e: PhantomData<E>,
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> ArmInverseKinematicsStream<G, E> {
+    ///Constructor for [`ArmInverseKinematicsStream`].
+    pub const fn new(target: Reference<G>, arm: ArmKinematics2, elbow: ElbowConfiguration) -> Self {
+        Self {
+            target: target,
+            arm: arm,
+            elbow: elbow,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Change which solution for the joint angles is used.
+    pub fn set_elbow_configuration(&mut self, elbow: ElbowConfiguration) {
+        self.elbow = elbow;
+    }
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> Getter<(f32, f32), E>
+    for ArmInverseKinematicsStream<G, E>
+{
+    fn get(&self) -> Output<(f32, f32), E> {
+        let target = match self.target.borrow().get()? {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        let (x, y) = target.value;
+        Ok(self
+            .arm
+            .inverse(x, y, self.elbow)
+            .map(|angles| Datum::new(target.time, angles)))
+    }
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ArmInverseKinematicsStream<G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A joint of a two-link arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArmJoint {
+    ///The joint between the base and the first link.
+    Shoulder,
+    ///The joint between the two links.
+    Elbow,
+}
+///A stream turning one of the angles from an [`ArmInverseKinematicsStream`] into a position
+///command for that joint, so that each joint's motor controller can follow it.
+pub struct ArmJointCommandStream<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> {
+    joint_angles: Reference<G>,
+    joint: ArmJoint,
+    phantom_e: PhantomData<E>,
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> ArmJointCommandStream<G, E> {
+    ///Constructor for [`ArmJointCommandStream`].
+    pub const fn new(joint_angles: Reference<G>, joint: ArmJoint) -> Self {
+        Self {
+            joint_angles: joint_angles,
+            joint: joint,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> Getter<Command, E>
+    for ArmJointCommandStream<G, E>
+{
+    fn get(&self) -> Output<Command, E> {
+        Ok(self.joint_angles.borrow().get()?.map(|angles| {
+            let angle = match self.joint {
+                ArmJoint::Shoulder => angles.value.0,
+                ArmJoint::Elbow => angles.value.1,
+            };
+            Datum::new(angles.time, Command::Position(angle))
+        }))
+    }
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ArmJointCommandStream<G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///The masses of the links of a two-link arm and where they are, for computing the torque needed to
+///hold the arm up. Torques are in units of `gravity` times mass times length, so with masses in
+///kilograms, lengths in millimeters, and `gravity` in meters per second squared, they are in
+///newton-millimeters. Scale them to motor output however the rest of the controller expects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArmGravityModel2 {
+    ///The lengths of the links.
+    pub arm: ArmKinematics2,
+    ///The mass of the first link.
+    pub upper_mass: f32,
+    ///The distance from the shoulder to the center of mass of the first link.
+    pub upper_center: f32,
+    ///The mass of the second link, including anything it is carrying.
+    pub lower_mass: f32,
+    ///The distance from the elbow to the center of mass of the second link.
+    pub lower_center: f32,
+    ///The acceleration due to gravity.
+    pub gravity: f32,
+}
+impl ArmGravityModel2 {
+    ///Constructor for [`ArmGravityModel2`].
+    pub const fn new(
+        arm: ArmKinematics2,
+        upper_mass: f32,
+        upper_center: f32,
+        lower_mass: f32,
+        lower_center: f32,
+        gravity: f32,
+    ) -> Self {
+        Self {
+            arm: arm,
+            upper_mass: upper_mass,
+            upper_center: upper_center,
+            lower_mass: lower_mass,
+            lower_center: lower_center,
+            gravity: gravity,
+        }
+    }
+    ///Get the torques, shoulder then elbow, that each joint must apply to hold the arm still at the
+    ///given angles. Positive torques are counterclockwise.
+    pub fn torques(&self, shoulder: f32, elbow: f32) -> (f32, f32) {
+        //The elbow only holds up the second link, but the shoulder holds up both, with the second
+        //link's weight acting through the elbow as well as through its own center of mass.
+        let elbow_torque =
+            self.lower_mass * self.gravity * self.lower_center * cosf(shoulder + elbow);
+        let shoulder_torque = (self.upper_mass * self.upper_center
+            + self.lower_mass * self.arm.upper_length)
+            * self.gravity
+            * cosf(shoulder)
+            + elbow_torque;
+        (shoulder_torque, elbow_torque)
+    }
+}
+///Computes gravity feedforward for both joints of an arm from both joint angles, since the torque
+///the shoulder needs depends on the elbow angle too. Returns the torques, shoulder then elbow, from
+///[`ArmGravityModel2::torques`]. Use [`ArmJointFeedforwardStream`] to get each joint's torque as a
+///separate getter. The timestamp is that of the newer angle. If either angle getter returns
+///`Ok(None)`, this does too.
+pub struct ArmGravityFeedforward<
+    GS: Getter<f32, E> + ?Sized,
+    GE: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    shoulder: Reference<GS>,
+    elbow: Reference<GE>,
+    model: ArmGravityModel2,
+    phantom_e: PhantomData<E>,
+}
+impl<GS: Getter<f32, E> + ?Sized, GE: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    ArmGravityFeedforward<GS, GE, E>
+{
+    ///Constructor for [`ArmGravityFeedforward`].
+    pub const fn new(
+        shoulder: Reference<GS>,
+        elbow: Reference<GE>,
+        model: ArmGravityModel2,
+    ) -> Self {
+        Self {
+            shoulder: shoulder,
+            elbow: elbow,
+            model: model,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get the model used to compute the torques.
+    pub fn get_model(&self) -> ArmGravityModel2 {
+        self.model
+    }
+    ///Change the model, for example when the arm picks something up.
+    pub fn set_model(&mut self, model: ArmGravityModel2) {
+        self.model = model;
+    }
+}
+impl<GS: Getter<f32, E> + ?Sized, GE: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    Getter<(f32, f32), E> for ArmGravityFeedforward<GS, GE, E>
+{
+    fn get(&self) -> Output<(f32, f32), E> {
+        let shoulder = match self.shoulder.borrow().get()? {
+            Some(shoulder) => shoulder,
+            None => return Ok(None),
+        };
+        let elbow = match self.elbow.borrow().get()? {
+            Some(elbow) => elbow,
+            None => return Ok(None),
+        };
+        let time = latest(shoulder, elbow).time;
+        Ok(Some(Datum::new(
+            time,
+            self.model.torques(shoulder.value, elbow.value),
+        )))
+    }
+}
+impl<GS: Getter<f32, E> + ?Sized, GE: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ArmGravityFeedforward<GS, GE, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///The feedforward for one joint from an [`ArmGravityFeedforward`].
+pub struct ArmJointFeedforwardStream<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> {
+    torques: Reference<G>,
+    joint: ArmJoint,
+    phantom_e: PhantomData<E>,
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> ArmJointFeedforwardStream<G, E> {
+    ///Constructor for [`ArmJointFeedforwardStream`].
+    pub const fn new(torques: Reference<G>, joint: ArmJoint) -> Self {
+        Self {
+            torques: torques,
+            joint: joint,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for ArmJointFeedforwardStream<G, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        Ok(self.torques.borrow().get()?.map(|torques| {
+            let torque = match self.joint {
+                ArmJoint::Shoulder => torques.value.0,
+                ArmJoint::Elbow => torques.value.1,
+            };
+            Datum::new(torques.time, torque)
+        }))
+    }
+}
+impl<G: Getter<(f32, f32), E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ArmJointFeedforwardStream<G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
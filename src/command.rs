@@ -20,6 +20,35 @@ impl Command {
             PositionDerivative::Acceleration => Self::Acceleration(value),
         }
     }
+    ///Checked constructor for [`Command`] taking a [`Quantity`]. Returns a [`UnitMismatch`] if the
+    ///unit of `value` is not the one for `position_derivative`. As with other unit checks, this
+    ///always succeeds with dimension checking off.
+    pub const fn try_new(
+        position_derivative: PositionDerivative,
+        value: Quantity,
+    ) -> Result<Self, UnitMismatch> {
+        let expected = match position_derivative {
+            PositionDerivative::Position => MILLIMETER,
+            PositionDerivative::Velocity => MILLIMETER_PER_SECOND,
+            PositionDerivative::Acceleration => MILLIMETER_PER_SECOND_SQUARED,
+        };
+        match value.unit.check(&expected) {
+            Ok(()) => Ok(Self::new(position_derivative, value.value)),
+            Err(mismatch) => Err(mismatch.with_operation("Command::try_new")),
+        }
+    }
+    ///Construct a position command from a [`Quantity`] in millimeters.
+    pub const fn from_position(position: Quantity) -> Result<Self, UnitMismatch> {
+        Self::try_new(PositionDerivative::Position, position)
+    }
+    ///Construct a velocity command from a [`Quantity`] in millimeters per second.
+    pub const fn from_velocity(velocity: Quantity) -> Result<Self, UnitMismatch> {
+        Self::try_new(PositionDerivative::Velocity, velocity)
+    }
+    ///Construct an acceleration command from a [`Quantity`] in millimeters per second squared.
+    pub const fn from_acceleration(acceleration: Quantity) -> Result<Self, UnitMismatch> {
+        Self::try_new(PositionDerivative::Acceleration, acceleration)
+    }
     ///Get the commanded constant position if there is one. If the position derivative is
     ///velocity or acceleration, this will return `None` as there is not a constant position.
     pub fn get_position(&self) -> Option<Quantity> {
@@ -70,13 +99,15 @@ impl From<State> for Command {
     all(debug_assertions, feature = "dim_check_debug")
 ))]
 impl TryFrom<Quantity> for Command {
-    type Error = ();
-    fn try_from(was: Quantity) -> Result<Self, ()> {
+    type Error = UnitMismatch;
+    ///If the unit is not that of a position derivative, the returned [`UnitMismatch`] gives
+    ///[`MILLIMETER`] as the expected unit.
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
         match was.unit {
             MILLIMETER => Ok(Self::Position(was.value)),
             MILLIMETER_PER_SECOND => Ok(Self::Velocity(was.value)),
             MILLIMETER_PER_SECOND_SQUARED => Ok(Self::Acceleration(was.value)),
-            _ => Err(()),
+            _ => Err(UnitMismatch::new(MILLIMETER, was.unit).with_operation("Command::try_from")),
         }
     }
 }
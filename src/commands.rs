@@ -0,0 +1,314 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Composable units of work for autonomous routines in the style of command-based robot
+//!frameworks. A [`Task`] is started, updated until it says it is finished, and then ended. Tasks can
+//!be combined with [`Sequential`], [`Parallel`], [`Race`], and [`Deadline`], which are tasks
+//!themselves, so whole routines can be built from small pieces and run by starting the outermost
+//!one and updating it every cycle.
+//!
+//!These are called tasks rather than commands to avoid confusion with [`Command`], which is a
+//!setpoint for a motor.
+use crate::*;
+///A unit of work that runs over several updates. Call [`start`](Task::start) once, then
+///[`update`](Updatable::update) every cycle until [`is_finished`](Task::is_finished) returns true,
+///and then [`end`](Task::end). The combinators in this module do this for you.
+pub trait Task<E: Copy + Debug>: Updatable<E> {
+    ///Called once before the first update. Tasks may be started again after they end.
+    fn start(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+    ///Returns true once the task has nothing left to do.
+    fn is_finished(&self) -> bool;
+    ///Called once after the last update. `interrupted` is true if the task was stopped before it
+    ///finished.
+    fn end(&mut self, interrupted: bool) -> NothingOrError<E> {
+        let _ = interrupted;
+        Ok(())
+    }
+}
+///A task that does nothing until a duration has passed since it was started.
+pub struct Wait<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    time_getter: Reference<TG>,
+    duration: Time,
+    end_time: Option<Time>,
+    finished: bool,
+    phantom_e: PhantomData<E>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Wait<TG, E> {
+    ///Constructor for [`Wait`].
+    pub const fn new(time_getter: Reference<TG>, duration: Time) -> Self {
+        Self {
+            time_getter: time_getter,
+            duration: duration,
+            end_time: None,
+            finished: false,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Task<E> for Wait<TG, E> {
+    fn start(&mut self) -> NothingOrError<E> {
+        self.end_time = Some(self.time_getter.borrow().get()? + self.duration);
+        self.finished = false;
+        Ok(())
+    }
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for Wait<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        if let Some(end_time) = self.end_time {
+            self.finished = self.time_getter.borrow().get()? >= end_time;
+        }
+        Ok(())
+    }
+}
+///Runs tasks one after another. Each task is started in the same update that the previous one
+///finishes in and is first updated in the next one. Finishes when the last task does.
+pub struct Sequential<const N: usize, E: Copy + Debug> {
+    tasks: [Reference<dyn Task<E>>; N],
+    current: usize,
+}
+impl<const N: usize, E: Copy + Debug> Sequential<N, E> {
+    ///Constructor for [`Sequential`].
+    pub const fn new(tasks: [Reference<dyn Task<E>>; N]) -> Self {
+        Self {
+            tasks: tasks,
+            current: N,
+        }
+    }
+    ///Get the index of the task currently running, or `None` if none is.
+    pub fn get_current(&self) -> Option<usize> {
+        if self.current < N {
+            Some(self.current)
+        } else {
+            None
+        }
+    }
+}
+impl<const N: usize, E: Copy + Debug> Task<E> for Sequential<N, E> {
+    fn start(&mut self) -> NothingOrError<E> {
+        self.current = 0;
+        if N > 0 {
+            self.tasks[0].borrow_mut().start()?;
+        }
+        Ok(())
+    }
+    fn is_finished(&self) -> bool {
+        self.current >= N
+    }
+    fn end(&mut self, interrupted: bool) -> NothingOrError<E> {
+        if interrupted && self.current < N {
+            let result = self.tasks[self.current].borrow_mut().end(true);
+            self.current = N;
+            return result;
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for Sequential<N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        if self.current >= N {
+            return Ok(());
+        }
+        let mut task = self.tasks[self.current].borrow_mut();
+        task.update()?;
+        if task.is_finished() {
+            task.end(false)?;
+            drop(task);
+            self.current += 1;
+            if self.current < N {
+                self.tasks[self.current].borrow_mut().start()?;
+            }
+        }
+        Ok(())
+    }
+}
+//Shared by Parallel, Race, and Deadline. Updates every running task, ending the ones that finish.
+//Returns true if any task finished.
+fn update_running<const N: usize, E: Copy + Debug>(
+    tasks: &[Reference<dyn Task<E>>; N],
+    running: &mut [bool; N],
+) -> Result<bool, Error<E>> {
+    let mut any_finished = false;
+    for (task, running) in tasks.iter().zip(running.iter_mut()) {
+        if !*running {
+            continue;
+        }
+        let mut task = task.borrow_mut();
+        task.update()?;
+        if task.is_finished() {
+            *running = false;
+            any_finished = true;
+            task.end(false)?;
+        }
+    }
+    Ok(any_finished)
+}
+//Ends every task still running as interrupted. Every task is ended even if one returns an error,
+//and the first error is returned.
+fn interrupt_running<const N: usize, E: Copy + Debug>(
+    tasks: &[Reference<dyn Task<E>>; N],
+    running: &mut [bool; N],
+) -> NothingOrError<E> {
+    let mut result = Ok(());
+    for (task, running) in tasks.iter().zip(running.iter_mut()) {
+        if *running {
+            *running = false;
+            let end_result = task.borrow_mut().end(true);
+            if result.is_ok() {
+                result = end_result;
+            }
+        }
+    }
+    result
+}
+fn start_all<const N: usize, E: Copy + Debug>(
+    tasks: &[Reference<dyn Task<E>>; N],
+    running: &mut [bool; N],
+) -> NothingOrError<E> {
+    for (task, running) in tasks.iter().zip(running.iter_mut()) {
+        task.borrow_mut().start()?;
+        *running = true;
+    }
+    Ok(())
+}
+///Runs tasks at the same time. Finishes when all of them have.
+pub struct Parallel<const N: usize, E: Copy + Debug> {
+    tasks: [Reference<dyn Task<E>>; N],
+    running: [bool; N],
+}
+impl<const N: usize, E: Copy + Debug> Parallel<N, E> {
+    ///Constructor for [`Parallel`].
+    pub const fn new(tasks: [Reference<dyn Task<E>>; N]) -> Self {
+        Self {
+            tasks: tasks,
+            running: [false; N],
+        }
+    }
+}
+impl<const N: usize, E: Copy + Debug> Task<E> for Parallel<N, E> {
+    fn start(&mut self) -> NothingOrError<E> {
+        start_all(&self.tasks, &mut self.running)
+    }
+    fn is_finished(&self) -> bool {
+        !self.running.contains(&true)
+    }
+    fn end(&mut self, interrupted: bool) -> NothingOrError<E> {
+        if interrupted {
+            return interrupt_running(&self.tasks, &mut self.running);
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for Parallel<N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        update_running(&self.tasks, &mut self.running)?;
+        Ok(())
+    }
+}
+///Runs tasks at the same time. Finishes as soon as any of them does, interrupting the rest.
+pub struct Race<const N: usize, E: Copy + Debug> {
+    tasks: [Reference<dyn Task<E>>; N],
+    running: [bool; N],
+    finished: bool,
+}
+impl<const N: usize, E: Copy + Debug> Race<N, E> {
+    ///Constructor for [`Race`].
+    pub const fn new(tasks: [Reference<dyn Task<E>>; N]) -> Self {
+        Self {
+            tasks: tasks,
+            running: [false; N],
+            finished: true,
+        }
+    }
+}
+impl<const N: usize, E: Copy + Debug> Task<E> for Race<N, E> {
+    fn start(&mut self) -> NothingOrError<E> {
+        self.finished = N == 0;
+        start_all(&self.tasks, &mut self.running)
+    }
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+    fn end(&mut self, interrupted: bool) -> NothingOrError<E> {
+        if interrupted {
+            self.finished = true;
+            return interrupt_running(&self.tasks, &mut self.running);
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for Race<N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        if self.finished {
+            return Ok(());
+        }
+        if update_running(&self.tasks, &mut self.running)? {
+            self.finished = true;
+            interrupt_running(&self.tasks, &mut self.running)?;
+        }
+        Ok(())
+    }
+}
+///Runs a deadline task and other tasks at the same time. Finishes when the deadline task does,
+///interrupting any of the others that are still running. Others that finish first are ended
+///normally.
+pub struct Deadline<const N: usize, E: Copy + Debug> {
+    deadline: Reference<dyn Task<E>>,
+    others: [Reference<dyn Task<E>>; N],
+    running: [bool; N],
+    finished: bool,
+}
+impl<const N: usize, E: Copy + Debug> Deadline<N, E> {
+    ///Constructor for [`Deadline`].
+    pub const fn new(
+        deadline: Reference<dyn Task<E>>,
+        others: [Reference<dyn Task<E>>; N],
+    ) -> Self {
+        Self {
+            deadline: deadline,
+            others: others,
+            running: [false; N],
+            finished: true,
+        }
+    }
+}
+impl<const N: usize, E: Copy + Debug> Task<E> for Deadline<N, E> {
+    fn start(&mut self) -> NothingOrError<E> {
+        self.finished = false;
+        self.deadline.borrow_mut().start()?;
+        start_all(&self.others, &mut self.running)
+    }
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+    fn end(&mut self, interrupted: bool) -> NothingOrError<E> {
+        if interrupted && !self.finished {
+            self.finished = true;
+            let result = self.deadline.borrow_mut().end(true);
+            let others_result = interrupt_running(&self.others, &mut self.running);
+            return result.and(others_result);
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for Deadline<N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        if self.finished {
+            return Ok(());
+        }
+        let mut deadline = self.deadline.borrow_mut();
+        deadline.update()?;
+        let deadline_finished = deadline.is_finished();
+        drop(deadline);
+        update_running(&self.others, &mut self.running)?;
+        if deadline_finished {
+            self.finished = true;
+            self.deadline.borrow_mut().end(false)?;
+            interrupt_running(&self.others, &mut self.running)?;
+        }
+        Ok(())
+    }
+}
@@ -26,6 +26,18 @@ impl<T> Datum<T> {
         }
         false
     }
+    ///Apply a function to the value, keeping the timestamp.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Datum<U> {
+        Datum::new(self.time, f(self.value))
+    }
+    ///Get a datum with a reference to the value and the same timestamp.
+    pub const fn as_ref(&self) -> Datum<&T> {
+        Datum::new(self.time, &self.value)
+    }
+    ///Get a datum with a mutable reference to the value and the same timestamp.
+    pub fn as_mut(&mut self) -> Datum<&mut T> {
+        Datum::new(self.time, &mut self.value)
+    }
 }
 ///Extension trait for `Option<Datum<T>>`.
 pub trait OptionDatumExt<T> {
@@ -36,6 +48,13 @@ pub trait OptionDatumExt<T> {
     ///If `maybe_replace_with` is `Some`, calls `replace_if_none_or_older_than`. If it is `None`,
     ///returns false immediately.
     fn replace_if_none_or_older_than_option(&mut self, maybe_replace_with: Self) -> bool;
+    ///Apply a function to the value if there is one, keeping the timestamp.
+    fn map_value<U>(self, f: impl FnOnce(T) -> U) -> Option<Datum<U>>
+    where
+        Self: Sized + Into<Option<Datum<T>>>,
+    {
+        self.into().map(|datum| datum.map(f))
+    }
 }
 impl<T> OptionDatumExt<T> for Option<Datum<T>> {
     fn replace_if_none_or_older_than(&mut self, maybe_replace_with: Datum<T>) -> bool {
@@ -55,6 +74,47 @@ impl<T> OptionDatumExt<T> for Option<Datum<T>> {
         self.replace_if_none_or_older_than(maybe_replace_with)
     }
 }
+///Extension trait for [`Output<T, E>`] reducing the matching needed to work with it.
+pub trait OutputExt<T, E: Copy + Debug> {
+    ///Apply a function to the value if there is one, keeping the timestamp. Errors and `Ok(None)`
+    ///are passed through.
+    fn map_value<U>(self, f: impl FnOnce(T) -> U) -> Output<U, E>;
+    ///Get the datum, treating `Ok(None)` as [`Error::FromNone`].
+    fn ok_or_from_none(self) -> Result<Datum<T>, Error<E>>;
+    ///Get the datum, replacing `Ok(None)` with `default` timestamped with the current time from
+    ///`time_getter`. Errors are passed through.
+    fn unwrap_or_value<TG: TimeGetter<E> + ?Sized>(
+        self,
+        default: T,
+        time_getter: &TG,
+    ) -> Result<Datum<T>, Error<E>>;
+    ///Discard the timestamp, keeping only the value if there is one.
+    fn value(self) -> Result<Option<T>, Error<E>>;
+}
+impl<T, E: Copy + Debug> OutputExt<T, E> for Output<T, E> {
+    fn map_value<U>(self, f: impl FnOnce(T) -> U) -> Output<U, E> {
+        self.map(|maybe_datum| maybe_datum.map_value(f))
+    }
+    fn ok_or_from_none(self) -> Result<Datum<T>, Error<E>> {
+        match self? {
+            Some(datum) => Ok(datum),
+            None => Err(Error::FromNone),
+        }
+    }
+    fn unwrap_or_value<TG: TimeGetter<E> + ?Sized>(
+        self,
+        default: T,
+        time_getter: &TG,
+    ) -> Result<Datum<T>, Error<E>> {
+        match self? {
+            Some(datum) => Ok(datum),
+            None => Ok(Datum::new(time_getter.get()?, default)),
+        }
+    }
+    fn value(self) -> Result<Option<T>, Error<E>> {
+        self.map(|maybe_datum| maybe_datum.map(|datum| datum.value))
+    }
+}
 //Unfortunately implementing the ops traits is really awkward here and has unnecessary restrictions
 //because of needing to provide implementations for T and Datum<T>. If we ever get negative trait
 //bounds, it will be possible to provide a much better generic implementation for cases where the
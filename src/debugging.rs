@@ -0,0 +1,544 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Tools for reproducing control bugs offline. A [`Recorder`] captures every input at the boundary
+//!of a pipeline each cycle. The recorded inputs can later be fed back into the same pipeline one
+//!cycle at a time, so that a problem seen on the robot can be stepped through deterministically.
+//!
+//!Build the pipeline on the [`RecordedInput`] and [`RecordedTime`] objects returned by the
+//![`Recorder`] rather than directly on the real sensors and clock. While recording, these pass
+//!through and log the real values. While replaying, they return the logged values instead.
+//!
+//!An [`Inspector`] is for finding where in a pipeline values go wrong. Insert the [`Tap`] objects
+//!it returns between stages of the pipeline, and [`snapshot`](Inspector::snapshot) shows the
+//!latest output that passed through each of them.
+//!
+//!For regression tests, [`ScriptedInput`] and [`ScriptedTime`] feed a pipeline a fixed trace of
+//!inputs, [`run_trace`] collects its output at every step, and a [`GoldenTrace`] checks that
+//!output against known good values, describing every sample that differs in a [`TraceDiff`].
+use crate::*;
+use std::collections::HashMap;
+///An input or clock that a [`Recorder`] can switch between live and replayed values. You should
+///not usually need to implement this yourself.
+pub trait Replayable {
+    ///Update the live source and log its output.
+    fn record(&mut self);
+    ///Return the logged value from cycle `step` instead of the live value, or go back to live
+    ///values if `step` is `None`.
+    fn set_replay_step(&mut self, step: Option<usize>);
+}
+///A [`Getter`] passing through and logging the output of another while recording and returning
+///logged outputs while replaying. Create this with [`Recorder::add_input`].
+pub struct RecordedInput<T: Clone, E: Copy + Debug> {
+    source: Reference<dyn Getter<T, E>>,
+    log: Vec<Output<T, E>>,
+    replay_step: Option<usize>,
+}
+impl<T: Clone, E: Copy + Debug> RecordedInput<T, E> {
+    ///Get every output logged so far, one per recorded cycle.
+    pub fn log(&self) -> &[Output<T, E>] {
+        &self.log
+    }
+}
+impl<T: Clone, E: Copy + Debug> Replayable for RecordedInput<T, E> {
+    fn record(&mut self) {
+        let update_result = self.source.borrow_mut().update();
+        let output = match update_result {
+            Ok(()) => self.source.borrow().get(),
+            Err(error) => Err(error),
+        };
+        self.log.push(output);
+    }
+    fn set_replay_step(&mut self, step: Option<usize>) {
+        self.replay_step = step;
+    }
+}
+impl<T: Clone, E: Copy + Debug> Getter<T, E> for RecordedInput<T, E> {
+    fn get(&self) -> Output<T, E> {
+        let index = match self.replay_step {
+            Some(step) => step,
+            None => match self.log.len() {
+                0 => return Ok(None),
+                len => len - 1,
+            },
+        };
+        match self.log.get(index) {
+            Some(output) => output.clone(),
+            None => Ok(None),
+        }
+    }
+}
+impl<T: Clone, E: Copy + Debug> Updatable<E> for RecordedInput<T, E> {
+    ///This does nothing. The [`Recorder`] updates the source when it records a cycle.
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A [`TimeGetter`] passing through and logging the time from another while recording and
+///returning logged times while replaying. Create this with [`Recorder::add_time`].
+pub struct RecordedTime<E: Copy + Debug> {
+    source: Reference<dyn TimeGetter<E>>,
+    log: Vec<TimeOutput<E>>,
+    replay_step: Option<usize>,
+}
+impl<E: Copy + Debug> RecordedTime<E> {
+    ///Get every time logged so far, one per recorded cycle.
+    pub fn log(&self) -> &[TimeOutput<E>] {
+        &self.log
+    }
+}
+impl<E: Copy + Debug> Replayable for RecordedTime<E> {
+    fn record(&mut self) {
+        let update_result = self.source.borrow_mut().update();
+        let output = match update_result {
+            Ok(()) => self.source.borrow().get(),
+            Err(error) => Err(error),
+        };
+        self.log.push(output);
+    }
+    fn set_replay_step(&mut self, step: Option<usize>) {
+        self.replay_step = step;
+    }
+}
+impl<E: Copy + Debug> TimeGetter<E> for RecordedTime<E> {
+    fn get(&self) -> TimeOutput<E> {
+        let index = match self.replay_step {
+            Some(step) => step,
+            None => match self.log.len() {
+                0 => return Err(Error::FromNone),
+                len => len - 1,
+            },
+        };
+        match self.log.get(index) {
+            Some(output) => *output,
+            None => Err(Error::FromNone),
+        }
+    }
+}
+impl<E: Copy + Debug> Updatable<E> for RecordedTime<E> {
+    ///This does nothing. The [`Recorder`] updates the source when it records a cycle.
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Records the inputs of a pipeline every cycle and re-executes the pipeline with them later. See
+///the [module-level documentation](self) for how to set this up.
+///
+///Replaying only reproduces the original run if the pipeline starts from the same state it did
+///when recording. Reset or reconstruct any stateful streams before calling
+///[`start_replay`](Recorder::start_replay).
+pub struct Recorder<E: Copy + Debug> {
+    pipeline: Reference<dyn Updatable<E>>,
+    inputs: Vec<Reference<dyn Replayable>>,
+    cycles: usize,
+    replay_position: Option<usize>,
+}
+impl<E: Copy + Debug + 'static> Recorder<E> {
+    ///Constructor for [`Recorder`]. `pipeline` is updated once per cycle after the inputs are
+    ///recorded. An [`UpdatePipeline`](pipeline::UpdatePipeline) works well here.
+    pub fn new(pipeline: Reference<dyn Updatable<E>>) -> Self {
+        Self {
+            pipeline: pipeline,
+            inputs: Vec::new(),
+            cycles: 0,
+            replay_position: None,
+        }
+    }
+    ///Register an input of the pipeline. Build the pipeline on the returned getter instead of
+    ///`source`. Inputs added after recording has started have no values for earlier cycles and
+    ///return `Ok(None)` for them during replay.
+    pub fn add_input<T: Clone + 'static>(
+        &mut self,
+        source: Reference<dyn Getter<T, E>>,
+    ) -> Reference<RecordedInput<T, E>> {
+        //Pad the log so that its indices line up with the cycles of the other inputs.
+        let mut log = Vec::new();
+        log.resize(self.cycles, Ok(None));
+        let input = Reference::from_rc_ref_cell(Rc::new(RefCell::new(RecordedInput {
+            source: source,
+            log: log,
+            replay_step: self.replay_position,
+        })));
+        self.inputs.push(to_dyn!(Replayable, input.clone()));
+        input
+    }
+    ///Register the clock of the pipeline. Build the pipeline on the returned time getter instead
+    ///of `source`. A clock added after recording has started has no times for earlier cycles and
+    ///returns [`Error::FromNone`] for them during replay.
+    pub fn add_time(&mut self, source: Reference<dyn TimeGetter<E>>) -> Reference<RecordedTime<E>> {
+        let mut log = Vec::new();
+        log.resize(self.cycles, Err(Error::FromNone));
+        let time = Reference::from_rc_ref_cell(Rc::new(RefCell::new(RecordedTime {
+            source: source,
+            log: log,
+            replay_step: self.replay_position,
+        })));
+        self.inputs.push(to_dyn!(Replayable, time.clone()));
+        time
+    }
+    ///Get the number of cycles recorded.
+    pub fn cycles(&self) -> usize {
+        self.cycles
+    }
+    ///Get the index of the next cycle to be replayed, or `None` if not replaying.
+    pub fn replay_position(&self) -> Option<usize> {
+        self.replay_position
+    }
+    ///Record every input and then update the pipeline. Errors from inputs are recorded and
+    ///passed on to the pipeline like any other output, so they do not stop the cycle.
+    ///
+    ///# Panics
+    ///
+    ///Panics if called while replaying; call [`stop_replay`](Recorder::stop_replay) first.
+    pub fn record(&mut self) -> NothingOrError<E> {
+        assert!(
+            self.replay_position.is_none(),
+            "rrtk::debugging::Recorder::record must not be called while replaying"
+        );
+        for input in &self.inputs {
+            input.borrow_mut().record();
+        }
+        self.cycles += 1;
+        self.pipeline.borrow_mut().update()
+    }
+    ///Stop recording and start replaying from the first recorded cycle.
+    pub fn start_replay(&mut self) {
+        self.replay_position = Some(0);
+    }
+    ///Re-execute the next recorded cycle. Returns `None` once every recorded cycle has been
+    ///replayed, and otherwise the result of updating the pipeline.
+    pub fn replay_step(&mut self) -> Option<NothingOrError<E>> {
+        let step = self.replay_position?;
+        if step >= self.cycles {
+            return None;
+        }
+        for input in &self.inputs {
+            input.borrow_mut().set_replay_step(Some(step));
+        }
+        self.replay_position = Some(step + 1);
+        Some(self.pipeline.borrow_mut().update())
+    }
+    ///Stop replaying and go back to live inputs. Recording continues after the previously
+    ///recorded cycles.
+    pub fn stop_replay(&mut self) {
+        self.replay_position = None;
+        for input in &self.inputs {
+            input.borrow_mut().set_replay_step(None);
+        }
+    }
+}
+impl<E: Copy + Debug + 'static> Updatable<E> for Recorder<E> {
+    ///Calls [`record`](Recorder::record) unless replaying, in which case this does nothing so
+    ///that replay is driven only by [`replay_step`](Recorder::replay_step).
+    fn update(&mut self) -> NothingOrError<E> {
+        if self.replay_position.is_some() {
+            return Ok(());
+        }
+        self.record()
+    }
+}
+///A [`Getter`] passing through the output of another and remembering the latest one for an
+///[`Inspector`]. Create this with [`Inspector::tap`]. The output is captured whenever
+///[`get`](Getter::get) is called, so it is exactly what the next stage of the pipeline saw.
+///Updating this does not update the source.
+pub struct Tap<T: Clone, E: Copy + Debug> {
+    source: Reference<dyn Getter<T, E>>,
+    latest: RefCell<Output<T, E>>,
+}
+impl<T: Clone, E: Copy + Debug> Tap<T, E> {
+    ///Get the latest output that passed through this tap. This is `Ok(None)` until the first
+    ///[`get`](Getter::get).
+    pub fn latest(&self) -> Output<T, E> {
+        self.latest.borrow().clone()
+    }
+}
+impl<T: Clone, E: Copy + Debug> Getter<T, E> for Tap<T, E> {
+    fn get(&self) -> Output<T, E> {
+        let output = self.source.borrow().get();
+        *self.latest.borrow_mut() = output.clone();
+        output
+    }
+}
+impl<T: Clone, E: Copy + Debug> Updatable<E> for Tap<T, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Collects named [`Tap`]s placed at different points of a pipeline so that the latest value at
+///every one of them can be seen at once. See the [module-level documentation](self).
+pub struct Inspector<T: Clone, E: Copy + Debug> {
+    taps: Vec<(&'static str, Reference<Tap<T, E>>)>,
+}
+impl<T: Clone + 'static, E: Copy + Debug + 'static> Inspector<T, E> {
+    ///Constructor for [`Inspector`].
+    pub fn new() -> Self {
+        Self { taps: Vec::new() }
+    }
+    ///Add a tap named `name` on the output of `source`. Build the next stage of the pipeline on
+    ///the returned getter instead of `source`. Names should be unique; if they are not,
+    ///[`snapshot`](Inspector::snapshot) only includes the last tap added with each name.
+    pub fn tap(
+        &mut self,
+        name: &'static str,
+        source: Reference<dyn Getter<T, E>>,
+    ) -> Reference<Tap<T, E>> {
+        let tap = Reference::from_rc_ref_cell(Rc::new(RefCell::new(Tap {
+            source: source,
+            latest: RefCell::new(Ok(None)),
+        })));
+        self.taps.push((name, tap.clone()));
+        tap
+    }
+    ///Get the latest output seen by the tap named `name`, or `None` if there is no such tap.
+    pub fn get(&self, name: &str) -> Option<Output<T, E>> {
+        self.taps
+            .iter()
+            .rev()
+            .find(|(tap_name, _)| *tap_name == name)
+            .map(|(_, tap)| tap.borrow().latest())
+    }
+    ///Get the latest output seen by every tap, keyed by name.
+    pub fn snapshot(&self) -> HashMap<&'static str, Output<T, E>> {
+        self.taps
+            .iter()
+            .map(|(name, tap)| (*name, tap.borrow().latest()))
+            .collect()
+    }
+    ///Get the names of the taps in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.taps.iter().map(|(name, _)| *name)
+    }
+}
+impl<T: Clone + 'static, E: Copy + Debug + 'static> Default for Inspector<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+///A [`Getter`] returning values from a fixed trace instead of a live source, one per step. Use
+///this with [`run_trace`] to test a pipeline against a scripted input. Until a step is set, and
+///for steps past the end of the trace, this returns `Ok(None)`.
+pub struct ScriptedInput<T: Clone, E: Copy + Debug> {
+    trace: Vec<Option<Datum<T>>>,
+    step: Option<usize>,
+    phantom_e: PhantomData<E>,
+}
+impl<T: Clone, E: Copy + Debug> ScriptedInput<T, E> {
+    ///Constructor for [`ScriptedInput`].
+    pub const fn new(trace: Vec<Option<Datum<T>>>) -> Self {
+        Self {
+            trace: trace,
+            step: None,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<T: Clone, E: Copy + Debug> Replayable for ScriptedInput<T, E> {
+    ///This does nothing since there is no live source to record.
+    fn record(&mut self) {}
+    fn set_replay_step(&mut self, step: Option<usize>) {
+        self.step = step;
+    }
+}
+impl<T: Clone, E: Copy + Debug> Getter<T, E> for ScriptedInput<T, E> {
+    fn get(&self) -> Output<T, E> {
+        match self.step {
+            Some(step) => Ok(self.trace.get(step).cloned().flatten()),
+            None => Ok(None),
+        }
+    }
+}
+impl<T: Clone, E: Copy + Debug> Updatable<E> for ScriptedInput<T, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A [`TimeGetter`] returning times from a fixed trace, one per step. Until a step is set, and for
+///steps past the end of the trace, this returns [`Error::FromNone`].
+pub struct ScriptedTime<E: Copy + Debug> {
+    trace: Vec<Time>,
+    step: Option<usize>,
+    phantom_e: PhantomData<E>,
+}
+impl<E: Copy + Debug> ScriptedTime<E> {
+    ///Constructor for [`ScriptedTime`].
+    pub const fn new(trace: Vec<Time>) -> Self {
+        Self {
+            trace: trace,
+            step: None,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<E: Copy + Debug> Replayable for ScriptedTime<E> {
+    ///This does nothing since there is no live clock to record.
+    fn record(&mut self) {}
+    fn set_replay_step(&mut self, step: Option<usize>) {
+        self.step = step;
+    }
+}
+impl<E: Copy + Debug> TimeGetter<E> for ScriptedTime<E> {
+    fn get(&self) -> TimeOutput<E> {
+        match self.step.and_then(|step| self.trace.get(step)) {
+            Some(time) => Ok(*time),
+            None => Err(Error::FromNone),
+        }
+    }
+}
+impl<E: Copy + Debug> Updatable<E> for ScriptedTime<E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Run a pipeline for `steps` steps. At each step, every input is set to that step, the pipeline is
+///updated, and the output is collected. Stops and returns the error if updating the pipeline or
+///getting the output fails.
+pub fn run_trace<E: Copy + Debug>(
+    inputs: &[Reference<dyn Replayable>],
+    pipeline: &Reference<dyn Updatable<E>>,
+    output: &Reference<dyn Getter<f32, E>>,
+    steps: usize,
+) -> Result<Vec<Option<Datum<f32>>>, Error<E>> {
+    let mut trace = Vec::with_capacity(steps);
+    for step in 0..steps {
+        for input in inputs {
+            input.borrow_mut().set_replay_step(Some(step));
+        }
+        pipeline.borrow_mut().update()?;
+        trace.push(output.borrow().get()?);
+    }
+    Ok(trace)
+}
+///One expected output in a [`GoldenTrace`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoldenSample {
+    ///The expected output. `None` means the output should be `Ok(None)`.
+    pub expected: Option<Datum<f32>>,
+    ///How far the actual value may be from the expected one. Times must match exactly.
+    pub tolerance: f32,
+}
+impl GoldenSample {
+    ///Returns true if `actual` matches this sample.
+    pub fn matches(&self, actual: &Option<Datum<f32>>) -> bool {
+        match (&self.expected, actual) {
+            (None, None) => true,
+            (Some(expected), Some(actual)) => {
+                expected.time == actual.time
+                    && (expected.value - actual.value).abs() <= self.tolerance
+            }
+            _ => false,
+        }
+    }
+}
+///One step at which a trace differed from a [`GoldenTrace`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TraceMismatch {
+    ///The index of the step.
+    pub step: usize,
+    ///The golden sample for the step.
+    pub expected: GoldenSample,
+    ///The output actually produced at the step.
+    pub actual: Option<Datum<f32>>,
+}
+///Every difference between a trace and a [`GoldenTrace`]. The [`Display`](core::fmt::Display)
+///implementation prints one line per difference, which makes for a readable test failure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceDiff {
+    ///Steps whose outputs did not match, in order.
+    pub mismatches: Vec<TraceMismatch>,
+    ///The number of samples in the golden trace.
+    pub expected_len: usize,
+    ///The number of outputs in the actual trace.
+    pub actual_len: usize,
+}
+fn fmt_trace_output(
+    f: &mut core::fmt::Formatter<'_>,
+    output: &Option<Datum<f32>>,
+) -> core::fmt::Result {
+    match output {
+        Some(datum) => write!(f, "{} at {}", datum.value, datum.time),
+        None => write!(f, "None"),
+    }
+}
+impl core::fmt::Display for TraceDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.expected_len != self.actual_len {
+            writeln!(
+                f,
+                "length: expected {} steps, got {}",
+                self.expected_len, self.actual_len
+            )?;
+        }
+        for mismatch in &self.mismatches {
+            write!(f, "step {}: expected ", mismatch.step)?;
+            fmt_trace_output(f, &mismatch.expected.expected)?;
+            if mismatch.expected.expected.is_some() {
+                write!(f, " (tolerance {})", mismatch.expected.tolerance)?;
+            }
+            write!(f, ", got ")?;
+            fmt_trace_output(f, &mismatch.actual)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for TraceDiff {}
+///Known good outputs of a pipeline to compare new runs against, with a tolerance for each sample.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GoldenTrace {
+    samples: Vec<GoldenSample>,
+}
+impl GoldenTrace {
+    ///Constructor for [`GoldenTrace`]. The trace starts empty.
+    pub const fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+        }
+    }
+    ///Make a golden trace from recorded outputs, such as those returned by [`run_trace`] on a
+    ///version of the pipeline known to be correct, with the same tolerance for every sample.
+    pub fn from_outputs(outputs: &[Option<Datum<f32>>], tolerance: f32) -> Self {
+        Self {
+            samples: outputs
+                .iter()
+                .map(|output| GoldenSample {
+                    expected: *output,
+                    tolerance: tolerance,
+                })
+                .collect(),
+        }
+    }
+    ///Add an expected output to the end of the trace.
+    pub fn push(&mut self, expected: Option<Datum<f32>>, tolerance: f32) {
+        self.samples.push(GoldenSample {
+            expected: expected,
+            tolerance: tolerance,
+        });
+    }
+    ///Get the samples of the trace.
+    pub fn samples(&self) -> &[GoldenSample] {
+        &self.samples
+    }
+    ///Compare a trace of outputs to this one. Returns a [`TraceDiff`] describing every step that
+    ///does not match if the traces differ in any sample or in length.
+    pub fn compare(&self, actual: &[Option<Datum<f32>>]) -> Result<(), TraceDiff> {
+        let mismatches: Vec<TraceMismatch> = self
+            .samples
+            .iter()
+            .zip(actual.iter())
+            .enumerate()
+            .filter(|(_, (expected, actual))| !expected.matches(actual))
+            .map(|(step, (expected, actual))| TraceMismatch {
+                step: step,
+                expected: *expected,
+                actual: *actual,
+            })
+            .collect();
+        if mismatches.is_empty() && self.samples.len() == actual.len() {
+            return Ok(());
+        }
+        Err(TraceDiff {
+            mismatches: mismatches,
+            expected_len: self.samples.len(),
+            actual_len: actual.len(),
+        })
+    }
+}
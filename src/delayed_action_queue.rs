@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///An error from [`DelayedActionQueue::schedule_after`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScheduleError<S, E: Copy + Debug> {
+    ///The queue is full. The value that could not be scheduled is given back.
+    Full(S),
+    ///The time getter returned an error.
+    Other(Error<E>),
+}
+impl<S, E: Copy + Debug> From<Error<E>> for ScheduleError<S, E> {
+    fn from(was: Error<E>) -> Self {
+        Self::Other(was)
+    }
+}
+///Applies values to a settable at scheduled times. This is useful for things like releasing a
+///pneumatic latch a set time after a button is pressed or staging autonomous actions without
+///writing a timer for each one. Up to `N` actions can be waiting at once. Each update, every action
+///whose time has come is applied in order of time, with actions scheduled for the same time applied
+///in the order they were scheduled, and then the settable is updated. The time getter is not
+///updated by this.
+pub struct DelayedActionQueue<
+    S: Clone,
+    T: Settable<S, E> + ?Sized,
+    TG: TimeGetter<E> + ?Sized,
+    const N: usize,
+    E: Copy + Debug,
+> {
+    target: Reference<T>,
+    time_getter: Reference<TG>,
+    //The u64 is a sequence number for keeping actions at the same time in order.
+    actions: [Option<(Time, u64, S)>; N],
+    next_sequence: u64,
+    phantom_e: PhantomData<E>,
+}
+impl<
+        S: Clone,
+        T: Settable<S, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        const N: usize,
+        E: Copy + Debug,
+    > DelayedActionQueue<S, T, TG, N, E>
+{
+    ///Constructor for [`DelayedActionQueue`].
+    pub fn new(target: Reference<T>, time_getter: Reference<TG>) -> Self {
+        Self {
+            target: target,
+            time_getter: time_getter,
+            actions: core::array::from_fn(|_| None),
+            next_sequence: 0,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Schedule a value to be set at a time. If the queue is full, the value is given back as the
+    ///error.
+    pub fn schedule(&mut self, time: Time, value: S) -> Result<(), S> {
+        match self.actions.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some((time, self.next_sequence, value));
+                self.next_sequence += 1;
+                Ok(())
+            }
+            None => Err(value),
+        }
+    }
+    ///Schedule a value to be set after a delay from the current time. If the queue is full, the
+    ///value is given back in [`ScheduleError::Full`].
+    pub fn schedule_after(&mut self, delay: Time, value: S) -> Result<(), ScheduleError<S, E>> {
+        let time = self.time_getter.borrow().get()?;
+        self.schedule(time + delay, value)
+            .map_err(ScheduleError::Full)
+    }
+    ///Get the number of actions waiting to be applied.
+    pub fn len(&self) -> usize {
+        self.actions.iter().flatten().count()
+    }
+    ///Returns true if no actions are waiting to be applied.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    ///Get the time of the next action to be applied, or `None` if there is none.
+    pub fn next_time(&self) -> Option<Time> {
+        self.actions
+            .iter()
+            .flatten()
+            .map(|(time, _, _)| *time)
+            .min()
+    }
+    ///Drop every waiting action without applying it.
+    pub fn clear(&mut self) {
+        for slot in &mut self.actions {
+            *slot = None;
+        }
+    }
+}
+impl<
+        S: Clone,
+        T: Settable<S, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        const N: usize,
+        E: Copy + Debug,
+    > Updatable<E> for DelayedActionQueue<S, T, TG, N, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let now = self.time_getter.borrow().get()?;
+        loop {
+            let next = self
+                .actions
+                .iter_mut()
+                .filter(|slot| matches!(slot, Some((time, _, _)) if *time <= now))
+                .min_by_key(|slot| match slot {
+                    Some((time, sequence, _)) => (*time, *sequence),
+                    None => unreachable!(),
+                });
+            let (_, _, value) = match next.and_then(Option::take) {
+                Some(action) => action,
+                None => break,
+            };
+            self.target.borrow_mut().set(value)?;
+        }
+        self.target.borrow_mut().update()
+    }
+}
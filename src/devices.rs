@@ -5,7 +5,25 @@
 //!Connected terminals hold references to eachother's [`RefCell`]s. This module holds builtin
 //!devices.
 use crate::*;
+pub mod pneumatics;
 pub mod wrappers;
+///Common failures of hardware such as motor controllers, encoders, and valves. This is the default
+///error type of the hardware wrappers in [`wrappers`] and [`pneumatics`], giving code that handles
+///device errors or reports them as telemetry a shared vocabulary. Return these from your own
+///hardware implementations as `Error::Other(HardwareError::...)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HardwareError {
+    ///The device did not respond in time.
+    Timeout,
+    ///The device is not connected or stopped responding entirely.
+    Disconnected,
+    ///A value sent to or read from the device is outside of what it supports.
+    OutOfRange,
+    ///The device cannot accept a request right now.
+    Busy,
+    ///A vendor-specific error code.
+    Other(u8),
+}
 ///A device such that positive for one terminal is negative for the other.
 ///As this device has only one degree of freedom, it propagates [`Command`]s given to its terminals
 ///as well as [`State`]s.
@@ -318,6 +336,7 @@ impl<const N: usize, E: Copy + Debug> Device<E> for Axle<'_, N, E> {
 ///on. For example, if you have encoders on two branches, you would probably want to calculate the
 ///third from their readings. If you have encoders on all three branches, you can also choose to
 ///use all three values from them with the [`Equal`](DifferentialDistrust::Equal) variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DifferentialDistrust {
     ///Calculate the state of side 1 from sum and side 2 and do not call [`get`](Terminal::get) on it.
     Side1,
@@ -331,29 +350,32 @@ pub enum DifferentialDistrust {
 ///A mechanical differential mechanism.
 ///As this device has two degrees of freedom, it is not able to propagate [`Command`]s given to its
 ///terminals as it does with [`State`]s.
+///
+///By default, the sum branch is exactly side 1 plus side 2. Unequal couplings, such as in ball
+///differentials and some H-drives, can be modeled with [`set_ratios`](Differential::set_ratios),
+///in which case the sum branch is `side1_ratio` times side 1 plus `side2_ratio` times side 2.
 pub struct Differential<'a, E: Copy + Debug> {
     side1: RefCell<Terminal<'a, E>>,
     side2: RefCell<Terminal<'a, E>>,
     sum: RefCell<Terminal<'a, E>>,
     distrust: DifferentialDistrust,
+    side1_ratio: f32,
+    side2_ratio: f32,
 }
 impl<'a, E: Copy + Debug> Differential<'a, E> {
     ///Constructor for [`Differential`]. Trusts all branches equally.
     pub const fn new() -> Self {
-        Self {
-            side1: Terminal::new(),
-            side2: Terminal::new(),
-            sum: Terminal::new(),
-            distrust: DifferentialDistrust::Equal,
-        }
+        Self::with_distrust(DifferentialDistrust::Equal)
     }
     ///Constructor for [`Differential`] where you choose what to distrust.
-    pub fn with_distrust(distrust: DifferentialDistrust) -> Self {
+    pub const fn with_distrust(distrust: DifferentialDistrust) -> Self {
         Self {
             side1: Terminal::new(),
             side2: Terminal::new(),
             sum: Terminal::new(),
             distrust: distrust,
+            side1_ratio: 1.0,
+            side2_ratio: 1.0,
         }
     }
     ///Get a reference to the side 1 terminal of the differential.
@@ -368,10 +390,35 @@ impl<'a, E: Copy + Debug> Differential<'a, E> {
     pub fn get_sum(&self) -> &'a RefCell<Terminal<'a, E>> {
         unsafe { &*(&self.sum as *const RefCell<Terminal<'a, E>>) }
     }
+    ///Get which branch is being calculated from the others.
+    pub fn get_distrust(&self) -> DifferentialDistrust {
+        self.distrust
+    }
+    ///Choose which branch to calculate from the others. This can be changed at runtime, for
+    ///example if an encoder is found to be unreliable.
+    pub fn set_distrust(&mut self, distrust: DifferentialDistrust) {
+        self.distrust = distrust;
+    }
+    ///Get the ratios of side 1 and side 2 to the sum branch.
+    pub fn get_ratios(&self) -> (f32, f32) {
+        (self.side1_ratio, self.side2_ratio)
+    }
+    ///Set the ratios of side 1 and side 2 to the sum branch so that sum = `side1_ratio` * side1 +
+    ///`side2_ratio` * side2. Panics if either ratio is zero since the sides could then not be
+    ///calculated from the sum.
+    pub fn set_ratios(&mut self, side1_ratio: f32, side2_ratio: f32) {
+        assert!(
+            side1_ratio != 0.0 && side2_ratio != 0.0,
+            "rrtk::devices::Differential ratios must not be zero."
+        );
+        self.side1_ratio = side1_ratio;
+        self.side2_ratio = side2_ratio;
+    }
 }
 impl<E: Copy + Debug> Updatable<E> for Differential<'_, E> {
     fn update(&mut self) -> NothingOrError<E> {
         self.update_terminals()?;
+        let (a, b) = (self.side1_ratio, self.side2_ratio);
         match self.distrust {
             DifferentialDistrust::Side1 => {
                 let sum: Datum<State> = match self.sum.borrow().get()? {
@@ -382,7 +429,7 @@ impl<E: Copy + Debug> Updatable<E> for Differential<'_, E> {
                     Some(side2) => side2,
                     None => return Ok(()),
                 };
-                self.side1.borrow_mut().set(sum - side2)?;
+                self.side1.borrow_mut().set((sum - side2 * b) / a)?;
             }
             DifferentialDistrust::Side2 => {
                 let sum: Datum<State> = match self.sum.borrow().get()? {
@@ -393,7 +440,7 @@ impl<E: Copy + Debug> Updatable<E> for Differential<'_, E> {
                     Some(side1) => side1,
                     None => return Ok(()),
                 };
-                self.side2.borrow_mut().set(sum - side1)?;
+                self.side2.borrow_mut().set((sum - side1 * a) / b)?;
             }
             DifferentialDistrust::Sum => {
                 let side1: Datum<State> = match self.side1.borrow().get()? {
@@ -404,7 +451,7 @@ impl<E: Copy + Debug> Updatable<E> for Differential<'_, E> {
                     Some(side2) => side2,
                     None => return Ok(()),
                 };
-                self.sum.borrow_mut().set(side1 + side2)?;
+                self.sum.borrow_mut().set(side1 * a + side2 * b)?;
             }
             DifferentialDistrust::Equal => {
                 let sum: Datum<State> = match self.sum.borrow().get()? {
@@ -419,20 +466,16 @@ impl<E: Copy + Debug> Updatable<E> for Differential<'_, E> {
                     Some(side2) => side2,
                     None => return Ok(()),
                 };
-                //This minimizes (x-a)^2+(y-b)^2+(z-c)^2 given a+b=c where x, y, and z are the
-                //measured values of side1, side2, and sum respectively and a, b, and c are their
+                //This minimizes (x-p)^2+(y-q)^2+(z-r)^2 given ap+bq=r where x, y, and z are the
+                //measured values of side1, side2, and sum respectively and p, q, and r are their
                 //calculated estimated values based on all three constrained to add. This
                 //essentially means that the estimated values will be as close to the measured
-                //values as possible while forcing the two sides to add to the sum branch.
-                self.sum
-                    .borrow_mut()
-                    .set((side1 + side2 + sum * 2.0) / 3.0)?;
-                self.side1
-                    .borrow_mut()
-                    .set((side1 * 2.0 - side2 + sum) / 3.0)?;
-                self.side2
-                    .borrow_mut()
-                    .set((-side1 + side2 * 2.0 + sum) / 3.0)?;
+                //values as possible while forcing the two sides to add to the sum branch. It is
+                //the projection of the measurements onto the plane with normal (a, b, -1).
+                let residual = (side1 * a + side2 * b - sum) / (a * a + b * b + 1.0);
+                self.sum.borrow_mut().set(sum + residual)?;
+                self.side1.borrow_mut().set(side1 - residual * a)?;
+                self.side2.borrow_mut().set(side2 - residual * b)?;
             }
         }
         Ok(())
@@ -446,3 +489,115 @@ impl<E: Copy + Debug> Device<E> for Differential<'_, E> {
         Ok(())
     }
 }
+///A device with two terminals that passes [`State`]s and [`Command`]s straight through like an
+///[`Axle`] while recording every new [`Command`] that passes through it. The last `N` commands are
+///kept in a ring buffer. This is useful for diagnosing oscillations and for going back to a
+///previous setpoint. It implements [`History<Command, E>`], so the recorded commands can also be
+///replayed through a [`GetterFromHistory`].
+pub struct CommandRecorder<'a, const N: usize, E: Copy + Debug> {
+    axle: Axle<'a, 2, E>,
+    commands: [Option<Datum<Command>>; N],
+    next_index: usize,
+}
+impl<'a, const N: usize, E: Copy + Debug> CommandRecorder<'a, N, E> {
+    ///Constructor for [`CommandRecorder`].
+    pub fn new() -> Self {
+        if N < 1 {
+            panic!("rrtk::devices::CommandRecorder N must be at least 1.");
+        }
+        Self {
+            axle: Axle::new(),
+            commands: [None; N],
+            next_index: 0,
+        }
+    }
+    ///Get a reference to the side 1 terminal of the recorder.
+    pub fn get_terminal_1(&self) -> &'a RefCell<Terminal<'a, E>> {
+        self.axle.get_terminal(0)
+    }
+    ///Get a reference to the side 2 terminal of the recorder.
+    pub fn get_terminal_2(&self) -> &'a RefCell<Terminal<'a, E>> {
+        self.axle.get_terminal(1)
+    }
+    ///Get the number of commands currently recorded. This is never more than `N`.
+    pub fn len(&self) -> usize {
+        let mut len = 0;
+        for i in &self.commands {
+            if i.is_some() {
+                len += 1;
+            }
+        }
+        len
+    }
+    ///Returns true if no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.get_recorded(0).is_none()
+    }
+    ///Get a recorded command. `steps_back` is how many commands before the newest one to go, so 0
+    ///is the newest command. Returns `None` if not that many commands are recorded.
+    pub fn get_recorded(&self, steps_back: usize) -> Option<Datum<Command>> {
+        if steps_back >= N {
+            return None;
+        }
+        self.commands[(self.next_index + N - 1 - steps_back) % N]
+    }
+    ///Send a previously recorded command to both terminals again with a new timestamp.
+    ///`steps_back` works the same way as in [`get_recorded`](CommandRecorder::get_recorded).
+    ///Returns true if the command was sent and false if not that many commands are recorded. The
+    ///resent command is recorded again on the next update.
+    pub fn return_to_previous(&mut self, steps_back: usize, time: Time) -> Result<bool, Error<E>> {
+        let command = match self.get_recorded(steps_back) {
+            Some(command) => command.value,
+            None => return Ok(false),
+        };
+        let datum = Datum::new(time, command);
+        self.get_terminal_1().borrow_mut().set(datum)?;
+        self.get_terminal_2().borrow_mut().set(datum)?;
+        Ok(true)
+    }
+    ///Forget all recorded commands.
+    pub fn clear(&mut self) {
+        self.commands = [None; N];
+        self.next_index = 0;
+    }
+}
+impl<const N: usize, E: Copy + Debug> Default for CommandRecorder<'_, N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize, E: Copy + Debug> History<Command, E> for CommandRecorder<'_, N, E> {
+    ///Get the command that was in effect at a given time, that being the newest recorded command
+    ///not newer than it.
+    fn get(&self, time: Time) -> Option<Datum<Command>> {
+        let mut output: Option<Datum<Command>> = None;
+        for i in self.commands.iter().flatten() {
+            if i.time <= time {
+                output.replace_if_none_or_older_than(*i);
+            }
+        }
+        output
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for CommandRecorder<'_, N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.axle.update()?;
+        let command: Option<Datum<Command>> = self.get_terminal_1().borrow().get()?;
+        if let Some(command) = command {
+            let is_new = match self.get_recorded(0) {
+                Some(newest) => command.time > newest.time,
+                None => true,
+            };
+            if is_new {
+                self.commands[self.next_index] = Some(command);
+                self.next_index = (self.next_index + 1) % N;
+            }
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Device<E> for CommandRecorder<'_, N, E> {
+    fn update_terminals(&mut self) -> NothingOrError<E> {
+        self.axle.update_terminals()
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Wrappers for pneumatic solenoid valves and other on/off actuators. Rapidly switching a valve
+//!back and forth wastes air and wears it out, so each wrapper can enforce a minimum time between
+//!changes. A change requested too soon is held and applied by a later update once enough time has
+//!passed.
+use super::HardwareError;
+use crate::*;
+//Shared by both solenoid types to decide when a requested output may actually be applied.
+struct ToggleLimiter<V: Copy + PartialEq> {
+    min_toggle_interval: Time,
+    output: Option<V>,
+    last_toggle: Option<Time>,
+    pending: Option<V>,
+}
+impl<V: Copy + PartialEq> ToggleLimiter<V> {
+    const fn new(min_toggle_interval: Time) -> Self {
+        Self {
+            min_toggle_interval: min_toggle_interval,
+            output: None,
+            last_toggle: None,
+            pending: None,
+        }
+    }
+    //Returns the value to apply now if there is one.
+    fn poll(&mut self, now: Time) -> Option<V> {
+        let pending = self.pending?;
+        if self.output == Some(pending) {
+            self.pending = None;
+            return None;
+        }
+        if let Some(last_toggle) = self.last_toggle {
+            if now - last_toggle < self.min_toggle_interval {
+                return None;
+            }
+        }
+        self.pending = None;
+        self.output = Some(pending);
+        self.last_toggle = Some(now);
+        Some(pending)
+    }
+}
+///A single-acting solenoid valve controlled by a [`Settable<bool, E>`], where `true` opens the
+///valve. Values set to this are forwarded to the inner settable, but no more often than once per
+///`min_toggle_interval`. Updating this applies any held change that is now allowed and updates the
+///inner settable. The time getter is not updated by this.
+pub struct Solenoid<
+    T: Settable<bool, E>,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug = HardwareError,
+> {
+    settable_data: SettableData<bool, E>,
+    inner: T,
+    time_getter: Reference<TG>,
+    limiter: ToggleLimiter<bool>,
+}
+impl<T: Settable<bool, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Solenoid<T, TG, E> {
+    ///Constructor for [`Solenoid`]. Use `Time(0)` as `min_toggle_interval` to apply every change
+    ///immediately.
+    pub const fn new(inner: T, time_getter: Reference<TG>, min_toggle_interval: Time) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            inner: inner,
+            time_getter: time_getter,
+            limiter: ToggleLimiter::new(min_toggle_interval),
+        }
+    }
+    ///Get the value last applied to the inner settable, or `None` if nothing has been applied yet.
+    pub fn get_output(&self) -> Option<bool> {
+        self.limiter.output
+    }
+    ///Returns true if a requested change is being held until the minimum toggle interval passes.
+    pub fn is_pending(&self) -> bool {
+        self.limiter.pending.is_some()
+    }
+    fn apply(&mut self) -> NothingOrError<E> {
+        let now = self.time_getter.borrow().get()?;
+        if let Some(value) = self.limiter.poll(now) {
+            self.inner.set(value)?;
+        }
+        Ok(())
+    }
+}
+impl<T: Settable<bool, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Settable<bool, E>
+    for Solenoid<T, TG, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<bool, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<bool, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: bool) -> NothingOrError<E> {
+        self.limiter.pending = Some(value);
+        self.apply()
+    }
+}
+impl<T: Settable<bool, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for Solenoid<T, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.apply()?;
+        self.inner.update()
+    }
+}
+///The position of a [`DoubleSolenoid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DoubleSolenoidState {
+    ///Neither side is open.
+    Off,
+    ///The forward side is open.
+    Forward,
+    ///The reverse side is open.
+    Reverse,
+}
+///A double-acting solenoid valve with separate [`Settable<bool, E>`]s for its forward and reverse
+///sides. The two sides are never opened at the same time: the side being closed is always set
+///before the side being opened. Like [`Solenoid`], changes are applied no more often than once per
+///`min_toggle_interval`, and updating this applies any held change and updates both sides.
+pub struct DoubleSolenoid<
+    TF: Settable<bool, E>,
+    TR: Settable<bool, E>,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug = HardwareError,
+> {
+    settable_data: SettableData<DoubleSolenoidState, E>,
+    forward: TF,
+    reverse: TR,
+    time_getter: Reference<TG>,
+    limiter: ToggleLimiter<DoubleSolenoidState>,
+}
+impl<TF: Settable<bool, E>, TR: Settable<bool, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    DoubleSolenoid<TF, TR, TG, E>
+{
+    ///Constructor for [`DoubleSolenoid`].
+    pub const fn new(
+        forward: TF,
+        reverse: TR,
+        time_getter: Reference<TG>,
+        min_toggle_interval: Time,
+    ) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            forward: forward,
+            reverse: reverse,
+            time_getter: time_getter,
+            limiter: ToggleLimiter::new(min_toggle_interval),
+        }
+    }
+    ///Get the state last applied to the valve, or `None` if nothing has been applied yet.
+    pub fn get_output(&self) -> Option<DoubleSolenoidState> {
+        self.limiter.output
+    }
+    ///Returns true if a requested change is being held until the minimum toggle interval passes.
+    pub fn is_pending(&self) -> bool {
+        self.limiter.pending.is_some()
+    }
+    fn apply(&mut self) -> NothingOrError<E> {
+        let now = self.time_getter.borrow().get()?;
+        match self.limiter.poll(now) {
+            Some(DoubleSolenoidState::Off) => {
+                self.forward.set(false)?;
+                self.reverse.set(false)?;
+            }
+            Some(DoubleSolenoidState::Forward) => {
+                self.reverse.set(false)?;
+                self.forward.set(true)?;
+            }
+            Some(DoubleSolenoidState::Reverse) => {
+                self.forward.set(false)?;
+                self.reverse.set(true)?;
+            }
+            None => (),
+        }
+        Ok(())
+    }
+}
+impl<TF: Settable<bool, E>, TR: Settable<bool, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    Settable<DoubleSolenoidState, E> for DoubleSolenoid<TF, TR, TG, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<DoubleSolenoidState, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<DoubleSolenoidState, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: DoubleSolenoidState) -> NothingOrError<E> {
+        self.limiter.pending = Some(value);
+        self.apply()
+    }
+}
+impl<TF: Settable<bool, E>, TR: Settable<bool, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    Updatable<E> for DoubleSolenoid<TF, TR, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.apply()?;
+        self.forward.update()?;
+        self.reverse.update()
+    }
+}
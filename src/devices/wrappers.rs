@@ -2,12 +2,16 @@
 // Copyright 2024 UxuginPython
 //!Provided [`Device`] implementors that allow a raw [`Getter`] or [`Settable`] to work with the device
 //!system.
+use super::HardwareError;
 use crate::*;
 ///Connect a [`Settable<Command, E>`] to a [`Terminal<E>`] for use as a servo motor in the device
-///system.
-pub struct ActuatorWrapper<'a, T: Settable<TerminalData, E>, E: Copy + Debug> {
+///system. While disabled through [`Enableable`], this sends a zero velocity command to the inner
+///settable on every update instead of the command from the terminal, even if the terminal has no
+///command.
+pub struct ActuatorWrapper<'a, T: Settable<TerminalData, E>, E: Copy + Debug = HardwareError> {
     inner: T,
     terminal: RefCell<Terminal<'a, E>>,
+    enabled: bool,
 }
 impl<'a, T: Settable<TerminalData, E>, E: Copy + Debug> ActuatorWrapper<'a, T, E> {
     ///Constructor for [`ActuatorWrapper`].
@@ -15,6 +19,7 @@ impl<'a, T: Settable<TerminalData, E>, E: Copy + Debug> ActuatorWrapper<'a, T, E
         Self {
             inner: inner,
             terminal: Terminal::new(),
+            enabled: true,
         }
     }
     ///Get a reference to this wrapper's terminal.
@@ -22,6 +27,14 @@ impl<'a, T: Settable<TerminalData, E>, E: Copy + Debug> ActuatorWrapper<'a, T, E
         unsafe { &*(&self.terminal as *const RefCell<Terminal<'a, E>>) }
     }
 }
+impl<T: Settable<TerminalData, E>, E: Copy + Debug> Enableable for ActuatorWrapper<'_, T, E> {
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
 impl<T: Settable<TerminalData, E>, E: Copy + Debug> Device<E> for ActuatorWrapper<'_, T, E> {
     fn update_terminals(&mut self) -> NothingOrError<E> {
         self.terminal.borrow_mut().update()?;
@@ -31,13 +44,32 @@ impl<T: Settable<TerminalData, E>, E: Copy + Debug> Device<E> for ActuatorWrappe
 impl<T: Settable<TerminalData, E>, E: Copy + Debug> Updatable<E> for ActuatorWrapper<'_, T, E> {
     fn update(&mut self) -> NothingOrError<E> {
         self.update_terminals()?;
-        match self
+        let terminal_data: Option<Datum<TerminalData>> = self
             .terminal
             .borrow()
             .get()
-            .expect("Terminal TerminalData get always returns Ok")
-        {
-            Some(terminal_data) => self.inner.set(terminal_data.value)?,
+            .expect("Terminal TerminalData get always returns Ok");
+        match terminal_data {
+            Some(terminal_data) => {
+                let mut terminal_data: TerminalData = terminal_data.value;
+                if !self.enabled {
+                    terminal_data.command = Some(Command::Velocity(0.0));
+                }
+                self.inner.set(terminal_data)?;
+            }
+            //The stop command is sent even if the terminal has nothing. Without a time from the
+            //terminal, the time of the last data sent is reused.
+            None if !self.enabled => {
+                let time = match self.inner.get_last_request() {
+                    Some(last_request) => last_request.time,
+                    None => Time::default(),
+                };
+                self.inner.set(TerminalData {
+                    time: time,
+                    command: Some(Command::Velocity(0.0)),
+                    state: None,
+                })?;
+            }
             None => {}
         }
         self.inner.update()?;
@@ -45,7 +77,7 @@ impl<T: Settable<TerminalData, E>, E: Copy + Debug> Updatable<E> for ActuatorWra
     }
 }
 ///Connect a [`Getter<State, E>`] to a [`Terminal<E>`] for use as an encoder in the device system.
-pub struct GetterStateDeviceWrapper<'a, T: Getter<State, E>, E: Copy + Debug> {
+pub struct GetterStateDeviceWrapper<'a, T: Getter<State, E>, E: Copy + Debug = HardwareError> {
     inner: T,
     terminal: RefCell<Terminal<'a, E>>,
 }
@@ -83,14 +115,17 @@ impl<T: Getter<State, E>, E: Copy + Debug> Updatable<E> for GetterStateDeviceWra
 ///Connect a [`Settable<f32, E>`] motor to the device system through a
 ///[`CommandPID`](streams::control::CommandPID). See
 ///[`streams::control::CommandPID`] documentation for more information about how this works.
+///While disabled through [`Enableable`], this sets the motor to 0 and does not update the PID
+///controller.
 #[cfg(feature = "alloc")]
-pub struct PIDWrapper<'a, T: Settable<f32, E>, E: Copy + Debug + 'static> {
+pub struct PIDWrapper<'a, T: Settable<f32, E>, E: Copy + Debug + 'static = HardwareError> {
     terminal: RefCell<Terminal<'a, E>>,
     time: Reference<Time>,
     state: Reference<ConstantGetter<State, Time, E>>,
     command: Reference<ConstantGetter<Command, Time, E>>,
     pid: Reference<streams::control::CommandPID<ConstantGetter<State, Time, E>, E>>,
     inner: T,
+    enabled: bool,
 }
 #[cfg(feature = "alloc")]
 impl<'a, T: Settable<f32, E>, E: Copy + Debug + 'static> PIDWrapper<'a, T, E> {
@@ -125,6 +160,7 @@ impl<'a, T: Settable<f32, E>, E: Copy + Debug + 'static> PIDWrapper<'a, T, E> {
             command: command,
             pid: pid,
             inner: inner,
+            enabled: true,
         }
     }
     ///Get a reference to this wrapper's terminal.
@@ -133,6 +169,26 @@ impl<'a, T: Settable<f32, E>, E: Copy + Debug + 'static> PIDWrapper<'a, T, E> {
     }
 }
 #[cfg(feature = "alloc")]
+impl<T: Settable<f32, E>, E: Copy + Debug + 'static> Enableable for PIDWrapper<'_, T, E> {
+    fn set_enabled(&mut self, enabled: bool) {
+        if enabled == self.enabled {
+            return;
+        }
+        self.enabled = enabled;
+        if enabled {
+            self.inner.follow(to_dyn!(Getter<f32, E>, self.pid.clone()));
+        } else {
+            //The motor must stop following the PID controller or its output would overwrite the
+            //neutral value in the motor's update method.
+            self.inner.stop_following();
+            self.pid.borrow_mut().reset();
+        }
+    }
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+#[cfg(feature = "alloc")]
 impl<T: Settable<f32, E>, E: Copy + Debug + 'static> Device<E> for PIDWrapper<'_, T, E> {
     fn update_terminals(&mut self) -> NothingOrError<E> {
         self.terminal.borrow_mut().update()?;
@@ -157,10 +213,316 @@ impl<T: Settable<f32, E>, E: Copy + Debug + 'static> Updatable<E> for PIDWrapper
                     Some(command) => self.command.borrow_mut().set(command)?,
                     None => (),
                 }
-                self.pid.borrow_mut().update()?;
+                if self.enabled {
+                    self.pid.borrow_mut().update()?;
+                }
             }
             None => (),
         }
+        if !self.enabled {
+            self.inner.set(0.0)?;
+        }
+        self.inner.update()?;
+        Ok(())
+    }
+}
+///Connect a hobby servo or other position-commanded actuator without feedback to the device
+///system. Position commands from the terminal are clamped to the travel range from `min_position`
+///to `max_position` and mapped linearly onto the inner settable's native units from `min_output`
+///to `max_output`, such as 0 to 1 or 1000 to 2000 microseconds. Velocity and acceleration commands
+///are ignored. Since the servo cannot report where it is, a [`State`] is modeled by moving toward
+///the commanded position at `max_speed` and set on the terminal every update, starting at the
+///first commanded position. While disabled through [`Enableable`], new commands are not sent to
+///the inner settable. The time getter is not updated by this.
+pub struct ServoWrapper<
+    'a,
+    T: Settable<f32, E>,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug = HardwareError,
+> {
+    inner: T,
+    terminal: RefCell<Terminal<'a, E>>,
+    time_getter: Reference<TG>,
+    min_position: f32,
+    max_position: f32,
+    min_output: f32,
+    max_output: f32,
+    max_speed: f32,
+    target: Option<f32>,
+    modeled: Option<Datum<State>>,
+    enabled: bool,
+}
+impl<'a, T: Settable<f32, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    ServoWrapper<'a, T, TG, E>
+{
+    ///Constructor for [`ServoWrapper`]. Panics if `min_position` is not less than `max_position` or
+    ///if `max_speed` is not positive.
+    pub const fn new(
+        inner: T,
+        time_getter: Reference<TG>,
+        min_position: f32,
+        max_position: f32,
+        min_output: f32,
+        max_output: f32,
+        max_speed: f32,
+    ) -> Self {
+        assert!(
+            min_position < max_position,
+            "rrtk::devices::wrappers::ServoWrapper min_position must be less than max_position"
+        );
+        assert!(
+            max_speed > 0.0,
+            "rrtk::devices::wrappers::ServoWrapper max_speed must be positive"
+        );
+        Self {
+            inner: inner,
+            terminal: Terminal::new(),
+            time_getter: time_getter,
+            min_position: min_position,
+            max_position: max_position,
+            min_output: min_output,
+            max_output: max_output,
+            max_speed: max_speed,
+            target: None,
+            modeled: None,
+            enabled: true,
+        }
+    }
+    ///Get a reference to this wrapper's terminal.
+    pub fn get_terminal(&self) -> &'a RefCell<Terminal<'a, E>> {
+        unsafe { &*(&self.terminal as *const RefCell<Terminal<'a, E>>) }
+    }
+    ///Get the clamped position the servo was last sent to, or `None` if it has not been sent
+    ///anywhere yet.
+    pub fn get_target(&self) -> Option<f32> {
+        self.target
+    }
+    ///Get the position in native units that a position in millimeters maps to. The position is
+    ///clamped to the travel range first.
+    pub fn to_output(&self, position: f32) -> f32 {
+        let position = position.clamp(self.min_position, self.max_position);
+        let fraction = (position - self.min_position) / (self.max_position - self.min_position);
+        self.min_output + fraction * (self.max_output - self.min_output)
+    }
+    fn update_model(&mut self, time: Time) {
+        let target = match self.target {
+            Some(target) => target,
+            None => return,
+        };
+        let (position, velocity) = match self.modeled {
+            None => (target, 0.0),
+            Some(modeled) => {
+                let delta_time = f32::from(Quantity::from(time - modeled.time));
+                //If time has not moved forward, the model cannot have either.
+                if delta_time <= 0.0 {
+                    (modeled.value.position, modeled.value.velocity)
+                } else {
+                    let max_step = self.max_speed * delta_time;
+                    let step = (target - modeled.value.position).clamp(-max_step, max_step);
+                    (modeled.value.position + step, step / delta_time)
+                }
+            }
+        };
+        self.modeled = Some(Datum::new(time, State::new_raw(position, velocity, 0.0)));
+    }
+}
+impl<T: Settable<f32, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Enableable
+    for ServoWrapper<'_, T, TG, E>
+{
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+impl<T: Settable<f32, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Device<E>
+    for ServoWrapper<'_, T, TG, E>
+{
+    fn update_terminals(&mut self) -> NothingOrError<E> {
+        self.terminal.borrow_mut().update()?;
+        Ok(())
+    }
+}
+impl<T: Settable<f32, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ServoWrapper<'_, T, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_terminals()?;
+        let time = self.time_getter.borrow().get()?;
+        let command: Option<Datum<Command>> = self
+            .terminal
+            .borrow()
+            .get()
+            .expect("Terminal Command get always returns Ok");
+        if let Some(Datum {
+            value: Command::Position(position),
+            ..
+        }) = command
+        {
+            if self.enabled {
+                let position = position.clamp(self.min_position, self.max_position);
+                self.inner.set(self.to_output(position))?;
+                self.target = Some(position);
+            }
+        }
+        self.update_model(time);
+        if let Some(modeled) = self.modeled {
+            self.terminal.borrow_mut().set(modeled)?;
+        }
+        self.inner.update()?;
+        Ok(())
+    }
+}
+///Connect stream pipelines to the device system. The terminal's state comes from a
+///[`Getter<State, E>`] and commands it receives are forwarded to a [`Settable<Command, E>`], so a
+///node in the device graph can be backed entirely by streams. Updating this updates the settable
+///but not the getter, which should be updated with the rest of its pipeline.
+pub struct StreamBridge<
+    'a,
+    G: Getter<State, E> + ?Sized,
+    S: Settable<Command, E> + ?Sized,
+    E: Copy + Debug = HardwareError,
+> {
+    state: Reference<G>,
+    command: Reference<S>,
+    terminal: RefCell<Terminal<'a, E>>,
+}
+impl<'a, G: Getter<State, E> + ?Sized, S: Settable<Command, E> + ?Sized, E: Copy + Debug>
+    StreamBridge<'a, G, S, E>
+{
+    ///Constructor for [`StreamBridge`].
+    pub const fn new(state: Reference<G>, command: Reference<S>) -> Self {
+        Self {
+            state: state,
+            command: command,
+            terminal: Terminal::new(),
+        }
+    }
+    ///Get a reference to this bridge's terminal.
+    pub fn get_terminal(&self) -> &'a RefCell<Terminal<'a, E>> {
+        unsafe { &*(&self.terminal as *const RefCell<Terminal<'a, E>>) }
+    }
+}
+impl<G: Getter<State, E> + ?Sized, S: Settable<Command, E> + ?Sized, E: Copy + Debug> Device<E>
+    for StreamBridge<'_, G, S, E>
+{
+    fn update_terminals(&mut self) -> NothingOrError<E> {
+        self.terminal.borrow_mut().update()?;
+        Ok(())
+    }
+}
+impl<G: Getter<State, E> + ?Sized, S: Settable<Command, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for StreamBridge<'_, G, S, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_terminals()?;
+        let state = self.state.borrow().get()?;
+        if let Some(state) = state {
+            self.terminal.borrow_mut().set(state)?;
+        }
+        let command: Option<Datum<Command>> = self
+            .terminal
+            .borrow()
+            .get()
+            .expect("Terminal Command get always returns Ok");
+        if let Some(command) = command {
+            self.command.borrow_mut().set(command.value)?;
+        }
+        self.command.borrow_mut().update()
+    }
+}
+///Connect an actuator that can follow velocity commands but has no motion profiling to the device
+///system as if it could follow position commands. Position commands from the terminal are turned
+///into velocity commands proportional to the distance from the terminal's state to the target,
+///`gain` times the error, capped at `max_speed` in either direction. Velocity commands are also
+///capped at `max_speed`, and acceleration commands are passed through unchanged. If there is a
+///position command but no state to compare it to, the actuator is told to stop. While disabled
+///through [`Enableable`], this sends a zero velocity command to the inner settable on every update,
+///even if the terminal has no command.
+pub struct VelocityLimitedPositionWrapper<
+    'a,
+    T: Settable<Command, E>,
+    E: Copy + Debug = HardwareError,
+> {
+    inner: T,
+    terminal: RefCell<Terminal<'a, E>>,
+    gain: f32,
+    max_speed: f32,
+    enabled: bool,
+}
+impl<'a, T: Settable<Command, E>, E: Copy + Debug> VelocityLimitedPositionWrapper<'a, T, E> {
+    ///Constructor for [`VelocityLimitedPositionWrapper`]. Panics if `max_speed` is not positive.
+    pub const fn new(inner: T, gain: f32, max_speed: f32) -> Self {
+        assert!(
+            max_speed > 0.0,
+            "rrtk::devices::wrappers::VelocityLimitedPositionWrapper max_speed must be positive"
+        );
+        Self {
+            inner: inner,
+            terminal: Terminal::new(),
+            gain: gain,
+            max_speed: max_speed,
+            enabled: true,
+        }
+    }
+    ///Get a reference to this wrapper's terminal.
+    pub fn get_terminal(&self) -> &'a RefCell<Terminal<'a, E>> {
+        unsafe { &*(&self.terminal as *const RefCell<Terminal<'a, E>>) }
+    }
+    ///Get the command that would be sent to the inner settable for a command and state from the
+    ///terminal.
+    pub fn convert(&self, command: Command, state: Option<State>) -> Command {
+        match command {
+            Command::Position(target) => match state {
+                Some(state) => Command::Velocity(
+                    (self.gain * (target - state.position)).clamp(-self.max_speed, self.max_speed),
+                ),
+                None => Command::Velocity(0.0),
+            },
+            Command::Velocity(velocity) => {
+                Command::Velocity(velocity.clamp(-self.max_speed, self.max_speed))
+            }
+            Command::Acceleration(_) => command,
+        }
+    }
+}
+impl<T: Settable<Command, E>, E: Copy + Debug> Enableable
+    for VelocityLimitedPositionWrapper<'_, T, E>
+{
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+impl<T: Settable<Command, E>, E: Copy + Debug> Device<E>
+    for VelocityLimitedPositionWrapper<'_, T, E>
+{
+    fn update_terminals(&mut self) -> NothingOrError<E> {
+        self.terminal.borrow_mut().update()?;
+        Ok(())
+    }
+}
+impl<T: Settable<Command, E>, E: Copy + Debug> Updatable<E>
+    for VelocityLimitedPositionWrapper<'_, T, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_terminals()?;
+        let terminal_data: Option<Datum<TerminalData>> = self
+            .terminal
+            .borrow()
+            .get()
+            .expect("Terminal TerminalData get always returns Ok");
+        if !self.enabled {
+            self.inner.set(Command::Velocity(0.0))?;
+        } else if let Some(terminal_data) = terminal_data {
+            if let Some(command) = terminal_data.value.command {
+                self.inner
+                    .set(self.convert(command, terminal_data.value.state))?;
+            }
+        }
         self.inner.update()?;
         Ok(())
     }
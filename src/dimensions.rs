@@ -116,6 +116,8 @@
 use super::*;
 pub mod constants;
 pub use constants::*;
+#[cfg(feature = "uom")]
+mod uom_conversions;
 ///A time in nanoseconds.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
@@ -136,15 +138,34 @@ impl From<Time> for i64 {
         was.0
     }
 }
-//TODO: figure out for to use the Error enum with this
-impl TryFrom<Quantity> for Time {
-    type Error = ();
-    fn try_from(was: Quantity) -> Result<Self, ()> {
-        if was.unit.eq_assume_true(&SECOND) {
-            Ok(Self((was.value * 1_000_000_000.0) as i64))
-        } else {
-            Err(())
+///Formats the time as an integer number of nanoseconds, such as `1500 ns`.
+impl core::fmt::Display for Time {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} ns", self.0)
+    }
+}
+///Parses an integer number of nanoseconds followed by `ns`, as produced by the
+///[`Display`](core::fmt::Display) implementation, or a [`Quantity`] in seconds such as `1.5 s`.
+impl core::str::FromStr for Time {
+    type Err = ParseQuantityError;
+    fn from_str(s: &str) -> Result<Self, ParseQuantityError> {
+        let s = s.trim();
+        if let Some(nanoseconds) = s.strip_suffix("ns") {
+            return match nanoseconds.trim().parse::<i64>() {
+                Ok(nanoseconds) => Ok(Self(nanoseconds)),
+                Err(_) => Err(ParseQuantityError::InvalidNumber),
+            };
         }
+        Self::try_from(s.parse::<Quantity>()?).map_err(|_| ParseQuantityError::InvalidUnit)
+    }
+}
+impl TryFrom<Quantity> for Time {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&SECOND)
+            .map_err(|mismatch| mismatch.with_operation("Time::try_from"))?;
+        Ok(Self((was.value * 1_000_000_000.0) as i64))
     }
 }
 impl From<Time> for Quantity {
@@ -260,16 +281,29 @@ impl From<DimensionlessInteger> for i64 {
         was.0
     }
 }
-impl TryFrom<Quantity> for DimensionlessInteger {
-    type Error = ();
-    fn try_from(was: Quantity) -> Result<Self, ()> {
-        if was.unit.eq_assume_true(&DIMENSIONLESS) {
-            Ok(Self(was.value as i64))
-        } else {
-            Err(())
+impl core::fmt::Display for DimensionlessInteger {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+impl core::str::FromStr for DimensionlessInteger {
+    type Err = ParseQuantityError;
+    fn from_str(s: &str) -> Result<Self, ParseQuantityError> {
+        match s.trim().parse::<i64>() {
+            Ok(value) => Ok(Self(value)),
+            Err(_) => Err(ParseQuantityError::InvalidNumber),
         }
     }
 }
+impl TryFrom<Quantity> for DimensionlessInteger {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&DIMENSIONLESS)
+            .map_err(|mismatch| mismatch.with_operation("DimensionlessInteger::try_from"))?;
+        Ok(Self(was.value as i64))
+    }
+}
 impl From<DimensionlessInteger> for Quantity {
     fn from(was: DimensionlessInteger) -> Self {
         Quantity::new(was.0 as f32, DIMENSIONLESS)
@@ -465,6 +499,46 @@ impl Unit {
         )))]
         false
     }
+    ///Get the unit of the square root of a quantity of this unit. This halves the exponents, so
+    ///with dimension checking on, it panics if any exponent is odd. Use
+    ///[`checked_sqrt`](Unit::checked_sqrt) if the unit is not known ahead of time.
+    pub const fn sqrt(self) -> Self {
+        match self.checked_sqrt() {
+            Ok(unit) => unit,
+            Err(_) => panic!(
+                "rrtk::dimensions::Unit::sqrt cannot take the square root of a unit with an odd exponent"
+            ),
+        }
+    }
+    ///Get the unit of the square root of a quantity of this unit, returning a [`UnitMismatch`]
+    ///instead of panicking if any exponent is odd. The expected unit of the mismatch is this unit
+    ///with each odd exponent moved one toward zero. Always returns `Ok` with dimension checking
+    ///off.
+    #[allow(unused)]
+    pub const fn checked_sqrt(self) -> Result<Self, UnitMismatch> {
+        #[cfg(any(
+            feature = "dim_check_release",
+            all(debug_assertions, feature = "dim_check_debug")
+        ))]
+        {
+            if self.millimeter_exp % 2 != 0 || self.second_exp % 2 != 0 {
+                let expected = Self {
+                    millimeter_exp: self.millimeter_exp - self.millimeter_exp % 2,
+                    second_exp: self.second_exp - self.second_exp % 2,
+                };
+                return Err(UnitMismatch::new(expected, self).with_operation("square root"));
+            }
+            return Ok(Self {
+                millimeter_exp: self.millimeter_exp / 2,
+                second_exp: self.second_exp / 2,
+            });
+        }
+        #[cfg(not(any(
+            feature = "dim_check_release",
+            all(debug_assertions, feature = "dim_check_debug")
+        )))]
+        Ok(Self {})
+    }
     ///With dimension checking on, behaves exactly like [`const_assert_eq`](Unit::const_assert_eq).
     ///With dimension checking off, never panics.
     pub const fn assert_eq_assume_ok(&self, rhs: &Self) {
@@ -475,6 +549,148 @@ impl Unit {
     pub const fn assert_eq_assume_not_ok(&self, rhs: &Self) {
         assert!(self.eq_assume_false(rhs))
     }
+    ///Check that this unit is `expected`, returning a [`UnitMismatch`] describing both if it is not.
+    ///Always returns `Ok(())` with dimension checking off.
+    pub const fn check(&self, expected: &Self) -> Result<(), UnitMismatch> {
+        if self.eq_assume_true(expected) {
+            Ok(())
+        } else {
+            Err(UnitMismatch::new(*expected, *self))
+        }
+    }
+}
+///Details of a unit check that failed: the unit that was expected, the unit that was actually
+///found, and optionally a short tag naming the operation that was being performed. This is
+///returned by the checked [`Quantity`] operations and [`TryFrom<Quantity>`] implementations and is
+///carried by [`Error::UnitInvalid`](crate::Error::UnitInvalid). Without dimension checking, all
+///units compare equal, so mismatches are never produced.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitMismatch {
+    ///The unit that was required.
+    pub expected: Unit,
+    ///The unit that was found instead.
+    pub actual: Unit,
+    ///The operation that was being performed, such as `"addition"`.
+    pub operation: Option<&'static str>,
+}
+impl UnitMismatch {
+    ///Constructor for [`UnitMismatch`] without an operation tag.
+    pub const fn new(expected: Unit, actual: Unit) -> Self {
+        Self {
+            expected: expected,
+            actual: actual,
+            operation: None,
+        }
+    }
+    ///Set the operation tag.
+    pub const fn with_operation(mut self, operation: &'static str) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+}
+impl PartialEq for UnitMismatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected.eq_assume_true(&other.expected)
+            && self.actual.eq_assume_true(&other.actual)
+            && self.operation == other.operation
+    }
+}
+impl Eq for UnitMismatch {}
+///An error from parsing a [`Quantity`], [`Unit`], [`Time`], or [`DimensionlessInteger`] from a
+///string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseQuantityError {
+    ///The number could not be parsed.
+    InvalidNumber,
+    ///The unit could not be parsed or is not allowed for the type being parsed.
+    InvalidUnit,
+}
+///Formats the unit with `mm` for millimeters and `s` for seconds, such as `mm/s^2` or `1/s`.
+///Dimensionless units are formatted as an empty string. Since units do not store their exponents
+///without dimension checking, they are always formatted as an empty string in that case.
+impl core::fmt::Display for Unit {
+    #[allow(unused)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(any(
+            feature = "dim_check_release",
+            all(debug_assertions, feature = "dim_check_debug")
+        ))]
+        {
+            let factors = [("mm", self.millimeter_exp), ("s", self.second_exp)];
+            let mut numerator_empty = true;
+            for (symbol, exp) in factors {
+                if exp > 0 {
+                    if !numerator_empty {
+                        write!(f, "*")?;
+                    }
+                    write!(f, "{}", symbol)?;
+                    if exp > 1 {
+                        write!(f, "^{}", exp)?;
+                    }
+                    numerator_empty = false;
+                }
+            }
+            for (symbol, exp) in factors {
+                if exp < 0 {
+                    if numerator_empty {
+                        write!(f, "1")?;
+                        numerator_empty = false;
+                    }
+                    write!(f, "/{}", symbol)?;
+                    if exp < -1 {
+                        write!(f, "^{}", -(exp as i16))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+///Parses the notation produced by the [`Display`](core::fmt::Display) implementation. More
+///generally, a unit is `mm`, `s`, or `1`, each optionally followed by `^` and an integer exponent,
+///joined by `*` or `/`, which are applied from left to right. An empty string is dimensionless.
+impl core::str::FromStr for Unit {
+    type Err = ParseQuantityError;
+    fn from_str(s: &str) -> Result<Self, ParseQuantityError> {
+        let mut millimeter_exp: i8 = 0;
+        let mut second_exp: i8 = 0;
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Ok(DIMENSIONLESS);
+        }
+        let mut sign = 1;
+        loop {
+            let end = rest.find(['*', '/']).unwrap_or(rest.len());
+            let term = rest[..end].trim();
+            let (symbol, exp) = match term.split_once('^') {
+                Some((symbol, exp)) => (
+                    symbol.trim(),
+                    exp.trim()
+                        .parse::<i8>()
+                        .map_err(|_| ParseQuantityError::InvalidUnit)?,
+                ),
+                None => (term, 1),
+            };
+            let exp = exp
+                .checked_mul(sign)
+                .ok_or(ParseQuantityError::InvalidUnit)?;
+            let total = match symbol {
+                "mm" => &mut millimeter_exp,
+                "s" => &mut second_exp,
+                "1" => &mut 0,
+                _ => return Err(ParseQuantityError::InvalidUnit),
+            };
+            *total = total
+                .checked_add(exp)
+                .ok_or(ParseQuantityError::InvalidUnit)?;
+            if end == rest.len() {
+                break;
+            }
+            sign = if rest[end..].starts_with('/') { -1 } else { 1 };
+            rest = &rest[end + 1..];
+        }
+        Ok(Unit::new(millimeter_exp, second_exp))
+    }
 }
 impl From<PositionDerivative> for Unit {
     #[allow(unused)]
@@ -516,13 +732,15 @@ impl TryFrom<MotionProfilePiece> for Unit {
 impl Add for Unit {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
-        self.assert_eq_assume_ok(&rhs);
+        if let Err(mismatch) = rhs.check(&self) {
+            panic!("{:?}", mismatch.with_operation("addition"));
+        }
         self
     }
 }
 impl AddAssign for Unit {
     fn add_assign(&mut self, rhs: Self) {
-        self.assert_eq_assume_ok(&rhs);
+        *self = *self + rhs;
     }
 }
 ///The [`Sub`] implementation for [`Unit`] acts like you are trying to subtract quantities of the unit,
@@ -535,13 +753,15 @@ impl AddAssign for Unit {
 impl Sub for Unit {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
-        self.assert_eq_assume_ok(&rhs);
+        if let Err(mismatch) = rhs.check(&self) {
+            panic!("{:?}", mismatch.with_operation("subtraction"));
+        }
         self
     }
 }
 impl SubAssign for Unit {
     fn sub_assign(&mut self, rhs: Self) {
-        self.assert_eq_assume_ok(&rhs);
+        *self = *self - rhs;
     }
 }
 ///The [`Mul`] implementation for [`Unit`] acts like you are trying to multiply quantities of the unit,
@@ -661,6 +881,53 @@ impl Quantity {
             self.unit,
         )
     }
+    ///Return the quantity if it has the expected unit or a [`UnitMismatch`] if it does not.
+    pub const fn expect_unit(self, expected: Unit) -> Result<Self, UnitMismatch> {
+        match self.unit.check(&expected) {
+            Ok(()) => Ok(self),
+            Err(mismatch) => Err(mismatch),
+        }
+    }
+    ///Add two quantities, returning a [`UnitMismatch`] instead of panicking if their units differ.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, UnitMismatch> {
+        rhs.unit
+            .check(&self.unit)
+            .map_err(|mismatch| mismatch.with_operation("addition"))?;
+        Ok(self + rhs)
+    }
+    ///Subtract two quantities, returning a [`UnitMismatch`] instead of panicking if their units
+    ///differ.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, UnitMismatch> {
+        rhs.unit
+            .check(&self.unit)
+            .map_err(|mismatch| mismatch.with_operation("subtraction"))?;
+        Ok(self - rhs)
+    }
+}
+///Formats the value followed by a space and the [`Unit`], such as `123.4 mm/s`. Formatting options
+///such as precision apply to the value. Dimensionless quantities, and all quantities without
+///dimension checking, are formatted without the space and unit.
+impl core::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.value, f)?;
+        if !self.unit.eq_assume_true(&DIMENSIONLESS) {
+            write!(f, " {}", self.unit)?;
+        }
+        Ok(())
+    }
+}
+///Parses a number optionally followed by whitespace and a [`Unit`] in the notation accepted by its
+///[`FromStr`](core::str::FromStr) implementation, such as `123.4 mm/s`.
+impl core::str::FromStr for Quantity {
+    type Err = ParseQuantityError;
+    fn from_str(s: &str) -> Result<Self, ParseQuantityError> {
+        let s = s.trim();
+        let (value, unit) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+        let value = value
+            .parse::<f32>()
+            .map_err(|_| ParseQuantityError::InvalidNumber)?;
+        Ok(Self::new(value, unit.parse()?))
+    }
 }
 impl From<Command> for Quantity {
     fn from(was: Command) -> Self {
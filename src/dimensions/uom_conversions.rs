@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Conversions between RRTK's types and [`uom`]'s `f32` SI quantities. Only available with the
+//!`uom` feature. Converting a [`Quantity`] to a `uom` quantity is a [`TryFrom`] since the unit
+//!must match. As with RRTK's other conversions, the unit is only checked with dimension checking
+//!on.
+use super::*;
+use uom::si::f32::{Acceleration, Length, Ratio, Time as UomTime, Velocity};
+use uom::si::{acceleration, length, ratio, time, velocity};
+impl From<Length> for Quantity {
+    fn from(was: Length) -> Self {
+        Quantity::new(was.get::<length::millimeter>(), MILLIMETER)
+    }
+}
+impl TryFrom<Quantity> for Length {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&MILLIMETER)
+            .map_err(|mismatch| mismatch.with_operation("Length::try_from"))?;
+        Ok(Length::new::<length::millimeter>(was.value))
+    }
+}
+impl From<Velocity> for Quantity {
+    fn from(was: Velocity) -> Self {
+        Quantity::new(
+            was.get::<velocity::millimeter_per_second>(),
+            MILLIMETER_PER_SECOND,
+        )
+    }
+}
+impl TryFrom<Quantity> for Velocity {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&MILLIMETER_PER_SECOND)
+            .map_err(|mismatch| mismatch.with_operation("Velocity::try_from"))?;
+        Ok(Velocity::new::<velocity::millimeter_per_second>(was.value))
+    }
+}
+impl From<Acceleration> for Quantity {
+    fn from(was: Acceleration) -> Self {
+        Quantity::new(
+            was.get::<acceleration::millimeter_per_second_squared>(),
+            MILLIMETER_PER_SECOND_SQUARED,
+        )
+    }
+}
+impl TryFrom<Quantity> for Acceleration {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&MILLIMETER_PER_SECOND_SQUARED)
+            .map_err(|mismatch| mismatch.with_operation("Acceleration::try_from"))?;
+        Ok(Acceleration::new::<
+            acceleration::millimeter_per_second_squared,
+        >(was.value))
+    }
+}
+impl From<UomTime> for Quantity {
+    fn from(was: UomTime) -> Self {
+        Quantity::new(was.get::<time::second>(), SECOND)
+    }
+}
+impl TryFrom<Quantity> for UomTime {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&SECOND)
+            .map_err(|mismatch| mismatch.with_operation("UomTime::try_from"))?;
+        Ok(UomTime::new::<time::second>(was.value))
+    }
+}
+impl From<Ratio> for Quantity {
+    fn from(was: Ratio) -> Self {
+        Quantity::new(was.get::<ratio::ratio>(), DIMENSIONLESS)
+    }
+}
+impl TryFrom<Quantity> for Ratio {
+    type Error = UnitMismatch;
+    fn try_from(was: Quantity) -> Result<Self, UnitMismatch> {
+        was.unit
+            .check(&DIMENSIONLESS)
+            .map_err(|mismatch| mismatch.with_operation("Ratio::try_from"))?;
+        Ok(Ratio::new::<ratio::ratio>(was.value))
+    }
+}
+impl From<UomTime> for Time {
+    fn from(was: UomTime) -> Self {
+        Self((was.get::<time::second>() * 1_000_000_000.0) as i64)
+    }
+}
+impl From<Time> for UomTime {
+    fn from(was: Time) -> Self {
+        UomTime::new::<time::second>(was.0 as f32 / 1_000_000_000.0)
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!High-level helpers for differential drive robots. These combine a [`MotionProfile`], a
+//![`CommandPID`](streams::control::CommandPID) for each side, and the kinematics of a differential
+//!drive into a single [`Updatable`] for common movements such as driving straight and turning in
+//!place.
+use crate::*;
+use streams::control::CommandPID;
+///Get the command that a profile made to start at position 0 should send at a given time if the
+///mechanism actually started at `start_position`.
+fn offset_command<E: Copy + Debug>(
+    profile: &MotionProfile,
+    time: Time,
+    start_position: f32,
+) -> Option<Command> {
+    let command = <MotionProfile as History<Command, E>>::get(profile, time)?.value;
+    Some(match command {
+        Command::Position(position) => Command::Position(position + start_position),
+        _ => command,
+    })
+}
+///The encoders, motors, and time getter of a differential drive that a [`DifferentialDriveMove`]
+///uses. The motors are moved into the movement, which updates them.
+pub struct DifferentialDriveHardware<G: ?Sized, M, TG: ?Sized> {
+    ///The encoder of the left side.
+    pub left_encoder: Reference<G>,
+    ///The encoder of the right side.
+    pub right_encoder: Reference<G>,
+    ///The motor of the left side.
+    pub left_motor: M,
+    ///The motor of the right side.
+    pub right_motor: M,
+    ///The time getter.
+    pub time_getter: Reference<TG>,
+}
+///A profiled movement of a differential drive. Each side follows its own [`MotionProfile`] relative
+///to where its encoder was at the first update with a [`CommandPID`] and sets its motor to the
+///result. The encoders and time getter are not updated by this, but the motors are. Create this
+///with [`drive_straight`](DifferentialDriveMove::drive_straight) or
+///[`turn_in_place`](DifferentialDriveMove::turn_in_place). As a [`Getter<bool, E>`], this returns
+///whether the movement is [`done`](DifferentialDriveMove::done).
+pub struct DifferentialDriveMove<
+    G: Getter<State, E> + ?Sized,
+    M: Settable<f32, E>,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    left_profile: MotionProfile,
+    right_profile: MotionProfile,
+    left_encoder: Reference<G>,
+    right_encoder: Reference<G>,
+    left_pid: CommandPID<G, E>,
+    right_pid: CommandPID<G, E>,
+    left_motor: M,
+    right_motor: M,
+    time_getter: Reference<TG>,
+    //Start time, left start position, and right start position.
+    start: Option<(Time, f32, f32)>,
+    last_time: Option<Time>,
+}
+impl<
+        G: Getter<State, E> + ?Sized,
+        M: Settable<f32, E>,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > DifferentialDriveMove<G, M, TG, E>
+{
+    ///Create a movement where the left and right sides move the given distances following their
+    ///own profiles. [`drive_straight`](DifferentialDriveMove::drive_straight) and
+    ///[`turn_in_place`](DifferentialDriveMove::turn_in_place) use this internally.
+    pub fn try_new(
+        hardware: DifferentialDriveHardware<G, M, TG>,
+        left_distance: Quantity,
+        right_distance: Quantity,
+        max_vel: Quantity,
+        max_acc: Quantity,
+        kvalues: PositionDerivativeDependentPIDKValues,
+    ) -> Result<Self, MotionProfileError> {
+        for distance in [left_distance, right_distance] {
+            distance.unit.check(&MILLIMETER).map_err(|mismatch| {
+                MotionProfileError::UnitInvalid(
+                    mismatch.with_operation("DifferentialDriveMove::try_new"),
+                )
+            })?;
+        }
+        let left_profile = MotionProfile::try_new(
+            State::default(),
+            State::new_raw(f32::from(left_distance), 0.0, 0.0),
+            max_vel,
+            max_acc,
+        )?;
+        let right_profile = MotionProfile::try_new(
+            State::default(),
+            State::new_raw(f32::from(right_distance), 0.0, 0.0),
+            max_vel,
+            max_acc,
+        )?;
+        let DifferentialDriveHardware {
+            left_encoder,
+            right_encoder,
+            left_motor,
+            right_motor,
+            time_getter,
+        } = hardware;
+        let initial_command = Command::Velocity(0.0);
+        Ok(Self {
+            left_profile: left_profile,
+            right_profile: right_profile,
+            left_pid: CommandPID::new(left_encoder.clone(), initial_command, kvalues),
+            right_pid: CommandPID::new(right_encoder.clone(), initial_command, kvalues),
+            left_encoder: left_encoder,
+            right_encoder: right_encoder,
+            left_motor: left_motor,
+            right_motor: right_motor,
+            time_getter: time_getter,
+            start: None,
+            last_time: None,
+        })
+    }
+    ///Drive straight forward by `distance`, or backward if it is negative.
+    pub fn drive_straight(
+        hardware: DifferentialDriveHardware<G, M, TG>,
+        distance: Quantity,
+        max_vel: Quantity,
+        max_acc: Quantity,
+        kvalues: PositionDerivativeDependentPIDKValues,
+    ) -> Result<Self, MotionProfileError> {
+        Self::try_new(hardware, distance, distance, max_vel, max_acc, kvalues)
+    }
+    ///Turn in place by `angle` radians, counterclockwise if it is positive. `track_width` is the
+    ///distance between the left and right wheels. `max_vel` and `max_acc` apply to the wheels, not
+    ///the angle.
+    pub fn turn_in_place(
+        hardware: DifferentialDriveHardware<G, M, TG>,
+        angle: f32,
+        track_width: Quantity,
+        max_vel: Quantity,
+        max_acc: Quantity,
+        kvalues: PositionDerivativeDependentPIDKValues,
+    ) -> Result<Self, MotionProfileError> {
+        let arc = Quantity::dimensionless(angle / 2.0) * track_width;
+        Self::try_new(hardware, -arc, arc, max_vel, max_acc, kvalues)
+    }
+    ///Returns true if the movement has started and both profiles are complete. The PID controllers
+    ///keep holding the end position after this.
+    pub fn done(&self) -> bool {
+        match (self.start, self.last_time) {
+            (Some((start_time, _, _)), Some(last_time)) => {
+                let elapsed = last_time - start_time;
+                elapsed >= self.left_profile.duration() && elapsed >= self.right_profile.duration()
+            }
+            _ => false,
+        }
+    }
+}
+impl<
+        G: Getter<State, E> + ?Sized,
+        M: Settable<f32, E>,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Getter<bool, E> for DifferentialDriveMove<G, M, TG, E>
+{
+    fn get(&self) -> Output<bool, E> {
+        match self.last_time {
+            Some(time) => Ok(Some(Datum::new(time, self.done()))),
+            None => Ok(None),
+        }
+    }
+}
+impl<
+        G: Getter<State, E> + ?Sized,
+        M: Settable<f32, E>,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Updatable<E> for DifferentialDriveMove<G, M, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        let (start_time, left_start, right_start) = match self.start {
+            Some(start) => start,
+            None => {
+                let left = self.left_encoder.borrow().get()?;
+                let right = self.right_encoder.borrow().get()?;
+                match (left, right) {
+                    (Some(left), Some(right)) => {
+                        let start = (time, left.value.position, right.value.position);
+                        self.start = Some(start);
+                        start
+                    }
+                    //The movement cannot start until both start positions are known.
+                    _ => return Ok(()),
+                }
+            }
+        };
+        self.last_time = Some(time);
+        let elapsed = time - start_time;
+        if let Some(command) = offset_command::<E>(&self.left_profile, elapsed, left_start) {
+            self.left_pid.set(command)?;
+        }
+        if let Some(command) = offset_command::<E>(&self.right_profile, elapsed, right_start) {
+            self.right_pid.set(command)?;
+        }
+        self.left_pid.update()?;
+        self.right_pid.update()?;
+        if let Some(output) = self.left_pid.get()? {
+            self.left_motor.set(output.value)?;
+        }
+        if let Some(output) = self.right_pid.get()? {
+            self.right_motor.set(output.value)?;
+        }
+        self.left_motor.update()?;
+        self.right_motor.update()?;
+        Ok(())
+    }
+}
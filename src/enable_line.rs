@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///A single robot-wide enable signal shared by reference between many outputs. Wrap each output in
+///an [`EnableLineGate`] holding a reference to the same line, and one call to
+///[`disable`](Enableable::disable) drives every one of them to its neutral value on its next
+///update. A line can also have a watchdog, which trips it if [`feed`](EnableLine::feed) is not
+///called often enough, for example because the loop sending commands has hung. Once tripped, the
+///watchdog stays tripped until [`reset_watchdog`](EnableLine::reset_watchdog) is called, so a
+///loop that hangs and then comes back does not start moving things again on its own. If an
+///[event log](events) is set, watchdog trips and recoveries are logged when the line is updated.
+pub struct EnableLine<E: Copy + Debug> {
+    enabled: bool,
+    watchdog: Option<(Time, Reference<dyn TimeGetter<E>>)>,
+    last_feed: Option<Time>,
+    event_log: Option<Reference<dyn events::EventSink>>,
+    trip_logged: bool,
+    latched: bool,
+}
+impl<E: Copy + Debug> EnableLine<E> {
+    ///Constructor for [`EnableLine`]. It starts enabled with no watchdog.
+    pub const fn new() -> Self {
+        Self {
+            enabled: true,
+            watchdog: None,
+            last_feed: None,
+            event_log: None,
+            trip_logged: false,
+            latched: false,
+        }
+    }
+    ///Trip the line whenever more than `timeout` has passed since the last
+    ///[`feed`](EnableLine::feed) according to `time_getter`. The line is tripped until it is first
+    ///fed. If `time_getter` returns an error, the line is also treated as tripped.
+    pub fn set_watchdog(&mut self, timeout: Time, time_getter: Reference<dyn TimeGetter<E>>) {
+        self.watchdog = Some((timeout, time_getter));
+        self.last_feed = None;
+        self.trip_logged = false;
+        self.latched = false;
+    }
+    ///Remove the watchdog.
+    pub fn clear_watchdog(&mut self) {
+        self.watchdog = None;
+        self.last_feed = None;
+        self.trip_logged = false;
+        self.latched = false;
+    }
+    ///Clear a watchdog trip. Like after [`set_watchdog`](EnableLine::set_watchdog), the line stays
+    ///tripped until it is next [fed](EnableLine::feed).
+    pub fn reset_watchdog(&mut self) {
+        self.last_feed = None;
+        self.latched = false;
+    }
+    ///Log watchdog trips and recoveries to an event log. Being tripped before the first feed is
+    ///not logged.
+    pub fn set_event_log(&mut self, event_log: Reference<dyn events::EventSink>) {
+        self.event_log = Some(event_log);
+    }
+    ///Stop logging events.
+    pub fn clear_event_log(&mut self) {
+        self.event_log = None;
+    }
+    ///Reset the watchdog timer. This does nothing if there is no watchdog, and it does not clear a
+    ///trip that has already happened; use [`reset_watchdog`](EnableLine::reset_watchdog) for that.
+    pub fn feed(&mut self) -> NothingOrError<E> {
+        self.latch();
+        if self.latched {
+            return Ok(());
+        }
+        if let Some((_, time_getter)) = &self.watchdog {
+            self.last_feed = Some(time_getter.borrow().get()?);
+        }
+        Ok(())
+    }
+    ///Returns true if the watchdog has tripped. This is always false without a watchdog.
+    pub fn is_tripped(&self) -> bool {
+        if self.latched {
+            return true;
+        }
+        match (&self.watchdog, self.last_feed) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some((timeout, time_getter)), Some(last_feed)) => match time_getter.borrow().get() {
+                Ok(time) => time - last_feed > *timeout,
+                Err(_) => true,
+            },
+        }
+    }
+    //Being tripped before the first feed is not latched since it is cleared by feeding.
+    fn latch(&mut self) {
+        if self.last_feed.is_some() && self.is_tripped() {
+            self.latched = true;
+        }
+    }
+    ///Returns true if outputs on this line may be driven, meaning that the line is enabled and the
+    ///watchdog has not tripped.
+    pub fn is_live(&self) -> bool {
+        self.enabled && !self.is_tripped()
+    }
+}
+impl<E: Copy + Debug> Enableable for EnableLine<E> {
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+impl<E: Copy + Debug> Updatable<E> for EnableLine<E> {
+    ///Updating latches watchdog trips as soon as they happen and logs events. Without updating,
+    ///a trip is still latched on the next [`feed`](EnableLine::feed).
+    fn update(&mut self) -> NothingOrError<E> {
+        self.latch();
+        let (event_log, (timeout, time_getter)) = match (&self.event_log, &self.watchdog) {
+            (Some(event_log), Some(watchdog)) => (event_log, watchdog),
+            _ => return Ok(()),
+        };
+        let tripped = self.is_tripped();
+        let (severity, code, payload) = if tripped && !self.trip_logged && self.last_feed.is_some()
+        {
+            (
+                events::Severity::Error,
+                events::WATCHDOG_TRIPPED,
+                f32::from(Quantity::from(*timeout)),
+            )
+        } else if !tripped && self.trip_logged {
+            (events::Severity::Info, events::WATCHDOG_RECOVERED, 0.0)
+        } else {
+            return Ok(());
+        };
+        let time = time_getter.borrow().get()?;
+        event_log
+            .borrow_mut()
+            .push(events::Event::new(time, severity, code, payload));
+        self.trip_logged = tripped;
+        Ok(())
+    }
+}
+impl<E: Copy + Debug> Default for EnableLine<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+///Connects an output to an [`EnableLine`]. Values set to this are forwarded to the inner settable
+///on update while the line is [live](EnableLine::is_live), and `neutral` is set instead while it
+///is not. Any value set before the line stopped being live is discarded, so the inner settable is
+///held at `neutral` until a new value is set after the line is live again. Updating this also
+///updates the inner settable.
+pub struct EnableLineGate<S: Clone, T: Settable<S, E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<S, E>,
+    inner: Reference<T>,
+    line: Reference<EnableLine<E>>,
+    neutral: S,
+}
+impl<S: Clone, T: Settable<S, E> + ?Sized, E: Copy + Debug> EnableLineGate<S, T, E> {
+    ///Constructor for [`EnableLineGate`].
+    pub const fn new(inner: Reference<T>, line: Reference<EnableLine<E>>, neutral: S) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            inner: inner,
+            line: line,
+            neutral: neutral,
+        }
+    }
+}
+impl<S: Clone, T: Settable<S, E> + ?Sized, E: Copy + Debug> Settable<S, E>
+    for EnableLineGate<S, T, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<S, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<S, E> {
+        &mut self.settable_data
+    }
+    ///The value is applied on the next update.
+    fn impl_set(&mut self, _value: S) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+impl<S: Clone, T: Settable<S, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for EnableLineGate<S, T, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        let output = if self.line.borrow().is_live() {
+            self.get_last_request()
+        } else {
+            self.settable_data.last_request = None;
+            Some(self.neutral.clone())
+        };
+        if let Some(output) = output {
+            self.inner.borrow_mut().set(output)?;
+        }
+        self.inner.borrow_mut().update()
+    }
+}
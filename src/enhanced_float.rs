@@ -7,7 +7,27 @@
 pub fn powf(x: f32, y: f32) -> f32 {
     x.powf(y)
 }
+#[cfg(any(feature = "std", all(feature = "micromath", not(feature = "libm"))))]
+#[inline]
+pub fn sqrtf(x: f32) -> f32 {
+    x.sqrt()
+}
+#[cfg(any(feature = "std", all(feature = "micromath", not(feature = "libm"))))]
+#[inline]
+pub fn sinf(x: f32) -> f32 {
+    x.sin()
+}
+#[cfg(any(feature = "std", all(feature = "micromath", not(feature = "libm"))))]
+#[inline]
+pub fn cosf(x: f32) -> f32 {
+    x.cos()
+}
+#[cfg(any(feature = "std", all(feature = "micromath", not(feature = "libm"))))]
+#[inline]
+pub fn atan2f(y: f32, x: f32) -> f32 {
+    y.atan2(x)
+}
 #[cfg(all(feature = "libm", not(feature = "std")))]
-pub use libm::powf;
+pub use libm::{atan2f, cosf, powf, sinf, sqrtf};
 #[cfg(all(feature = "micromath", not(feature = "std"), not(feature = "libm")))]
 pub use micromath::F32Ext;
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!A fixed-capacity event log for recording things like watchdog trips and faults as they happen
+//!so that they can be looked at after a match or sent out as telemetry. Subsystems push
+//![`Event`]s to anything implementing [`EventSink`], which is normally an [`EventLog`]. Types in
+//!RRTK that can log events have a `set_event_log` method and log nothing until it is called.
+//!
+//!Event codes below 1000 are reserved for RRTK. The ones it currently uses are the constants in
+//!this module.
+use crate::*;
+///Logged by [`EnableLine`] when its watchdog trips after having been fed. The payload is the
+///watchdog timeout in seconds.
+pub const WATCHDOG_TRIPPED: u16 = 1;
+///Logged by [`EnableLine`] when its watchdog is fed again after a trip has been
+///[reset](EnableLine::reset_watchdog). The payload is 0.
+pub const WATCHDOG_RECOVERED: u16 = 2;
+///Logged by [`FollowingErrorMonitor`](streams::control::FollowingErrorMonitor) when it faults.
+///The payload is the following error that caused the fault.
+pub const FOLLOWING_ERROR_FAULT: u16 = 3;
+///How serious an [`Event`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    ///Only useful when debugging.
+    Debug,
+    ///Normal operation worth knowing about.
+    Info,
+    ///Something unexpected that did not stop anything from working.
+    Warning,
+    ///Something stopped working.
+    Error,
+    ///Something stopped working in a way that may be unsafe.
+    Critical,
+}
+///A single entry in an event log.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Event {
+    ///When the event happened.
+    pub time: Time,
+    ///How serious the event is.
+    pub severity: Severity,
+    ///What happened. Codes below 1000 are reserved for RRTK.
+    pub code: u16,
+    ///Extra information about the event. What this means depends on the code.
+    pub payload: f32,
+}
+impl Event {
+    ///Constructor for [`Event`].
+    pub const fn new(time: Time, severity: Severity, code: u16, payload: f32) -> Self {
+        Self {
+            time: time,
+            severity: severity,
+            code: code,
+            payload: payload,
+        }
+    }
+}
+///Something that events can be pushed to. This is implemented by [`EventLog`] and lets types hold
+///a log without knowing its capacity.
+pub trait EventSink {
+    ///Record an event.
+    fn push(&mut self, event: Event);
+}
+///A ring buffer holding the newest `N` [`Event`]s. Once it is full, each new event overwrites the
+///oldest one, and the number of events lost this way is counted.
+pub struct EventLog<const N: usize> {
+    events: [Option<Event>; N],
+    next_index: usize,
+    overwritten: u32,
+}
+impl<const N: usize> EventLog<N> {
+    ///Constructor for [`EventLog`].
+    pub const fn new() -> Self {
+        if N < 1 {
+            panic!("rrtk::events::EventLog N must be at least 1.");
+        }
+        Self {
+            events: [None; N],
+            next_index: 0,
+            overwritten: 0,
+        }
+    }
+    ///Record an event with the given fields. This is the same as pushing an [`Event`].
+    pub fn log(&mut self, time: Time, severity: Severity, code: u16, payload: f32) {
+        self.push(Event::new(time, severity, code, payload));
+    }
+    ///Get the number of events currently in the log. This is never more than `N`.
+    pub fn len(&self) -> usize {
+        let mut len = 0;
+        for i in &self.events {
+            if i.is_some() {
+                len += 1;
+            }
+        }
+        len
+    }
+    ///Returns true if there are no events in the log.
+    pub fn is_empty(&self) -> bool {
+        self.get(0).is_none()
+    }
+    ///Get how many events have been overwritten because the log was full since it was created or
+    ///last [cleared](EventLog::clear).
+    pub const fn get_overwritten(&self) -> u32 {
+        self.overwritten
+    }
+    ///Get an event. `steps_back` is how many events before the newest one to go, so 0 is the
+    ///newest event. Returns `None` if not that many events are in the log.
+    pub fn get(&self, steps_back: usize) -> Option<Event> {
+        if steps_back >= N {
+            return None;
+        }
+        self.events[(self.next_index + N - 1 - steps_back) % N]
+    }
+    ///Get a copy of every event in the log from oldest to newest without removing them.
+    pub fn snapshot(&self) -> EventLogIter<N> {
+        EventLogIter {
+            events: self.events,
+            index: self.next_index,
+            remaining: N,
+        }
+    }
+    ///Remove every event from the log, returning them from oldest to newest. The overwritten count
+    ///is reset.
+    pub fn drain(&mut self) -> EventLogIter<N> {
+        let output = self.snapshot();
+        self.clear();
+        output
+    }
+    ///Remove every event from the log and reset the overwritten count.
+    pub fn clear(&mut self) {
+        self.events = [None; N];
+        self.next_index = 0;
+        self.overwritten = 0;
+    }
+}
+impl<const N: usize> EventSink for EventLog<N> {
+    fn push(&mut self, event: Event) {
+        if self.events[self.next_index].is_some() {
+            self.overwritten = self.overwritten.saturating_add(1);
+        }
+        self.events[self.next_index] = Some(event);
+        self.next_index = (self.next_index + 1) % N;
+    }
+}
+impl<const N: usize> Default for EventLog<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+///An iterator over events copied out of an [`EventLog`], from oldest to newest. Returned by
+///[`snapshot`](EventLog::snapshot) and [`drain`](EventLog::drain).
+pub struct EventLogIter<const N: usize> {
+    events: [Option<Event>; N],
+    index: usize,
+    remaining: usize,
+}
+impl<const N: usize> Iterator for EventLogIter<N> {
+    type Item = Event;
+    fn next(&mut self) -> Option<Event> {
+        while self.remaining > 0 {
+            let event = self.events[self.index];
+            self.index = (self.index + 1) % N;
+            self.remaining -= 1;
+            if event.is_some() {
+                return event;
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,229 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!A lightweight registry of named 2D coordinate frames and the transforms between them. Each frame
+//!other than a root has a parent and a transform giving its pose in the parent frame. Transforms
+//!can be constant, come from a [`Getter`] such as odometry, or come from a [`History`] so that they
+//!can be looked up at a past time. Transforms between any two frames in the same tree can then be
+//!queried. Positions are in millimeters and angles are in radians, counterclockwise positive.
+use crate::*;
+///A 2D rigid transform, or equivalently the pose of one frame in another. Applying it to a point
+///rotates the point by `angle` and then translates it by `x` and `y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2d {
+    ///Translation along the x axis in millimeters.
+    pub x: f32,
+    ///Translation along the y axis in millimeters.
+    pub y: f32,
+    ///Rotation in radians.
+    pub angle: f32,
+}
+impl Transform2d {
+    ///Constructor for [`Transform2d`].
+    pub const fn new(x: f32, y: f32, angle: f32) -> Self {
+        Self {
+            x: x,
+            y: y,
+            angle: angle,
+        }
+    }
+    ///A transform that does nothing.
+    pub const fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+    ///Apply the transform to a point.
+    pub fn transform_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let (sin, cos) = (sinf(self.angle), cosf(self.angle));
+        (self.x + cos * x - sin * y, self.y + sin * x + cos * y)
+    }
+    ///Get the transform that undoes this one.
+    pub fn inverse(&self) -> Self {
+        let (sin, cos) = (sinf(self.angle), cosf(self.angle));
+        Self::new(
+            -(cos * self.x + sin * self.y),
+            -(-sin * self.x + cos * self.y),
+            -self.angle,
+        )
+    }
+    ///Get the transform that applies `other` and then this. If this is the pose of frame B in
+    ///frame A and `other` is the pose of frame C in frame B, the result is the pose of frame C in
+    ///frame A.
+    pub fn compose(&self, other: &Self) -> Self {
+        let (x, y) = self.transform_point(other.x, other.y);
+        Self::new(x, y, angles::wrap_to_pi(self.angle + other.angle))
+    }
+}
+impl Mul for Transform2d {
+    type Output = Self;
+    ///Same as [`compose`](Transform2d::compose).
+    fn mul(self, rhs: Self) -> Self {
+        self.compose(&rhs)
+    }
+}
+///An identifier for a frame in a [`FrameRegistry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameId(usize);
+///An error from a [`FrameRegistry`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameError<E: Copy + Debug> {
+    ///The registry cannot hold any more frames.
+    Full,
+    ///A frame with the same name is already registered.
+    DuplicateName,
+    ///The frame does not exist in this registry.
+    UnknownFrame,
+    ///The two frames do not share a root, so there is no transform between them.
+    NotConnected,
+    ///A dynamic transform's getter or history did not have a value.
+    Unavailable,
+    ///A dynamic transform's getter returned an error.
+    Other(Error<E>),
+}
+///Where a frame's transform to its parent comes from.
+pub enum TransformSource<E: Copy + Debug> {
+    ///The frame is a root and has no parent.
+    Root,
+    ///The transform never changes.
+    Static(Transform2d),
+    ///The transform is the latest output of a getter regardless of the time asked for.
+    Dynamic(Reference<dyn Getter<Transform2d, E>>),
+    ///The transform is looked up from a history at the time asked for.
+    History(Reference<dyn History<Transform2d, E>>),
+}
+struct Frame<'a, E: Copy + Debug> {
+    name: &'a str,
+    parent: Option<FrameId>,
+    source: TransformSource<E>,
+}
+///A fixed-capacity registry of up to `N` named frames.
+pub struct FrameRegistry<'a, const N: usize, E: Copy + Debug> {
+    frames: [Option<Frame<'a, E>>; N],
+}
+impl<'a, const N: usize, E: Copy + Debug> FrameRegistry<'a, N, E> {
+    ///Constructor for [`FrameRegistry`].
+    pub fn new() -> Self {
+        Self {
+            frames: core::array::from_fn(|_| None),
+        }
+    }
+    fn add(
+        &mut self,
+        name: &'a str,
+        parent: Option<FrameId>,
+        source: TransformSource<E>,
+    ) -> Result<FrameId, FrameError<E>> {
+        if self.find(name).is_some() {
+            return Err(FrameError::DuplicateName);
+        }
+        if let Some(parent) = parent {
+            self.get_frame(parent)?;
+        }
+        for (i, slot) in self.frames.iter_mut().enumerate() {
+            if slot.is_none() {
+                *slot = Some(Frame {
+                    name: name,
+                    parent: parent,
+                    source: source,
+                });
+                return Ok(FrameId(i));
+            }
+        }
+        Err(FrameError::Full)
+    }
+    fn get_frame(&self, id: FrameId) -> Result<&Frame<'a, E>, FrameError<E>> {
+        match self.frames.get(id.0) {
+            Some(Some(frame)) => Ok(frame),
+            _ => Err(FrameError::UnknownFrame),
+        }
+    }
+    ///Register a root frame, such as the field, which has no parent.
+    pub fn add_root(&mut self, name: &'a str) -> Result<FrameId, FrameError<E>> {
+        self.add(name, None, TransformSource::Root)
+    }
+    ///Register a frame whose pose in its parent never changes, such as a camera mounted on the
+    ///robot.
+    pub fn add_static(
+        &mut self,
+        name: &'a str,
+        parent: FrameId,
+        transform: Transform2d,
+    ) -> Result<FrameId, FrameError<E>> {
+        self.add(name, Some(parent), TransformSource::Static(transform))
+    }
+    ///Register a frame whose pose in its parent is the latest output of a getter, such as the robot
+    ///in the field frame from odometry.
+    pub fn add_dynamic(
+        &mut self,
+        name: &'a str,
+        parent: FrameId,
+        getter: Reference<dyn Getter<Transform2d, E>>,
+    ) -> Result<FrameId, FrameError<E>> {
+        self.add(name, Some(parent), TransformSource::Dynamic(getter))
+    }
+    ///Register a frame whose pose in its parent is looked up from a history at the requested time.
+    pub fn add_history(
+        &mut self,
+        name: &'a str,
+        parent: FrameId,
+        history: Reference<dyn History<Transform2d, E>>,
+    ) -> Result<FrameId, FrameError<E>> {
+        self.add(name, Some(parent), TransformSource::History(history))
+    }
+    ///Find a frame by name.
+    pub fn find(&self, name: &str) -> Option<FrameId> {
+        for (i, slot) in self.frames.iter().enumerate() {
+            if let Some(frame) = slot {
+                if frame.name == name {
+                    return Some(FrameId(i));
+                }
+            }
+        }
+        None
+    }
+    ///Get the pose of a frame in its root frame at a time along with the root's id.
+    fn to_root(&self, id: FrameId, time: Time) -> Result<(Transform2d, FrameId), FrameError<E>> {
+        let mut transform = Transform2d::identity();
+        let mut current = id;
+        //Parents must be registered before their children, so there cannot be cycles and this
+        //will always reach a root.
+        loop {
+            let frame = self.get_frame(current)?;
+            let parent_transform = match &frame.source {
+                TransformSource::Root => return Ok((transform, current)),
+                TransformSource::Static(transform) => *transform,
+                TransformSource::Dynamic(getter) => match getter.borrow().get() {
+                    Ok(Some(datum)) => datum.value,
+                    Ok(None) => return Err(FrameError::Unavailable),
+                    Err(error) => return Err(FrameError::Other(error)),
+                },
+                TransformSource::History(history) => match history.borrow().get(time) {
+                    Some(datum) => datum.value,
+                    None => return Err(FrameError::Unavailable),
+                },
+            };
+            transform = parent_transform * transform;
+            current = frame
+                .parent
+                .expect("only root frames have no parent, and they return above");
+        }
+    }
+    ///Get the pose of frame `from` in frame `to` at a time. Applying the result to a point in
+    ///`from` coordinates gives the same point in `to` coordinates.
+    pub fn lookup(
+        &self,
+        from: FrameId,
+        to: FrameId,
+        time: Time,
+    ) -> Result<Transform2d, FrameError<E>> {
+        let (from_transform, from_root) = self.to_root(from, time)?;
+        let (to_transform, to_root) = self.to_root(to, time)?;
+        if from_root != to_root {
+            return Err(FrameError::NotConnected);
+        }
+        Ok(to_transform.inverse() * from_transform)
+    }
+}
+impl<const N: usize, E: Copy + Debug> Default for FrameRegistry<'_, N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -13,6 +13,7 @@
 //!- `libm` - Use [`libm`](https://crates.io/crates/libm) for float exponentiation when `std` is not available.
 //!- `micromath` - Use [`micromath`](https://crates.io/crates/micromath) for float exponentiation
 //!when `std` and `libm` are unavailable.
+//!- `uom` - Enable conversions between RRTK's types and [`uom`](https://crates.io/crates/uom)'s `f32` SI quantities.
 //!- `internal_enhanced_float` - Do not enable this yourself.
 //!
 //!RRTK prefers **`std`** over **`libm`** and `libm` over **`micromath`** when multiple are
@@ -33,6 +34,8 @@ use std::sync::{Mutex, RwLock};
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
 use alloc::rc::Rc;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
@@ -45,29 +48,62 @@ use core::marker::PhantomData;
 use core::ops::{
     Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Not, Sub, SubAssign,
 };
+pub mod angles;
+#[cfg(feature = "internal_enhanced_float")]
+pub mod arm;
 mod command;
+pub mod commands;
 mod datum;
+#[cfg(feature = "std")]
+pub mod debugging;
+mod delayed_action_queue;
 #[cfg(feature = "devices")]
 pub mod devices;
 pub mod dimensions;
+pub mod drive;
+mod enable_line;
 #[cfg(feature = "internal_enhanced_float")]
 mod enhanced_float;
+pub mod events;
+#[cfg(feature = "internal_enhanced_float")]
+pub mod frames;
+pub mod linalg;
 pub use dimensions::*;
 mod motion_profile;
+mod motor_model;
+#[cfg(feature = "internal_enhanced_float")]
+pub mod orientation;
+mod output_mode;
+pub mod packing;
+pub mod pipeline;
+pub mod rand_lite;
 pub mod reference;
+pub mod registry;
+pub mod script;
+mod setpoint_ramp;
+pub mod sim;
+mod soft_start;
 mod state;
 pub mod streams;
+mod tip_guard;
 pub use command::*;
 pub use datum::*;
+pub use delayed_action_queue::*;
+pub use enable_line::*;
 #[cfg(feature = "internal_enhanced_float")]
 use enhanced_float::*;
 pub use motion_profile::*;
+pub use motor_model::*;
+pub use output_mode::*;
 #[cfg(feature = "alloc")]
 pub use reference::rc_ref_cell_reference;
 pub use reference::Reference;
 #[cfg(feature = "std")]
 pub use reference::{arc_mutex_reference, arc_rw_lock_reference};
+pub use setpoint_ramp::*;
+pub use soft_start::*;
 pub use state::*;
+pub use tip_guard::*;
 ///RRTK follows the enum style of error handling. This is the error type returned from nearly all
 ///RRTK types, but you can add your own custom error type using `Other(O)`. It is strongly
 ///recommended that you use a single `O` type across your crate.
@@ -77,10 +113,19 @@ pub enum Error<O: Copy + Debug> {
     ///Returned when a `None` is elevated to an error by a
     ///[`NoneToError`](streams::converters::NoneToError).
     FromNone,
+    ///Returned when quantities with incompatible units are combined, such as by
+    ///[`QuantitySumStream`](streams::math::QuantitySumStream). This is only returned with dimension
+    ///checking enabled. The [`UnitMismatch`] records the expected and actual units.
+    UnitInvalid(UnitMismatch),
     ///A custom error of a user-defined type. Not created by any RRTK type but can be propagated by
     ///them.
     Other(O),
 }
+impl<O: Copy + Debug> From<UnitMismatch> for Error<O> {
+    fn from(was: UnitMismatch) -> Self {
+        Self::UnitInvalid(was)
+    }
+}
 ///A derivative of position: position, velocity, or acceleration.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum PositionDerivative {
@@ -154,6 +199,67 @@ impl PIDKValues {
         self.kp * error + self.ki * error_integral + self.kd * error_derivative
     }
 }
+impl PIDKValueProvider for PIDKValues {
+    ///The same k-values are used for every position derivative and error.
+    #[inline]
+    fn get_k_values(&self, _position_derivative: PositionDerivative, _error: f32) -> PIDKValues {
+        *self
+    }
+}
+///Separate PID k-values for small and large errors. A single set of gains rarely works well both
+///for gross moves and for holding position precisely, especially on mechanisms with a lot of
+///friction. When the magnitude of the error is at most `small_error_threshold`, the `small_error`
+///k-values are used, and when it is at least `large_error_threshold`, the `large_error` k-values
+///are used. Between the two thresholds, each coefficient is linearly interpolated so that the
+///output does not jump when crossing from one zone to the other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZonedPIDKValues {
+    ///Use these k-values when the error is small.
+    pub small_error: PIDKValues,
+    ///Use these k-values when the error is large.
+    pub large_error: PIDKValues,
+    ///At or below this error magnitude, only the small error k-values are used.
+    pub small_error_threshold: f32,
+    ///At or above this error magnitude, only the large error k-values are used.
+    pub large_error_threshold: f32,
+}
+impl ZonedPIDKValues {
+    ///Constructor for [`ZonedPIDKValues`].
+    pub const fn new(
+        small_error: PIDKValues,
+        large_error: PIDKValues,
+        small_error_threshold: f32,
+        large_error_threshold: f32,
+    ) -> Self {
+        Self {
+            small_error: small_error,
+            large_error: large_error,
+            small_error_threshold: small_error_threshold,
+            large_error_threshold: large_error_threshold,
+        }
+    }
+}
+impl PIDKValueProvider for ZonedPIDKValues {
+    ///The same zones are used for every position derivative.
+    fn get_k_values(&self, _position_derivative: PositionDerivative, error: f32) -> PIDKValues {
+        let error = if error < 0.0 { -error } else { error };
+        if error <= self.small_error_threshold {
+            return self.small_error;
+        }
+        if error >= self.large_error_threshold {
+            return self.large_error;
+        }
+        let factor = (error - self.small_error_threshold)
+            / (self.large_error_threshold - self.small_error_threshold);
+        let small = self.small_error;
+        let large = self.large_error;
+        PIDKValues::new(
+            small.kp + (large.kp - small.kp) * factor,
+            small.ki + (large.ki - small.ki) * factor,
+            small.kd + (large.kd - small.kd) * factor,
+        )
+    }
+}
 ///A set of PID k-values for controlling each position derivative.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct PositionDerivativeDependentPIDKValues {
@@ -196,6 +302,40 @@ impl PositionDerivativeDependentPIDKValues {
             .evaluate(error, error_integral, error_derivative)
     }
 }
+impl PIDKValueProvider for PositionDerivativeDependentPIDKValues {
+    ///The k-values do not depend on the error.
+    #[inline]
+    fn get_k_values(&self, position_derivative: PositionDerivative, _error: f32) -> PIDKValues {
+        PositionDerivativeDependentPIDKValues::get_k_values(self, position_derivative)
+    }
+}
+///Something that provides PID k-values depending on the position derivative being controlled and
+///the current error. This is used by both [`CommandPID`](streams::control::CommandPID) and
+///[`PIDControllerStream`](streams::control::PIDControllerStream). It is implemented by
+///[`PIDKValues`] for fixed gains, by [`PositionDerivativeDependentPIDKValues`] for different gains
+///for each position derivative, and by [`ZonedPIDKValues`] for different gains at large and small
+///errors. Implementors may also change their k-values at runtime, allowing for things like gain
+///scheduling. See [`streams::control::SpeedScheduledPIDKValues`] for an example.
+pub trait PIDKValueProvider {
+    ///Get the k-values to use for controlling a specific position derivative at a given error.
+    fn get_k_values(&self, position_derivative: PositionDerivative, error: f32) -> PIDKValues;
+    ///Calculate the control variable using the coefficients for a given position derivative and
+    ///error given error, its integral, and its derivative.
+    #[inline]
+    fn evaluate(
+        &self,
+        position_derivative: PositionDerivative,
+        error: f32,
+        error_integral: f32,
+        error_derivative: f32,
+    ) -> f32 {
+        self.get_k_values(position_derivative, error).evaluate(
+            error,
+            error_integral,
+            error_derivative,
+        )
+    }
+}
 ///A generic output type when something may return an error, nothing, or something with a
 ///timestamp.
 pub type Output<T, E> = Result<Option<Datum<T>>, Error<E>>;
@@ -213,6 +353,13 @@ pub trait History<T, E: Copy + Debug>: Updatable<E> {
     ///Get a value at a time.
     fn get(&self, time: Time) -> Option<Datum<T>>;
 }
+///A [`History`] that finishes changing at a known time, such as a [`MotionProfile`]. This lets a
+///[`HistorySequence`] work out how long each segment is on its own.
+pub trait FiniteHistory<T, E: Copy + Debug>: History<T, E> {
+    ///Get the time at which the history is finished, measured from the same zero as the times
+    ///passed to [`get`](History::get).
+    fn end_time(&self) -> Time;
+}
 ///Something with an [`update`](Updatable::update) method. Mostly for subtraiting.
 pub trait Updatable<E: Copy + Debug> {
     ///As this trait is very generic, exactly what this does will be very dependent on the
@@ -229,6 +376,17 @@ pub trait Getter<G, E: Copy + Debug>: Updatable<E> {
     ///Get something.
     fn get(&self) -> Output<G, E>;
 }
+///Something that returns multiple samples at once from a single poll, such as an IMU with a FIFO
+///buffer. Where a [`Getter`] only has its latest value, this provides every sample it got during
+///its last [`update`](Updatable::update). See [`streams::converters::Unbatcher`] for processing each
+///sample with ordinary streams.
+pub trait BatchGetter<T, E: Copy + Debug>: Updatable<E> {
+    ///Get the number of samples in the current batch.
+    fn batch_len(&self) -> usize;
+    ///Get a sample from the current batch by index, oldest first. Returns `Ok(None)` if the index
+    ///is out of range.
+    fn get_sample(&self, index: usize) -> Output<T, E>;
+}
 ///Internal data needed for following a [`Getter`] with a [`Settable`].
 pub struct SettableData<S, E: Copy + Debug> {
     following: Option<Reference<dyn Getter<S, E>>>,
@@ -308,6 +466,25 @@ pub trait Settable<S: Clone, E: Copy + Debug>: Updatable<E> {
         data.last_request.clone()
     }
 }
+///Something that can be enabled and disabled, usually an output such as a motor. While disabled,
+///implementors should output a neutral value during [`update`](Updatable::update) no matter what
+///they are told to do. This allows a single robot-wide disable, for example from an e-stop, without
+///managing a separate flag for every output.
+pub trait Enableable {
+    ///Enable or disable this.
+    fn set_enabled(&mut self, enabled: bool);
+    ///Returns true if this is enabled and false if it is disabled.
+    fn is_enabled(&self) -> bool;
+    ///Enable this. This is the same as calling [`set_enabled`](Enableable::set_enabled) with `true`.
+    fn enable(&mut self) {
+        self.set_enabled(true);
+    }
+    ///Disable this. This is the same as calling [`set_enabled`](Enableable::set_enabled) with
+    ///`false`.
+    fn disable(&mut self) {
+        self.set_enabled(false);
+    }
+}
 ///Because [`Getter`]s always return a timestamp (as long as they don't return `Err(_)` or
 ///`Ok(None)`), we can use this to treat them like [`TimeGetter`]s.
 pub struct TimeGetterFromGetter<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
@@ -422,6 +599,317 @@ impl<G, TG: TimeGetter<E>, E: Copy + Debug> Getter<G, E> for GetterFromHistory<'
         })
     }
 }
+///Repeats the part of an inner [`History`] from `start` to `start + period` indefinitely or a set
+///number of times. This is useful for cyclic test motions and periodic scanning without
+///regenerating a profile every cycle. Times before `start` are passed to the inner history
+///directly, and after the last repetition, the value at the end of the period is held. Returned
+///data are timestamped with the requested time rather than the time in the inner history.
+pub struct LoopingHistory<T, H: History<T, E>, E: Copy + Debug> {
+    inner: H,
+    start: Time,
+    period: Time,
+    repetitions: Option<u32>,
+    phantom_t: PhantomData<T>,
+    phantom_e: PhantomData<E>,
+}
+impl<T, H: History<T, E>, E: Copy + Debug> LoopingHistory<T, H, E> {
+    ///Constructor for [`LoopingHistory`]. If `repetitions` is `None`, the history repeats forever.
+    ///Panics if `period` is not positive.
+    pub const fn new(inner: H, start: Time, period: Time, repetitions: Option<u32>) -> Self {
+        assert!(period.0 > 0, "rrtk::LoopingHistory period must be positive");
+        Self {
+            inner: inner,
+            start: start,
+            period: period,
+            repetitions: repetitions,
+            phantom_t: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get a reference to the inner history.
+    pub fn get_inner(&self) -> &H {
+        &self.inner
+    }
+    ///Get the time in the inner history that corresponds to a given time.
+    pub fn get_inner_time(&self, time: Time) -> Time {
+        let offset = time.0 - self.start.0;
+        if offset < 0 {
+            return time;
+        }
+        if let Some(repetitions) = self.repetitions {
+            if offset / self.period.0 >= repetitions as i64 {
+                return self.start + self.period;
+            }
+        }
+        Time(self.start.0 + offset % self.period.0)
+    }
+}
+impl<T, H: History<T, E>, E: Copy + Debug> History<T, E> for LoopingHistory<T, H, E> {
+    fn get(&self, time: Time) -> Option<Datum<T>> {
+        let datum = self.inner.get(self.get_inner_time(time))?;
+        Some(Datum::new(time, datum.value))
+    }
+}
+impl<T, H: History<T, E>, E: Copy + Debug> Updatable<E> for LoopingHistory<T, H, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.inner.update()
+    }
+}
+///Runs several [`History`]s back to back, such as a series of [`MotionProfile`]s making up a path.
+///Each segment is given a duration when it is added, and it starts when the one before it ends,
+///so each inner history sees time starting from zero at its own start just like it would on its
+///own. Times before the first segment are passed to it directly, and times after the last
+///segment ends are passed to the last one, so a motion profile's end command is held. Returned
+///data have their timestamps moved by the same offset as the requested time.
+#[cfg(feature = "alloc")]
+pub struct HistorySequence<T, E: Copy + Debug> {
+    segments: Vec<(Box<dyn History<T, E>>, Time)>,
+}
+#[cfg(feature = "alloc")]
+impl<T, E: Copy + Debug> HistorySequence<T, E> {
+    ///Constructor for [`HistorySequence`]. It starts with no segments.
+    pub const fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+    ///Add a segment to the end of the sequence that lasts for `duration`. For a [`FiniteHistory`]
+    ///such as a [`MotionProfile`], [`push_finite`](HistorySequence::push_finite) works out the
+    ///duration automatically.
+    pub fn push(&mut self, history: impl History<T, E> + 'static, duration: Time) {
+        self.segments.push((Box::new(history), duration));
+    }
+    ///Add a segment to the end of the sequence that lasts until its
+    ///[end time](FiniteHistory::end_time).
+    pub fn push_finite(&mut self, history: impl FiniteHistory<T, E> + 'static) {
+        let duration = history.end_time();
+        self.push(history, duration);
+    }
+    ///Get the number of segments.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+    ///Returns true if there are no segments.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+    ///Get the total duration of every segment.
+    pub fn duration(&self) -> Time {
+        let mut output = Time::default();
+        for (_, duration) in &self.segments {
+            output += *duration;
+        }
+        output
+    }
+    ///Get the time at which a segment starts, or `None` if there is no segment with that index.
+    pub fn get_segment_start(&self, index: usize) -> Option<Time> {
+        if index >= self.segments.len() {
+            return None;
+        }
+        let mut start = Time::default();
+        for (_, duration) in &self.segments[..index] {
+            start += *duration;
+        }
+        Some(start)
+    }
+    ///Get the index of the segment used for a given time and the time at which it starts, or
+    ///`None` if there are no segments.
+    pub fn get_segment(&self, time: Time) -> Option<(usize, Time)> {
+        let mut start = Time::default();
+        for (index, (_, duration)) in self.segments.iter().enumerate() {
+            if time < start + *duration || index == self.segments.len() - 1 {
+                return Some((index, start));
+            }
+            start += *duration;
+        }
+        None
+    }
+}
+#[cfg(feature = "alloc")]
+impl<T, E: Copy + Debug> History<T, E> for HistorySequence<T, E> {
+    fn get(&self, time: Time) -> Option<Datum<T>> {
+        let (index, start) = self.get_segment(time)?;
+        let datum = self.segments[index].0.get(time - start)?;
+        Some(Datum::new(datum.time + start, datum.value))
+    }
+}
+#[cfg(feature = "alloc")]
+impl<T, E: Copy + Debug> Updatable<E> for HistorySequence<T, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        for (history, _) in &mut self.segments {
+            history.update()?;
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "alloc")]
+impl<T, E: Copy + Debug> Default for HistorySequence<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+///Which [`Command`] a [`CommandFromStateHistory`] creates from a [`State`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StateCommandPreference {
+    ///Command the highest derivative that is not zero, falling back to position if the state is
+    ///not moving. This is the same as [`Command::from(State)`](Command::from).
+    HighestNonzero,
+    ///Always command the given derivative. For example, a trajectory sent to a device that holds
+    ///position well may be better followed by position commands even while it is moving.
+    Always(PositionDerivative),
+}
+///Adapts a [`History<State, E>`] into a [`History<Command, E>`] so that trajectories from custom
+///planners can be used wherever commands are expected, such as by a [`GetterFromHistory`] feeding
+///a device. How each state is turned into a command is chosen with a [`StateCommandPreference`].
+pub struct CommandFromStateHistory<H: History<State, E>, E: Copy + Debug> {
+    inner: H,
+    preference: StateCommandPreference,
+    phantom_e: PhantomData<E>,
+}
+impl<H: History<State, E>, E: Copy + Debug> CommandFromStateHistory<H, E> {
+    ///Constructor for [`CommandFromStateHistory`].
+    pub const fn new(inner: H, preference: StateCommandPreference) -> Self {
+        Self {
+            inner: inner,
+            preference: preference,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get a reference to the inner history.
+    pub fn get_inner(&self) -> &H {
+        &self.inner
+    }
+    ///Get the preference used to create commands.
+    pub fn get_preference(&self) -> StateCommandPreference {
+        self.preference
+    }
+    ///Change the preference used to create commands.
+    pub fn set_preference(&mut self, preference: StateCommandPreference) {
+        self.preference = preference;
+    }
+}
+impl<H: History<State, E>, E: Copy + Debug> History<Command, E> for CommandFromStateHistory<H, E> {
+    fn get(&self, time: Time) -> Option<Datum<Command>> {
+        let datum = self.inner.get(time)?;
+        let command = match self.preference {
+            StateCommandPreference::HighestNonzero => Command::from(datum.value),
+            StateCommandPreference::Always(position_derivative) => Command::new(
+                position_derivative,
+                datum.value.get_value(position_derivative).into(),
+            ),
+        };
+        Some(Datum::new(datum.time, command))
+    }
+}
+impl<H: History<State, E>, E: Copy + Debug> Updatable<E> for CommandFromStateHistory<H, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.inner.update()
+    }
+}
+///How a [`SetpointHistory`] gets values between its knots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetpointInterpolation {
+    ///Hold the value of the previous knot until the next one.
+    Hold,
+    ///Move in a straight line from one knot to the next.
+    Linear,
+    ///Follow a smooth cubic Hermite spline through the knots. Tangents are estimated from the
+    ///neighboring knots.
+    Cubic,
+}
+///A [`History`] built from a small set of `(time, value)` knots. This is an easy way to sketch out
+///a motion without generating a full profile. Before the first knot, this returns `None`, and
+///after the last one, the last knot's value is held. As well as [`History<f32, E>`], this
+///implements [`History<Command, E>`], creating commands of a given position derivative from the
+///interpolated values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetpointHistory<const N: usize> {
+    knots: [(Time, f32); N],
+    interpolation: SetpointInterpolation,
+    position_derivative: PositionDerivative,
+}
+impl<const N: usize> SetpointHistory<N> {
+    ///Constructor for [`SetpointHistory`]. Panics if there are no knots or their times are not
+    ///strictly increasing.
+    pub const fn new(
+        knots: [(Time, f32); N],
+        interpolation: SetpointInterpolation,
+        position_derivative: PositionDerivative,
+    ) -> Self {
+        assert!(N > 0, "rrtk::SetpointHistory must have at least one knot");
+        let mut i = 1;
+        while i < N {
+            assert!(
+                knots[i - 1].0 .0 < knots[i].0 .0,
+                "rrtk::SetpointHistory knot times must be strictly increasing"
+            );
+            i += 1;
+        }
+        Self {
+            knots: knots,
+            interpolation: interpolation,
+            position_derivative: position_derivative,
+        }
+    }
+    ///Get the interpolated value at a time.
+    pub fn get_value(&self, time: Time) -> Option<f32> {
+        if time < self.knots[0].0 {
+            return None;
+        }
+        //The index of the last knot at or before the time.
+        let mut i = 0;
+        while i + 1 < N && self.knots[i + 1].0 <= time {
+            i += 1;
+        }
+        if i + 1 == N {
+            return Some(self.knots[i].1);
+        }
+        let (t0, p0) = self.knots[i];
+        let (t1, p1) = self.knots[i + 1];
+        let h = (t1.0 - t0.0) as f32;
+        let s = (time.0 - t0.0) as f32 / h;
+        Some(match self.interpolation {
+            SetpointInterpolation::Hold => p0,
+            SetpointInterpolation::Linear => p0 + (p1 - p0) * s,
+            SetpointInterpolation::Cubic => {
+                let m0 = self.tangent(i);
+                let m1 = self.tangent(i + 1);
+                let s2 = s * s;
+                let s3 = s2 * s;
+                (2.0 * s3 - 3.0 * s2 + 1.0) * p0
+                    + (s3 - 2.0 * s2 + s) * h * m0
+                    + (-2.0 * s3 + 3.0 * s2) * p1
+                    + (s3 - s2) * h * m1
+            }
+        })
+    }
+    ///The estimated slope at a knot in value per nanosecond.
+    fn tangent(&self, i: usize) -> f32 {
+        let before = if i == 0 { 0 } else { i - 1 };
+        let after = if i + 1 == N { i } else { i + 1 };
+        let (t0, p0) = self.knots[before];
+        let (t1, p1) = self.knots[after];
+        (p1 - p0) / (t1.0 - t0.0) as f32
+    }
+}
+impl<const N: usize, E: Copy + Debug> History<f32, E> for SetpointHistory<N> {
+    fn get(&self, time: Time) -> Option<Datum<f32>> {
+        Some(Datum::new(time, self.get_value(time)?))
+    }
+}
+impl<const N: usize, E: Copy + Debug> History<Command, E> for SetpointHistory<N> {
+    fn get(&self, time: Time) -> Option<Datum<Command>> {
+        Some(Datum::new(
+            time,
+            Command::new(self.position_derivative, self.get_value(time)?),
+        ))
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for SetpointHistory<N> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
 ///Getter for returning a constant value.
 pub struct ConstantGetter<T: Clone, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
     settable_data: SettableData<T, E>,
@@ -469,6 +957,445 @@ impl<T: Clone, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
         Ok(())
     }
 }
+///How a [`TeeSettable`] should handle errors from the settables it forwards to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TeeErrorPolicy {
+    ///Return an error from the first settable immediately without touching the second, and return
+    ///any error from the second.
+    FailFast,
+    ///Always forward to both settables, then return the first error if there were any.
+    ForwardToBoth,
+    ///Return errors from the first settable, but ignore errors from the second. This is useful
+    ///when the second settable is something like a logger that should not be able to stop the
+    ///first from working.
+    IgnoreSecond,
+}
+///Forwards everything it is set to to two settables, for example a motor and a telemetry sink, so
+///that outputs can be observed without modifying either. Updating this also updates both settables.
+pub struct TeeSettable<
+    S: Clone,
+    S1: Settable<S, E> + ?Sized,
+    S2: Settable<S, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    settable_data: SettableData<S, E>,
+    first: Reference<S1>,
+    second: Reference<S2>,
+    policy: TeeErrorPolicy,
+}
+impl<S: Clone, S1: Settable<S, E> + ?Sized, S2: Settable<S, E> + ?Sized, E: Copy + Debug>
+    TeeSettable<S, S1, S2, E>
+{
+    ///Constructor for [`TeeSettable`].
+    pub const fn new(first: Reference<S1>, second: Reference<S2>, policy: TeeErrorPolicy) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            first: first,
+            second: second,
+            policy: policy,
+        }
+    }
+    fn combine(
+        &self,
+        first: impl FnOnce() -> NothingOrError<E>,
+        second: impl FnOnce() -> NothingOrError<E>,
+    ) -> NothingOrError<E> {
+        match self.policy {
+            TeeErrorPolicy::FailFast => {
+                first()?;
+                second()
+            }
+            TeeErrorPolicy::ForwardToBoth => {
+                let first_result = first();
+                let second_result = second();
+                first_result?;
+                second_result
+            }
+            TeeErrorPolicy::IgnoreSecond => {
+                let first_result = first();
+                let _ = second();
+                first_result
+            }
+        }
+    }
+}
+impl<S: Clone, S1: Settable<S, E> + ?Sized, S2: Settable<S, E> + ?Sized, E: Copy + Debug>
+    Settable<S, E> for TeeSettable<S, S1, S2, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<S, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<S, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: S) -> NothingOrError<E> {
+        let second_value = value.clone();
+        self.combine(
+            || self.first.borrow_mut().set(value),
+            || self.second.borrow_mut().set(second_value),
+        )
+    }
+}
+impl<S: Clone, S1: Settable<S, E> + ?Sized, S2: Settable<S, E> + ?Sized, E: Copy + Debug>
+    Updatable<E> for TeeSettable<S, S1, S2, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.combine(
+            || self.first.borrow_mut().update(),
+            || self.second.borrow_mut().update(),
+        )
+    }
+}
+///Buffers values for several settables and applies them all together. This is useful when
+///actuators must change together, such as the angle and speed of a swerve module, and forwarding
+///each value as soon as it is known could leave them briefly inconsistent. Values are staged with
+///[`stage`](SettableGroup::stage), or for every member at once by setting the group itself, and
+///are only forwarded to the members by [`commit`](SettableGroup::commit). Updating the group
+///commits any staged values and then updates every member.
+pub struct SettableGroup<S: Clone, const N: usize, E: Copy + Debug> {
+    settable_data: SettableData<[S; N], E>,
+    members: [Reference<dyn Settable<S, E>>; N],
+    pending: [Option<S>; N],
+}
+impl<S: Clone, const N: usize, E: Copy + Debug> SettableGroup<S, N, E> {
+    ///Constructor for [`SettableGroup`].
+    pub fn new(members: [Reference<dyn Settable<S, E>>; N]) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            members: members,
+            pending: core::array::from_fn(|_| None),
+        }
+    }
+    ///Stage a value for the member at `index` to be applied at the next commit. This replaces any
+    ///value already staged for that member. Panics if `index` is out of bounds.
+    pub fn stage(&mut self, index: usize, value: S) {
+        self.pending[index] = Some(value);
+    }
+    ///Returns true if any value is staged but not yet committed.
+    pub fn has_pending(&self) -> bool {
+        self.pending.iter().any(Option::is_some)
+    }
+    ///Drop every staged value without applying it.
+    pub fn discard(&mut self) {
+        for pending in &mut self.pending {
+            *pending = None;
+        }
+    }
+    ///Apply every staged value to its member. Members with no staged value are left alone. Every
+    ///staged value is applied even if setting an earlier member fails so that the members stay
+    ///consistent with each other, and the first error is returned.
+    pub fn commit(&mut self) -> NothingOrError<E> {
+        let mut result = Ok(());
+        for (member, pending) in self.members.iter().zip(self.pending.iter_mut()) {
+            if let Some(value) = pending.take() {
+                let member_result = member.borrow_mut().set(value);
+                if result.is_ok() {
+                    result = member_result;
+                }
+            }
+        }
+        result
+    }
+}
+impl<S: Clone, const N: usize, E: Copy + Debug> Settable<[S; N], E> for SettableGroup<S, N, E> {
+    fn get_settable_data_ref(&self) -> &SettableData<[S; N], E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<[S; N], E> {
+        &mut self.settable_data
+    }
+    ///Stage a value for every member. Nothing is forwarded until the next commit.
+    fn impl_set(&mut self, values: [S; N]) -> NothingOrError<E> {
+        for (pending, value) in self.pending.iter_mut().zip(values) {
+            *pending = Some(value);
+        }
+        Ok(())
+    }
+}
+impl<S: Clone, const N: usize, E: Copy + Debug> Updatable<E> for SettableGroup<S, N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.commit()?;
+        for member in &self.members {
+            member.borrow_mut().update()?;
+        }
+        Ok(())
+    }
+}
+///Negates everything it is set to before forwarding it to another settable. This is useful for
+///fixing motor direction conventions at the output without changing any controller code.
+pub struct InvertedSettable<S: Clone + Neg<Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug>
+{
+    settable_data: SettableData<S, E>,
+    inner: Reference<T>,
+}
+impl<S: Clone + Neg<Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug>
+    InvertedSettable<S, T, E>
+{
+    ///Constructor for [`InvertedSettable`].
+    pub const fn new(inner: Reference<T>) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            inner: inner,
+        }
+    }
+}
+impl<S: Clone + Neg<Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug> Settable<S, E>
+    for InvertedSettable<S, T, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<S, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<S, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: S) -> NothingOrError<E> {
+        self.inner.borrow_mut().set(-value)
+    }
+}
+impl<S: Clone + Neg<Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for InvertedSettable<S, T, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.inner.borrow_mut().update()
+    }
+}
+///Multiplies everything it is set to by a constant before forwarding it to another settable. This
+///is useful for converting units or applying a gear ratio at the output without changing any
+///controller code. A negative scale also inverts direction.
+pub struct ScaledSettable<
+    S: Clone + Mul<f32, Output = S>,
+    T: Settable<S, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    settable_data: SettableData<S, E>,
+    inner: Reference<T>,
+    scale: f32,
+}
+impl<S: Clone + Mul<f32, Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug>
+    ScaledSettable<S, T, E>
+{
+    ///Constructor for [`ScaledSettable`].
+    pub const fn new(inner: Reference<T>, scale: f32) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            inner: inner,
+            scale: scale,
+        }
+    }
+    ///Get the scale factor.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+    ///Change the scale factor. This only affects values set after it is changed.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+}
+impl<S: Clone + Mul<f32, Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug> Settable<S, E>
+    for ScaledSettable<S, T, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<S, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<S, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: S) -> NothingOrError<E> {
+        self.inner.borrow_mut().set(value * self.scale)
+    }
+}
+impl<S: Clone + Mul<f32, Output = S>, T: Settable<S, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ScaledSettable<S, T, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.inner.borrow_mut().update()
+    }
+}
+///A [`Getter<bool, E>`] for "wait a while, then do something." The timer starts on the first update
+///after construction or [`reset`](OneShotAfter::reset), and the output is `true` for the first
+///update at least `duration` after that and `false` otherwise, so whatever is triggered happens
+///exactly once. The time getter is not updated by this.
+pub struct OneShotAfter<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    time_getter: Reference<TG>,
+    duration: Time,
+    start: Option<Time>,
+    fired: bool,
+    output: Option<Datum<bool>>,
+    phantom_e: PhantomData<E>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> OneShotAfter<TG, E> {
+    ///Constructor for [`OneShotAfter`].
+    pub const fn new(time_getter: Reference<TG>, duration: Time) -> Self {
+        Self {
+            time_getter: time_getter,
+            duration: duration,
+            start: None,
+            fired: false,
+            output: None,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Returns true if the trigger has gone off since it was started.
+    pub fn has_fired(&self) -> bool {
+        self.fired
+    }
+    ///Stop the timer so that it starts over on the next update.
+    pub fn reset(&mut self) {
+        self.start = None;
+        self.fired = false;
+        self.output = None;
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<bool, E> for OneShotAfter<TG, E> {
+    fn get(&self) -> Output<bool, E> {
+        Ok(self.output)
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for OneShotAfter<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        let start = *self.start.get_or_insert(time);
+        let fire = !self.fired && time - start >= self.duration;
+        self.fired |= fire;
+        self.output = Some(Datum::new(time, fire));
+        Ok(())
+    }
+}
+///A [`Getter<bool, E>`] for "every so often, do something." The timer starts on the first update
+///after construction or [`reset`](PeriodicTrigger::reset), and the output is `true` for the first
+///update at or after each multiple of `period` since then and `false` otherwise. If updates are so
+///far apart that several periods pass between them, the trigger only goes off once and the missed
+///periods are skipped. The time getter is not updated by this.
+pub struct PeriodicTrigger<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    time_getter: Reference<TG>,
+    period: Time,
+    next: Option<Time>,
+    output: Option<Datum<bool>>,
+    phantom_e: PhantomData<E>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> PeriodicTrigger<TG, E> {
+    ///Constructor for [`PeriodicTrigger`]. Panics if `period` is not positive.
+    pub const fn new(time_getter: Reference<TG>, period: Time) -> Self {
+        assert!(
+            period.0 > 0,
+            "rrtk::PeriodicTrigger period must be positive"
+        );
+        Self {
+            time_getter: time_getter,
+            period: period,
+            next: None,
+            output: None,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Stop the timer so that it starts over on the next update.
+    pub fn reset(&mut self) {
+        self.next = None;
+        self.output = None;
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<bool, E> for PeriodicTrigger<TG, E> {
+    fn get(&self) -> Output<bool, E> {
+        Ok(self.output)
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for PeriodicTrigger<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        let next = *self.next.get_or_insert(time + self.period);
+        let fire = time >= next;
+        if fire {
+            let missed = (time - next).0 / self.period.0;
+            self.next = Some(next + Time(self.period.0 * (missed + 1)));
+        }
+        self.output = Some(Datum::new(time, fire));
+        Ok(())
+    }
+}
+///A [`TimeGetter`] returning the time since it was constructed or [`reset`](UptimeGetter::reset)
+///according to another time getter. This can be used directly as the clock of a pipeline that
+///should start at zero. The inner time getter is not updated by this.
+pub struct UptimeGetter<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    time_getter: Reference<TG>,
+    start: Time,
+    phantom_e: PhantomData<E>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> UptimeGetter<TG, E> {
+    ///Constructor for [`UptimeGetter`]. Returns an error if the time getter does.
+    pub fn new(time_getter: Reference<TG>) -> Result<Self, Error<E>> {
+        let start = time_getter.borrow().get()?;
+        Ok(Self {
+            time_getter: time_getter,
+            start: start,
+            phantom_e: PhantomData,
+        })
+    }
+    ///Get the time, according to the inner time getter, that uptime is measured from.
+    pub fn get_start(&self) -> Time {
+        self.start
+    }
+    ///Start measuring uptime from the current time.
+    pub fn reset(&mut self) -> NothingOrError<E> {
+        self.start = self.time_getter.borrow().get()?;
+        Ok(())
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> TimeGetter<E> for UptimeGetter<TG, E> {
+    fn get(&self) -> TimeOutput<E> {
+        Ok(self.time_getter.borrow().get()? - self.start)
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for UptimeGetter<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Counts how many times it has been updated. Update this once per iteration of the main loop to get
+///the loop iteration number. [`get`](Getter::get) returns the count with the time of the latest
+///update and `Ok(None)` before the first one. The time getter is not updated by this.
+pub struct CycleCounter<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    time_getter: Reference<TG>,
+    count: u64,
+    output: Option<Datum<u64>>,
+    phantom_e: PhantomData<E>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> CycleCounter<TG, E> {
+    ///Constructor for [`CycleCounter`].
+    pub const fn new(time_getter: Reference<TG>) -> Self {
+        Self {
+            time_getter: time_getter,
+            count: 0,
+            output: None,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get the number of updates so far.
+    pub fn get_count(&self) -> u64 {
+        self.count
+    }
+    ///Set the count back to zero.
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.output = None;
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<u64, E> for CycleCounter<TG, E> {
+    fn get(&self) -> Output<u64, E> {
+        Ok(self.output)
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for CycleCounter<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        self.count += 1;
+        self.output = Some(Datum::new(time, self.count));
+        Ok(())
+    }
+}
 ///Getter always returning `Ok(None)`.
 pub struct NoneGetter;
 impl NoneGetter {
@@ -488,6 +1415,196 @@ impl<E: Copy + Debug> Updatable<E> for NoneGetter {
         Ok(())
     }
 }
+///Wraps a closure as a [`Getter`]. This saves defining a new struct for quick glue logic and tests.
+///The closure is called every time [`get`](Getter::get) is called, so if it is expensive, consider
+///wrapping this in a [`MemoizedGetter`](streams::MemoizedGetter).
+pub struct FnGetter<T, F: Fn() -> Output<T, E>, E: Copy + Debug> {
+    function: F,
+    phantom_t: PhantomData<T>,
+    phantom_e: PhantomData<E>,
+}
+impl<T, F: Fn() -> Output<T, E>, E: Copy + Debug> FnGetter<T, F, E> {
+    ///Constructor for [`FnGetter`].
+    pub const fn new(function: F) -> Self {
+        Self {
+            function: function,
+            phantom_t: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<T, F: Fn() -> Output<T, E>, E: Copy + Debug> Getter<T, E> for FnGetter<T, F, E> {
+    fn get(&self) -> Output<T, E> {
+        (self.function)()
+    }
+}
+impl<T, F: Fn() -> Output<T, E>, E: Copy + Debug> Updatable<E> for FnGetter<T, F, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Wraps a closure as a [`Settable`]. The closure is called with every value set, and if the
+///settable is following a getter, updating it sets the getter's latest value.
+pub struct FnSettable<S: Clone, F: FnMut(S) -> NothingOrError<E>, E: Copy + Debug> {
+    settable_data: SettableData<S, E>,
+    function: F,
+}
+impl<S: Clone, F: FnMut(S) -> NothingOrError<E>, E: Copy + Debug> FnSettable<S, F, E> {
+    ///Constructor for [`FnSettable`].
+    pub const fn new(function: F) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            function: function,
+        }
+    }
+}
+impl<S: Clone, F: FnMut(S) -> NothingOrError<E>, E: Copy + Debug> Settable<S, E>
+    for FnSettable<S, F, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<S, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<S, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: S) -> NothingOrError<E> {
+        (self.function)(value)
+    }
+}
+impl<S: Clone, F: FnMut(S) -> NothingOrError<E>, E: Copy + Debug> Updatable<E>
+    for FnSettable<S, F, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        Ok(())
+    }
+}
+///Wraps a closure as a [`TimeGetter`].
+pub struct FnTimeGetter<F: Fn() -> TimeOutput<E>, E: Copy + Debug> {
+    function: F,
+    phantom_e: PhantomData<E>,
+}
+impl<F: Fn() -> TimeOutput<E>, E: Copy + Debug> FnTimeGetter<F, E> {
+    ///Constructor for [`FnTimeGetter`].
+    pub const fn new(function: F) -> Self {
+        Self {
+            function: function,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<F: Fn() -> TimeOutput<E>, E: Copy + Debug> TimeGetter<E> for FnTimeGetter<F, E> {
+    fn get(&self) -> TimeOutput<E> {
+        (self.function)()
+    }
+}
+impl<F: Fn() -> TimeOutput<E>, E: Copy + Debug> Updatable<E> for FnTimeGetter<F, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Drives a [`Getter`] as an [`Iterator`]. Each call to [`next`](Iterator::next) updates the getter
+///and then gets from it, yielding its output. `Ok(None)`s are skipped. Iteration ends once
+///`max_updates` updates have been done, once the getter returns a datum after `end_time`, or once
+///it returns an error, which can then be retrieved with
+///[`get_error`](GetterIterator::get_error). Without `max_updates`, a getter that keeps returning
+///`Ok(None)` will make [`next`](Iterator::next) loop forever.
+pub struct GetterIterator<T, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
+    getter: Reference<G>,
+    max_updates: Option<usize>,
+    end_time: Option<Time>,
+    updates: usize,
+    done: bool,
+    error: Option<Error<E>>,
+    phantom_t: PhantomData<T>,
+}
+impl<T, G: Getter<T, E> + ?Sized, E: Copy + Debug> GetterIterator<T, G, E> {
+    ///Constructor for [`GetterIterator`].
+    pub const fn new(
+        getter: Reference<G>,
+        max_updates: Option<usize>,
+        end_time: Option<Time>,
+    ) -> Self {
+        Self {
+            getter: getter,
+            max_updates: max_updates,
+            end_time: end_time,
+            updates: 0,
+            done: false,
+            error: None,
+            phantom_t: PhantomData,
+        }
+    }
+    ///Get the error that ended iteration if there was one.
+    pub fn get_error(&self) -> Option<Error<E>> {
+        self.error
+    }
+}
+impl<T, G: Getter<T, E> + ?Sized, E: Copy + Debug> Iterator for GetterIterator<T, G, E> {
+    type Item = Datum<T>;
+    fn next(&mut self) -> Option<Datum<T>> {
+        while !self.done {
+            if let Some(max_updates) = self.max_updates {
+                if self.updates >= max_updates {
+                    self.done = true;
+                    break;
+                }
+            }
+            self.updates += 1;
+            let mut getter = self.getter.borrow_mut();
+            let output = match getter.update() {
+                Ok(()) => getter.get(),
+                Err(error) => Err(error),
+            };
+            match output {
+                Ok(Some(datum)) => {
+                    if let Some(end_time) = self.end_time {
+                        if datum.time > end_time {
+                            self.done = true;
+                            break;
+                        }
+                    }
+                    return Some(datum);
+                }
+                Ok(None) => (),
+                Err(error) => {
+                    self.error = Some(error);
+                    self.done = true;
+                }
+            }
+        }
+        None
+    }
+}
+///Serves values from an [`Iterator`] as a [`Getter`]. Each update takes the next item from the
+///iterator, and [`get`](Getter::get) returns the latest one. Before the first update and after the
+///iterator runs out, this returns `Ok(None)`.
+pub struct IterGetter<T: Clone, I: Iterator<Item = Datum<T>>, E: Copy + Debug> {
+    iterator: I,
+    value: Option<Datum<T>>,
+    phantom_e: PhantomData<E>,
+}
+impl<T: Clone, I: Iterator<Item = Datum<T>>, E: Copy + Debug> IterGetter<T, I, E> {
+    ///Constructor for [`IterGetter`].
+    pub const fn new(iterator: I) -> Self {
+        Self {
+            iterator: iterator,
+            value: None,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<T: Clone, I: Iterator<Item = Datum<T>>, E: Copy + Debug> Getter<T, E> for IterGetter<T, I, E> {
+    fn get(&self) -> Output<T, E> {
+        Ok(self.value.clone())
+    }
+}
+impl<T: Clone, I: Iterator<Item = Datum<T>>, E: Copy + Debug> Updatable<E> for IterGetter<T, I, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self.iterator.next();
+        Ok(())
+    }
+}
 impl<E: Copy + Debug> TimeGetter<E> for Time {
     fn get(&self) -> TimeOutput<E> {
         Ok(*self)
@@ -498,15 +1615,154 @@ impl<E: Copy + Debug> Updatable<E> for Time {
         Ok(())
     }
 }
+///How a connection between two [`Terminal`]s chooses between the commands set on each end. Both
+///terminals of a connection always use the same policy, so getting a command from either of them
+///gives the same result.
+#[cfg(feature = "devices")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CommandPolicy {
+    ///Use the command with the newest timestamp. This is the default.
+    #[default]
+    LatestWins,
+    ///Use the command whose [`PositionDerivative`] comes first in the array, falling back to the
+    ///newest timestamp if both are of the same derivative. For example,
+    ///`[Velocity, Position, Acceleration]` lets a velocity command from a safety system override a
+    ///position command even if the position command is newer.
+    PriorityByDerivative([PositionDerivative; 3]),
+    ///Use the command set on the terminal with the higher
+    ///[source priority](Terminal::set_source_priority), falling back to the newest timestamp if
+    ///they are equal.
+    PriorityBySource,
+}
+#[cfg(feature = "devices")]
+impl CommandPolicy {
+    ///Returns true if `challenger` should be used instead of `incumbent`. Each command is paired
+    ///with the source priority of the terminal it was set on.
+    fn prefers(&self, incumbent: (Datum<Command>, u8), challenger: (Datum<Command>, u8)) -> bool {
+        let newer = challenger.0.time > incumbent.0.time;
+        match self {
+            Self::LatestWins => newer,
+            Self::PriorityByDerivative(order) => {
+                let rank = |command: &Command| {
+                    let position_derivative = PositionDerivative::from(*command);
+                    order
+                        .iter()
+                        .position(|x| *x == position_derivative)
+                        .unwrap_or(order.len())
+                };
+                let incumbent_rank = rank(&incumbent.0.value);
+                let challenger_rank = rank(&challenger.0.value);
+                challenger_rank < incumbent_rank || (challenger_rank == incumbent_rank && newer)
+            }
+            Self::PriorityBySource => {
+                challenger.1 > incumbent.1 || (challenger.1 == incumbent.1 && newer)
+            }
+        }
+    }
+}
+///How a connection between two [`Terminal`]s combines the states set on each end when both have
+///one. Set one on a terminal with [`set_state_merger`](Terminal::set_state_merger). Like a
+///[`CommandPolicy`], the merger belongs to the connection, and both terminals merge in the same
+///order, so getting a state from either of them gives the same result.
+#[cfg(feature = "devices")]
+pub trait StateMerger {
+    ///Combine `own`, the state set on the terminal that the merger was set on, with `other`, the
+    ///state set on the terminal it is connected to.
+    fn merge(&self, own: Datum<State>, other: Datum<State>) -> Datum<State>;
+}
+///Averages the two states equally. This is what a [`Terminal`] does without a [`StateMerger`].
+#[cfg(feature = "devices")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AverageStateMerger;
+#[cfg(feature = "devices")]
+impl StateMerger for AverageStateMerger {
+    fn merge(&self, own: Datum<State>, other: Datum<State>) -> Datum<State> {
+        (own + other) / 2.0
+    }
+}
+///Uses whichever state is newer, preferring the terminal's own state if they have the same
+///timestamp.
+#[cfg(feature = "devices")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NewestStateMerger;
+#[cfg(feature = "devices")]
+impl StateMerger for NewestStateMerger {
+    fn merge(&self, own: Datum<State>, other: Datum<State>) -> Datum<State> {
+        latest(own, other)
+    }
+}
+///Averages the two states with a fixed weight given to the terminal's own state. The other state
+///gets the rest of the weight.
+#[cfg(feature = "devices")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedStateMerger {
+    ///The weight of the terminal's own state, from 0 to 1.
+    pub own_weight: f32,
+}
+#[cfg(feature = "devices")]
+impl WeightedStateMerger {
+    ///Constructor for [`WeightedStateMerger`].
+    pub const fn new(own_weight: f32) -> Self {
+        Self {
+            own_weight: own_weight,
+        }
+    }
+}
+#[cfg(feature = "devices")]
+impl StateMerger for WeightedStateMerger {
+    fn merge(&self, own: Datum<State>, other: Datum<State>) -> Datum<State> {
+        own * self.own_weight + other * (1.0 - self.own_weight)
+    }
+}
+///Averages the two states weighted by the inverse of the variance of each source, so the more
+///trustworthy measurement counts for more. If both variances are zero, the states are averaged
+///equally.
+#[cfg(feature = "devices")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VarianceWeightedStateMerger {
+    ///The variance of the state set on the terminal itself.
+    pub own_variance: f32,
+    ///The variance of the state set on the terminal it is connected to.
+    pub other_variance: f32,
+}
+#[cfg(feature = "devices")]
+impl VarianceWeightedStateMerger {
+    ///Constructor for [`VarianceWeightedStateMerger`].
+    pub const fn new(own_variance: f32, other_variance: f32) -> Self {
+        Self {
+            own_variance: own_variance,
+            other_variance: other_variance,
+        }
+    }
+}
+#[cfg(feature = "devices")]
+impl StateMerger for VarianceWeightedStateMerger {
+    fn merge(&self, own: Datum<State>, other: Datum<State>) -> Datum<State> {
+        let total_variance = self.own_variance + self.other_variance;
+        if total_variance == 0.0 {
+            return AverageStateMerger.merge(own, other);
+        }
+        let own_weight = self.other_variance / total_variance;
+        own * own_weight + other * (1.0 - own_weight)
+    }
+}
 ///A place where a device can connect to another.
 #[cfg(feature = "devices")]
 pub struct Terminal<'a, E: Copy + Debug> {
     settable_data_state: SettableData<Datum<State>, E>,
     settable_data_command: SettableData<Datum<Command>, E>,
     other: Option<&'a RefCell<Terminal<'a, E>>>,
+    max_command_age: Option<(Time, Reference<dyn TimeGetter<E>>)>,
+    command_policy: CommandPolicy,
+    //True if the command policy was copied from the connected terminal rather than set on this one.
+    command_policy_inherited: bool,
+    source_priority: u8,
+    //The bool is true if the merger was set on this terminal rather than the one it is connected
+    //to, so that both ends merge in the same order.
+    state_merger: Option<(&'a dyn StateMerger, bool)>,
 }
 #[cfg(feature = "devices")]
-impl<E: Copy + Debug> Terminal<'_, E> {
+impl<'a, E: Copy + Debug> Terminal<'a, E> {
     ///Direct constructor for a [`Terminal`]. You almost always actually want [`RefCell<Terminal>`]
     ///however, in which case you should call [`new`](Terminal::new), which returns [`RefCell<Terminal>`].
     pub const fn new_raw() -> Self {
@@ -514,6 +1770,11 @@ impl<E: Copy + Debug> Terminal<'_, E> {
             settable_data_state: SettableData::new(),
             settable_data_command: SettableData::new(),
             other: None,
+            max_command_age: None,
+            command_policy: CommandPolicy::LatestWins,
+            command_policy_inherited: false,
+            source_priority: 0,
+            state_merger: None,
         }
     }
     ///This constructs a [`RefCell<Terminal>`]. This is almost always what you want, and what is
@@ -523,17 +1784,92 @@ impl<E: Copy + Debug> Terminal<'_, E> {
         RefCell::new(Self::new_raw())
     }
     ///Disconnect this terminal and the one that it is connected to. You can connect terminals by
-    ///calling the [`rrtk::connect`](connect) function.
+    ///calling the [`rrtk::connect`](connect) function. A [`CommandPolicy`] or [`StateMerger`] that
+    ///a terminal only had because of the connection is removed from it, so each terminal keeps
+    ///only what was set on it directly.
     pub fn disconnect(&mut self) {
         match self.other {
             Some(other) => {
                 let mut other = other.borrow_mut();
                 other.other = None;
+                other.drop_inherited();
                 self.other = None;
+                self.drop_inherited();
             }
             None => (),
         }
     }
+    fn drop_inherited(&mut self) {
+        if let Some((_, false)) = self.state_merger {
+            self.state_merger = None;
+        }
+        if self.command_policy_inherited {
+            self.command_policy = CommandPolicy::LatestWins;
+            self.command_policy_inherited = false;
+        }
+    }
+    ///Stop returning commands older than `max_age` according to `time_getter`. By default, a
+    ///terminal keeps returning the last command it received forever, so a device will keep acting
+    ///on it even if whatever was sending commands has stopped. With a maximum age, getting a
+    ///command from this terminal returns `None` once the newest command is too old, and devices
+    ///connected to it will stop acting on it. If `time_getter` returns an error, commands are
+    ///also treated as too old since their age cannot be determined. Commands keep their original
+    ///timestamps as they are passed between devices, so this should be set on the terminal that
+    ///the device acting on the command reads from.
+    pub fn set_max_command_age(
+        &mut self,
+        max_age: Time,
+        time_getter: Reference<dyn TimeGetter<E>>,
+    ) {
+        self.max_command_age = Some((max_age, time_getter));
+    }
+    ///Go back to returning the last command regardless of its age.
+    pub fn clear_max_command_age(&mut self) {
+        self.max_command_age = None;
+    }
+    ///Set how the connection this terminal is part of chooses between the command set on it and
+    ///the command set on the terminal it is connected to. The policy belongs to the connection, so
+    ///it is also set on the connected terminal, and [`connect`] gives both terminals the policy of
+    ///the first one.
+    pub fn set_command_policy(&mut self, command_policy: CommandPolicy) {
+        self.command_policy = command_policy;
+        self.command_policy_inherited = false;
+        if let Some(other) = self.other {
+            let mut other = other.borrow_mut();
+            other.command_policy = command_policy;
+            other.command_policy_inherited = true;
+        }
+    }
+    ///Get the policy this terminal uses to choose between commands.
+    pub fn get_command_policy(&self) -> CommandPolicy {
+        self.command_policy
+    }
+    ///Set the priority of commands set on this terminal for
+    ///[`CommandPolicy::PriorityBySource`]. Higher values win. The default is 0.
+    pub fn set_source_priority(&mut self, source_priority: u8) {
+        self.source_priority = source_priority;
+    }
+    ///Get the priority of commands set on this terminal.
+    pub fn get_source_priority(&self) -> u8 {
+        self.source_priority
+    }
+    ///Set how the connection this terminal is part of combines the state set on this terminal
+    ///with the state of the terminal it is connected to. By default, they are averaged equally.
+    ///The merger belongs to the connection, so it is also set on the connected terminal, and
+    ///[`connect`] gives both terminals the merger of the first one.
+    pub fn set_state_merger(&mut self, state_merger: &'a dyn StateMerger) {
+        self.state_merger = Some((state_merger, true));
+        if let Some(other) = self.other {
+            other.borrow_mut().state_merger = Some((state_merger, false));
+        }
+    }
+    ///Go back to averaging states equally on both ends of the connection.
+    pub fn clear_state_merger(&mut self) {
+        self.state_merger = None;
+        if let Some(other) = self.other {
+            other.borrow_mut().state_merger = None;
+        }
+    }
 }
 #[cfg(feature = "devices")]
 impl<E: Copy + Debug> Settable<Datum<State>, E> for Terminal<'_, E> {
@@ -588,9 +1924,13 @@ impl<E: Copy + Debug> Getter<State, E> for Terminal<'_, E> {
                 0 => return Ok(None),
                 1 => return Ok(Some(addends[0].assume_init())),
                 2 => {
-                    return Ok(Some(
-                        (addends[0].assume_init() + addends[1].assume_init()) / 2.0,
-                    ))
+                    let own = addends[0].assume_init();
+                    let other = addends[1].assume_init();
+                    return Ok(Some(match self.state_merger {
+                        Some((state_merger, true)) => state_merger.merge(own, other),
+                        Some((state_merger, false)) => state_merger.merge(other, own),
+                        None => AverageStateMerger.merge(own, other),
+                    }));
                 }
                 _ => unimplemented!(),
             }
@@ -600,33 +1940,45 @@ impl<E: Copy + Debug> Getter<State, E> for Terminal<'_, E> {
 #[cfg(feature = "devices")]
 impl<E: Copy + Debug> Getter<Command, E> for Terminal<'_, E> {
     fn get(&self) -> Output<Command, E> {
-        let mut maybe_command: Option<Datum<Command>> = None;
+        let mut maybe_command: Option<(Datum<Command>, u8)> = None;
         match self.get_last_request() {
             Some(command) => {
-                maybe_command = Some(command);
+                maybe_command = Some((command, self.source_priority));
             }
             None => {}
         }
         match self.other {
             Some(other) => {
-                match <Terminal<'_, E> as Settable<Datum<Command>, E>>::get_last_request(
-                    &other.borrow(),
-                ) {
-                    Some(gotten_command) => match maybe_command {
-                        Some(command_some) => {
-                            if gotten_command.time > command_some.time {
+                let other = other.borrow();
+                match <Terminal<'_, E> as Settable<Datum<Command>, E>>::get_last_request(&other) {
+                    Some(gotten_command) => {
+                        let gotten_command = (gotten_command, other.source_priority);
+                        match maybe_command {
+                            Some(command_some) => {
+                                if self.command_policy.prefers(command_some, gotten_command) {
+                                    maybe_command = Some(gotten_command);
+                                }
+                            }
+                            None => {
                                 maybe_command = Some(gotten_command);
                             }
                         }
-                        None => {
-                            maybe_command = Some(gotten_command);
-                        }
-                    },
+                    }
                     None => (),
                 }
             }
             None => (),
         }
+        let maybe_command = maybe_command.map(|(command, _)| command);
+        if let (Some(command), Some((max_age, time_getter))) =
+            (maybe_command, &self.max_command_age)
+        {
+            let time = time_getter.borrow().get();
+            match time {
+                Ok(time) if time - command.time <= *max_age => {}
+                _ => return Ok(None),
+            }
+        }
         Ok(maybe_command)
     }
 }
@@ -670,7 +2022,8 @@ impl<E: Copy + Debug> Updatable<E> for Terminal<'_, E> {
 ///Connect two terminals. Connected terminals should represent a physical connection between
 ///mechanical devices. This function will automatically disconnect the specified terminals if they
 ///are connected. You can manually disconnect terminals by calling the
-///[`disconnect`](Terminal::disconnect) method on either of them.
+///[`disconnect`](Terminal::disconnect) method on either of them. The connection uses the
+///[`CommandPolicy`] and [`StateMerger`] of `term1`, which are copied to `term2`.
 #[cfg(feature = "devices")]
 pub fn connect<'a, E: Copy + Debug>(
     term1: &'a RefCell<Terminal<'a, E>>,
@@ -682,6 +2035,11 @@ pub fn connect<'a, E: Copy + Debug>(
     term2_borrow.disconnect();
     term1_borrow.other = Some(term2);
     term2_borrow.other = Some(term1);
+    term2_borrow.command_policy = term1_borrow.command_policy;
+    term2_borrow.command_policy_inherited = true;
+    term2_borrow.state_merger = term1_borrow
+        .state_merger
+        .map(|(state_merger, own)| (state_merger, !own));
 }
 ///Data that are sent between terminals: A timestamp, an optional command, and a state.
 #[cfg(feature = "devices")]
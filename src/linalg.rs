@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Minimal fixed-size linear algebra for small matrices of [`f32`]. This is meant for things like
+//!state-space control, Kalman filtering, and kinematics, not for large problems. Everything here
+//!works without `std` or `alloc`.
+use crate::*;
+#[inline]
+fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+///A matrix with `R` rows and `C` columns stored in row-major order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    ///The elements of the matrix. `data[i][j]` is the element in row `i` and column `j`.
+    pub data: [[f32; C]; R],
+}
+///A column vector with `N` elements.
+pub type Vector<const N: usize> = Matrix<N, 1>;
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    ///Constructor for [`Matrix`] from rows.
+    pub const fn new(data: [[f32; C]; R]) -> Self {
+        Self { data: data }
+    }
+    ///Create a matrix of all zeros.
+    pub const fn zeros() -> Self {
+        Self::new([[0.0; C]; R])
+    }
+    ///Get the element in row `row` and column `column`.
+    #[inline]
+    pub const fn get(&self, row: usize, column: usize) -> f32 {
+        self.data[row][column]
+    }
+    ///Set the element in row `row` and column `column`.
+    #[inline]
+    pub fn set(&mut self, row: usize, column: usize, value: f32) {
+        self.data[row][column] = value;
+    }
+    ///Get the transpose of the matrix.
+    pub fn transpose(&self) -> Matrix<C, R> {
+        let mut output = Matrix::<C, R>::zeros();
+        for i in 0..R {
+            for j in 0..C {
+                output.data[j][i] = self.data[i][j];
+            }
+        }
+        output
+    }
+}
+impl<const N: usize> Matrix<N, N> {
+    ///Create an identity matrix.
+    pub const fn identity() -> Self {
+        let mut output = Self::zeros();
+        let mut i = 0;
+        while i < N {
+            output.data[i][i] = 1.0;
+            i += 1;
+        }
+        output
+    }
+    ///Solve `self * x = b` for `x` using Gaussian elimination with partial pivoting. Returns `None`
+    ///if the matrix is singular or very close to it relative to the size of its largest element.
+    pub fn solve<const K: usize>(&self, b: &Matrix<N, K>) -> Option<Matrix<N, K>> {
+        let mut a = self.data;
+        let mut x = b.data;
+        let mut max_abs = 0.0;
+        for row in &a {
+            for element in row {
+                if abs(*element) > max_abs {
+                    max_abs = abs(*element);
+                }
+            }
+        }
+        let tolerance = f32::EPSILON * max_abs * N as f32;
+        for column in 0..N {
+            let mut pivot = column;
+            for row in column + 1..N {
+                if abs(a[row][column]) > abs(a[pivot][column]) {
+                    pivot = row;
+                }
+            }
+            if abs(a[pivot][column]) <= tolerance {
+                return None;
+            }
+            a.swap(column, pivot);
+            x.swap(column, pivot);
+            let (a_upper, a_lower) = a.split_at_mut(column + 1);
+            let (x_upper, x_lower) = x.split_at_mut(column + 1);
+            let (a_pivot, x_pivot) = (&a_upper[column], &x_upper[column]);
+            for (a_row, x_row) in a_lower.iter_mut().zip(x_lower.iter_mut()) {
+                let factor = a_row[column] / a_pivot[column];
+                for (element, pivot_element) in a_row.iter_mut().zip(a_pivot).skip(column) {
+                    *element -= factor * pivot_element;
+                }
+                for (element, pivot_element) in x_row.iter_mut().zip(x_pivot) {
+                    *element -= factor * pivot_element;
+                }
+            }
+        }
+        for column in (0..N).rev() {
+            let (x_upper, x_lower) = x.split_at_mut(column + 1);
+            for (k, element) in x_upper[column].iter_mut().enumerate() {
+                let mut sum = *element;
+                for (coefficient, x_row) in a[column][column + 1..].iter().zip(x_lower.iter()) {
+                    sum -= coefficient * x_row[k];
+                }
+                *element = sum / a[column][column];
+            }
+        }
+        Some(Matrix::new(x))
+    }
+    ///Get the inverse of the matrix. Returns `None` if the matrix is singular or very close to
+    ///it.
+    #[inline]
+    pub fn inverse(&self) -> Option<Self> {
+        self.solve(&Self::identity())
+    }
+}
+impl<const N: usize> Vector<N> {
+    ///Create a column vector from its elements.
+    pub const fn from_elements(elements: [f32; N]) -> Self {
+        let mut output = Self::zeros();
+        let mut i = 0;
+        while i < N {
+            output.data[i][0] = elements[i];
+            i += 1;
+        }
+        output
+    }
+    ///Get the dot product of two vectors.
+    pub fn dot(&self, rhs: &Self) -> f32 {
+        let mut output = 0.0;
+        for i in 0..N {
+            output += self.data[i][0] * rhs.data[i][0];
+        }
+        output
+    }
+}
+impl<const R: usize, const C: usize> Default for Matrix<R, C> {
+    fn default() -> Self {
+        Self::zeros()
+    }
+}
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let mut output = self;
+        for i in 0..R {
+            for j in 0..C {
+                output.data[i][j] += rhs.data[i][j];
+            }
+        }
+        output
+    }
+}
+impl<const R: usize, const C: usize> AddAssign for Matrix<R, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<const R: usize, const C: usize> Sub for Matrix<R, C> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let mut output = self;
+        for i in 0..R {
+            for j in 0..C {
+                output.data[i][j] -= rhs.data[i][j];
+            }
+        }
+        output
+    }
+}
+impl<const R: usize, const C: usize> SubAssign for Matrix<R, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+impl<const R: usize, const C: usize> Neg for Matrix<R, C> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * -1.0
+    }
+}
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+    fn mul(self, rhs: Matrix<C, K>) -> Matrix<R, K> {
+        let mut output = Matrix::<R, K>::zeros();
+        for i in 0..R {
+            for j in 0..K {
+                let mut sum = 0.0;
+                for k in 0..C {
+                    sum += self.data[i][k] * rhs.data[k][j];
+                }
+                output.data[i][j] = sum;
+            }
+        }
+        output
+    }
+}
+impl<const R: usize, const C: usize> Mul<f32> for Matrix<R, C> {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self {
+        let mut output = self;
+        for i in 0..R {
+            for j in 0..C {
+                output.data[i][j] *= rhs;
+            }
+        }
+        output
+    }
+}
+impl<const R: usize, const C: usize> MulAssign<f32> for Matrix<R, C> {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+impl<const R: usize, const C: usize> Div<f32> for Matrix<R, C> {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self {
+        let mut output = self;
+        for i in 0..R {
+            for j in 0..C {
+                output.data[i][j] /= rhs;
+            }
+        }
+        output
+    }
+}
+impl<const R: usize, const C: usize> DivAssign<f32> for Matrix<R, C> {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+///A polynomial with `N` coefficients, so of degree `N - 1`. This is mostly for sensor calibration
+///curves, which rarely need more than a cubic, and fitting becomes numerically poor for higher
+///degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Polynomial<const N: usize> {
+    ///The coefficients in ascending order of power, so `coefficients[i]` is multiplied by `x^i`.
+    pub coefficients: [f32; N],
+}
+impl<const N: usize> Polynomial<N> {
+    ///Constructor for [`Polynomial`] from coefficients in ascending order of power.
+    pub const fn new(coefficients: [f32; N]) -> Self {
+        Self {
+            coefficients: coefficients,
+        }
+    }
+    ///Evaluate the polynomial at `x`.
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let mut output = 0.0;
+        for coefficient in self.coefficients.iter().rev() {
+            output = output * x + coefficient;
+        }
+        output
+    }
+    ///Fit a polynomial to `(x, y)` points by least squares. Returns `None` if there are not enough
+    ///distinct `x` values to determine all the coefficients. The `x` values are centered and scaled
+    ///internally so that the fit does not depend on where they are, but with [`f32`] precision, fits
+    ///of a degree higher than 3 are still unreliable.
+    pub fn fit(points: &[(f32, f32)]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let mut mean = 0.0;
+        for &(x, _) in points {
+            mean += x;
+        }
+        mean /= points.len() as f32;
+        let mut scale = 0.0;
+        for &(x, _) in points {
+            if abs(x - mean) > scale {
+                scale = abs(x - mean);
+            }
+        }
+        if scale == 0.0 {
+            scale = 1.0;
+        }
+        //Solve the normal equations (AᵀA)c = Aᵀy where each row of A is [1, u, u², ...] with
+        //u = (x - mean) / scale.
+        let mut ata = Matrix::<N, N>::zeros();
+        let mut aty = Vector::<N>::zeros();
+        for &(x, y) in points {
+            let mut powers = [0.0; N];
+            let mut power = 1.0;
+            for element in &mut powers {
+                *element = power;
+                power *= (x - mean) / scale;
+            }
+            for i in 0..N {
+                for j in 0..N {
+                    ata.data[i][j] += powers[i] * powers[j];
+                }
+                aty.data[i][0] += powers[i] * y;
+            }
+        }
+        let solution = ata.solve(&aty)?;
+        //Expand the polynomial in u back into one in x. basis holds the coefficients of u^k in
+        //terms of x.
+        let mut coefficients = [0.0; N];
+        let mut basis = [0.0; N];
+        if let Some(first) = basis.first_mut() {
+            *first = 1.0;
+        }
+        for row in solution.data {
+            for (coefficient, basis_coefficient) in coefficients.iter_mut().zip(basis) {
+                *coefficient += row[0] * basis_coefficient;
+            }
+            let mut next = [0.0; N];
+            for (j, element) in next.iter_mut().enumerate() {
+                *element = -mean / scale * basis[j];
+                if j > 0 {
+                    *element += basis[j - 1] / scale;
+                }
+            }
+            basis = next;
+        }
+        Some(Self::new(coefficients))
+    }
+}
@@ -15,14 +15,39 @@ pub enum MotionProfilePiece {
     ///You are done with the motion profile.
     Complete,
 }
-///A motion profile for getting from one state to another.
+///Why a [`MotionProfile`] could not be created by [`MotionProfile::try_new`] or an
+///[`SCurveMotionProfile`] could not be created by [`SCurveMotionProfile::try_new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MotionProfileError {
+    ///A position, velocity, or constraint was infinite or NaN.
+    NonFinite,
+    ///A position or constraint did not have the unit it should. This is only checked with dimension
+    ///checking on.
+    UnitInvalid(UnitMismatch),
+    ///The maximum acceleration was zero.
+    ZeroAcceleration,
+    ///The maximum velocity was zero.
+    ZeroVelocity,
+    ///The maximum jerk was zero.
+    ZeroJerk,
+    ///The start or end velocity was faster than the maximum velocity in the direction of travel.
+    MaxVelocityTooLow,
+    ///The distance between the start and end positions was too short to accelerate to the maximum
+    ///velocity and decelerate again. Lowering the maximum velocity may help.
+    DistanceTooShort,
+}
+///A motion profile for getting from one state to another. Times passed to a profile are measured
+///from the same zero as its [start time](MotionProfile::start_time), which is zero unless it has
+///been [replanned](MotionProfile::replan).
 #[derive(Clone, Debug, PartialEq)]
 pub struct MotionProfile {
+    start_time: Time,
     start_pos: Quantity,
     start_vel: Quantity,
     t1: Time,
     t2: Time,
     t3: Time,
+    max_vel: Quantity,
     max_acc: Quantity,
     end_command: Command,
 }
@@ -48,6 +73,13 @@ impl<E: Copy + Debug> History<Command, E> for MotionProfile {
         Some(Datum::new(time, Command::new(mode, value.into())))
     }
 }
+impl<E: Copy + Debug> FiniteHistory<Command, E> for MotionProfile {
+    ///The profile is complete at its [start time](MotionProfile::start_time) plus its
+    ///[duration](MotionProfile::duration).
+    fn end_time(&self) -> Time {
+        self.start_time + self.t3
+    }
+}
 impl<E: Copy + Debug> Updatable<E> for MotionProfile {
     fn update(&mut self) -> NothingOrError<E> {
         Ok(())
@@ -87,6 +119,7 @@ impl MotionProfile {
         let t3 = t2 + d_t3;
         let end_command = Command::from(end_state);
         MotionProfile {
+            start_time: Time::default(),
             start_pos: start_state.get_position(),
             start_vel: start_state.get_velocity(),
             t1: Time::try_from(t1).expect(
@@ -98,12 +131,100 @@ impl MotionProfile {
             t3: Time::try_from(t3).expect(
                 "t3 must always be in seconds in max_vel and max_acc have correct dimensions",
             ),
+            max_vel: max_vel,
             max_acc: max_acc,
             end_command: end_command,
         }
     }
+    ///Constructor for [`MotionProfile`] that checks that a valid profile can be created from its
+    ///inputs and returns an error describing why if it cannot. [`new`](MotionProfile::new) may panic
+    ///or create a profile with meaningless times for the same inputs.
+    pub fn try_new(
+        start_state: State,
+        end_state: State,
+        max_vel: Quantity,
+        max_acc: Quantity,
+    ) -> Result<MotionProfile, MotionProfileError> {
+        for (quantity, expected) in [
+            (max_vel, MILLIMETER_PER_SECOND),
+            (max_acc, MILLIMETER_PER_SECOND_SQUARED),
+        ] {
+            quantity.unit.check(&expected).map_err(|mismatch| {
+                MotionProfileError::UnitInvalid(mismatch.with_operation("MotionProfile::try_new"))
+            })?;
+        }
+        for value in [
+            start_state.position,
+            start_state.velocity,
+            end_state.position,
+            end_state.velocity,
+            max_vel.value,
+            max_acc.value,
+        ] {
+            if !value.is_finite() {
+                return Err(MotionProfileError::NonFinite);
+            }
+        }
+        if max_acc.value == 0.0 {
+            return Err(MotionProfileError::ZeroAcceleration);
+        }
+        if max_vel.value == 0.0 {
+            return Err(MotionProfileError::ZeroVelocity);
+        }
+        //This mirrors the calculations in new using raw values so that problems can be found
+        //before they cause a panic.
+        let sign = if end_state.position < start_state.position {
+            -1.0
+        } else {
+            1.0
+        };
+        let max_vel_raw = f32::from(max_vel.abs()) * sign;
+        let max_acc_raw = f32::from(max_acc.abs()) * sign;
+        let t1 = (max_vel_raw - start_state.velocity) / max_acc_raw;
+        let d_t3 = (end_state.velocity - max_vel_raw) / -max_acc_raw;
+        if t1 < 0.0 || d_t3 < 0.0 {
+            return Err(MotionProfileError::MaxVelocityTooLow);
+        }
+        let d_t1_pos = (start_state.velocity + max_vel_raw) / 2.0 * t1;
+        let d_t3_pos = (max_vel_raw + end_state.velocity) / 2.0 * d_t3;
+        let d_t2_pos = (end_state.position - start_state.position) - (d_t1_pos + d_t3_pos);
+        if d_t2_pos / max_vel_raw < 0.0 {
+            return Err(MotionProfileError::DistanceTooShort);
+        }
+        Ok(Self::new(start_state, end_state, max_vel, max_acc))
+    }
+    ///Get the time from the start of the motion profile until it is complete.
+    #[inline]
+    pub fn duration(&self) -> Time {
+        self.t3
+    }
+    ///Get the time at which the motion profile starts. This is zero unless it has been
+    ///[replanned](MotionProfile::replan).
+    #[inline]
+    pub fn start_time(&self) -> Time {
+        self.start_time
+    }
+    ///Regenerate the profile mid-motion so that it goes from `new_start` to `new_end` starting at
+    ///time `t`, keeping the same maximum velocity and acceleration. Times keep being measured from
+    ///the same zero, so anything already following the profile, such as a
+    ///[`GetterFromHistory`], does not need its time
+    ///adjusted. To continue smoothly from where the profile currently is, use
+    ///[`get_state(t)`](MotionProfile::get_state) as `new_start`. If a valid profile cannot be
+    ///created, the error is returned and the profile is left unchanged.
+    pub fn replan(
+        &mut self,
+        new_start: State,
+        new_end: State,
+        t: Time,
+    ) -> Result<(), MotionProfileError> {
+        let mut replanned = Self::try_new(new_start, new_end, self.max_vel, self.max_acc)?;
+        replanned.start_time = t;
+        *self = replanned;
+        Ok(())
+    }
     ///Get the intended [`PositionDerivative`] at a given time.
     pub fn get_mode(&self, t: Time) -> Option<PositionDerivative> {
+        let t = t - self.start_time;
         if t < Time::default() {
             return None;
         } else if t < self.t1 {
@@ -118,6 +239,7 @@ impl MotionProfile {
     }
     ///Get the [`MotionProfilePiece`] at a given time.
     pub fn get_piece(&self, t: Time) -> MotionProfilePiece {
+        let t = t - self.start_time;
         if t < Time::default() {
             return MotionProfilePiece::BeforeStart;
         } else if t < self.t1 {
@@ -132,6 +254,7 @@ impl MotionProfile {
     }
     ///Get the intended acceleration at a given time.
     pub fn get_acceleration(&self, t: Time) -> Option<Quantity> {
+        let t = t - self.start_time;
         if t < Time::default() {
             return None;
         } else if t < self.t1 {
@@ -146,6 +269,7 @@ impl MotionProfile {
     }
     ///Get the intended velocity at a given time.
     pub fn get_velocity(&self, t: Time) -> Option<Quantity> {
+        let t = t - self.start_time;
         if t < Time::default() {
             return None;
         } else if t < self.t1 {
@@ -160,6 +284,7 @@ impl MotionProfile {
     }
     ///Get the intended position at a given time.
     pub fn get_position(&self, t: Time) -> Option<Quantity> {
+        let t = t - self.start_time;
         if t < Time::default() {
             return None;
         } else if t < self.t1 {
@@ -176,18 +301,358 @@ impl MotionProfile {
                     + self.start_pos,
             );
         } else if t < self.t3 {
-            return Some(
-                self.max_acc * (self.t1 * (-self.t1 / DimensionlessInteger(2) + self.t2))
-                    - Quantity::dimensionless(0.5)
-                        * self.max_acc
-                        * ((t - self.t2) * (t - DimensionlessInteger(2) * self.t1 - self.t2))
-                    + self.start_vel * Quantity::from(t)
-                    + self.start_pos,
-            );
+            return Some(self.end_acceleration_position(t));
         } else {
             return self.end_command.get_position();
         }
     }
+    fn end_acceleration_position(&self, t: Time) -> Quantity {
+        self.max_acc * (self.t1 * (-self.t1 / DimensionlessInteger(2) + self.t2))
+            - Quantity::dimensionless(0.5)
+                * self.max_acc
+                * ((t - self.t2) * (t - DimensionlessInteger(2) * self.t1 - self.t2))
+            + self.start_vel * Quantity::from(t)
+            + self.start_pos
+    }
+    ///Get the times at which the initial acceleration ends, the constant velocity ends, and the end
+    ///acceleration ends, in that order. The last one is the
+    ///[`start_time`](MotionProfile::start_time) plus the [`duration`](MotionProfile::duration).
+    pub fn phase_boundaries(&self) -> [Time; 3] {
+        [
+            self.start_time + self.t1,
+            self.start_time + self.t2,
+            self.start_time + self.t3,
+        ]
+    }
+    ///Get the velocity during the constant velocity part of the profile.
+    pub fn peak_velocity(&self) -> Quantity {
+        self.max_acc * Quantity::from(self.t1) + self.start_vel
+    }
+    ///Get the full intended [`State`] at a given time. Unlike [`get_position`](MotionProfile::get_position)
+    ///and [`get_velocity`](MotionProfile::get_velocity), this does not depend on the end command
+    ///after the profile is complete; instead, the end velocity is assumed to be held with no
+    ///acceleration. Returns `None` before the start.
+    pub fn get_state(&self, t: Time) -> Option<State> {
+        let t = t - self.start_time;
+        if t < Time::default() {
+            return None;
+        }
+        if t < self.t3 {
+            let t = t + self.start_time;
+            return Some(State::new(
+                self.get_position(t)?,
+                self.get_velocity(t)?,
+                self.get_acceleration(t)?,
+            ));
+        }
+        let end_velocity =
+            self.max_acc * Quantity::from(self.t1 + self.t2 - self.t3) + self.start_vel;
+        let end_position =
+            self.end_acceleration_position(self.t3) + end_velocity * Quantity::from(t - self.t3);
+        Some(State::new(
+            end_position,
+            end_velocity,
+            Quantity::new(0.0, MILLIMETER_PER_SECOND_SQUARED),
+        ))
+    }
+    ///Iterate over the intended [`State`] every `time_step` from the start time of the profile, followed
+    ///by the state at the end of the profile. This is useful for plotting a profile and checking it
+    ///before running it on hardware. Panics if `time_step` is not positive.
+    pub fn sample_iter(&self, time_step: Time) -> MotionProfileSamples<'_> {
+        assert!(time_step > Time::default());
+        MotionProfileSamples {
+            profile: self,
+            time_step: time_step,
+            next_time: Some(self.start_time),
+        }
+    }
+}
+///An iterator over `(Time, State)` points along a [`MotionProfile`]. Created with
+///[`MotionProfile::sample_iter`].
+pub struct MotionProfileSamples<'a> {
+    profile: &'a MotionProfile,
+    time_step: Time,
+    next_time: Option<Time>,
+}
+impl Iterator for MotionProfileSamples<'_> {
+    type Item = (Time, State);
+    fn next(&mut self) -> Option<(Time, State)> {
+        let time = self.next_time?;
+        let end_time = self.profile.start_time() + self.profile.duration();
+        self.next_time = if time >= end_time {
+            None
+        } else if time + self.time_step >= end_time {
+            Some(end_time)
+        } else {
+            Some(time + self.time_step)
+        };
+        Some((
+            time,
+            self.profile
+                .get_state(time)
+                .expect("sample times are never before the start"),
+        ))
+    }
+}
+///A jerk-limited motion profile, also called an S-curve profile, for getting from one position to
+///another.
+///
+///**This takes start and end positions, not [`State`]s like [`MotionProfile`] does.** The profile
+///always starts and ends at rest, so it cannot be used to continue a motion that is already under
+///way.
+///
+///Unlike [`MotionProfile`], acceleration ramps up and down at no more than `max_jerk`
+///instead of changing instantly, which avoids shaking mechanisms such as arms at the corners of a
+///trapezoidal profile. The profile starts and ends at rest. It has up to seven phases: increasing
+///acceleration, constant acceleration, decreasing acceleration, constant velocity, and the same
+///three mirrored while slowing down. If the distance is too short to reach `max_vel` or
+///`max_acc`, the phases that would reach them are shortened or left out. Like [`MotionProfile`],
+///it implements [`History<Command, E>`], returning acceleration commands while accelerating, a
+///velocity command while at constant velocity, and the end position once complete.
+#[cfg(feature = "internal_enhanced_float")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SCurveMotionProfile {
+    start_pos: f32,
+    end_pos: f32,
+    //The jerk in each of the seven phases.
+    jerks: [f32; 7],
+    //The start of each phase followed by the end of the profile.
+    boundaries: [Time; 8],
+    //The position, velocity, and acceleration at each boundary.
+    boundary_states: [(f32, f32, f32); 8],
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<E: Copy + Debug> History<Command, E> for SCurveMotionProfile {
+    fn get(&self, time: Time) -> Option<Datum<Command>> {
+        let state = self.get_state(time)?;
+        let command = match self.get_piece(time) {
+            MotionProfilePiece::BeforeStart => return None,
+            MotionProfilePiece::InitialAcceleration | MotionProfilePiece::EndAcceleration => {
+                Command::Acceleration(state.acceleration)
+            }
+            MotionProfilePiece::ConstantVelocity => Command::Velocity(state.velocity),
+            MotionProfilePiece::Complete => Command::Position(self.end_pos),
+        };
+        Some(Datum::new(time, command))
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<E: Copy + Debug> FiniteHistory<Command, E> for SCurveMotionProfile {
+    fn end_time(&self) -> Time {
+        self.duration()
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<E: Copy + Debug> Updatable<E> for SCurveMotionProfile {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl SCurveMotionProfile {
+    ///Constructor for [`SCurveMotionProfile`]. Panics if a valid profile cannot be created; use
+    ///[`try_new`](SCurveMotionProfile::try_new) to get an error instead.
+    pub fn new(
+        start_position: Quantity,
+        end_position: Quantity,
+        max_vel: Quantity,
+        max_acc: Quantity,
+        max_jerk: Quantity,
+    ) -> Self {
+        Self::try_new(start_position, end_position, max_vel, max_acc, max_jerk)
+            .expect("invalid SCurveMotionProfile constraints")
+    }
+    ///Constructor for [`SCurveMotionProfile`] that returns an error describing why a valid profile
+    ///cannot be created from its inputs if it cannot.
+    pub fn try_new(
+        start_position: Quantity,
+        end_position: Quantity,
+        max_vel: Quantity,
+        max_acc: Quantity,
+        max_jerk: Quantity,
+    ) -> Result<Self, MotionProfileError> {
+        for (quantity, expected) in [
+            (start_position, MILLIMETER),
+            (end_position, MILLIMETER),
+            (max_vel, MILLIMETER_PER_SECOND),
+            (max_acc, MILLIMETER_PER_SECOND_SQUARED),
+            (max_jerk, MILLIMETER_PER_SECOND_CUBED),
+        ] {
+            quantity.unit.check(&expected).map_err(|mismatch| {
+                MotionProfileError::UnitInvalid(
+                    mismatch.with_operation("SCurveMotionProfile::try_new"),
+                )
+            })?;
+        }
+        let start_pos = f32::from(start_position);
+        let end_pos = f32::from(end_position);
+        let max_vel = f32::from(max_vel.abs());
+        let max_acc = f32::from(max_acc.abs());
+        let max_jerk = f32::from(max_jerk.abs());
+        for value in [start_pos, end_pos, max_vel, max_acc, max_jerk] {
+            if !value.is_finite() {
+                return Err(MotionProfileError::NonFinite);
+            }
+        }
+        if max_acc == 0.0 {
+            return Err(MotionProfileError::ZeroAcceleration);
+        }
+        if max_vel == 0.0 {
+            return Err(MotionProfileError::ZeroVelocity);
+        }
+        if max_jerk == 0.0 {
+            return Err(MotionProfileError::ZeroJerk);
+        }
+        let distance = (end_pos - start_pos).abs();
+        let sign = if end_pos < start_pos { -1.0 } else { 1.0 };
+        //jerk_time is the length of each phase where acceleration is changing, accel_time is the
+        //length of the whole speeding up or slowing down part, and cruise_time is the length of
+        //the constant velocity part.
+        let acc_reachable = max_vel * max_jerk >= max_acc * max_acc;
+        let (mut jerk_time, mut accel_time) = if acc_reachable {
+            (max_acc / max_jerk, max_acc / max_jerk + max_vel / max_acc)
+        } else {
+            let jerk_time = sqrtf(max_vel / max_jerk);
+            (jerk_time, 2.0 * jerk_time)
+        };
+        let mut cruise_time = distance / max_vel - accel_time;
+        if cruise_time < 0.0 {
+            //The maximum velocity is not reached.
+            cruise_time = 0.0;
+            if acc_reachable && distance >= 2.0 * powf(max_acc, 3.0) / (max_jerk * max_jerk) {
+                jerk_time = max_acc / max_jerk;
+                accel_time =
+                    jerk_time / 2.0 + sqrtf(jerk_time * jerk_time / 4.0 + distance / max_acc);
+            } else {
+                jerk_time = powf(distance / (2.0 * max_jerk), 1.0 / 3.0);
+                accel_time = 2.0 * jerk_time;
+            }
+        }
+        let const_acc_time = accel_time - 2.0 * jerk_time;
+        let durations = [
+            jerk_time,
+            const_acc_time,
+            jerk_time,
+            cruise_time,
+            jerk_time,
+            const_acc_time,
+            jerk_time,
+        ];
+        let jerk = max_jerk * sign;
+        let jerks = [jerk, 0.0, -jerk, 0.0, -jerk, 0.0, jerk];
+        let mut boundaries = [Time::default(); 8];
+        let mut boundary_states = [(start_pos, 0.0, 0.0); 8];
+        let mut time = Time::default();
+        let mut state = (start_pos, 0.0, 0.0);
+        for ((duration, jerk), (boundary, boundary_state)) in durations.into_iter().zip(jerks).zip(
+            boundaries[1..]
+                .iter_mut()
+                .zip(boundary_states[1..].iter_mut()),
+        ) {
+            let duration = Time::try_from(Quantity::new(duration.max(0.0), SECOND))
+                .expect("durations are always in seconds");
+            time += duration;
+            //Integrate over the rounded duration so that the phases line up exactly.
+            state = Self::integrate(state, jerk, duration);
+            *boundary = time;
+            *boundary_state = state;
+        }
+        Ok(Self {
+            start_pos: start_pos,
+            end_pos: end_pos,
+            jerks: jerks,
+            boundaries: boundaries,
+            boundary_states: boundary_states,
+        })
+    }
+    fn integrate(state: (f32, f32, f32), jerk: f32, time: Time) -> (f32, f32, f32) {
+        let (pos, vel, acc) = state;
+        let t = f32::from(Quantity::from(time));
+        (
+            pos + vel * t + acc * t * t / 2.0 + jerk * t * t * t / 6.0,
+            vel + acc * t + jerk * t * t / 2.0,
+            acc + jerk * t,
+        )
+    }
+    ///Get the time from the start of the motion profile until it is complete.
+    #[inline]
+    pub fn duration(&self) -> Time {
+        self.boundaries[7]
+    }
+    ///Get the times at which each of the seven phases ends. The last one is the same as the
+    ///[`duration`](SCurveMotionProfile::duration). Phases that are left out end at the same time as
+    ///the one before them.
+    pub fn phase_boundaries(&self) -> [Time; 7] {
+        let mut output = [Time::default(); 7];
+        output.copy_from_slice(&self.boundaries[1..]);
+        output
+    }
+    ///Get the velocity during the constant velocity part of the profile, or the fastest velocity
+    ///reached if there is no constant velocity part.
+    pub fn peak_velocity(&self) -> Quantity {
+        Quantity::new(self.boundary_states[3].1, MILLIMETER_PER_SECOND)
+    }
+    ///Get the [`MotionProfilePiece`] at a given time. The three phases at the beginning are all
+    ///[`InitialAcceleration`](MotionProfilePiece::InitialAcceleration) and the three at the end
+    ///are all [`EndAcceleration`](MotionProfilePiece::EndAcceleration).
+    pub fn get_piece(&self, t: Time) -> MotionProfilePiece {
+        if t < Time::default() {
+            MotionProfilePiece::BeforeStart
+        } else if t < self.boundaries[3] {
+            MotionProfilePiece::InitialAcceleration
+        } else if t < self.boundaries[4] {
+            MotionProfilePiece::ConstantVelocity
+        } else if t < self.boundaries[7] {
+            MotionProfilePiece::EndAcceleration
+        } else {
+            MotionProfilePiece::Complete
+        }
+    }
+    ///Get the intended [`State`] at a given time. Returns `None` before the start. After the end,
+    ///the end position is held.
+    pub fn get_state(&self, t: Time) -> Option<State> {
+        if t < Time::default() {
+            return None;
+        }
+        if t >= self.boundaries[7] {
+            return Some(State::new_raw(self.end_pos, 0.0, 0.0));
+        }
+        let mut phase = 0;
+        while t >= self.boundaries[phase + 1] {
+            phase += 1;
+        }
+        let (pos, vel, acc) = Self::integrate(
+            self.boundary_states[phase],
+            self.jerks[phase],
+            t - self.boundaries[phase],
+        );
+        Some(State::new_raw(pos, vel, acc))
+    }
+    ///Get the intended position at a given time.
+    pub fn get_position(&self, t: Time) -> Option<Quantity> {
+        Some(self.get_state(t)?.get_position())
+    }
+    ///Get the intended velocity at a given time.
+    pub fn get_velocity(&self, t: Time) -> Option<Quantity> {
+        Some(self.get_state(t)?.get_velocity())
+    }
+    ///Get the intended acceleration at a given time.
+    pub fn get_acceleration(&self, t: Time) -> Option<Quantity> {
+        Some(self.get_state(t)?.get_acceleration())
+    }
+    ///Get the intended jerk at a given time.
+    pub fn get_jerk(&self, t: Time) -> Option<Quantity> {
+        if t < Time::default() {
+            return None;
+        }
+        let mut jerk = 0.0;
+        for phase in 0..7 {
+            if t >= self.boundaries[phase] && t < self.boundaries[phase + 1] {
+                jerk = self.jerks[phase];
+            }
+        }
+        Some(Quantity::new(jerk, MILLIMETER_PER_SECOND_CUBED))
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -334,4 +799,49 @@ mod tests {
             Quantity::new(-0.01, MILLIMETER_PER_SECOND_SQUARED)
         );
     }
+    #[test]
+    fn motion_profile_try_new() {
+        let max_vel = Quantity::new(0.1, MILLIMETER_PER_SECOND);
+        let max_acc = Quantity::new(0.01, MILLIMETER_PER_SECOND_SQUARED);
+        let start = State::new_raw(0.0, 0.0, 0.0);
+        let motion_profile =
+            MotionProfile::try_new(start, State::new_raw(-3.0, 0.0, 0.0), max_vel, max_acc)
+                .unwrap();
+        assert_eq!(motion_profile.duration(), Time(40_000_000_000));
+        assert_eq!(
+            MotionProfile::try_new(
+                start,
+                State::new_raw(3.0, 0.0, 0.0),
+                max_vel,
+                Quantity::new(0.0, MILLIMETER_PER_SECOND_SQUARED)
+            ),
+            Err(MotionProfileError::ZeroAcceleration)
+        );
+        assert_eq!(
+            MotionProfile::try_new(
+                start,
+                State::new_raw(3.0, 0.0, 0.0),
+                Quantity::new(0.0, MILLIMETER_PER_SECOND),
+                max_acc
+            ),
+            Err(MotionProfileError::ZeroVelocity)
+        );
+        assert_eq!(
+            MotionProfile::try_new(
+                State::new_raw(0.0, 0.2, 0.0),
+                State::new_raw(3.0, 0.0, 0.0),
+                max_vel,
+                max_acc
+            ),
+            Err(MotionProfileError::MaxVelocityTooLow)
+        );
+        assert_eq!(
+            MotionProfile::try_new(start, State::new_raw(0.5, 0.0, 0.0), max_vel, max_acc),
+            Err(MotionProfileError::DistanceTooShort)
+        );
+        assert_eq!(
+            MotionProfile::try_new(start, State::new_raw(f32::NAN, 0.0, 0.0), max_vel, max_acc),
+            Err(MotionProfileError::NonFinite)
+        );
+    }
 }
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///A model of a brushed DC motor from the values usually found on its datasheet. Velocities are in
+///radians per second, torques in newton meters, currents in amperes, and voltages in volts. Free
+///current is neglected, so the model is slightly optimistic near free speed. Use this for
+///feedforward and simulation so that both agree on how the motor behaves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MotorModel {
+    ///The speed the motor spins at with no load at the nominal voltage.
+    pub free_speed: f32,
+    ///The torque the motor produces when stalled at the nominal voltage.
+    pub stall_torque: f32,
+    ///The current the motor draws when stalled at the nominal voltage.
+    pub stall_current: f32,
+    ///The voltage the other values are specified at.
+    pub nominal_voltage: f32,
+}
+impl MotorModel {
+    ///Constructor for [`MotorModel`].
+    pub const fn new(
+        free_speed: f32,
+        stall_torque: f32,
+        stall_current: f32,
+        nominal_voltage: f32,
+    ) -> Self {
+        Self {
+            free_speed: free_speed,
+            stall_torque: stall_torque,
+            stall_current: stall_current,
+            nominal_voltage: nominal_voltage,
+        }
+    }
+    ///The resistance of the windings in ohms.
+    #[inline]
+    pub fn resistance(&self) -> f32 {
+        self.nominal_voltage / self.stall_current
+    }
+    ///The torque constant in newton meters per ampere.
+    #[inline]
+    pub fn torque_constant(&self) -> f32 {
+        self.stall_torque / self.stall_current
+    }
+    ///The velocity constant in radians per second per volt.
+    #[inline]
+    pub fn velocity_constant(&self) -> f32 {
+        self.free_speed / self.nominal_voltage
+    }
+    ///Get the voltage needed to produce a torque while spinning at a velocity.
+    pub fn voltage(&self, torque: f32, velocity: f32) -> f32 {
+        torque / self.torque_constant() * self.resistance() + velocity / self.velocity_constant()
+    }
+    ///Get the fraction of the nominal voltage needed to produce a torque while spinning at a
+    ///velocity. This is useful for motor controllers that take a duty cycle from -1 to 1.
+    pub fn duty_cycle(&self, torque: f32, velocity: f32) -> f32 {
+        self.voltage(torque, velocity) / self.nominal_voltage
+    }
+    ///Get the current drawn at a voltage while spinning at a velocity.
+    pub fn current(&self, voltage: f32, velocity: f32) -> f32 {
+        (voltage - velocity / self.velocity_constant()) / self.resistance()
+    }
+    ///Get the torque produced at a voltage while spinning at a velocity.
+    pub fn torque(&self, voltage: f32, velocity: f32) -> f32 {
+        self.torque_constant() * self.current(voltage, velocity)
+    }
+    ///Get the velocity the motor spins at with a voltage while producing a torque.
+    pub fn velocity(&self, voltage: f32, torque: f32) -> f32 {
+        self.velocity_constant() * (voltage - torque / self.torque_constant() * self.resistance())
+    }
+}
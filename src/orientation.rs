@@ -0,0 +1,229 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Minimal 3D orientation types for things like IMU orientation filters and arm kinematics.
+//![`UnitQuaternion`] is compact and cheap to compose and renormalize, so it is the better choice
+//!for integrating gyro rates. [`Rotation3`] is a rotation matrix, which is easier to use alongside
+//!the [`linalg`] module. The two can be converted to each other. Angles are in radians, and
+//!rotations follow the right-hand rule.
+use crate::linalg::*;
+use crate::*;
+///A rotation in 3D represented as a quaternion of norm 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitQuaternion {
+    w: f32,
+    x: f32,
+    y: f32,
+    z: f32,
+}
+impl UnitQuaternion {
+    ///Constructor for [`UnitQuaternion`]. The quaternion is normalized. If all of the components
+    ///are zero, the identity is returned.
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        let norm = sqrtf(w * w + x * x + y * y + z * z);
+        if norm == 0.0 {
+            return Self::identity();
+        }
+        Self {
+            w: w / norm,
+            x: x / norm,
+            y: y / norm,
+            z: z / norm,
+        }
+    }
+    ///The rotation that does nothing.
+    pub const fn identity() -> Self {
+        Self {
+            w: 1.0,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    }
+    ///A rotation of `angle` about `axis`. The axis does not need to be normalized. If it is zero,
+    ///the identity is returned.
+    pub fn from_axis_angle(axis: Vector<3>, angle: f32) -> Self {
+        let norm = sqrtf(axis.dot(&axis));
+        if norm == 0.0 {
+            return Self::identity();
+        }
+        let (sin, cos) = (sinf(angle / 2.0), cosf(angle / 2.0));
+        Self::new(
+            cos,
+            sin * axis.get(0, 0) / norm,
+            sin * axis.get(1, 0) / norm,
+            sin * axis.get(2, 0) / norm,
+        )
+    }
+    ///A rotation about the direction of `rotation_vector` by an angle of its length.
+    pub fn from_rotation_vector(rotation_vector: Vector<3>) -> Self {
+        Self::from_axis_angle(
+            rotation_vector,
+            sqrtf(rotation_vector.dot(&rotation_vector)),
+        )
+    }
+    ///Get the components as `[w, x, y, z]`.
+    pub const fn components(&self) -> [f32; 4] {
+        [self.w, self.x, self.y, self.z]
+    }
+    ///Get the angle of the rotation, from 0 to 2π.
+    pub fn angle(&self) -> f32 {
+        2.0 * atan2f(
+            sqrtf(self.x * self.x + self.y * self.y + self.z * self.z),
+            self.w,
+        )
+    }
+    ///Get the rotation that undoes this one.
+    pub const fn inverse(&self) -> Self {
+        Self {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+    ///Rotate a vector.
+    pub fn rotate_vector(&self, vector: Vector<3>) -> Vector<3> {
+        Rotation3::from(*self).rotate_vector(vector)
+    }
+    ///Advance the orientation by body-frame angular velocities in radians per second, such as
+    ///those from a gyro, held for `delta_time`. Call this once per gyro reading.
+    pub fn integrate(&self, angular_velocity: Vector<3>, delta_time: Time) -> Self {
+        let delta_time = f32::from(Quantity::from(delta_time));
+        *self * Self::from_rotation_vector(angular_velocity * delta_time)
+    }
+}
+impl Default for UnitQuaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+///Compose two rotations. `a * b` rotates by `b` and then by `a`.
+impl Mul for UnitQuaternion {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        //Renormalizing keeps rounding error from building up over many compositions.
+        Self::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+impl MulAssign for UnitQuaternion {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+impl From<Rotation3> for UnitQuaternion {
+    fn from(was: Rotation3) -> Self {
+        let m = was.matrix.data;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        //Work from the largest of w, x, y, and z to avoid dividing by something close to zero.
+        if trace > 0.0 {
+            let s = sqrtf(trace + 1.0) * 2.0;
+            Self::new(
+                s / 4.0,
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = sqrtf(1.0 + m[0][0] - m[1][1] - m[2][2]) * 2.0;
+            Self::new(
+                (m[2][1] - m[1][2]) / s,
+                s / 4.0,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = sqrtf(1.0 + m[1][1] - m[0][0] - m[2][2]) * 2.0;
+            Self::new(
+                (m[0][2] - m[2][0]) / s,
+                (m[0][1] + m[1][0]) / s,
+                s / 4.0,
+                (m[1][2] + m[2][1]) / s,
+            )
+        } else {
+            let s = sqrtf(1.0 + m[2][2] - m[0][0] - m[1][1]) * 2.0;
+            Self::new(
+                (m[1][0] - m[0][1]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                s / 4.0,
+            )
+        }
+    }
+}
+///A rotation in 3D represented as an orthonormal 3x3 matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rotation3 {
+    matrix: Matrix<3, 3>,
+}
+impl Rotation3 {
+    ///Constructor for [`Rotation3`] from a rotation matrix. This does not check that the matrix is
+    ///actually a rotation.
+    pub const fn from_matrix_unchecked(matrix: Matrix<3, 3>) -> Self {
+        Self { matrix: matrix }
+    }
+    ///The rotation that does nothing.
+    pub const fn identity() -> Self {
+        Self::from_matrix_unchecked(Matrix::identity())
+    }
+    ///A rotation of `angle` about `axis`. The axis does not need to be normalized. If it is zero,
+    ///the identity is returned.
+    pub fn from_axis_angle(axis: Vector<3>, angle: f32) -> Self {
+        UnitQuaternion::from_axis_angle(axis, angle).into()
+    }
+    ///Get the rotation matrix.
+    pub const fn matrix(&self) -> Matrix<3, 3> {
+        self.matrix
+    }
+    ///Get the rotation that undoes this one.
+    pub fn inverse(&self) -> Self {
+        Self::from_matrix_unchecked(self.matrix.transpose())
+    }
+    ///Rotate a vector.
+    pub fn rotate_vector(&self, vector: Vector<3>) -> Vector<3> {
+        self.matrix * vector
+    }
+}
+impl Default for Rotation3 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+///Compose two rotations. `a * b` rotates by `b` and then by `a`.
+impl Mul for Rotation3 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_matrix_unchecked(self.matrix * rhs.matrix)
+    }
+}
+impl MulAssign for Rotation3 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+impl From<UnitQuaternion> for Rotation3 {
+    fn from(was: UnitQuaternion) -> Self {
+        let UnitQuaternion { w, x, y, z } = was;
+        Self::from_matrix_unchecked(Matrix::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ]))
+    }
+}
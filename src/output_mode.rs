@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///The unit a motor output is expressed in. Different motor controllers take different native
+///commands, so [`OutputModeSettable`] uses this to convert between them. Every mode maps full
+///output to a value, and conversions go through the fraction of full output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputMode {
+    ///A fraction of full output from -1 to 1.
+    PercentOutput,
+    ///Volts, with full output being `nominal_voltage`.
+    Voltage {
+        ///The voltage corresponding to full output, usually 12.
+        nominal_voltage: f32,
+    },
+    ///A velocity that the motor controller closes the loop on internally, with full output being
+    ///`max_velocity`, usually the free speed of the mechanism.
+    Velocity {
+        ///The velocity corresponding to full output.
+        max_velocity: f32,
+    },
+}
+impl OutputMode {
+    ///Get the value in this mode that corresponds to full output.
+    pub const fn full_output(&self) -> f32 {
+        match self {
+            Self::PercentOutput => 1.0,
+            Self::Voltage { nominal_voltage } => *nominal_voltage,
+            Self::Velocity { max_velocity } => *max_velocity,
+        }
+    }
+    ///Convert a value in this mode to a fraction of full output.
+    pub fn to_percent_output(&self, value: f32) -> f32 {
+        value / self.full_output()
+    }
+    ///Convert a fraction of full output to a value in this mode.
+    pub fn from_percent_output(&self, percent_output: f32) -> f32 {
+        percent_output * self.full_output()
+    }
+    ///Convert a value in this mode to the same output in another mode.
+    pub fn convert(&self, value: f32, to: OutputMode) -> f32 {
+        to.from_percent_output(self.to_percent_output(value))
+    }
+}
+///Lets the same controller drive motors with different native command interfaces. Values set to
+///this are in `input_mode`, usually whatever a controller stream outputs, and are converted to
+///`output_mode`, the native mode of the inner settable, before being set to it. For example, a
+///controller written in volts can drive a motor controller taking percent output, or one taking
+///percent output can drive a motor controller running its own velocity loop. Updating this also
+///updates the inner settable.
+pub struct OutputModeSettable<T: Settable<f32, E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<f32, E>,
+    inner: Reference<T>,
+    input_mode: OutputMode,
+    output_mode: OutputMode,
+}
+impl<T: Settable<f32, E> + ?Sized, E: Copy + Debug> OutputModeSettable<T, E> {
+    ///Constructor for [`OutputModeSettable`].
+    pub const fn new(inner: Reference<T>, input_mode: OutputMode, output_mode: OutputMode) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            inner: inner,
+            input_mode: input_mode,
+            output_mode: output_mode,
+        }
+    }
+    ///Get the mode of values set to this.
+    pub fn get_input_mode(&self) -> OutputMode {
+        self.input_mode
+    }
+    ///Change the mode of values set to this. This only affects values set after it is changed.
+    pub fn set_input_mode(&mut self, input_mode: OutputMode) {
+        self.input_mode = input_mode;
+    }
+    ///Get the native mode of the inner settable.
+    pub fn get_output_mode(&self) -> OutputMode {
+        self.output_mode
+    }
+    ///Change the native mode of the inner settable. This only affects values set after it is
+    ///changed.
+    pub fn set_output_mode(&mut self, output_mode: OutputMode) {
+        self.output_mode = output_mode;
+    }
+}
+impl<T: Settable<f32, E> + ?Sized, E: Copy + Debug> Settable<f32, E> for OutputModeSettable<T, E> {
+    fn get_settable_data_ref(&self) -> &SettableData<f32, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: f32) -> NothingOrError<E> {
+        self.inner
+            .borrow_mut()
+            .set(self.input_mode.convert(value, self.output_mode))
+    }
+}
+impl<T: Settable<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for OutputModeSettable<T, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        self.inner.borrow_mut().update()
+    }
+}
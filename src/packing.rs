@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Helpers for quantizing [`State`] and [`Command`] objects into fixed-width integer fields for
+//!compact transmission, such as in 8-byte CAN frames. Each value is linearly mapped from a
+//![`QuantizationRange`] onto an unsigned integer of a chosen width. Values outside of the range are
+//!clamped to it.
+use crate::*;
+///Get the largest value that fits in an unsigned integer with this many bits.
+#[inline]
+const fn max_raw(bits: u8) -> u64 {
+    (1u64 << bits) - 1
+}
+///A range of floats that is linearly mapped onto the full range of an unsigned integer field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuantizationRange {
+    ///The value represented by a field of all zeros.
+    pub min: f32,
+    ///The value represented by a field of all ones.
+    pub max: f32,
+}
+impl QuantizationRange {
+    ///Constructor for [`QuantizationRange`]. `min` must be less than `max`.
+    pub const fn new(min: f32, max: f32) -> Self {
+        if min >= max {
+            panic!("rrtk::packing::QuantizationRange min must be less than max");
+        }
+        Self { min: min, max: max }
+    }
+    ///Get the difference between the values represented by two adjacent integers with a field of
+    ///this many bits. This is the most a value can change when it is quantized and dequantized,
+    ///not counting clamping.
+    #[inline]
+    pub fn resolution(&self, bits: u8) -> f32 {
+        (self.max - self.min) / max_raw(bits) as f32
+    }
+    ///Quantize a value into a field of this many bits, rounding to the nearest representable
+    ///value. Values outside of the range, including infinities, are clamped to it, and NaN becomes
+    ///0.
+    pub fn quantize(&self, value: f32, bits: u8) -> u64 {
+        let max_raw = max_raw(bits);
+        //f32 does not have enough precision for wide fields.
+        let scaled =
+            (value as f64 - self.min as f64) / (self.max as f64 - self.min as f64) * max_raw as f64;
+        if scaled.is_nan() || scaled <= 0.0 {
+            return 0;
+        }
+        //Float to integer casts saturate, so this cannot overflow.
+        let raw = (scaled + 0.5) as u64;
+        if raw > max_raw {
+            max_raw
+        } else {
+            raw
+        }
+    }
+    ///Get the value represented by a field of this many bits.
+    #[inline]
+    pub fn dequantize(&self, raw: u64, bits: u8) -> f32 {
+        (self.min as f64 + raw as f64 / max_raw(bits) as f64 * (self.max as f64 - self.min as f64))
+            as f32
+    }
+}
+///Panics if a set of field widths is not usable in an 8-byte frame.
+const fn check_bits(bits: &[u8]) {
+    let mut total = 0;
+    let mut i = 0;
+    while i < bits.len() {
+        if bits[i] < 1 || bits[i] > 32 {
+            panic!("rrtk::packing fields must be between 1 and 32 bits wide");
+        }
+        total += bits[i] as u16;
+        i += 1;
+    }
+    if total > 64 {
+        panic!("rrtk::packing fields must fit in 64 bits");
+    }
+}
+///Packs a [`State`] into 8 bytes. The position, velocity, and acceleration are each quantized into
+///their own field, position in the least significant bits, and the resulting 64-bit integer is
+///stored little-endian.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatePacker {
+    position_range: QuantizationRange,
+    position_bits: u8,
+    velocity_range: QuantizationRange,
+    velocity_bits: u8,
+    acceleration_range: QuantizationRange,
+    acceleration_bits: u8,
+}
+impl StatePacker {
+    ///Constructor for [`StatePacker`]. Each field must be between 1 and 32 bits wide, and the
+    ///widths must add up to at most 64.
+    pub const fn new(
+        position_range: QuantizationRange,
+        position_bits: u8,
+        velocity_range: QuantizationRange,
+        velocity_bits: u8,
+        acceleration_range: QuantizationRange,
+        acceleration_bits: u8,
+    ) -> Self {
+        check_bits(&[position_bits, velocity_bits, acceleration_bits]);
+        Self {
+            position_range: position_range,
+            position_bits: position_bits,
+            velocity_range: velocity_range,
+            velocity_bits: velocity_bits,
+            acceleration_range: acceleration_range,
+            acceleration_bits: acceleration_bits,
+        }
+    }
+    ///Quantize a [`State`] and pack it into 8 bytes.
+    pub fn encode(&self, state: State) -> [u8; 8] {
+        let position = self
+            .position_range
+            .quantize(state.position, self.position_bits);
+        let velocity = self
+            .velocity_range
+            .quantize(state.velocity, self.velocity_bits);
+        let acceleration = self
+            .acceleration_range
+            .quantize(state.acceleration, self.acceleration_bits);
+        let packed = position
+            | velocity << self.position_bits
+            | acceleration << (self.position_bits + self.velocity_bits);
+        packed.to_le_bytes()
+    }
+    ///Unpack a [`State`] from 8 bytes.
+    pub fn decode(&self, bytes: [u8; 8]) -> State {
+        let packed = u64::from_le_bytes(bytes);
+        let position = packed & max_raw(self.position_bits);
+        let velocity = packed >> self.position_bits & max_raw(self.velocity_bits);
+        let acceleration =
+            packed >> (self.position_bits + self.velocity_bits) & max_raw(self.acceleration_bits);
+        State::new_raw(
+            self.position_range.dequantize(position, self.position_bits),
+            self.velocity_range.dequantize(velocity, self.velocity_bits),
+            self.acceleration_range
+                .dequantize(acceleration, self.acceleration_bits),
+        )
+    }
+}
+///Why [`CommandPacker::decode`] could not unpack a [`Command`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandDecodeError {
+    ///The first byte does not refer to a position derivative. The byte is included.
+    UnknownPositionDerivative(u8),
+}
+///Packs a [`Command`] into 8 bytes. The first byte says which position derivative the command is
+///for, and the value is quantized using that position derivative's range into a field of
+///`value_bits` bits stored little-endian in the following 4 bytes. The last 3 bytes are 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CommandPacker {
+    position_range: QuantizationRange,
+    velocity_range: QuantizationRange,
+    acceleration_range: QuantizationRange,
+    value_bits: u8,
+}
+impl CommandPacker {
+    ///Constructor for [`CommandPacker`]. `value_bits` must be between 1 and 32.
+    pub const fn new(
+        position_range: QuantizationRange,
+        velocity_range: QuantizationRange,
+        acceleration_range: QuantizationRange,
+        value_bits: u8,
+    ) -> Self {
+        check_bits(&[value_bits]);
+        Self {
+            position_range: position_range,
+            velocity_range: velocity_range,
+            acceleration_range: acceleration_range,
+            value_bits: value_bits,
+        }
+    }
+    #[inline]
+    fn get_range(&self, position_derivative: PositionDerivative) -> QuantizationRange {
+        match position_derivative {
+            PositionDerivative::Position => self.position_range,
+            PositionDerivative::Velocity => self.velocity_range,
+            PositionDerivative::Acceleration => self.acceleration_range,
+        }
+    }
+    ///Quantize a [`Command`] and pack it into 8 bytes.
+    pub fn encode(&self, command: Command) -> [u8; 8] {
+        let position_derivative = PositionDerivative::from(command);
+        let tag = match position_derivative {
+            PositionDerivative::Position => 0,
+            PositionDerivative::Velocity => 1,
+            PositionDerivative::Acceleration => 2,
+        };
+        let value = self
+            .get_range(position_derivative)
+            .quantize(f32::from(command), self.value_bits) as u32;
+        let value = value.to_le_bytes();
+        [tag, value[0], value[1], value[2], value[3], 0, 0, 0]
+    }
+    ///Unpack a [`Command`] from 8 bytes.
+    pub fn decode(&self, bytes: [u8; 8]) -> Result<Command, CommandDecodeError> {
+        let position_derivative = match bytes[0] {
+            0 => PositionDerivative::Position,
+            1 => PositionDerivative::Velocity,
+            2 => PositionDerivative::Acceleration,
+            tag => return Err(CommandDecodeError::UnknownPositionDerivative(tag)),
+        };
+        let value = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as u64
+            & max_raw(self.value_bits);
+        Ok(Command::new(
+            position_derivative,
+            self.get_range(position_derivative)
+                .dequantize(value, self.value_bits),
+        ))
+    }
+}
@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Explicit update ordering for stream graphs. Streams generally need their inputs to be updated
+//!before they are, and getting this order right by hand is error-prone in large graphs. Declare
+//!each [`Updatable`] and what it depends on with an [`UpdatePipelineBuilder`], and the resulting
+//![`UpdatePipeline`] will update them in a valid order.
+use crate::*;
+///An identifier for a node in an [`UpdatePipelineBuilder`] and the [`UpdatePipeline`] built from
+///it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeId(usize);
+///An error from building an [`UpdatePipeline`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineError {
+    ///The builder cannot hold any more nodes.
+    Full,
+    ///The node does not exist in this builder.
+    UnknownNode,
+    ///The dependencies contain a cycle, so there is no valid update order.
+    Cycle,
+}
+///Declares the nodes of an [`UpdatePipeline`] and their dependencies. Holds up to `N` nodes.
+pub struct UpdatePipelineBuilder<const N: usize, E: Copy + Debug> {
+    nodes: [Option<Reference<dyn Updatable<E>>>; N],
+    //dependencies[i][j] is true if node i depends on node j.
+    dependencies: [[bool; N]; N],
+    len: usize,
+}
+impl<const N: usize, E: Copy + Debug> UpdatePipelineBuilder<N, E> {
+    ///Constructor for [`UpdatePipelineBuilder`].
+    pub fn new() -> Self {
+        Self {
+            nodes: core::array::from_fn(|_| None),
+            dependencies: [[false; N]; N],
+            len: 0,
+        }
+    }
+    ///Add a node to the pipeline.
+    pub fn add(&mut self, updatable: Reference<dyn Updatable<E>>) -> Result<NodeId, PipelineError> {
+        if self.len >= N {
+            return Err(PipelineError::Full);
+        }
+        self.nodes[self.len] = Some(updatable);
+        self.len += 1;
+        Ok(NodeId(self.len - 1))
+    }
+    ///Declare that `node` must be updated after `depends_on`, usually because `depends_on` is one
+    ///of its inputs.
+    pub fn add_dependency(
+        &mut self,
+        node: NodeId,
+        depends_on: NodeId,
+    ) -> Result<(), PipelineError> {
+        if node.0 >= self.len || depends_on.0 >= self.len {
+            return Err(PipelineError::UnknownNode);
+        }
+        self.dependencies[node.0][depends_on.0] = true;
+        Ok(())
+    }
+    ///Compute the update order and create the pipeline.
+    pub fn build(self) -> Result<UpdatePipeline<N, E>, PipelineError> {
+        let mut order = [0; N];
+        let mut placed = [false; N];
+        //Kahn's algorithm. Nodes with no remaining dependencies are placed in the order they were
+        //added, so the order is deterministic.
+        for slot in order.iter_mut().take(self.len) {
+            let mut next = None;
+            for candidate in 0..self.len {
+                if placed[candidate] {
+                    continue;
+                }
+                let ready = !self.dependencies[candidate]
+                    .iter()
+                    .zip(placed.iter())
+                    .take(self.len)
+                    .any(|(&depends, &dependency_placed)| depends && !dependency_placed);
+                if ready {
+                    next = Some(candidate);
+                    break;
+                }
+            }
+            let next = match next {
+                Some(next) => next,
+                None => return Err(PipelineError::Cycle),
+            };
+            placed[next] = true;
+            *slot = next;
+        }
+        Ok(UpdatePipeline {
+            nodes: self.nodes,
+            order: order,
+            len: self.len,
+            last_failed: None,
+        })
+    }
+}
+impl<const N: usize, E: Copy + Debug> Default for UpdatePipelineBuilder<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+///Updates a set of [`Updatable`]s in an order respecting their dependencies. Create this with an
+///[`UpdatePipelineBuilder`].
+pub struct UpdatePipeline<const N: usize, E: Copy + Debug> {
+    nodes: [Option<Reference<dyn Updatable<E>>>; N],
+    order: [usize; N],
+    len: usize,
+    last_failed: Option<NodeId>,
+}
+impl<const N: usize, E: Copy + Debug> UpdatePipeline<N, E> {
+    ///Get the order in which the nodes are updated.
+    pub fn order(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.order[..self.len].iter().map(|&index| NodeId(index))
+    }
+    ///Update every node in order, stopping at the first error and returning it along with the
+    ///node that returned it.
+    pub fn run(&mut self) -> Result<(), (NodeId, Error<E>)> {
+        self.last_failed = None;
+        for &index in &self.order[..self.len] {
+            let node = self.nodes[index]
+                .as_ref()
+                .expect("every index in the order has a node");
+            if let Err(error) = node.borrow_mut().update() {
+                self.last_failed = Some(NodeId(index));
+                return Err((NodeId(index), error));
+            }
+        }
+        Ok(())
+    }
+    ///Get the node that returned an error during the last update, if any.
+    pub fn last_failed(&self) -> Option<NodeId> {
+        self.last_failed
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for UpdatePipeline<N, E> {
+    ///Same as [`run`](UpdatePipeline::run), but the node that failed is only available through
+    ///[`last_failed`](UpdatePipeline::last_failed).
+    fn update(&mut self) -> NothingOrError<E> {
+        match self.run() {
+            Ok(()) => Ok(()),
+            Err((_, error)) => Err(error),
+        }
+    }
+}
+///A stage of a control cycle. Every [`Updatable`] in one phase is updated before any in the next,
+///in the order the variants are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UpdatePhase {
+    ///Reading sensors and other raw inputs.
+    Sensors,
+    ///Filters, estimators, and anything else turning raw inputs into useful data.
+    Estimators,
+    ///Controllers and decision making.
+    Controllers,
+    ///Motors and other outputs.
+    Outputs,
+}
+impl UpdatePhase {
+    ///Every phase in the order it runs.
+    pub const ALL: [UpdatePhase; 4] = [
+        UpdatePhase::Sensors,
+        UpdatePhase::Estimators,
+        UpdatePhase::Controllers,
+        UpdatePhase::Outputs,
+    ];
+}
+type PhasedEntry<E> = (UpdatePhase, Reference<dyn Updatable<E>>);
+///Updates a set of [`Updatable`]s grouped into [`UpdatePhase`]s. This is a simpler alternative to
+///an [`UpdatePipeline`] for when declaring each dependency is more detail than needed: as long as
+///everything is in the right phase, data read within a cycle is always from that cycle. Within a
+///phase, updatables are updated in the order they were added. Holds up to `N` updatables.
+pub struct PhasedRunner<const N: usize, E: Copy + Debug> {
+    entries: [Option<PhasedEntry<E>>; N],
+    len: usize,
+}
+impl<const N: usize, E: Copy + Debug> PhasedRunner<N, E> {
+    ///Constructor for [`PhasedRunner`].
+    pub fn new() -> Self {
+        Self {
+            entries: core::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+    ///Add an updatable to a phase.
+    pub fn add(
+        &mut self,
+        phase: UpdatePhase,
+        updatable: Reference<dyn Updatable<E>>,
+    ) -> Result<(), PipelineError> {
+        if self.len >= N {
+            return Err(PipelineError::Full);
+        }
+        self.entries[self.len] = Some((phase, updatable));
+        self.len += 1;
+        Ok(())
+    }
+    ///Get the number of updatables in a phase.
+    pub fn phase_len(&self, phase: UpdatePhase) -> usize {
+        self.entries
+            .iter()
+            .flatten()
+            .filter(|(entry_phase, _)| *entry_phase == phase)
+            .count()
+    }
+    ///Update every updatable in one phase, stopping at the first error.
+    pub fn run_phase(&mut self, phase: UpdatePhase) -> NothingOrError<E> {
+        for (entry_phase, updatable) in self.entries.iter().flatten() {
+            if *entry_phase == phase {
+                updatable.borrow_mut().update()?;
+            }
+        }
+        Ok(())
+    }
+    ///Run every phase in order, stopping at the first error and returning it along with the phase
+    ///it happened in.
+    pub fn run(&mut self) -> Result<(), (UpdatePhase, Error<E>)> {
+        for phase in UpdatePhase::ALL {
+            self.run_phase(phase).map_err(|error| (phase, error))?;
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Default for PhasedRunner<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for PhasedRunner<N, E> {
+    ///Same as [`run`](PhasedRunner::run) without the phase that failed.
+    fn update(&mut self) -> NothingOrError<E> {
+        self.run().map_err(|(_, error)| error)
+    }
+}
+///Spreads a long-running iterative computation, such as path planning, across many updates so
+///that it does not make any one loop take too long. `step` is called with the computation's state
+///up to `steps_per_update` times per update and returns `Some` with the result once the computation
+///is finished. After that, updating this does nothing and [`get`](Getter::get) returns the result
+///with the time it was finished at. Before that, [`get`](Getter::get) returns `Ok(None)`. The time
+///is gotten before any steps are run, so if the time getter returns an error, the error is returned
+///without running any steps. Add this to an [`UpdatePipeline`] or [`PhasedRunner`] like any other
+///[`Updatable`].
+pub struct ChunkedComputation<
+    S,
+    T: Clone,
+    F: FnMut(&mut S) -> Option<T>,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    time_getter: Reference<TG>,
+    state: S,
+    step: F,
+    steps_per_update: usize,
+    steps: usize,
+    result: Option<Datum<T>>,
+    phantom_e: PhantomData<E>,
+}
+impl<S, T: Clone, F: FnMut(&mut S) -> Option<T>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    ChunkedComputation<S, T, F, TG, E>
+{
+    ///Constructor for [`ChunkedComputation`].
+    pub const fn new(
+        time_getter: Reference<TG>,
+        state: S,
+        step: F,
+        steps_per_update: usize,
+    ) -> Self {
+        Self {
+            time_getter: time_getter,
+            state: state,
+            step: step,
+            steps_per_update: steps_per_update,
+            steps: 0,
+            result: None,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Returns true if the computation has finished.
+    pub fn is_finished(&self) -> bool {
+        self.result.is_some()
+    }
+    ///Get the total number of times `step` has been called.
+    pub fn get_steps(&self) -> usize {
+        self.steps
+    }
+    ///Get the state of the computation.
+    pub fn get_state(&self) -> &S {
+        &self.state
+    }
+    ///Discard any result and start the computation over from a new state.
+    pub fn restart(&mut self, state: S) {
+        self.state = state;
+        self.steps = 0;
+        self.result = None;
+    }
+}
+impl<S, T: Clone, F: FnMut(&mut S) -> Option<T>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    Getter<T, E> for ChunkedComputation<S, T, F, TG, E>
+{
+    fn get(&self) -> Output<T, E> {
+        Ok(self.result.clone())
+    }
+}
+impl<S, T: Clone, F: FnMut(&mut S) -> Option<T>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    Updatable<E> for ChunkedComputation<S, T, F, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        if self.result.is_some() {
+            return Ok(());
+        }
+        //Get the time first so that an error from the time getter cannot lose a finished result.
+        let time = self.time_getter.borrow().get()?;
+        for _ in 0..self.steps_per_update {
+            self.steps += 1;
+            if let Some(result) = (self.step)(&mut self.state) {
+                self.result = Some(Datum::new(time, result));
+                break;
+            }
+        }
+        Ok(())
+    }
+}
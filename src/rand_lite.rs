@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!A tiny seedable pseudorandom number generator for simulations and tests. It works without `std`
+//!or `alloc` and does not depend on any external crates. The same seed always gives the same
+//!sequence on every platform, so simulations using it are reproducible. It is not suitable for
+//!anything needing real randomness such as cryptography.
+///A SplitMix64 pseudorandom number generator. Every seed, including 0, gives a good sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prng {
+    state: u64,
+}
+impl Prng {
+    ///Constructor for [`Prng`].
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    ///Get the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    ///Get the next 32 random bits.
+    pub fn next_u32(&mut self) -> u32 {
+        //The high bits are the best mixed.
+        (self.next_u64() >> 32) as u32
+    }
+    ///Get a uniformly distributed float from 0 (inclusive) to 1 (exclusive). Only 24 bits are used
+    ///so that every possible value is exactly representable as an `f32`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+    ///Get a uniformly distributed float from `min` (inclusive) to `max` (exclusive).
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + self.next_f32() * (max - min)
+    }
+    ///Get an approximately normally distributed float with a mean of 0 and a standard deviation
+    ///of 1. It is the sum of 12 uniform samples minus 6, so it is never more than 6 from 0, but it
+    ///needs no floating point functions other than addition.
+    pub fn next_normal(&mut self) -> f32 {
+        let mut sum = 0.0;
+        for _ in 0..12 {
+            sum += self.next_f32();
+        }
+        sum - 6.0
+    }
+}
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Registries mapping short static names to getters and settables so that telemetry, tuning
+//!tools, and scripting layers can look up signals by name at runtime. [`Registry`] has a fixed
+//!capacity and works without `alloc`, while [`HashRegistry`] grows as needed and requires `std`.
+use crate::*;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+///A type-erased handle to a getter or settable of one of the common RRTK types.
+pub enum RegistryHandle<E: Copy + Debug> {
+    ///A [`Getter<f32, E>`].
+    F32Getter(Reference<dyn Getter<f32, E>>),
+    ///A [`Getter<Quantity, E>`].
+    QuantityGetter(Reference<dyn Getter<Quantity, E>>),
+    ///A [`Getter<bool, E>`].
+    BoolGetter(Reference<dyn Getter<bool, E>>),
+    ///A [`Getter<State, E>`].
+    StateGetter(Reference<dyn Getter<State, E>>),
+    ///A [`Getter<Command, E>`].
+    CommandGetter(Reference<dyn Getter<Command, E>>),
+    ///A [`Settable<f32, E>`].
+    F32Settable(Reference<dyn Settable<f32, E>>),
+    ///A [`Settable<Quantity, E>`].
+    QuantitySettable(Reference<dyn Settable<Quantity, E>>),
+    ///A [`Settable<bool, E>`].
+    BoolSettable(Reference<dyn Settable<bool, E>>),
+    ///A [`Settable<Command, E>`].
+    CommandSettable(Reference<dyn Settable<Command, E>>),
+}
+impl<E: Copy + Debug> Clone for RegistryHandle<E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::F32Getter(x) => Self::F32Getter(x.clone()),
+            Self::QuantityGetter(x) => Self::QuantityGetter(x.clone()),
+            Self::BoolGetter(x) => Self::BoolGetter(x.clone()),
+            Self::StateGetter(x) => Self::StateGetter(x.clone()),
+            Self::CommandGetter(x) => Self::CommandGetter(x.clone()),
+            Self::F32Settable(x) => Self::F32Settable(x.clone()),
+            Self::QuantitySettable(x) => Self::QuantitySettable(x.clone()),
+            Self::BoolSettable(x) => Self::BoolSettable(x.clone()),
+            Self::CommandSettable(x) => Self::CommandSettable(x.clone()),
+        }
+    }
+}
+impl<E: Copy + Debug> RegistryHandle<E> {
+    ///Get the handle as a [`Getter<f32, E>`] if it is one.
+    pub fn as_f32_getter(&self) -> Option<Reference<dyn Getter<f32, E>>> {
+        match self {
+            Self::F32Getter(x) => Some(x.clone()),
+            _ => None,
+        }
+    }
+    ///Get the handle as a [`Settable<f32, E>`] if it is one.
+    pub fn as_f32_settable(&self) -> Option<Reference<dyn Settable<f32, E>>> {
+        match self {
+            Self::F32Settable(x) => Some(x.clone()),
+            _ => None,
+        }
+    }
+}
+///Something that handles can be looked up in by name. This is implemented by both [`Registry`] and
+///[`HashRegistry`] so that code using a registry does not have to care which one it is.
+pub trait RegistryLookup<E: Copy + Debug> {
+    ///Look up a handle by name.
+    fn lookup(&self, name: &str) -> Option<&RegistryHandle<E>>;
+}
+///An error from a registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    ///The registry cannot hold any more handles.
+    Full,
+    ///A handle with the same name is already registered.
+    DuplicateName,
+}
+///A fixed-capacity registry of up to `N` named handles. Lookups are linear, which is fast for the
+///small numbers of signals this is meant for.
+pub struct Registry<const N: usize, E: Copy + Debug> {
+    entries: [Option<(&'static str, RegistryHandle<E>)>; N],
+}
+impl<const N: usize, E: Copy + Debug> Registry<N, E> {
+    ///Constructor for [`Registry`].
+    pub fn new() -> Self {
+        Self {
+            entries: core::array::from_fn(|_| None),
+        }
+    }
+    ///Register a handle under a name.
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        handle: RegistryHandle<E>,
+    ) -> Result<(), RegistryError> {
+        if self.get(name).is_some() {
+            return Err(RegistryError::DuplicateName);
+        }
+        for slot in &mut self.entries {
+            if slot.is_none() {
+                *slot = Some((name, handle));
+                return Ok(());
+            }
+        }
+        Err(RegistryError::Full)
+    }
+    ///Look up a handle by name.
+    pub fn get(&self, name: &str) -> Option<&RegistryHandle<E>> {
+        self.entries
+            .iter()
+            .flatten()
+            .find(|(entry_name, _)| *entry_name == name)
+            .map(|(_, handle)| handle)
+    }
+    ///Remove a handle by name, returning it if it was registered.
+    pub fn remove(&mut self, name: &str) -> Option<RegistryHandle<E>> {
+        for slot in &mut self.entries {
+            if let Some((entry_name, _)) = slot {
+                if *entry_name == name {
+                    return slot.take().map(|(_, handle)| handle);
+                }
+            }
+        }
+        None
+    }
+    ///Iterate over all registered names and handles.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &RegistryHandle<E>)> {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|(name, handle)| (*name, handle))
+    }
+    ///Get the number of registered handles.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    ///Returns true if no handles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+impl<const N: usize, E: Copy + Debug> RegistryLookup<E> for Registry<N, E> {
+    fn lookup(&self, name: &str) -> Option<&RegistryHandle<E>> {
+        self.get(name)
+    }
+}
+impl<const N: usize, E: Copy + Debug> Default for Registry<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+///A registry backed by a [`HashMap`] that can hold any number of named handles. Only available
+///with `std`.
+#[cfg(feature = "std")]
+pub struct HashRegistry<E: Copy + Debug> {
+    entries: HashMap<&'static str, RegistryHandle<E>>,
+}
+#[cfg(feature = "std")]
+impl<E: Copy + Debug> HashRegistry<E> {
+    ///Constructor for [`HashRegistry`].
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+    ///Register a handle under a name.
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        handle: RegistryHandle<E>,
+    ) -> Result<(), RegistryError> {
+        if self.entries.contains_key(name) {
+            return Err(RegistryError::DuplicateName);
+        }
+        self.entries.insert(name, handle);
+        Ok(())
+    }
+    ///Look up a handle by name.
+    pub fn get(&self, name: &str) -> Option<&RegistryHandle<E>> {
+        self.entries.get(name)
+    }
+    ///Remove a handle by name, returning it if it was registered.
+    pub fn remove(&mut self, name: &str) -> Option<RegistryHandle<E>> {
+        self.entries.remove(name)
+    }
+    ///Iterate over all registered names and handles in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &RegistryHandle<E>)> {
+        self.entries.iter().map(|(name, handle)| (*name, handle))
+    }
+    ///Get the number of registered handles.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    ///Returns true if no handles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+#[cfg(feature = "std")]
+impl<E: Copy + Debug> RegistryLookup<E> for HashRegistry<E> {
+    fn lookup(&self, name: &str) -> Option<&RegistryHandle<E>> {
+        self.get(name)
+    }
+}
+#[cfg(feature = "std")]
+impl<E: Copy + Debug> Default for HashRegistry<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!A tiny interpreter for data-defined command sequences such as autonomous routines. A program is
+//!a slice of [`ScriptOp`]s that refer to getters and settables by their names in a
+//![`registry`](crate::registry), so routines can be swapped without recompiling.
+use crate::registry::*;
+use crate::*;
+///One instruction for a [`ScriptInterpreter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScriptOp {
+    ///Set a [`Settable<Command, E>`] registered under a name.
+    SetCommand(&'static str, Command),
+    ///Set a [`Settable<f32, E>`] registered under a name.
+    SetF32(&'static str, f32),
+    ///Set a [`Settable<bool, E>`] registered under a name.
+    SetBool(&'static str, bool),
+    ///Wait until a [`Getter<bool, E>`] registered under a name returns true.
+    WaitUntil(&'static str),
+    ///Wait for a duration.
+    WaitFor(Time),
+    ///Continue at another index in the program.
+    Jump(usize),
+    ///Continue at another index in the program if a [`Getter<bool, E>`] registered under a name
+    ///returns true.
+    JumpIf(&'static str, usize),
+    ///Stop running the program.
+    End,
+}
+///An error encountered while running a script.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScriptError<E: Copy + Debug> {
+    ///No handle is registered under a name used by the program.
+    UnknownName(&'static str),
+    ///The handle registered under a name is not of the type the operation needs.
+    WrongType(&'static str),
+    ///A jump targeted an index past the end of the program.
+    InvalidJump(usize),
+    ///A getter, settable, or time getter returned an error.
+    Other(Error<E>),
+}
+impl<E: Copy + Debug> From<Error<E>> for ScriptError<E> {
+    fn from(was: Error<E>) -> Self {
+        Self::Other(was)
+    }
+}
+///Runs a program of [`ScriptOp`]s. Each update executes operations until one waits or the program
+///ends. To keep a loop without waits from hanging the robot, at most as many operations as the
+///program is long are executed per update. If an error is encountered, the interpreter stops, and
+///the error can be retrieved with [`get_error`](ScriptInterpreter::get_error).
+pub struct ScriptInterpreter<
+    'a,
+    R: RegistryLookup<E> + ?Sized,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    program: &'a [ScriptOp],
+    registry: Reference<R>,
+    time_getter: Reference<TG>,
+    program_counter: usize,
+    wait_start: Option<Time>,
+    finished: bool,
+    error: Option<ScriptError<E>>,
+}
+impl<'a, R: RegistryLookup<E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    ScriptInterpreter<'a, R, TG, E>
+{
+    ///Constructor for [`ScriptInterpreter`].
+    pub const fn new(
+        program: &'a [ScriptOp],
+        registry: Reference<R>,
+        time_getter: Reference<TG>,
+    ) -> Self {
+        Self {
+            program: program,
+            registry: registry,
+            time_getter: time_getter,
+            program_counter: 0,
+            wait_start: None,
+            finished: false,
+            error: None,
+        }
+    }
+    ///Get the index of the next operation to be executed.
+    pub fn get_program_counter(&self) -> usize {
+        self.program_counter
+    }
+    ///Returns true if the program has ended or stopped because of an error.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    ///Get the error that stopped the program if there was one.
+    pub fn get_error(&self) -> Option<ScriptError<E>> {
+        self.error
+    }
+    ///Start the program over from the beginning.
+    pub fn restart(&mut self) {
+        self.program_counter = 0;
+        self.wait_start = None;
+        self.finished = false;
+        self.error = None;
+    }
+    fn get_bool(&self, name: &'static str) -> Result<bool, ScriptError<E>> {
+        let registry = self.registry.borrow();
+        match registry.lookup(name) {
+            Some(RegistryHandle::BoolGetter(getter)) => Ok(matches!(
+                getter.borrow().get()?,
+                Some(Datum { value: true, .. })
+            )),
+            Some(_) => Err(ScriptError::WrongType(name)),
+            None => Err(ScriptError::UnknownName(name)),
+        }
+    }
+    fn jump(&mut self, target: usize) -> Result<(), ScriptError<E>> {
+        if target >= self.program.len() {
+            return Err(ScriptError::InvalidJump(target));
+        }
+        self.program_counter = target;
+        Ok(())
+    }
+    ///Execute one operation. Returns `Ok(true)` if the program should move on to the next operation
+    ///in the same update and `Ok(false)` if it is waiting or finished.
+    fn execute(&mut self) -> Result<bool, ScriptError<E>> {
+        let op = match self.program.get(self.program_counter) {
+            Some(op) => *op,
+            None => {
+                self.finished = true;
+                return Ok(false);
+            }
+        };
+        match op {
+            ScriptOp::SetCommand(name, command) => {
+                let registry = self.registry.borrow();
+                match registry.lookup(name) {
+                    Some(RegistryHandle::CommandSettable(settable)) => {
+                        settable.borrow_mut().set(command)?
+                    }
+                    Some(_) => return Err(ScriptError::WrongType(name)),
+                    None => return Err(ScriptError::UnknownName(name)),
+                }
+            }
+            ScriptOp::SetF32(name, value) => {
+                let registry = self.registry.borrow();
+                match registry.lookup(name) {
+                    Some(RegistryHandle::F32Settable(settable)) => {
+                        settable.borrow_mut().set(value)?
+                    }
+                    Some(_) => return Err(ScriptError::WrongType(name)),
+                    None => return Err(ScriptError::UnknownName(name)),
+                }
+            }
+            ScriptOp::SetBool(name, value) => {
+                let registry = self.registry.borrow();
+                match registry.lookup(name) {
+                    Some(RegistryHandle::BoolSettable(settable)) => {
+                        settable.borrow_mut().set(value)?
+                    }
+                    Some(_) => return Err(ScriptError::WrongType(name)),
+                    None => return Err(ScriptError::UnknownName(name)),
+                }
+            }
+            ScriptOp::WaitUntil(name) => {
+                if !self.get_bool(name)? {
+                    return Ok(false);
+                }
+            }
+            ScriptOp::WaitFor(duration) => {
+                let time = self.time_getter.borrow().get()?;
+                let wait_start = match self.wait_start {
+                    Some(wait_start) => wait_start,
+                    None => {
+                        self.wait_start = Some(time);
+                        time
+                    }
+                };
+                if time - wait_start < duration {
+                    return Ok(false);
+                }
+                self.wait_start = None;
+            }
+            ScriptOp::Jump(target) => {
+                self.jump(target)?;
+                return Ok(true);
+            }
+            ScriptOp::JumpIf(name, target) => {
+                if self.get_bool(name)? {
+                    self.jump(target)?;
+                    return Ok(true);
+                }
+            }
+            ScriptOp::End => {
+                self.finished = true;
+                return Ok(false);
+            }
+        }
+        self.program_counter += 1;
+        Ok(true)
+    }
+}
+impl<R: RegistryLookup<E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ScriptInterpreter<'_, R, TG, E>
+{
+    ///Run the program until it waits or ends. Only errors from getters, settables, and the time
+    ///getter are returned here; errors in the program itself are only available through
+    ///[`get_error`](ScriptInterpreter::get_error).
+    fn update(&mut self) -> NothingOrError<E> {
+        for _ in 0..self.program.len().max(1) {
+            if self.finished {
+                break;
+            }
+            match self.execute() {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(error) => {
+                    self.finished = true;
+                    self.error = Some(error);
+                    if let ScriptError::Other(error) = error {
+                        return Err(error);
+                    }
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
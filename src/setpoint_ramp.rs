@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///Moves a value smoothly toward a target at a limited rate. This is a lighter-weight alternative to a
+///[`MotionProfile`] for simple mechanisms like hood angles where the target can change at any time.
+///Set the target with [`set`](Settable::set), update this periodically, and get the ramped value
+///with [`get`](Getter::get). The value moves at no more than `max_rate` units per second. If
+///`max_acceleration` is given, the rate of change itself changes at no more than that many units
+///per second squared, and the value slows down in time to stop at the target. If the target moves
+///too close to stop in time, the value overshoots and comes back rather than stopping faster than
+///`max_acceleration` allows. The value starts at `initial` and [`get`](Getter::get) returns
+///`Ok(None)` until the first update. The time getter is not updated by this.
+pub struct SetpointRamp<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<f32, E>,
+    time_getter: Reference<TG>,
+    max_rate: f32,
+    max_acceleration: Option<f32>,
+    target: f32,
+    value: f32,
+    velocity: f32,
+    at_target: bool,
+    time: Option<Time>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> SetpointRamp<TG, E> {
+    ///Constructor for [`SetpointRamp`]. Panics if `max_rate` or `max_acceleration` is not
+    ///positive.
+    pub const fn new(
+        time_getter: Reference<TG>,
+        initial: f32,
+        max_rate: f32,
+        max_acceleration: Option<f32>,
+    ) -> Self {
+        assert!(
+            max_rate > 0.0,
+            "rrtk::SetpointRamp max_rate must be positive"
+        );
+        if let Some(max_acceleration) = max_acceleration {
+            assert!(
+                max_acceleration > 0.0,
+                "rrtk::SetpointRamp max_acceleration must be positive"
+            );
+        }
+        Self {
+            settable_data: SettableData::new(),
+            time_getter: time_getter,
+            max_rate: max_rate,
+            max_acceleration: max_acceleration,
+            target: initial,
+            value: initial,
+            velocity: 0.0,
+            at_target: true,
+            time: None,
+        }
+    }
+    ///Get the target the value is moving toward.
+    pub fn get_target(&self) -> f32 {
+        self.target
+    }
+    ///Get the current rate of change of the value in units per second.
+    pub fn get_velocity(&self) -> f32 {
+        self.velocity
+    }
+    ///Returns true if the value has reached the target. This becomes false when the target is
+    ///changed and true again on the update that moves the value onto it.
+    pub fn is_at_target(&self) -> bool {
+        self.at_target
+    }
+    ///Jump straight to a value and stop there, making it the target as well.
+    pub fn reset(&mut self, value: f32) {
+        self.target = value;
+        self.value = value;
+        self.velocity = 0.0;
+        self.at_target = true;
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Settable<f32, E> for SetpointRamp<TG, E> {
+    fn get_settable_data_ref(&self) -> &SettableData<f32, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, target: f32) -> NothingOrError<E> {
+        if target != self.target {
+            self.target = target;
+            self.at_target = false;
+        }
+        Ok(())
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<f32, E> for SetpointRamp<TG, E> {
+    fn get(&self) -> Output<f32, E> {
+        Ok(self.time.map(|time| Datum::new(time, self.value)))
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for SetpointRamp<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        let time = self.time_getter.borrow().get()?;
+        let prev_time = match self.time {
+            Some(prev_time) => prev_time,
+            None => {
+                self.time = Some(time);
+                return Ok(());
+            }
+        };
+        self.time = Some(time);
+        let delta_time = f32::from(Quantity::from(time - prev_time));
+        if delta_time <= 0.0 {
+            return Ok(());
+        }
+        let error = self.target - self.value;
+        match self.max_acceleration {
+            None => {
+                let max_step = self.max_rate * delta_time;
+                //The last step lands exactly on the target.
+                if error.abs() <= max_step {
+                    self.value = self.target;
+                    self.velocity = error / delta_time;
+                    self.at_target = true;
+                } else {
+                    let step = error.clamp(-max_step, max_step);
+                    self.value += step;
+                    self.velocity = step / delta_time;
+                }
+            }
+            Some(max_acceleration) => {
+                let stopping_distance = self.velocity * self.velocity / (2.0 * max_acceleration);
+                let desired_velocity =
+                    if error * self.velocity > 0.0 && stopping_distance >= error.abs() {
+                        0.0
+                    } else if error > 0.0 {
+                        self.max_rate
+                    } else if error < 0.0 {
+                        -self.max_rate
+                    } else {
+                        0.0
+                    };
+                let max_change = max_acceleration * delta_time;
+                self.velocity += (desired_velocity - self.velocity).clamp(-max_change, max_change);
+                self.value += self.velocity * delta_time;
+                //Stop at the target instead of oscillating around it if stopping is within the
+                //acceleration limit.
+                if (self.target - self.value) * error <= 0.0 && self.velocity.abs() <= max_change {
+                    self.value = self.target;
+                    self.velocity = 0.0;
+                    self.at_target = true;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,507 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//!Tools for testing controllers against simulated plants in virtual time. A [`TestBench`] steps a
+//!manually controlled clock and updates a controller and plant in order, and a
+//![`StepResponseAnalyzer`] computes step response metrics from the result, so regressions in
+//!controller behavior can be caught with ordinary unit tests. [`DelayedGetter`] and [`NoisyGetter`]
+//!make simulated sensors less ideal by adding latency and noise.
+use crate::*;
+#[cfg(feature = "alloc")]
+use alloc::collections::vec_deque::VecDeque;
+///Metrics describing how a system responded to a step in its setpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepResponseMetrics {
+    ///The time it took for the output to go from 10% to 90% of the way from its initial value to
+    ///the setpoint, or `None` if it never reached 90%.
+    pub rise_time: Option<Time>,
+    ///How far the output went past the setpoint as a fraction of the step size. This is 0 if it
+    ///never went past the setpoint.
+    pub overshoot: f32,
+    ///The time from the first sample after which the output stayed within the settling band of
+    ///the setpoint, or `None` if it was outside of the band at the last sample.
+    pub settling_time: Option<Time>,
+    ///The setpoint minus the last output.
+    pub steady_state_error: f32,
+}
+///Computes [`StepResponseMetrics`] online from the samples of a step response. The first sample
+///pushed is treated as the time of the step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepResponseAnalyzer {
+    initial: f32,
+    setpoint: f32,
+    settling_band: f32,
+    start_time: Option<Time>,
+    rise_start: Option<Time>,
+    rise_end: Option<Time>,
+    peak: f32,
+    settled_since: Option<Time>,
+    last: Option<Datum<f32>>,
+}
+impl StepResponseAnalyzer {
+    ///Constructor for [`StepResponseAnalyzer`]. `settling_band` is the fraction of the step size
+    ///that the output must stay within around the setpoint to be considered settled, commonly
+    ///0.02 or 0.05. `initial` and `setpoint` must not be equal.
+    pub const fn new(initial: f32, setpoint: f32, settling_band: f32) -> Self {
+        if initial == setpoint {
+            panic!("rrtk::sim::StepResponseAnalyzer initial and setpoint must not be equal");
+        }
+        Self {
+            initial: initial,
+            setpoint: setpoint,
+            settling_band: settling_band,
+            start_time: None,
+            rise_start: None,
+            rise_end: None,
+            peak: 0.0,
+            settled_since: None,
+            last: None,
+        }
+    }
+    ///Add a sample. Samples should be pushed in chronological order.
+    pub fn push(&mut self, sample: Datum<f32>) {
+        if self.start_time.is_none() {
+            self.start_time = Some(sample.time);
+        }
+        //How far the output is from its initial value to the setpoint, 0 being the initial value
+        //and 1 being the setpoint.
+        let progress = (sample.value - self.initial) / (self.setpoint - self.initial);
+        if progress > self.peak {
+            self.peak = progress;
+        }
+        if self.rise_start.is_none() && progress >= 0.1 {
+            self.rise_start = Some(sample.time);
+        }
+        if self.rise_end.is_none() && progress >= 0.9 {
+            self.rise_end = Some(sample.time);
+        }
+        let error = if progress > 1.0 {
+            progress - 1.0
+        } else {
+            1.0 - progress
+        };
+        if error <= self.settling_band {
+            if self.settled_since.is_none() {
+                self.settled_since = Some(sample.time);
+            }
+        } else {
+            self.settled_since = None;
+        }
+        self.last = Some(sample);
+    }
+    ///Get the metrics for the samples pushed so far. Returns `None` if no samples have been
+    ///pushed.
+    pub fn metrics(&self) -> Option<StepResponseMetrics> {
+        let start_time = self.start_time?;
+        let last = self.last?;
+        let rise_time = match (self.rise_start, self.rise_end) {
+            (Some(rise_start), Some(rise_end)) => Some(rise_end - rise_start),
+            _ => None,
+        };
+        let overshoot = if self.peak > 1.0 {
+            self.peak - 1.0
+        } else {
+            0.0
+        };
+        let settling_time = self
+            .settled_since
+            .map(|settled_since| settled_since - start_time);
+        Some(StepResponseMetrics {
+            rise_time: rise_time,
+            overshoot: overshoot,
+            settling_time: settling_time,
+            steady_state_error: self.setpoint - last.value,
+        })
+    }
+}
+///A simulated first-order plant, such as the velocity of a DC motor. Its output approaches `gain`
+///times its input with time constant `time_constant` seconds. The input is held constant between
+///updates and integrated with Euler's method, so the time between updates should be much shorter
+///than the time constant. Returns `Ok(None)` until it has been updated.
+pub struct FirstOrderPlant<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<f32, E>,
+    time_getter: Reference<TG>,
+    gain: f32,
+    time_constant: f32,
+    input: f32,
+    value: f32,
+    time: Option<Time>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> FirstOrderPlant<TG, E> {
+    ///Constructor for [`FirstOrderPlant`].
+    pub const fn new(
+        time_getter: Reference<TG>,
+        gain: f32,
+        time_constant: f32,
+        initial_value: f32,
+    ) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            time_getter: time_getter,
+            gain: gain,
+            time_constant: time_constant,
+            input: 0.0,
+            value: initial_value,
+            time: None,
+        }
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Settable<f32, E> for FirstOrderPlant<TG, E> {
+    fn get_settable_data_ref(&self) -> &SettableData<f32, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: f32) -> NothingOrError<E> {
+        self.input = value;
+        Ok(())
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<f32, E> for FirstOrderPlant<TG, E> {
+    fn get(&self) -> Output<f32, E> {
+        match self.time {
+            Some(time) => Ok(Some(Datum::new(time, self.value))),
+            None => Ok(None),
+        }
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for FirstOrderPlant<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        if let Some(prev_time) = self.time {
+            let delta_time = f32::from(Quantity::from(time - prev_time));
+            self.value += (self.gain * self.input - self.value) / self.time_constant * delta_time;
+        }
+        self.time = Some(time);
+        //The new input takes effect for the next interval.
+        self.update_following_data()?;
+        Ok(())
+    }
+}
+///A simulated DC motor described by a [`MotorModel`] driving a load with a moment of inertia in
+///kilogram square meters. It is set to a voltage and returns its angular velocity in radians per
+///second. Like [`FirstOrderPlant`], the voltage is held constant between updates and integrated
+///with Euler's method. Returns `Ok(None)` until it has been updated.
+pub struct SimulatedMotor<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<f32, E>,
+    time_getter: Reference<TG>,
+    model: MotorModel,
+    inertia: f32,
+    voltage: f32,
+    velocity: f32,
+    time: Option<Time>,
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> SimulatedMotor<TG, E> {
+    ///Constructor for [`SimulatedMotor`].
+    pub const fn new(time_getter: Reference<TG>, model: MotorModel, inertia: f32) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            time_getter: time_getter,
+            model: model,
+            inertia: inertia,
+            voltage: 0.0,
+            velocity: 0.0,
+            time: None,
+        }
+    }
+    ///Get the current drawn by the motor at the last update.
+    pub fn get_current(&self) -> f32 {
+        self.model.current(self.voltage, self.velocity)
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Settable<f32, E> for SimulatedMotor<TG, E> {
+    fn get_settable_data_ref(&self) -> &SettableData<f32, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: f32) -> NothingOrError<E> {
+        self.voltage = value;
+        Ok(())
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<f32, E> for SimulatedMotor<TG, E> {
+    fn get(&self) -> Output<f32, E> {
+        match self.time {
+            Some(time) => Ok(Some(Datum::new(time, self.velocity))),
+            None => Ok(None),
+        }
+    }
+}
+impl<TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E> for SimulatedMotor<TG, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        if let Some(prev_time) = self.time {
+            let delta_time = f32::from(Quantity::from(time - prev_time));
+            let torque = self.model.torque(self.voltage, self.velocity);
+            self.velocity += torque / self.inertia * delta_time;
+        }
+        self.time = Some(time);
+        //The new voltage takes effect for the next interval.
+        self.update_following_data()?;
+        Ok(())
+    }
+}
+///Runs a closed-loop simulation in virtual time. Each step, every [`Updatable`] is updated in the
+///order given, then the output is read and passed to a [`StepResponseAnalyzer`], and then the clock
+///is advanced. Usually the plant should come before the controller so that the controller sees the
+///plant's latest output. The clock should be the time getter used by everything being simulated.
+pub struct TestBench<const N: usize, E: Copy + Debug> {
+    time: Reference<Time>,
+    updatables: [Reference<dyn Updatable<E>>; N],
+    output: Reference<dyn Getter<f32, E>>,
+}
+impl<const N: usize, E: Copy + Debug> TestBench<N, E> {
+    ///Constructor for [`TestBench`].
+    pub const fn new(
+        time: Reference<Time>,
+        updatables: [Reference<dyn Updatable<E>>; N],
+        output: Reference<dyn Getter<f32, E>>,
+    ) -> Self {
+        Self {
+            time: time,
+            updatables: updatables,
+            output: output,
+        }
+    }
+    ///Run the simulation for `duration` of virtual time in steps of `time_step`, then return the
+    ///metrics from the analyzer. Nothing is run if `duration` is negative. Panics if `time_step`
+    ///is not positive.
+    pub fn run(
+        &mut self,
+        duration: Time,
+        time_step: Time,
+        mut analyzer: StepResponseAnalyzer,
+    ) -> Result<Option<StepResponseMetrics>, Error<E>> {
+        assert!(
+            time_step > Time::default(),
+            "rrtk::sim::TestBench time_step must be positive"
+        );
+        let end_time = *self.time.borrow() + duration;
+        while *self.time.borrow() <= end_time {
+            for updatable in &self.updatables {
+                updatable.borrow_mut().update()?;
+            }
+            if let Some(output) = self.output.borrow().get()? {
+                analyzer.push(output);
+            }
+            *self.time.borrow_mut() += time_step;
+        }
+        Ok(analyzer.metrics())
+    }
+}
+///Steps a set of [`Updatable`]s through virtual time with a fixed time step. Each step updates
+///every updatable in the order given and then advances the clock by exactly `time_step`. Since
+///[`Time`] is an integer and nothing depends on the real time, running the same objects from the
+///same starting conditions always gives bit-for-bit identical results, which makes this suitable
+///for regression tests of controllers and estimators. The clock should be the time getter used by
+///everything being stepped.
+pub struct FixedStepRunner<const N: usize, E: Copy + Debug> {
+    time: Reference<Time>,
+    time_step: Time,
+    updatables: [Reference<dyn Updatable<E>>; N],
+    step_count: u64,
+}
+impl<const N: usize, E: Copy + Debug> FixedStepRunner<N, E> {
+    ///Constructor for [`FixedStepRunner`].
+    pub const fn new(
+        time: Reference<Time>,
+        time_step: Time,
+        updatables: [Reference<dyn Updatable<E>>; N],
+    ) -> Self {
+        Self {
+            time: time,
+            time_step: time_step,
+            updatables: updatables,
+            step_count: 0,
+        }
+    }
+    ///Get the fixed time step.
+    pub fn get_time_step(&self) -> Time {
+        self.time_step
+    }
+    ///Get the number of steps completed so far.
+    pub fn get_step_count(&self) -> u64 {
+        self.step_count
+    }
+    ///Update every updatable once and then advance the clock by one time step. If an update returns
+    ///an error, the remaining updatables are not updated, the clock is not advanced, and the error
+    ///is returned.
+    pub fn step(&mut self) -> NothingOrError<E> {
+        for updatable in &self.updatables {
+            updatable.borrow_mut().update()?;
+        }
+        *self.time.borrow_mut() += self.time_step;
+        self.step_count += 1;
+        Ok(())
+    }
+    ///Run `steps` steps, stopping early if one returns an error.
+    pub fn run(&mut self, steps: u64) -> NothingOrError<E> {
+        for _ in 0..steps {
+            self.step()?;
+        }
+        Ok(())
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for FixedStepRunner<N, E> {
+    ///Run a single step.
+    fn update(&mut self) -> NothingOrError<E> {
+        self.step()
+    }
+}
+///Simulates a sensor with latency. Each new value from the input is held back until `latency`
+///has passed since its timestamp according to the time getter, and is then returned with its
+///timestamp moved forward by `latency`, as if it had been measured when it arrived. Returns
+///`Ok(None)` until the first value is released. Updating this does not update the input.
+#[cfg(feature = "alloc")]
+pub struct DelayedGetter<
+    T: Clone,
+    G: Getter<T, E> + ?Sized,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    input: Reference<G>,
+    time_getter: Reference<TG>,
+    latency: Time,
+    pending: VecDeque<Datum<T>>,
+    output: Output<T, E>,
+}
+#[cfg(feature = "alloc")]
+impl<T: Clone, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    DelayedGetter<T, G, TG, E>
+{
+    ///Constructor for [`DelayedGetter`].
+    pub const fn new(input: Reference<G>, time_getter: Reference<TG>, latency: Time) -> Self {
+        Self {
+            input: input,
+            time_getter: time_getter,
+            latency: latency,
+            pending: VecDeque::new(),
+            output: Ok(None),
+        }
+    }
+    ///Get the latency.
+    pub fn get_latency(&self) -> Time {
+        self.latency
+    }
+    ///Change the latency. This also applies to values that have not been released yet.
+    pub fn set_latency(&mut self, latency: Time) {
+        self.latency = latency;
+    }
+}
+#[cfg(feature = "alloc")]
+impl<T: Clone, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<T, E>
+    for DelayedGetter<T, G, TG, E>
+{
+    fn get(&self) -> Output<T, E> {
+        self.output.clone()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<T: Clone, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for DelayedGetter<T, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let input = self.input.borrow().get();
+        match input {
+            Ok(Some(input)) => {
+                let is_new = match self.pending.back() {
+                    Some(newest) => input.time > newest.time,
+                    None => match &self.output {
+                        Ok(Some(output)) => input.time + self.latency > output.time,
+                        _ => true,
+                    },
+                };
+                if is_new {
+                    self.pending.push_back(input);
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        }
+        let time = self.time_getter.borrow().get()?;
+        while let Some(oldest) = self.pending.front() {
+            if oldest.time + self.latency > time {
+                break;
+            }
+            let oldest = self.pending.pop_front().expect("front was just checked");
+            self.output = Ok(Some(Datum::new(oldest.time + self.latency, oldest.value)));
+        }
+        Ok(())
+    }
+}
+///The distribution of the noise added by a [`NoisyGetter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoiseModel {
+    ///Noise spread evenly from `-amplitude` to `amplitude`.
+    Uniform {
+        ///The largest possible magnitude of the noise.
+        amplitude: f32,
+    },
+    ///Approximately normally distributed noise with a mean of 0. See
+    ///[`Prng::next_normal`](rand_lite::Prng::next_normal).
+    Gaussian {
+        ///The standard deviation of the noise.
+        standard_deviation: f32,
+    },
+}
+///Simulates a noisy sensor by adding random noise to every value from the input. The noise comes
+///from a [`Prng`](rand_lite::Prng) seeded with `seed`, so the same seed always gives the same
+///noise, keeping simulations reproducible. New noise is generated on every update, and updating
+///this does not update the input.
+pub struct NoisyGetter<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    noise_model: NoiseModel,
+    prng: rand_lite::Prng,
+    output: Output<f32, E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> NoisyGetter<G, E> {
+    ///Constructor for [`NoisyGetter`].
+    pub const fn new(input: Reference<G>, noise_model: NoiseModel, seed: u64) -> Self {
+        Self {
+            input: input,
+            noise_model: noise_model,
+            prng: rand_lite::Prng::new(seed),
+            output: Ok(None),
+        }
+    }
+    ///Get the noise model.
+    pub fn get_noise_model(&self) -> NoiseModel {
+        self.noise_model
+    }
+    ///Change the noise model. This takes effect on the next update.
+    pub fn set_noise_model(&mut self, noise_model: NoiseModel) {
+        self.noise_model = noise_model;
+    }
+    fn sample(&mut self) -> f32 {
+        match self.noise_model {
+            NoiseModel::Uniform { amplitude } => self.prng.range_f32(-amplitude, amplitude),
+            NoiseModel::Gaussian { standard_deviation } => {
+                self.prng.next_normal() * standard_deviation
+            }
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for NoisyGetter<G, E> {
+    fn get(&self) -> Output<f32, E> {
+        self.output
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for NoisyGetter<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let input = self.input.borrow().get();
+        self.output = match input {
+            Ok(Some(input)) => Ok(Some(Datum::new(input.time, input.value + self.sample()))),
+            Ok(None) => Ok(None),
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        Ok(())
+    }
+}
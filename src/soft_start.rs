@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///Protects gearboxes from shock loads by ramping a motor output up from zero instead of applying
+///it all at once. Whenever this is enabled through [`Enableable`] or the value set to it becomes
+///nonzero after being zero, the output to the inner settable starts at zero and scales linearly up
+///to the requested value over `duration`. While disabled, the output is zero. The output is
+///recalculated and set on every update, which also updates the inner settable but not the time
+///getter. Use `Time(0)` as `duration` to disable ramping.
+pub struct SoftStart<T: Settable<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<f32, E>,
+    inner: Reference<T>,
+    time_getter: Reference<TG>,
+    duration: Time,
+    ramp_start: Option<Time>,
+    ramping: bool,
+    enabled: bool,
+}
+impl<T: Settable<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    SoftStart<T, TG, E>
+{
+    ///Constructor for [`SoftStart`]. It starts enabled.
+    pub const fn new(inner: Reference<T>, time_getter: Reference<TG>, duration: Time) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            inner: inner,
+            time_getter: time_getter,
+            duration: duration,
+            ramp_start: None,
+            ramping: false,
+            enabled: true,
+        }
+    }
+    ///Get the ramp duration.
+    pub fn get_duration(&self) -> Time {
+        self.duration
+    }
+    ///Change the ramp duration. This also affects a ramp in progress.
+    pub fn set_duration(&mut self, duration: Time) {
+        self.duration = duration;
+    }
+    ///Returns true if the output was being ramped up rather than passed through as of the last
+    ///update.
+    pub fn is_ramping(&self) -> bool {
+        self.ramping
+    }
+}
+impl<T: Settable<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Enableable
+    for SoftStart<T, TG, E>
+{
+    fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.ramp_start = None;
+            self.ramping = false;
+        }
+        self.enabled = enabled;
+    }
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+impl<T: Settable<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Settable<f32, E>
+    for SoftStart<T, TG, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<f32, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, E> {
+        &mut self.settable_data
+    }
+    ///The value is applied on the next update.
+    fn impl_set(&mut self, _value: f32) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+impl<T: Settable<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for SoftStart<T, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        let request = match self.get_last_request() {
+            Some(request) if self.enabled => request,
+            _ => 0.0,
+        };
+        let output = if request == 0.0 {
+            self.ramp_start = None;
+            self.ramping = false;
+            0.0
+        } else {
+            let time = self.time_getter.borrow().get()?;
+            let ramp_start = *self.ramp_start.get_or_insert(time);
+            let elapsed = time - ramp_start;
+            self.ramping = elapsed < self.duration;
+            if self.ramping {
+                request * (elapsed.0 as f32 / self.duration.0 as f32)
+            } else {
+                request
+            }
+        };
+        self.inner.borrow_mut().set(output)?;
+        self.inner.borrow_mut().update()
+    }
+}
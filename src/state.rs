@@ -49,16 +49,15 @@ impl State {
     ///acceleration and returns [`Ok`] if the argument's [`Unit`] is correct, otherwise leaves it
     ///unchanged and returns [`Err`]. With dimension checking disabled, always sets the acceleration
     ///to the [`Quantity`]'s value and returns [`Ok`], ignoring the [`Unit`].
-    pub const fn set_constant_acceleration(&mut self, acceleration: Quantity) -> Result<(), ()> {
-        if acceleration
-            .unit
-            .eq_assume_true(&MILLIMETER_PER_SECOND_SQUARED)
-        {
-            self.acceleration = acceleration.value;
-            Ok(())
-        } else {
-            Err(())
+    pub const fn set_constant_acceleration(
+        &mut self,
+        acceleration: Quantity,
+    ) -> Result<(), UnitMismatch> {
+        if let Err(mismatch) = acceleration.unit.check(&MILLIMETER_PER_SECOND_SQUARED) {
+            return Err(mismatch);
         }
+        self.acceleration = acceleration.value;
+        Ok(())
     }
     ///Set the acceleration with an [`f32`] of millimeters per second squared.
     #[inline]
@@ -70,14 +69,13 @@ impl State {
     ///argument's [`Unit`] is correct, otherwise leaves them unchanged and returns [`Err`]. With
     ///dimension checking disabled, ignores the [`Unit`] and always sets velocity and acceleration
     ///and returns [`Ok`].
-    pub const fn set_constant_velocity(&mut self, velocity: Quantity) -> Result<(), ()> {
-        if velocity.unit.eq_assume_true(&MILLIMETER_PER_SECOND) {
-            self.acceleration = 0.0;
-            self.velocity = velocity.value;
-            Ok(())
-        } else {
-            Err(())
+    pub const fn set_constant_velocity(&mut self, velocity: Quantity) -> Result<(), UnitMismatch> {
+        if let Err(mismatch) = velocity.unit.check(&MILLIMETER_PER_SECOND) {
+            return Err(mismatch);
         }
+        self.acceleration = 0.0;
+        self.velocity = velocity.value;
+        Ok(())
     }
     ///Set the velocity to a given value with an [`f32`] of millimeters per second, and set acceleration to zero.
     #[inline]
@@ -90,15 +88,14 @@ impl State {
     ///returns [`Ok`] if the argument's [`Unit`] is correct, otherwise leaves them unchanged and
     ///returns [`Err`]. With dimension checking disabled, always sets the position, velocity, and
     ///acceleration and returns [`Ok`], ignoring the [`Unit`].
-    pub const fn set_constant_position(&mut self, position: Quantity) -> Result<(), ()> {
-        if position.unit.eq_assume_true(&MILLIMETER) {
-            self.acceleration = 0.0;
-            self.velocity = 0.0;
-            self.position = position.value;
-            Ok(())
-        } else {
-            Err(())
+    pub const fn set_constant_position(&mut self, position: Quantity) -> Result<(), UnitMismatch> {
+        if let Err(mismatch) = position.unit.check(&MILLIMETER) {
+            return Err(mismatch);
         }
+        self.acceleration = 0.0;
+        self.velocity = 0.0;
+        self.position = position.value;
+        Ok(())
     }
     ///Set the position to a given value with an [`f32`] of millimeters, and set velocity and acceleration to zero.
     #[inline]
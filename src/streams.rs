@@ -97,3 +97,601 @@ impl<T, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> U
         Ok(())
     }
 }
+///What a [`StaleDataPolicyStream`] should do when its input's data are too old or it returns
+///`Ok(None)`.
+pub enum StaleDataPolicy<T, E: Copy + Debug> {
+    ///Return the input's output unchanged. Whether it is stale can be checked with
+    ///[`StaleDataPolicyStream::is_stale`].
+    PassThrough,
+    ///Return the last datum that was not stale for up to this long after it becomes stale, then
+    ///return `Ok(None)`.
+    HoldLastGood(Time),
+    ///Extrapolate from the last datum that was not stale using the output of this getter as its
+    ///derivative with respect to time in seconds. The extrapolated datum has the current time. If
+    ///there is no last good datum or the derivative getter returns `Ok(None)`, return `Ok(None)`.
+    Extrapolate(Reference<dyn Getter<T, E>>),
+    ///Return `Err(Error::Other(_))` with this value.
+    ReturnError(E),
+}
+///Applies a [`StaleDataPolicy`] when its input's data are older than a maximum age. This is a more
+///configurable version of [`Expirer`], which always returns `Ok(None)` for stale data. Unlike
+///[`Expirer`], this must be updated to work.
+pub struct StaleDataPolicyStream<
+    T: Clone,
+    G: Getter<T, E> + ?Sized,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    input: Reference<G>,
+    time_getter: Reference<TG>,
+    max_time_delta: Time,
+    policy: StaleDataPolicy<T, E>,
+    last_good: Option<Datum<T>>,
+    stale: bool,
+    value: Output<T, E>,
+}
+impl<T: Clone, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    StaleDataPolicyStream<T, G, TG, E>
+{
+    ///Constructor for [`StaleDataPolicyStream`].
+    pub const fn new(
+        input: Reference<G>,
+        time_getter: Reference<TG>,
+        max_time_delta: Time,
+        policy: StaleDataPolicy<T, E>,
+    ) -> Self {
+        Self {
+            input: input,
+            time_getter: time_getter,
+            max_time_delta: max_time_delta,
+            policy: policy,
+            last_good: None,
+            stale: false,
+            value: Ok(None),
+        }
+    }
+    ///Returns true if the input's data were stale or missing during the last update.
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+    fn update_with(&mut self, extrapolate: fn(T, T, Time) -> T) -> NothingOrError<E> {
+        let time = match self.time_getter.borrow().get() {
+            Ok(time) => time,
+            Err(error) => {
+                self.value = Err(error);
+                return Err(error);
+            }
+        };
+        let output = match self.input.borrow().get() {
+            Ok(output) => output,
+            Err(error) => {
+                self.value = Err(error);
+                return Err(error);
+            }
+        };
+        match &output {
+            Some(datum) if time - datum.time <= self.max_time_delta => {
+                self.last_good = output.clone();
+                self.stale = false;
+                self.value = Ok(output);
+                return Ok(());
+            }
+            _ => {}
+        }
+        self.stale = true;
+        self.value = match &self.policy {
+            StaleDataPolicy::PassThrough => Ok(output),
+            StaleDataPolicy::HoldLastGood(hold_time) => match &self.last_good {
+                Some(last_good) if time - last_good.time <= self.max_time_delta + *hold_time => {
+                    Ok(Some(last_good.clone()))
+                }
+                _ => Ok(None),
+            },
+            StaleDataPolicy::Extrapolate(derivative) => match &self.last_good {
+                Some(last_good) => match derivative.borrow().get() {
+                    Ok(Some(derivative)) => Ok(Some(Datum::new(
+                        time,
+                        extrapolate(
+                            last_good.value.clone(),
+                            derivative.value,
+                            time - last_good.time,
+                        ),
+                    ))),
+                    Ok(None) => Ok(None),
+                    Err(error) => {
+                        self.value = Err(error);
+                        return Err(error);
+                    }
+                },
+                None => Ok(None),
+            },
+            StaleDataPolicy::ReturnError(error) => Err(Error::Other(*error)),
+        };
+        Ok(())
+    }
+}
+impl<
+        T: Clone + Add<Output = T> + Mul<f32, Output = T>,
+        G: Getter<T, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Getter<T, E> for StaleDataPolicyStream<T, G, TG, E>
+{
+    fn get(&self) -> Output<T, E> {
+        self.value.clone()
+    }
+}
+impl<G: Getter<Quantity, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    Getter<Quantity, E> for StaleDataPolicyStream<Quantity, G, TG, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        self.value.clone()
+    }
+}
+impl<
+        T: Clone + Add<Output = T> + Mul<f32, Output = T>,
+        G: Getter<T, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Updatable<E> for StaleDataPolicyStream<T, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_with(|value, derivative, delta_time| {
+            value + derivative * f32::from(Quantity::from(delta_time))
+        })
+    }
+}
+impl<G: Getter<Quantity, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for StaleDataPolicyStream<Quantity, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_with(|value, derivative, delta_time| {
+            value + derivative * Quantity::from(delta_time)
+        })
+    }
+}
+///Measures how often its input produces data with new timestamps and outputs the rate in hertz.
+///The rate is smoothed with an exponentially weighted moving average where each new timestamp has
+///weight `smoothing_constant`. If the input stops producing new data, the output falls to the
+///reciprocal of the time since the last new datum so that a dying sensor is noticed without
+///waiting for it to produce another datum. Returns `Ok(None)` until two new timestamps have been
+///seen. Must be updated to work.
+pub struct FrequencyStream<T, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+{
+    input: Reference<G>,
+    time_getter: Reference<TG>,
+    smoothing_constant: f32,
+    last_input_time: Option<Time>,
+    rate: Option<f32>,
+    value: Output<f32, E>,
+    phantom_t: PhantomData<T>,
+}
+impl<T, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    FrequencyStream<T, G, TG, E>
+{
+    ///Constructor for [`FrequencyStream`]. `smoothing_constant` should be greater than 0 and at
+    ///most 1, with 1 meaning no smoothing.
+    pub const fn new(
+        input: Reference<G>,
+        time_getter: Reference<TG>,
+        smoothing_constant: f32,
+    ) -> Self {
+        Self {
+            input: input,
+            time_getter: time_getter,
+            smoothing_constant: smoothing_constant,
+            last_input_time: None,
+            rate: None,
+            value: Ok(None),
+            phantom_t: PhantomData,
+        }
+    }
+}
+impl<T, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for FrequencyStream<T, G, TG, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.value.clone()
+    }
+}
+impl<T, G: Getter<T, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for FrequencyStream<T, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = match self.time_getter.borrow().get() {
+            Ok(time) => time,
+            Err(error) => {
+                self.value = Err(error);
+                return Err(error);
+            }
+        };
+        let input_time = match self.input.borrow().get() {
+            Ok(Some(datum)) => Some(datum.time),
+            Ok(None) => None,
+            Err(error) => {
+                self.value = Err(error);
+                return Err(error);
+            }
+        };
+        if let Some(input_time) = input_time {
+            match self.last_input_time {
+                Some(last_input_time) if input_time > last_input_time => {
+                    let instant_rate =
+                        1.0 / f32::from(Quantity::from(input_time - last_input_time));
+                    self.rate = Some(match self.rate {
+                        Some(rate) => rate + self.smoothing_constant * (instant_rate - rate),
+                        None => instant_rate,
+                    });
+                    self.last_input_time = Some(input_time);
+                }
+                Some(_) => {}
+                None => self.last_input_time = Some(input_time),
+            }
+        }
+        let (rate, last_input_time) = match (self.rate, self.last_input_time) {
+            (Some(rate), Some(last_input_time)) => (rate, last_input_time),
+            _ => {
+                self.value = Ok(None);
+                return Ok(());
+            }
+        };
+        let since_last = f32::from(Quantity::from(time - last_input_time));
+        let rate = if since_last > 0.0 && 1.0 / since_last < rate {
+            1.0 / since_last
+        } else {
+            rate
+        };
+        self.value = Ok(Some(Datum::new(time, rate)));
+        Ok(())
+    }
+}
+///What a [`StateValidator`] should do with an invalid [`State`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InvalidStateAction<E: Copy + Debug> {
+    ///Fix the state as well as possible. Non-finite fields are replaced with those of the last
+    ///valid state, and a position too far from where it should be is moved to the edge of the
+    ///allowed range. If there is no last valid state to work from, returns `Ok(None)`.
+    Clamp,
+    ///Return `Ok(None)` instead of the state.
+    Reject,
+    ///Return `Err(Error::Other(_))` with this value.
+    ReturnError(E),
+}
+///Checks each [`State`] from its input for corruption before passing it on, protecting filters and
+///controllers from bad sensor data. A state is invalid if any field is NaN or infinite or if its
+///position is more than `max_position_error` millimeters away from where the last valid state
+///and the average of the two velocities say it should be. Invalid states are handled according to
+///an [`InvalidStateAction`].
+pub struct StateValidator<G: Getter<State, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    max_position_error: f32,
+    action: InvalidStateAction<E>,
+    last_valid: Option<Datum<State>>,
+    value: Output<State, E>,
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> StateValidator<G, E> {
+    ///Constructor for [`StateValidator`].
+    pub const fn new(
+        input: Reference<G>,
+        max_position_error: f32,
+        action: InvalidStateAction<E>,
+    ) -> Self {
+        Self {
+            input: input,
+            max_position_error: max_position_error,
+            action: action,
+            last_valid: None,
+            value: Ok(None),
+        }
+    }
+    //Returns the state with non-finite fields replaced, or None if there is nothing to replace
+    //them with.
+    fn replace_non_finite(&self, state: State) -> Option<State> {
+        if state.position.is_finite()
+            && state.velocity.is_finite()
+            && state.acceleration.is_finite()
+        {
+            return Some(state);
+        }
+        let last = self.last_valid?.value;
+        let replace = |value: f32, last: f32| if value.is_finite() { value } else { last };
+        Some(State::new_raw(
+            replace(state.position, last.position),
+            replace(state.velocity, last.velocity),
+            replace(state.acceleration, last.acceleration),
+        ))
+    }
+    //Returns the range of positions that a state at the given time with the given velocity may
+    //have, or None if there is no last valid state to check against.
+    fn position_range(&self, time: Time, velocity: f32) -> Option<(f32, f32)> {
+        let last = self.last_valid?;
+        if time <= last.time {
+            return None;
+        }
+        let delta_time = f32::from(Quantity::from(time - last.time));
+        let expected = last.value.position + (last.value.velocity + velocity) / 2.0 * delta_time;
+        Some((
+            expected - self.max_position_error,
+            expected + self.max_position_error,
+        ))
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<State, E> for StateValidator<G, E> {
+    fn get(&self) -> Output<State, E> {
+        self.value
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E> for StateValidator<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let datum = match self.input.borrow().get() {
+            Ok(Some(datum)) => datum,
+            Ok(None) => {
+                self.value = Ok(None);
+                return Ok(());
+            }
+            Err(error) => {
+                self.value = Err(error);
+                return Err(error);
+            }
+        };
+        let mut state = datum.value;
+        let finite = match self.replace_non_finite(state) {
+            Some(replaced) => {
+                let finite = replaced == state;
+                state = replaced;
+                finite
+            }
+            None => false,
+        };
+        let in_range = match self.position_range(datum.time, state.velocity) {
+            Some((min, max)) if state.position < min || state.position > max => {
+                if let InvalidStateAction::Clamp = self.action {
+                    state.position = if state.position < min { min } else { max };
+                }
+                false
+            }
+            _ => true,
+        };
+        if !(finite && in_range) {
+            match self.action {
+                //Non-finite states with no last valid state cannot be fixed.
+                InvalidStateAction::Clamp if self.last_valid.is_some() => {}
+                InvalidStateAction::Clamp | InvalidStateAction::Reject => {
+                    self.value = Ok(None);
+                    return Ok(());
+                }
+                InvalidStateAction::ReturnError(error) => {
+                    self.value = Err(Error::Other(error));
+                    return Err(Error::Other(error));
+                }
+            }
+        }
+        let datum = Datum::new(datum.time, state);
+        self.last_valid = Some(datum);
+        self.value = Ok(Some(datum));
+        Ok(())
+    }
+}
+///What a [`FiniteGuardStream`] should do when its input returns NaN or infinity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonFiniteAction<T, E: Copy + Debug> {
+    ///Return `Err(Error::Other(_))` with this value.
+    ReturnError(E),
+    ///Return the last finite datum, or `Ok(None)` if there has not been one.
+    HoldLastGood,
+    ///Return this value with the timestamp of the non-finite datum.
+    Substitute(T),
+}
+///Catches NaN and infinite values from its input and handles them according to a
+///[`NonFiniteAction`]. A single NaN, such as from a division by zero, will otherwise poison the
+///integral of any controller downstream without any indication. Implemented for [`f32`] and
+///[`Quantity`].
+pub struct FiniteGuardStream<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    action: NonFiniteAction<T, E>,
+    last_good: Option<Datum<T>>,
+    value: Output<T, E>,
+}
+impl<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> FiniteGuardStream<T, G, E> {
+    ///Constructor for [`FiniteGuardStream`].
+    pub const fn new(input: Reference<G>, action: NonFiniteAction<T, E>) -> Self {
+        Self {
+            input: input,
+            action: action,
+            last_good: None,
+            value: Ok(None),
+        }
+    }
+    fn update_with(&mut self, is_finite: fn(&T) -> bool) -> NothingOrError<E> {
+        let datum = match self.input.borrow().get() {
+            Ok(Some(datum)) => datum,
+            Ok(None) => {
+                self.value = Ok(None);
+                return Ok(());
+            }
+            Err(error) => {
+                self.value = Err(error);
+                return Err(error);
+            }
+        };
+        if is_finite(&datum.value) {
+            self.last_good = Some(datum.clone());
+            self.value = Ok(Some(datum));
+            return Ok(());
+        }
+        match &self.action {
+            NonFiniteAction::ReturnError(error) => {
+                self.value = Err(Error::Other(*error));
+                return Err(Error::Other(*error));
+            }
+            NonFiniteAction::HoldLastGood => self.value = Ok(self.last_good.clone()),
+            NonFiniteAction::Substitute(value) => {
+                self.value = Ok(Some(Datum::new(datum.time, value.clone())))
+            }
+        }
+        Ok(())
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for FiniteGuardStream<f32, G, E> {
+    fn get(&self) -> Output<f32, E> {
+        self.value
+    }
+}
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E>
+    for FiniteGuardStream<Quantity, G, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        self.value
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for FiniteGuardStream<f32, G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_with(|value| value.is_finite())
+    }
+}
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for FiniteGuardStream<Quantity, G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_with(|value| value.value.is_finite())
+    }
+}
+///Caches the output of its input so that [`get`](Getter::get) is cheap. The input is only asked
+///for a new value in [`update`](Updatable::update), and only if the cache has been invalidated with
+///[`invalidate`](MemoizedGetter::invalidate) or has never been filled. This formalizes the
+///compute-on-update pattern for expensive computations such as those wrapped in an
+///[`FnGetter`](crate::FnGetter).
+pub struct MemoizedGetter<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<T, E>,
+    invalidated: bool,
+}
+impl<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> MemoizedGetter<T, G, E> {
+    ///Constructor for [`MemoizedGetter`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+            invalidated: true,
+        }
+    }
+    ///Mark the cached value as outdated so that it is recomputed on the next update. Until then,
+    ///the old value is still returned.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+    ///Returns true if the cached value will be recomputed on the next update.
+    pub fn is_invalidated(&self) -> bool {
+        self.invalidated
+    }
+}
+impl<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> Getter<T, E> for MemoizedGetter<T, G, E> {
+    fn get(&self) -> Output<T, E> {
+        self.value.clone()
+    }
+}
+impl<T: Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> Updatable<E> for MemoizedGetter<T, G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        if self.invalidated {
+            self.value = self.input.borrow().get();
+            self.invalidated = false;
+        }
+        Ok(())
+    }
+}
+///Shares one getter between several consumers that each want to update it. Unlike streams, this
+///owns its source and updates it, but only once per time reported by the time getter no matter how
+///many times this or any [`BroadcastReader`] of it is updated. The source's output is buffered on
+///each real update so that every consumer sees the same value for the cycle. Give each consumer a
+///[`BroadcastReader`] and let them update it as they would any other input.
+pub struct Broadcast<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    source: G,
+    time_getter: Reference<TG>,
+    value: Output<T, E>,
+    last_update: Option<Time>,
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    Broadcast<T, G, TG, E>
+{
+    ///Constructor for [`Broadcast`].
+    pub const fn new(source: G, time_getter: Reference<TG>) -> Self {
+        Self {
+            source: source,
+            time_getter: time_getter,
+            value: Ok(None),
+            last_update: None,
+        }
+    }
+    ///Get a reference to the source.
+    pub fn get_source(&self) -> &G {
+        &self.source
+    }
+    ///Get the time the source was last updated at, or `None` if it never has been.
+    pub fn get_last_update(&self) -> Option<Time> {
+        self.last_update
+    }
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<T, E>
+    for Broadcast<T, G, TG, E>
+{
+    fn get(&self) -> Output<T, E> {
+        self.value.clone()
+    }
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for Broadcast<T, G, TG, E>
+{
+    ///Update the source and buffer its output if it has not already been updated at the current
+    ///time. If updating the source fails, the error is buffered as well, so readers see it for the
+    ///rest of the cycle.
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = self.time_getter.borrow().get()?;
+        if self.last_update == Some(time) {
+            return Ok(());
+        }
+        self.last_update = Some(time);
+        if let Err(error) = self.source.update() {
+            self.value = Err(error);
+            return Err(error);
+        }
+        self.value = self.source.get();
+        Ok(())
+    }
+}
+///A cheap cloneable handle reading the buffered value of a [`Broadcast`]. Updating this updates the
+///[`Broadcast`], which only updates its source once per cycle.
+pub struct BroadcastReader<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    broadcast: Reference<Broadcast<T, G, TG, E>>,
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    BroadcastReader<T, G, TG, E>
+{
+    ///Constructor for [`BroadcastReader`].
+    pub const fn new(broadcast: Reference<Broadcast<T, G, TG, E>>) -> Self {
+        Self {
+            broadcast: broadcast,
+        }
+    }
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Clone
+    for BroadcastReader<T, G, TG, E>
+{
+    fn clone(&self) -> Self {
+        Self::new(self.broadcast.clone())
+    }
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<T, E>
+    for BroadcastReader<T, G, TG, E>
+{
+    fn get(&self) -> Output<T, E> {
+        self.broadcast.borrow().get()
+    }
+}
+impl<T: Clone, G: Getter<T, E>, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for BroadcastReader<T, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.broadcast.borrow_mut().update()
+    }
+}
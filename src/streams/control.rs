@@ -7,18 +7,35 @@ use alloc::collections::vec_deque::VecDeque;
 //This does store the timestamp twice, once in prev_error and once in output. Processor performance
 //and readability would suggest doing it this way, but 8 bytes could technically be saved here if
 //needed in the future. The difference is extremely minimal.
-///A PID controller for use with the stream system.
-pub struct PIDControllerStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+///A PID controller for use with the stream system. The k-values can come from any
+///[`PIDKValueProvider`], so gains depending on the error such as [`ZonedPIDKValues`] can be used in
+///place of a fixed [`PIDKValues`]. The process variable is treated as a position when getting
+///k-values, so a [`PositionDerivativeDependentPIDKValues`] gives its position k-values. The
+///k-values can be changed while running with [`set_k_values`](PIDControllerStream::set_k_values) or
+///through [`Settable`] without losing the accumulated integral. Only [`Settable`] requires the
+///k-values to be [`Clone`], so updating does not update its following data; to
+///[follow](Settable::follow) a getter of k-values, call
+///[`update_following_data`](Settable::update_following_data) before each update.
+pub struct PIDControllerStream<
+    G: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+    K: PIDKValueProvider = PIDKValues,
+> {
     input: Reference<G>,
     setpoint: f32,
-    kvals: PIDKValues,
+    kvals: K,
     prev_error: Option<Datum<f32>>,
     int_error: f32,
     output: Output<f32, E>,
+    settable_data: SettableData<K, E>,
+    output_limits: Option<OutputLimits>,
+    anti_windup: Option<AntiWindup>,
 }
-impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> PIDControllerStream<G, E> {
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider>
+    PIDControllerStream<G, E, K>
+{
     ///Constructor for `PIDControllerStream`.
-    pub const fn new(input: Reference<G>, setpoint: f32, kvals: PIDKValues) -> Self {
+    pub const fn new(input: Reference<G>, setpoint: f32, kvals: K) -> Self {
         Self {
             input: input,
             setpoint: setpoint,
@@ -26,7 +43,51 @@ impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> PIDControllerStream<G, E> {
             prev_error: None,
             int_error: 0.0,
             output: Ok(None),
+            settable_data: SettableData::new(),
+            output_limits: None,
+            anti_windup: None,
+        }
+    }
+    ///Limit the output, or remove the limits with `None`.
+    pub fn set_output_limits(&mut self, output_limits: Option<OutputLimits>) {
+        self.output_limits = output_limits;
+    }
+    ///Get the output limits.
+    pub fn get_output_limits(&self) -> Option<OutputLimits> {
+        self.output_limits
+    }
+    ///Set how the integral is kept from winding up, or turn anti-windup off with `None`. It is off
+    ///by default.
+    pub fn set_anti_windup(&mut self, anti_windup: Option<AntiWindup>) {
+        self.anti_windup = anti_windup;
+    }
+    ///Get how the integral is kept from winding up.
+    pub fn get_anti_windup(&self) -> Option<AntiWindup> {
+        self.anti_windup
+    }
+    ///Get the k-values currently in use.
+    pub fn get_k_values(&self) -> &K {
+        &self.kvals
+    }
+    ///Change the k-values without resetting the controller. The accumulated integral is rescaled
+    ///so that the integral term stays the same at the latest error, so the output does not jump
+    ///when only ki changes. If the new ki is zero, the accumulated integral is kept as is so that it
+    ///is not lost if ki is later made nonzero again. The new k-values take effect on the next
+    ///update.
+    pub fn set_k_values(&mut self, kvals: K) {
+        if let Some(prev_error) = self.prev_error {
+            let old_ki = self
+                .kvals
+                .get_k_values(PositionDerivative::Position, prev_error.value)
+                .ki;
+            let new_ki = kvals
+                .get_k_values(PositionDerivative::Position, prev_error.value)
+                .ki;
+            if new_ki != 0.0 {
+                self.int_error *= old_ki / new_ki;
+            }
         }
+        self.kvals = kvals;
     }
     #[inline]
     fn reset(&mut self) {
@@ -35,12 +96,31 @@ impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> PIDControllerStream<G, E> {
         self.output = Ok(None);
     }
 }
-impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for PIDControllerStream<G, E> {
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider> Getter<f32, E>
+    for PIDControllerStream<G, E, K>
+{
     fn get(&self) -> Output<f32, E> {
         self.output.clone()
     }
 }
-impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for PIDControllerStream<G, E> {
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider + Clone> Settable<K, E>
+    for PIDControllerStream<G, E, K>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<K, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<K, E> {
+        &mut self.settable_data
+    }
+    ///Calls [`set_k_values`](PIDControllerStream::set_k_values).
+    fn impl_set(&mut self, kvals: K) -> NothingOrError<E> {
+        self.set_k_values(kvals);
+        Ok(())
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider> Updatable<E>
+    for PIDControllerStream<G, E, K>
+{
     fn update(&mut self) -> NothingOrError<E> {
         let process = self.input.borrow().get();
         let process = match process {
@@ -56,12 +136,128 @@ impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for PIDController
             }
         };
         let error = self.setpoint - process.value;
-        let [int_error_addend, drv_error] = match &self.prev_error {
+        let [int_error_addend, drv_error, delta_time] = match &self.prev_error {
             Some(prev_error) => {
                 let delta_time = f32::from(Quantity::from(process.time - prev_error.time));
                 let drv_error = (error - prev_error.value) / delta_time;
                 //Trapezoidal integral approximation is more precise than rectangular.
                 let int_error_addend = delta_time * (prev_error.value + error) / 2.0;
+                [int_error_addend, drv_error, delta_time]
+            }
+            None => {
+                debug_assert_eq!(self.int_error, 0.0);
+                [0.0, 0.0, 0.0]
+            }
+        };
+        let (int_error, output) = integrate_with_anti_windup(
+            self.anti_windup,
+            self.output_limits.map(Saturation::direct),
+            PIDStep {
+                kvals: self.kvals.get_k_values(PositionDerivative::Position, error),
+                error: error,
+                prev_int_error: self.int_error,
+                int_error_addend: int_error_addend,
+                drv_error: drv_error,
+                delta_time: delta_time,
+            },
+        );
+        self.int_error = int_error;
+        let output = match self.output_limits {
+            Some(limits) => limits.clamp(output),
+            None => output,
+        };
+        self.output = Ok(Some(Datum::new(process.time, output)));
+        self.prev_error = Some(Datum::new(process.time, error));
+        Ok(())
+    }
+}
+///A PID controller like [`PIDControllerStream`] whose setpoint comes from a getter instead of being
+///fixed, which is useful for cascaded loops and following trajectories without the [`Settable`]
+///machinery of [`CommandPID`]. The derivative term is calculated from the process variable alone
+///rather than from the error, so a sudden change of setpoint does not cause a spike in the output.
+///While the process is moving toward a fixed setpoint, this is the same as the derivative of the
+///error. If either getter returns `Ok(None)`, the controller resets and returns `Ok(None)`.
+pub struct DynamicSetpointPIDControllerStream<
+    G: Getter<f32, E> + ?Sized,
+    GS: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+    K: PIDKValueProvider = PIDKValues,
+> {
+    input: Reference<G>,
+    setpoint: Reference<GS>,
+    kvals: K,
+    prev_process: Option<Datum<f32>>,
+    prev_error: f32,
+    int_error: f32,
+    output: Output<f32, E>,
+}
+impl<
+        G: Getter<f32, E> + ?Sized,
+        GS: Getter<f32, E> + ?Sized,
+        E: Copy + Debug,
+        K: PIDKValueProvider,
+    > DynamicSetpointPIDControllerStream<G, GS, E, K>
+{
+    ///Constructor for [`DynamicSetpointPIDControllerStream`].
+    pub const fn new(input: Reference<G>, setpoint: Reference<GS>, kvals: K) -> Self {
+        Self {
+            input: input,
+            setpoint: setpoint,
+            kvals: kvals,
+            prev_process: None,
+            prev_error: 0.0,
+            int_error: 0.0,
+            output: Ok(None),
+        }
+    }
+    #[inline]
+    fn reset(&mut self) {
+        self.prev_process = None;
+        self.prev_error = 0.0;
+        self.int_error = 0.0;
+        self.output = Ok(None);
+    }
+}
+impl<
+        G: Getter<f32, E> + ?Sized,
+        GS: Getter<f32, E> + ?Sized,
+        E: Copy + Debug,
+        K: PIDKValueProvider,
+    > Getter<f32, E> for DynamicSetpointPIDControllerStream<G, GS, E, K>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.output.clone()
+    }
+}
+impl<
+        G: Getter<f32, E> + ?Sized,
+        GS: Getter<f32, E> + ?Sized,
+        E: Copy + Debug,
+        K: PIDKValueProvider,
+    > Updatable<E> for DynamicSetpointPIDControllerStream<G, GS, E, K>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let process = self.input.borrow().get();
+        let setpoint = self.setpoint.borrow().get();
+        let (process, setpoint) = match (process, setpoint) {
+            (Ok(Some(process)), Ok(Some(setpoint))) => (process, setpoint),
+            (Err(error), _) | (_, Err(error)) => {
+                self.reset();
+                self.output = Err(error);
+                return Err(error);
+            }
+            _ => {
+                self.reset();
+                return Ok(());
+            }
+        };
+        let error = setpoint.value - process.value;
+        let [int_error_addend, drv_error] = match &self.prev_process {
+            Some(prev_process) => {
+                let delta_time = f32::from(Quantity::from(process.time - prev_process.time));
+                //Derivative on measurement: the setpoint's change is left out.
+                let drv_error = -(process.value - prev_process.value) / delta_time;
+                let int_error_addend = delta_time * (self.prev_error + error) / 2.0;
                 [int_error_addend, drv_error]
             }
             None => {
@@ -72,12 +268,129 @@ impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for PIDController
         self.int_error += int_error_addend;
         self.output = Ok(Some(Datum::new(
             process.time,
-            self.kvals.kp * error + self.kvals.ki * self.int_error + self.kvals.kd * drv_error,
+            self.kvals.evaluate(
+                PositionDerivative::Position,
+                error,
+                self.int_error,
+                drv_error,
+            ),
         )));
-        self.prev_error = Some(Datum::new(process.time, error));
+        self.prev_process = Some(process);
+        self.prev_error = error;
         Ok(())
     }
 }
+///Minimum and maximum values for the output of a controller.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutputLimits {
+    ///The lowest value the output may take.
+    pub min: f32,
+    ///The highest value the output may take.
+    pub max: f32,
+}
+impl OutputLimits {
+    ///Constructor for [`OutputLimits`].
+    pub const fn new(min: f32, max: f32) -> Self {
+        Self { min: min, max: max }
+    }
+    ///Restrict a value to be within the limits.
+    #[inline]
+    pub fn clamp(&self, value: f32) -> f32 {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+}
+///A way of keeping the integral of a PID controller from winding up, which otherwise causes large
+///overshoot after the output has been saturated for a while.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AntiWindup {
+    ///Keep the integral term, the integral coefficient times the integral of the error, within
+    ///these limits. This works with or without output limits.
+    ClampIntegral(OutputLimits),
+    ///While the output is past the controller's output limits, bleed off the integral at a rate of
+    ///this tracking gain times how far past the limits it is. A tracking gain around the
+    ///integral coefficient is a reasonable starting point. This does nothing without output limits.
+    BackCalculation(f32),
+    ///Stop integrating while the output without the new part of the integral is past the
+    ///controller's output limits and integrating would push it further past them. This does
+    ///nothing without output limits.
+    ConditionalIntegration,
+}
+//The inputs of one step of a PID controller.
+struct PIDStep {
+    kvals: PIDKValues,
+    error: f32,
+    prev_int_error: f32,
+    int_error_addend: f32,
+    drv_error: f32,
+    delta_time: f32,
+}
+//The limits of whatever actually saturates, which is base + scale * the PID output. This is just
+//the PID output for PIDControllerStream but its integral or double integral for CommandPID
+//following velocity or acceleration commands.
+#[derive(Clone, Copy)]
+struct Saturation {
+    limits: OutputLimits,
+    base: f32,
+    scale: f32,
+}
+impl Saturation {
+    const fn direct(limits: OutputLimits) -> Self {
+        Self {
+            limits: limits,
+            base: 0.0,
+            scale: 1.0,
+        }
+    }
+    #[inline]
+    fn saturated(&self, output: f32) -> f32 {
+        self.base + self.scale * output
+    }
+}
+//Shared by PIDControllerStream and CommandPID. Returns the new integral of the error and the
+//unlimited output.
+fn integrate_with_anti_windup(
+    anti_windup: Option<AntiWindup>,
+    saturation: Option<Saturation>,
+    step: PIDStep,
+) -> (f32, f32) {
+    let kvals = step.kvals;
+    let mut int_error = step.prev_int_error + step.int_error_addend;
+    match (anti_windup, saturation) {
+        (Some(AntiWindup::ClampIntegral(limits)), _) if kvals.ki != 0.0 => {
+            int_error = limits.clamp(kvals.ki * int_error) / kvals.ki;
+        }
+        (Some(AntiWindup::BackCalculation(tracking_gain)), Some(saturation)) if kvals.ki != 0.0 => {
+            let unlimited =
+                saturation.saturated(kvals.evaluate(step.error, int_error, step.drv_error));
+            let excess = saturation.limits.clamp(unlimited) - unlimited;
+            int_error += tracking_gain * excess * step.delta_time / kvals.ki;
+        }
+        (Some(AntiWindup::ConditionalIntegration), Some(saturation)) => {
+            let unlimited = saturation.saturated(kvals.evaluate(
+                step.error,
+                step.prev_int_error,
+                step.drv_error,
+            ));
+            let push = kvals.ki * step.int_error_addend;
+            if (unlimited > saturation.limits.max && push > 0.0)
+                || (unlimited < saturation.limits.min && push < 0.0)
+            {
+                int_error = step.prev_int_error;
+            }
+        }
+        _ => (),
+    }
+    (
+        int_error,
+        kvals.evaluate(step.error, int_error, step.drv_error),
+    )
+}
 pub use command_pid::CommandPID;
 mod command_pid {
     use super::*;
@@ -96,28 +409,214 @@ mod command_pid {
     }
     ///Automatically integrates the command variable of a PID controller based on the position
     ///derivative of a [`Command`]. Designed to make it easier to use a standard DC motor and an encoder
-    ///as a de facto servo.
-    pub struct CommandPID<G: Getter<State, E> + ?Sized, E: Copy + Debug> {
+    ///as a de facto servo. The k-values can come from any [`PIDKValueProvider`], so gains that
+    ///change at runtime such as those from [`SpeedScheduledPIDKValues`](super::SpeedScheduledPIDKValues)
+    ///can be used in place of a fixed [`PositionDerivativeDependentPIDKValues`].
+    pub struct CommandPID<
+        G: Getter<State, E> + ?Sized,
+        E: Copy + Debug,
+        K: PIDKValueProvider = PositionDerivativeDependentPIDKValues,
+    > {
         settable_data: SettableData<Command, E>,
         input: Reference<G>,
         command: Command,
-        kvals: PositionDerivativeDependentPIDKValues,
+        kvals: K,
         update_state: Result<Option<Update0>, Error<E>>,
+        //Indexed by position derivative: position, velocity, acceleration.
+        output_limits: [Option<OutputLimits>; 3],
+        backlash: Option<f32>,
+        //The last direction of motion, true meaning positive, and the position at which the
+        //mechanism last reversed into it.
+        backlash_reversal: Option<(bool, f32)>,
+        in_backlash: bool,
+        anti_windup: Option<AntiWindup>,
     }
-    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> CommandPID<G, E> {
+    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider> CommandPID<G, E, K> {
         ///Constructor for `CommandPID`.
-        pub const fn new(
-            input: Reference<G>,
-            command: Command,
-            kvalues: PositionDerivativeDependentPIDKValues,
-        ) -> Self {
+        pub const fn new(input: Reference<G>, command: Command, kvalues: K) -> Self {
             Self {
                 settable_data: SettableData::new(),
                 input: input,
                 command: command,
                 kvals: kvalues,
                 update_state: Ok(None),
+                output_limits: [None; 3],
+                backlash: None,
+                backlash_reversal: None,
+                in_backlash: false,
+                anti_windup: None,
+            }
+        }
+        ///Set how the integral of the error is kept from winding up, or turn anti-windup off with
+        ///`None`. It is off by default. [`BackCalculation`](AntiWindup::BackCalculation) and
+        ///[`ConditionalIntegration`](AntiWindup::ConditionalIntegration) use the
+        ///[output limits](CommandPID::set_output_limits) of the current command, so for velocity
+        ///and acceleration commands they act when the integrated output saturates.
+        pub fn set_anti_windup(&mut self, anti_windup: Option<AntiWindup>) {
+            self.anti_windup = anti_windup;
+        }
+        ///Get how the integral of the error is kept from winding up.
+        pub fn get_anti_windup(&self) -> Option<AntiWindup> {
+            self.anti_windup
+        }
+        //What the anti-windup should consider to saturate given the state from the last update.
+        //The velocity output is output_int + (update_0.output + output) / 2 * delta_time, and the
+        //acceleration output integrates that again in the same way.
+        fn anti_windup_saturation(
+            &self,
+            update_0: &Update0,
+            delta_time: f32,
+        ) -> Option<Saturation> {
+            let position_derivative: PositionDerivative = self.command.into();
+            let limits = self.get_output_limits(position_derivative)?;
+            let (output_int, output_int_int) = match &update_0.maybe_update_1 {
+                Some(update_1) => (update_1.output_int, update_1.output_int_int),
+                None => (0.0, None),
+            };
+            let output_int_base = output_int + update_0.output / 2.0 * delta_time;
+            match position_derivative {
+                PositionDerivative::Position => Some(Saturation::direct(limits)),
+                PositionDerivative::Velocity => Some(Saturation {
+                    limits: limits,
+                    base: output_int_base,
+                    scale: delta_time / 2.0,
+                }),
+                //The double integral only starts once there is a previous integral.
+                PositionDerivative::Acceleration => {
+                    update_0.maybe_update_1.as_ref()?;
+                    Some(Saturation {
+                        limits: limits,
+                        base: output_int_int.unwrap_or(0.0)
+                            + (output_int + output_int_base) / 2.0 * delta_time,
+                        scale: delta_time * delta_time / 4.0,
+                    })
+                }
+            }
+        }
+        ///Set the width of the backlash band in millimeters, or `None` to disable backlash
+        ///handling. After the mechanism reverses direction, while it is within this distance of
+        ///where it reversed, the gears are assumed not to be engaged. The error is then not
+        ///integrated and its derivative is treated as zero so that the slack does not wind up the
+        ///integral or cause a derivative kick.
+        pub fn set_backlash(&mut self, backlash: Option<f32>) {
+            self.backlash = backlash;
+            self.backlash_reversal = None;
+            self.in_backlash = false;
+        }
+        ///Get the width of the backlash band in millimeters.
+        pub fn get_backlash(&self) -> Option<f32> {
+            self.backlash
+        }
+        ///Returns true if the mechanism is currently within the backlash band after a reversal.
+        pub fn is_in_backlash(&self) -> bool {
+            self.in_backlash
+        }
+        fn update_backlash(&mut self, state: &State) {
+            let backlash = match self.backlash {
+                Some(backlash) => backlash,
+                None => return,
+            };
+            if state.velocity != 0.0 {
+                let direction = state.velocity > 0.0;
+                match self.backlash_reversal {
+                    Some((last_direction, _)) if last_direction == direction => (),
+                    Some(_) => self.backlash_reversal = Some((direction, state.position)),
+                    //The first direction seen is not a reversal, so the reversal point is put a
+                    //full backlash behind the mechanism in the direction it is moving.
+                    None => {
+                        let reversal_position = if direction {
+                            state.position - backlash
+                        } else {
+                            state.position + backlash
+                        };
+                        self.backlash_reversal = Some((direction, reversal_position));
+                    }
+                }
+            }
+            self.in_backlash = match self.backlash_reversal {
+                Some((_, reversal_position)) => {
+                    (state.position - reversal_position).abs() < backlash
+                }
+                None => false,
+            };
+        }
+        ///Limit the output while following commands of a given position derivative, or remove the
+        ///limits with `None`. Each position derivative has its own limits since, for example, a
+        ///lower cap may be wanted when following velocity commands than position commands. When
+        ///following velocity or acceleration commands, the integrated output is limited so that it
+        ///does not wind up past the limits.
+        pub fn set_output_limits(
+            &mut self,
+            position_derivative: PositionDerivative,
+            limits: Option<OutputLimits>,
+        ) {
+            self.output_limits[Self::limit_index(position_derivative)] = limits;
+        }
+        ///Get the output limits used while following commands of a given position derivative.
+        pub fn get_output_limits(
+            &self,
+            position_derivative: PositionDerivative,
+        ) -> Option<OutputLimits> {
+            self.output_limits[Self::limit_index(position_derivative)]
+        }
+        #[inline]
+        fn limit_index(position_derivative: PositionDerivative) -> usize {
+            match position_derivative {
+                PositionDerivative::Position => 0,
+                PositionDerivative::Velocity => 1,
+                PositionDerivative::Acceleration => 2,
+            }
+        }
+        fn apply_output_limits(&mut self) {
+            let position_derivative: PositionDerivative = self.command.into();
+            let limits = match self.get_output_limits(position_derivative) {
+                Some(limits) => limits,
+                None => return,
+            };
+            let update_0 = match &mut self.update_state {
+                Ok(Some(update_0)) => update_0,
+                _ => return,
+            };
+            match position_derivative {
+                PositionDerivative::Position => update_0.output = limits.clamp(update_0.output),
+                PositionDerivative::Velocity => {
+                    if let Some(update_1) = &mut update_0.maybe_update_1 {
+                        update_1.output_int = limits.clamp(update_1.output_int);
+                    }
+                }
+                PositionDerivative::Acceleration => {
+                    if let Some(update_1) = &mut update_0.maybe_update_1 {
+                        if let Some(output_int_int) = &mut update_1.output_int_int {
+                            *output_int_int = limits.clamp(*output_int_int);
+                        }
+                    }
+                }
+            }
+        }
+        ///Get the k-values currently in use.
+        pub fn get_k_values(&self) -> &K {
+            &self.kvals
+        }
+        ///Change the k-values without a bump in the output. The stored integral of the error is
+        ///rescaled so that the integral term for the current command stays the same under the new
+        ///integral gain. If the new integral gain is zero, the stored integral is left alone since
+        ///it no longer contributes, and if the old one was zero, the integral starts over from zero.
+        ///The proportional and derivative terms still change immediately.
+        pub fn set_k_values(&mut self, kvals: K) {
+            let position_derivative: PositionDerivative = self.command.into();
+            if let Ok(Some(Update0 {
+                error,
+                maybe_update_1: Some(update_1),
+                ..
+            })) = &mut self.update_state
+            {
+                let old_ki = self.kvals.get_k_values(position_derivative, *error).ki;
+                let new_ki = kvals.get_k_values(position_derivative, *error).ki;
+                if new_ki != 0.0 {
+                    update_1.error_int *= old_ki / new_ki;
+                }
             }
+            self.kvals = kvals;
         }
         ///Clear cached data for calculating integral and derivative. After this is called, the PID
         ///controller will use the next few updates to rebuild its cache in the same way as it does
@@ -128,7 +627,9 @@ mod command_pid {
             self.update_state = Ok(None);
         }
     }
-    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Settable<Command, E> for CommandPID<G, E> {
+    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider> Settable<Command, E>
+        for CommandPID<G, E, K>
+    {
         fn get_settable_data_ref(&self) -> &SettableData<Command, E> {
             &self.settable_data
         }
@@ -143,7 +644,9 @@ mod command_pid {
             Ok(())
         }
     }
-    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for CommandPID<G, E> {
+    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider> Getter<f32, E>
+        for CommandPID<G, E, K>
+    {
         fn get(&self) -> Output<f32, E> {
             match &self.update_state {
                 Err(error) => Err(*error),
@@ -171,7 +674,9 @@ mod command_pid {
             }
         }
     }
-    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E> for CommandPID<G, E> {
+    impl<G: Getter<State, E> + ?Sized, E: Copy + Debug, K: PIDKValueProvider> Updatable<E>
+        for CommandPID<G, E, K>
+    {
         fn update(&mut self) -> NothingOrError<E> {
             self.update_following_data()?;
             let raw_get = self.input.borrow().get();
@@ -186,6 +691,7 @@ mod command_pid {
                     return Err(error);
                 }
             };
+            self.update_backlash(&datum_state.value);
             let error = f32::from(self.command)
                 - f32::from(datum_state.value.get_value(self.command.into()));
             match &self.update_state {
@@ -200,15 +706,27 @@ mod command_pid {
                 }
                 Ok(Some(update_0)) => {
                     let delta_time = f32::from(Quantity::from(datum_state.time - update_0.time));
-                    let error_drv = (error - update_0.error) / delta_time;
-                    let error_int_addend = (update_0.error + error) / 2.0 * delta_time;
+                    let (error_drv, error_int_addend) = if self.in_backlash {
+                        (0.0, 0.0)
+                    } else {
+                        (
+                            (error - update_0.error) / delta_time,
+                            (update_0.error + error) / 2.0 * delta_time,
+                        )
+                    };
                     match &update_0.maybe_update_1 {
                         None => {
-                            let output = self.kvals.evaluate(
-                                self.command.into(),
-                                error,
-                                error_int_addend,
-                                error_drv,
+                            let (error_int, output) = integrate_with_anti_windup(
+                                self.anti_windup,
+                                self.anti_windup_saturation(update_0, delta_time),
+                                PIDStep {
+                                    kvals: self.kvals.get_k_values(self.command.into(), error),
+                                    error: error,
+                                    prev_int_error: 0.0,
+                                    int_error_addend: error_int_addend,
+                                    drv_error: error_drv,
+                                    delta_time: delta_time,
+                                },
                             );
                             let output_int = (update_0.output + output) / 2.0 * delta_time;
                             self.update_state = Ok(Some(Update0 {
@@ -217,18 +735,23 @@ mod command_pid {
                                 error: error,
                                 maybe_update_1: Some(Update1 {
                                     output_int: output_int,
-                                    error_int: error_int_addend,
+                                    error_int: error_int,
                                     output_int_int: None,
                                 }),
                             }));
                         }
                         Some(update_1) => {
-                            let error_int = update_1.error_int + error_int_addend;
-                            let output = self.kvals.evaluate(
-                                self.command.into(),
-                                error,
-                                error_int,
-                                error_drv,
+                            let (error_int, output) = integrate_with_anti_windup(
+                                self.anti_windup,
+                                self.anti_windup_saturation(update_0, delta_time),
+                                PIDStep {
+                                    kvals: self.kvals.get_k_values(self.command.into(), error),
+                                    error: error,
+                                    prev_int_error: update_1.error_int,
+                                    int_error_addend: error_int_addend,
+                                    drv_error: error_drv,
+                                    delta_time: delta_time,
+                                },
                             );
                             let output_int =
                                 update_1.output_int + (update_0.output + output) / 2.0 * delta_time;
@@ -266,10 +789,72 @@ mod command_pid {
                     }
                 }
             }
+            self.apply_output_limits();
             Ok(())
         }
     }
 }
+///A [`PIDKValueProvider`] that linearly interpolates between a low-speed and a high-speed set of
+///k-values based on the absolute value of a velocity getter. At or below `low_speed`, the low-speed
+///k-values are used, and at or above `high_speed`, the high-speed k-values are used. If the
+///velocity getter returns `Ok(None)` or an error, the low-speed k-values are used.
+pub struct SpeedScheduledPIDKValues<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    velocity: Reference<G>,
+    low_speed_k_values: PositionDerivativeDependentPIDKValues,
+    high_speed_k_values: PositionDerivativeDependentPIDKValues,
+    low_speed: f32,
+    high_speed: f32,
+    phantom_e: PhantomData<E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> SpeedScheduledPIDKValues<G, E> {
+    ///Constructor for [`SpeedScheduledPIDKValues`]. `low_speed` should be less than `high_speed`.
+    pub const fn new(
+        velocity: Reference<G>,
+        low_speed_k_values: PositionDerivativeDependentPIDKValues,
+        high_speed_k_values: PositionDerivativeDependentPIDKValues,
+        low_speed: f32,
+        high_speed: f32,
+    ) -> Self {
+        Self {
+            velocity: velocity,
+            low_speed_k_values: low_speed_k_values,
+            high_speed_k_values: high_speed_k_values,
+            low_speed: low_speed,
+            high_speed: high_speed,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get the interpolation factor, 0 meaning entirely low-speed k-values and 1 meaning entirely
+    ///high-speed k-values.
+    fn factor(&self) -> f32 {
+        let speed = match self.velocity.borrow().get() {
+            Ok(Some(velocity)) => velocity.value.abs(),
+            Ok(None) | Err(_) => return 0.0,
+        };
+        if speed <= self.low_speed {
+            0.0
+        } else if speed >= self.high_speed {
+            1.0
+        } else {
+            (speed - self.low_speed) / (self.high_speed - self.low_speed)
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> PIDKValueProvider
+    for SpeedScheduledPIDKValues<G, E>
+{
+    ///The k-values do not depend on the error.
+    fn get_k_values(&self, position_derivative: PositionDerivative, _error: f32) -> PIDKValues {
+        let low = self.low_speed_k_values.get_k_values(position_derivative);
+        let high = self.high_speed_k_values.get_k_values(position_derivative);
+        let factor = self.factor();
+        PIDKValues::new(
+            low.kp + (high.kp - low.kp) * factor,
+            low.ki + (high.ki - low.ki) * factor,
+            low.kd + (high.kd - low.kd) * factor,
+        )
+    }
+}
 ///An Exponentially Weighted Moving Average stream for use with the stream system. See <https://www.itl.nist.gov/div898/handbook/pmc/section3/pmc324.htm> for more information. Because a standard EWMA requires that new data always arrive at the same interval, this implementation uses λ=1-(1-`smoothing_constant`)^Δt instead of the usual weighting factor.
 #[cfg(feature = "internal_enhanced_float")]
 pub struct EWMAStream<T: Clone + Add<Output = T>, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
@@ -399,6 +984,123 @@ impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for EWMAStre
         Ok(())
     }
 }
+///An [`EWMAStream`] whose smoothing constant adapts to the input. It keeps a running estimate of
+///the variance of the innovation, the difference between a new input value and the current
+///average. When a new innovation is small compared to the usual noise, the input is considered
+///steady and the smoothing constant approaches `min_smoothing_constant`, filtering heavily. As the
+///innovation grows to `change_threshold` standard deviations, the smoothing constant approaches
+///`max_smoothing_constant` so that real changes are tracked quickly. As in [`EWMAStream`], both
+///the average and the variance estimate use λ=1-(1-smoothing constant)^Δt to account for irregular
+///update intervals.
+#[cfg(feature = "internal_enhanced_float")]
+pub struct AdaptiveEWMAStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    min_smoothing_constant: f32,
+    max_smoothing_constant: f32,
+    variance_smoothing_constant: f32,
+    change_threshold: f32,
+    value: Output<f32, E>,
+    variance: f32,
+    smoothing_constant: f32,
+    update_time: Option<Time>,
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> AdaptiveEWMAStream<G, E> {
+    ///Constructor for [`AdaptiveEWMAStream`].
+    pub const fn new(
+        input: Reference<G>,
+        min_smoothing_constant: f32,
+        max_smoothing_constant: f32,
+        variance_smoothing_constant: f32,
+        change_threshold: f32,
+    ) -> Self {
+        Self {
+            input: input,
+            min_smoothing_constant: min_smoothing_constant,
+            max_smoothing_constant: max_smoothing_constant,
+            variance_smoothing_constant: variance_smoothing_constant,
+            change_threshold: change_threshold,
+            value: Ok(None),
+            variance: 0.0,
+            smoothing_constant: max_smoothing_constant,
+            update_time: None,
+        }
+    }
+    ///Get the smoothing constant used in the most recent update.
+    pub fn smoothing_constant(&self) -> f32 {
+        self.smoothing_constant
+    }
+    ///Get the current estimate of the innovation variance.
+    pub fn variance(&self) -> f32 {
+        self.variance
+    }
+    fn reset(&mut self) {
+        self.variance = 0.0;
+        self.smoothing_constant = self.max_smoothing_constant;
+        self.update_time = None;
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for AdaptiveEWMAStream<G, E> {
+    fn get(&self) -> Output<f32, E> {
+        self.value.clone()
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for AdaptiveEWMAStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        let output = self.input.borrow().get();
+        let output = match output {
+            Err(error) => {
+                self.value = Err(error);
+                self.reset();
+                return Err(error);
+            }
+            Ok(None) => {
+                match self.value {
+                    Err(_) => {
+                        self.value = Ok(None);
+                        self.reset();
+                    }
+                    Ok(_) => {}
+                }
+                return Ok(());
+            }
+            Ok(Some(some)) => some,
+        };
+        let (prev_value, prev_time) = match (&self.value, self.update_time) {
+            (Ok(Some(some)), Some(update_time)) => (some.value, update_time),
+            _ => {
+                self.value = Ok(Some(output));
+                self.update_time = Some(output.time);
+                return Ok(());
+            }
+        };
+        let delta_time = f32::from(Quantity::from(output.time - prev_time));
+        let innovation = output.value - prev_value;
+        let standard_deviation = sqrtf(self.variance);
+        //How many standard deviations the new value is from the average, scaled so that 1 is the
+        //change threshold.
+        let change = if standard_deviation > 0.0 {
+            innovation.abs() / (standard_deviation * self.change_threshold)
+        } else if innovation == 0.0 {
+            0.0
+        } else {
+            1.0
+        };
+        let change = if change > 1.0 { 1.0 } else { change };
+        self.smoothing_constant = self.min_smoothing_constant
+            + (self.max_smoothing_constant - self.min_smoothing_constant) * change;
+        let lambda = 1.0 - powf(1.0 - self.smoothing_constant, delta_time);
+        let value = prev_value * (1.0 - lambda) + output.value * lambda;
+        let variance_lambda = 1.0 - powf(1.0 - self.variance_smoothing_constant, delta_time);
+        self.variance =
+            self.variance * (1.0 - variance_lambda) + innovation * innovation * variance_lambda;
+        self.value = Ok(Some(Datum::new(output.time, value)));
+        self.update_time = Some(output.time);
+        Ok(())
+    }
+}
 ///A moving average stream for use with the stream system.
 #[cfg(feature = "alloc")]
 pub struct MovingAverageStream<T, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
@@ -549,3 +1251,938 @@ impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E>
         Ok(())
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct VisionGateHypothesis {
+    measurement: f32,
+    odometry: f32,
+}
+///Validates sporadic absolute measurements, such as pose or target estimates from a camera,
+///against the relative motion reported by odometry. Each new measurement is compared to the last
+///accepted one moved by the odometry change since then, and the difference is normalized by its
+///expected standard deviation, which grows with the distance traveled. Measurements farther than
+///`threshold` standard deviations away are rejected and cause this to return `Ok(None)`.
+///
+///This is a latch with multiple hypotheses: if `relatch_count` rejected measurements in a row all
+///agree with each other, the gate assumes that the latched measurement was the bad one and
+///switches to the new hypothesis. The first measurement is always accepted. Only measurements with
+///a new timestamp are considered, so this returns `Ok(None)` between measurements.
+pub struct VisionGate<GM: Getter<f32, E> + ?Sized, GO: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    measurement: Reference<GM>,
+    odometry: Reference<GO>,
+    threshold: f32,
+    measurement_std_dev: f32,
+    odometry_std_dev_per_millimeter: f32,
+    relatch_count: u8,
+    latched: Option<VisionGateHypothesis>,
+    candidate: Option<(VisionGateHypothesis, u8)>,
+    last_measurement_time: Option<Time>,
+    output: Output<f32, E>,
+}
+impl<GM: Getter<f32, E> + ?Sized, GO: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    VisionGate<GM, GO, E>
+{
+    ///Constructor for [`VisionGate`]. `measurement_std_dev` is the standard deviation of a single
+    ///measurement and `odometry_std_dev_per_millimeter` is how much the standard deviation of the
+    ///odometry's error grows per unit it travels.
+    pub const fn new(
+        measurement: Reference<GM>,
+        odometry: Reference<GO>,
+        threshold: f32,
+        measurement_std_dev: f32,
+        odometry_std_dev_per_millimeter: f32,
+        relatch_count: u8,
+    ) -> Self {
+        Self {
+            measurement: measurement,
+            odometry: odometry,
+            threshold: threshold,
+            measurement_std_dev: measurement_std_dev,
+            odometry_std_dev_per_millimeter: odometry_std_dev_per_millimeter,
+            relatch_count: relatch_count,
+            latched: None,
+            candidate: None,
+            last_measurement_time: None,
+            output: Ok(None),
+        }
+    }
+    ///Forget all accepted and candidate measurements. The next measurement will be accepted
+    ///unconditionally, even if its timestamp is not newer than the last one's.
+    pub fn reset(&mut self) {
+        self.latched = None;
+        self.candidate = None;
+        self.last_measurement_time = None;
+        self.output = Ok(None);
+    }
+    fn consistent(&self, with: &VisionGateHypothesis, new: &VisionGateHypothesis) -> bool {
+        let odometry_delta = new.odometry - with.odometry;
+        let innovation = new.measurement - (with.measurement + odometry_delta);
+        let odometry_std_dev = self.odometry_std_dev_per_millimeter * odometry_delta;
+        //Both measurements are noisy, so the measurement variance is counted twice.
+        let variance = 2.0 * self.measurement_std_dev * self.measurement_std_dev
+            + odometry_std_dev * odometry_std_dev;
+        //Comparing squares avoids needing a square root.
+        innovation * innovation <= self.threshold * self.threshold * variance
+    }
+}
+impl<GM: Getter<f32, E> + ?Sized, GO: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for VisionGate<GM, GO, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.output
+    }
+}
+impl<GM: Getter<f32, E> + ?Sized, GO: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for VisionGate<GM, GO, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.output = Ok(None);
+        let measurement = match self.measurement.borrow().get() {
+            Ok(Some(measurement)) => measurement,
+            Ok(None) => return Ok(()),
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        if let Some(last_measurement_time) = self.last_measurement_time {
+            if measurement.time <= last_measurement_time {
+                return Ok(());
+            }
+        }
+        let odometry = match self.odometry.borrow().get() {
+            Ok(Some(odometry)) => odometry,
+            Ok(None) => return Ok(()),
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        self.last_measurement_time = Some(measurement.time);
+        let new = VisionGateHypothesis {
+            measurement: measurement.value,
+            odometry: odometry.value,
+        };
+        let mut accept = match &self.latched {
+            None => true,
+            Some(latched) => self.consistent(latched, &new),
+        };
+        if !accept {
+            let count = match &self.candidate {
+                Some((candidate, count)) if self.consistent(candidate, &new) => count + 1,
+                _ => 1,
+            };
+            self.candidate = Some((new, count));
+            if count >= self.relatch_count {
+                accept = true;
+            }
+        }
+        if accept {
+            self.latched = Some(new);
+            self.candidate = None;
+            self.output = Ok(Some(measurement));
+        }
+        Ok(())
+    }
+}
+///Adds static and viscous friction compensation to a control output. `k_s` times the sign of the
+///velocity plus `k_v` times the velocity is added to the control output. While the magnitude of the
+///velocity is within `deadband`, the static term is left out so that the output does not chatter
+///around zero. If the velocity getter returns `Ok(None)`, the control output is returned unchanged.
+pub struct FrictionCompensator<
+    GC: Getter<f32, E> + ?Sized,
+    GV: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    control: Reference<GC>,
+    velocity: Reference<GV>,
+    k_s: f32,
+    k_v: f32,
+    deadband: f32,
+    phantom_e: PhantomData<E>,
+}
+impl<GC: Getter<f32, E> + ?Sized, GV: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    FrictionCompensator<GC, GV, E>
+{
+    ///Constructor for [`FrictionCompensator`].
+    pub const fn new(
+        control: Reference<GC>,
+        velocity: Reference<GV>,
+        k_s: f32,
+        k_v: f32,
+        deadband: f32,
+    ) -> Self {
+        Self {
+            control: control,
+            velocity: velocity,
+            k_s: k_s,
+            k_v: k_v,
+            deadband: deadband,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get the compensation that would be added to the control output at a given velocity.
+    pub fn compensation(&self, velocity: f32) -> f32 {
+        let static_term = if velocity.abs() <= self.deadband {
+            0.0
+        } else if velocity > 0.0 {
+            self.k_s
+        } else {
+            -self.k_s
+        };
+        static_term + self.k_v * velocity
+    }
+}
+impl<GC: Getter<f32, E> + ?Sized, GV: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for FrictionCompensator<GC, GV, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        let control = match self.control.borrow().get()? {
+            Some(control) => control,
+            None => return Ok(None),
+        };
+        let velocity = match self.velocity.borrow().get()? {
+            Some(velocity) => velocity,
+            None => return Ok(Some(control)),
+        };
+        let time = if control.time > velocity.time {
+            control.time
+        } else {
+            velocity.time
+        };
+        Ok(Some(Datum::new(
+            time,
+            control.value + self.compensation(velocity.value),
+        )))
+    }
+}
+impl<GC: Getter<f32, E> + ?Sized, GV: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for FrictionCompensator<GC, GV, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Watches how closely a mechanism follows a profiled target such as a
+///[`MotionProfile`](crate::MotionProfile). At each update, the target command is taken from a
+///[`History`] at the current time and compared to the measured state in the command's position
+///derivative. If the difference is greater than `threshold` for at least `duration`, the monitor
+///faults: it stops passing through the target and returns `safe_command` instead until
+///[`reset`](FollowingErrorMonitor::reset) is called. Use [`FollowingErrorFaultStream`] to get
+///whether it has faulted as a [`Getter<bool, E>`]. If an [event log](crate::events) is set, each
+///fault is logged.
+pub struct FollowingErrorMonitor<
+    H: History<Command, E> + ?Sized,
+    G: Getter<State, E> + ?Sized,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    target: Reference<H>,
+    state: Reference<G>,
+    time_getter: Reference<TG>,
+    threshold: f32,
+    duration: Time,
+    safe_command: Command,
+    exceeded_since: Option<Time>,
+    faulted: bool,
+    output: Output<Command, E>,
+    event_log: Option<Reference<dyn crate::events::EventSink>>,
+}
+impl<
+        H: History<Command, E> + ?Sized,
+        G: Getter<State, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > FollowingErrorMonitor<H, G, TG, E>
+{
+    ///Constructor for [`FollowingErrorMonitor`].
+    pub const fn new(
+        target: Reference<H>,
+        state: Reference<G>,
+        time_getter: Reference<TG>,
+        threshold: f32,
+        duration: Time,
+        safe_command: Command,
+    ) -> Self {
+        Self {
+            target: target,
+            state: state,
+            time_getter: time_getter,
+            threshold: threshold,
+            duration: duration,
+            safe_command: safe_command,
+            exceeded_since: None,
+            faulted: false,
+            output: Ok(None),
+            event_log: None,
+        }
+    }
+    ///Returns true if the following error has been too large for too long.
+    pub fn is_faulted(&self) -> bool {
+        self.faulted
+    }
+    ///Log faults to an event log.
+    pub fn set_event_log(&mut self, event_log: Reference<dyn crate::events::EventSink>) {
+        self.event_log = Some(event_log);
+    }
+    ///Stop logging events.
+    pub fn clear_event_log(&mut self) {
+        self.event_log = None;
+    }
+    ///Clear a fault and resume passing through the target.
+    pub fn reset(&mut self) {
+        self.exceeded_since = None;
+        self.faulted = false;
+        self.output = Ok(None);
+    }
+}
+impl<
+        H: History<Command, E> + ?Sized,
+        G: Getter<State, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Getter<Command, E> for FollowingErrorMonitor<H, G, TG, E>
+{
+    fn get(&self) -> Output<Command, E> {
+        self.output
+    }
+}
+impl<
+        H: History<Command, E> + ?Sized,
+        G: Getter<State, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Updatable<E> for FollowingErrorMonitor<H, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = match self.time_getter.borrow().get() {
+            Ok(time) => time,
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        if self.faulted {
+            self.output = Ok(Some(Datum::new(time, self.safe_command)));
+            return Ok(());
+        }
+        let target = match self.target.borrow().get(time) {
+            Some(target) => target,
+            None => {
+                self.exceeded_since = None;
+                self.output = Ok(None);
+                return Ok(());
+            }
+        };
+        let state = match self.state.borrow().get() {
+            Ok(Some(state)) => state,
+            Ok(None) => {
+                self.output = Ok(Some(target));
+                return Ok(());
+            }
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        let position_derivative = PositionDerivative::from(target.value);
+        let following_error =
+            f32::from(target.value) - state.value.get_value(position_derivative).value;
+        if following_error.abs() > self.threshold {
+            let exceeded_since = match self.exceeded_since {
+                Some(exceeded_since) => exceeded_since,
+                None => {
+                    self.exceeded_since = Some(time);
+                    time
+                }
+            };
+            if time - exceeded_since >= self.duration {
+                self.faulted = true;
+                if let Some(event_log) = &self.event_log {
+                    event_log.borrow_mut().push(crate::events::Event::new(
+                        time,
+                        crate::events::Severity::Error,
+                        crate::events::FOLLOWING_ERROR_FAULT,
+                        following_error,
+                    ));
+                }
+                self.output = Ok(Some(Datum::new(time, self.safe_command)));
+                return Ok(());
+            }
+        } else {
+            self.exceeded_since = None;
+        }
+        self.output = Ok(Some(target));
+        Ok(())
+    }
+}
+///Reports whether a [`FollowingErrorMonitor`] has faulted. The timestamp is that of the monitor's
+///output, and this returns `Ok(None)` if the monitor has no output.
+pub struct FollowingErrorFaultStream<
+    H: History<Command, E> + ?Sized,
+    G: Getter<State, E> + ?Sized,
+    TG: TimeGetter<E> + ?Sized,
+    E: Copy + Debug,
+> {
+    monitor: Reference<FollowingErrorMonitor<H, G, TG, E>>,
+}
+impl<
+        H: History<Command, E> + ?Sized,
+        G: Getter<State, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > FollowingErrorFaultStream<H, G, TG, E>
+{
+    ///Constructor for [`FollowingErrorFaultStream`].
+    pub const fn new(monitor: Reference<FollowingErrorMonitor<H, G, TG, E>>) -> Self {
+        Self { monitor: monitor }
+    }
+}
+impl<
+        H: History<Command, E> + ?Sized,
+        G: Getter<State, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Getter<bool, E> for FollowingErrorFaultStream<H, G, TG, E>
+{
+    fn get(&self) -> Output<bool, E> {
+        let monitor = self.monitor.borrow();
+        match monitor.get() {
+            Ok(Some(datum)) => Ok(Some(Datum::new(datum.time, monitor.is_faulted()))),
+            _ => Ok(None),
+        }
+    }
+}
+impl<
+        H: History<Command, E> + ?Sized,
+        G: Getter<State, E> + ?Sized,
+        TG: TimeGetter<E> + ?Sized,
+        E: Copy + Debug,
+    > Updatable<E> for FollowingErrorFaultStream<H, G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Feedforward for a DC motor described by a [`MotorModel`]. Given a desired torque and the current
+///or desired velocity, returns the voltage to apply. If the velocity input returns `Ok(None)`, the
+///velocity is assumed to be zero.
+pub struct MotorFeedforward<
+    GT: Getter<f32, E> + ?Sized,
+    GV: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    torque: Reference<GT>,
+    velocity: Reference<GV>,
+    model: MotorModel,
+    phantom_e: PhantomData<E>,
+}
+impl<GT: Getter<f32, E> + ?Sized, GV: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    MotorFeedforward<GT, GV, E>
+{
+    ///Constructor for [`MotorFeedforward`].
+    pub const fn new(torque: Reference<GT>, velocity: Reference<GV>, model: MotorModel) -> Self {
+        Self {
+            torque: torque,
+            velocity: velocity,
+            model: model,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<GT: Getter<f32, E> + ?Sized, GV: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for MotorFeedforward<GT, GV, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        let torque = match self.torque.borrow().get()? {
+            Some(torque) => torque,
+            None => return Ok(None),
+        };
+        let (time, velocity) = match self.velocity.borrow().get()? {
+            Some(velocity) if velocity.time > torque.time => (velocity.time, velocity.value),
+            Some(velocity) => (torque.time, velocity.value),
+            None => (torque.time, 0.0),
+        };
+        Ok(Some(Datum::new(
+            time,
+            self.model.voltage(torque.value, velocity),
+        )))
+    }
+}
+impl<GT: Getter<f32, E> + ?Sized, GV: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for MotorFeedforward<GT, GV, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Traction control for a driven wheel. The slip ratio is the fraction of the wheel's surface speed
+///that is not matched by the reference speed, which usually comes from an undriven wheel or an
+///integrated accelerometer: `(|wheel| - |reference|) / |wheel|`. While the slip ratio exceeds
+///`threshold`, the drive output is scaled down by `reduction_rate` per second until it reaches 0.
+///Otherwise, the scale is brought back up by `recovery_rate` per second until it reaches 1, so
+///power is reapplied gradually. Slip is not checked while the wheel is slower than `min_speed` since
+///the ratio is meaningless near a standstill. Time is taken from the drive input's timestamps. If
+///either speed getter returns `Ok(None)`, the wheel is assumed not to be slipping.
+pub struct SlipLimiter<
+    GD: Getter<f32, E> + ?Sized,
+    GW: Getter<f32, E> + ?Sized,
+    GR: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    drive: Reference<GD>,
+    wheel_speed: Reference<GW>,
+    reference_speed: Reference<GR>,
+    threshold: f32,
+    min_speed: f32,
+    reduction_rate: f32,
+    recovery_rate: f32,
+    scale: f32,
+    slip_ratio: f32,
+    last_time: Option<Time>,
+    output: Output<f32, E>,
+}
+impl<
+        GD: Getter<f32, E> + ?Sized,
+        GW: Getter<f32, E> + ?Sized,
+        GR: Getter<f32, E> + ?Sized,
+        E: Copy + Debug,
+    > SlipLimiter<GD, GW, GR, E>
+{
+    ///Constructor for [`SlipLimiter`].
+    pub const fn new(
+        drive: Reference<GD>,
+        wheel_speed: Reference<GW>,
+        reference_speed: Reference<GR>,
+        threshold: f32,
+        min_speed: f32,
+        reduction_rate: f32,
+        recovery_rate: f32,
+    ) -> Self {
+        Self {
+            drive: drive,
+            wheel_speed: wheel_speed,
+            reference_speed: reference_speed,
+            threshold: threshold,
+            min_speed: min_speed,
+            reduction_rate: reduction_rate,
+            recovery_rate: recovery_rate,
+            scale: 1.0,
+            slip_ratio: 0.0,
+            last_time: None,
+            output: Ok(None),
+        }
+    }
+    ///Get the factor from 0 to 1 the drive output is currently being multiplied by.
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+    ///Get the slip ratio calculated in the last update.
+    pub fn get_slip_ratio(&self) -> f32 {
+        self.slip_ratio
+    }
+    ///Returns true if the slip ratio exceeded the threshold in the last update.
+    pub fn is_slipping(&self) -> bool {
+        self.slip_ratio > self.threshold
+    }
+    ///Stop limiting the drive output immediately.
+    pub fn reset(&mut self) {
+        self.scale = 1.0;
+        self.slip_ratio = 0.0;
+        self.last_time = None;
+    }
+    fn calculate_slip_ratio(&self) -> Result<f32, Error<E>> {
+        let wheel = match self.wheel_speed.borrow().get()? {
+            Some(wheel) => wheel.value.abs(),
+            None => return Ok(0.0),
+        };
+        let reference = match self.reference_speed.borrow().get()? {
+            Some(reference) => reference.value.abs(),
+            None => return Ok(0.0),
+        };
+        if wheel < self.min_speed || wheel <= reference {
+            return Ok(0.0);
+        }
+        Ok((wheel - reference) / wheel)
+    }
+}
+impl<
+        GD: Getter<f32, E> + ?Sized,
+        GW: Getter<f32, E> + ?Sized,
+        GR: Getter<f32, E> + ?Sized,
+        E: Copy + Debug,
+    > Getter<f32, E> for SlipLimiter<GD, GW, GR, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.output
+    }
+}
+impl<
+        GD: Getter<f32, E> + ?Sized,
+        GW: Getter<f32, E> + ?Sized,
+        GR: Getter<f32, E> + ?Sized,
+        E: Copy + Debug,
+    > Updatable<E> for SlipLimiter<GD, GW, GR, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let drive = match self.drive.borrow().get() {
+            Ok(Some(drive)) => drive,
+            Ok(None) => {
+                self.output = Ok(None);
+                return Ok(());
+            }
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        self.slip_ratio = match self.calculate_slip_ratio() {
+            Ok(slip_ratio) => slip_ratio,
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        if let Some(last_time) = self.last_time {
+            let delta_time = f32::from(Quantity::from(drive.time - last_time));
+            if self.is_slipping() {
+                self.scale = (self.scale - self.reduction_rate * delta_time).max(0.0);
+            } else {
+                self.scale = (self.scale + self.recovery_rate * delta_time).min(1.0);
+            }
+        }
+        self.last_time = Some(drive.time);
+        self.output = Ok(Some(Datum::new(drive.time, drive.value * self.scale)));
+        Ok(())
+    }
+}
+///A teleop assist that keeps a drivetrain pointed the same way while the driver is not turning it.
+///While the magnitude of the driver's rotation input is within `deadband`, the heading from the gyro
+///when the driver let go is held with a PID controller, and its output replaces the rotation input
+///so that bumps and uneven wheels do not turn the robot. Once the driver turns again, the rotation
+///input is returned unchanged and the hold is released. Headings are in radians and the error is
+///taken the short way around the circle. If the heading getter returns `Ok(None)`, the rotation
+///input is returned unchanged.
+pub struct HeadingHold<GR: Getter<f32, E> + ?Sized, GH: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    rotation: Reference<GR>,
+    heading: Reference<GH>,
+    kvals: PIDKValues,
+    deadband: f32,
+    target: Option<f32>,
+    prev_error: Option<Datum<f32>>,
+    int_error: f32,
+    output: Output<f32, E>,
+}
+impl<GR: Getter<f32, E> + ?Sized, GH: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    HeadingHold<GR, GH, E>
+{
+    ///Constructor for [`HeadingHold`].
+    pub const fn new(
+        rotation: Reference<GR>,
+        heading: Reference<GH>,
+        kvals: PIDKValues,
+        deadband: f32,
+    ) -> Self {
+        Self {
+            rotation: rotation,
+            heading: heading,
+            kvals: kvals,
+            deadband: deadband,
+            target: None,
+            prev_error: None,
+            int_error: 0.0,
+            output: Ok(None),
+        }
+    }
+    ///Get the heading being held, or `None` if the driver is turning.
+    pub fn get_target(&self) -> Option<f32> {
+        self.target
+    }
+    ///Release the hold. If the driver is still not turning, the heading at the next update will be
+    ///held.
+    pub fn release(&mut self) {
+        self.target = None;
+        self.prev_error = None;
+        self.int_error = 0.0;
+    }
+}
+impl<GR: Getter<f32, E> + ?Sized, GH: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for HeadingHold<GR, GH, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.output
+    }
+}
+impl<GR: Getter<f32, E> + ?Sized, GH: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for HeadingHold<GR, GH, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let rotation = self.rotation.borrow().get();
+        let rotation = match rotation {
+            Ok(Some(rotation)) => rotation,
+            Ok(None) => {
+                self.release();
+                self.output = Ok(None);
+                return Ok(());
+            }
+            Err(error) => {
+                self.release();
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        if rotation.value.abs() > self.deadband {
+            self.release();
+            self.output = Ok(Some(rotation));
+            return Ok(());
+        }
+        let heading = self.heading.borrow().get();
+        let heading = match heading {
+            Ok(Some(heading)) => heading,
+            Ok(None) => {
+                self.release();
+                self.output = Ok(Some(rotation));
+                return Ok(());
+            }
+            Err(error) => {
+                self.release();
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        let target = *self.target.get_or_insert(heading.value);
+        let error = angles::shortest_arc(heading.value, target);
+        let [int_error_addend, drv_error] = match &self.prev_error {
+            Some(prev_error) if heading.time > prev_error.time => {
+                let delta_time = f32::from(Quantity::from(heading.time - prev_error.time));
+                let drv_error = (error - prev_error.value) / delta_time;
+                let int_error_addend = delta_time * (prev_error.value + error) / 2.0;
+                [int_error_addend, drv_error]
+            }
+            _ => [0.0, 0.0],
+        };
+        self.int_error += int_error_addend;
+        let time = if heading.time > rotation.time {
+            heading.time
+        } else {
+            rotation.time
+        };
+        self.output = Ok(Some(Datum::new(
+            time,
+            self.kvals.evaluate(error, self.int_error, drv_error),
+        )));
+        self.prev_error = Some(Datum::new(heading.time, error));
+        Ok(())
+    }
+}
+///Combines a smooth but relative position, usually from an incremental encoder, with a noisy but
+///absolute one, such as from a potentiometer or absolute encoder. The output is the incremental
+///position plus an offset that is seeded from the absolute sensor at the first update where both
+///are available. Afterward, the absolute sensor is only used to correct drift: if the output and
+///the absolute position disagree by more than `tolerance` for `confirm_count` updates in a row,
+///the offset is reseeded. Requiring several disagreeing updates keeps a single noisy absolute
+///reading from making the output jump. Returns `Ok(None)` until it has been seeded.
+pub struct SeededPosition<GI: Getter<f32, E> + ?Sized, GA: Getter<f32, E> + ?Sized, E: Copy + Debug>
+{
+    incremental: Reference<GI>,
+    absolute: Reference<GA>,
+    tolerance: f32,
+    confirm_count: u8,
+    offset: Option<f32>,
+    disagreement_count: u8,
+    output: Output<f32, E>,
+}
+impl<GI: Getter<f32, E> + ?Sized, GA: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    SeededPosition<GI, GA, E>
+{
+    ///Constructor for [`SeededPosition`].
+    pub const fn new(
+        incremental: Reference<GI>,
+        absolute: Reference<GA>,
+        tolerance: f32,
+        confirm_count: u8,
+    ) -> Self {
+        Self {
+            incremental: incremental,
+            absolute: absolute,
+            tolerance: tolerance,
+            confirm_count: confirm_count,
+            offset: None,
+            disagreement_count: 0,
+            output: Ok(None),
+        }
+    }
+    ///Get the offset currently added to the incremental position, or `None` if it has not been
+    ///seeded yet.
+    pub fn get_offset(&self) -> Option<f32> {
+        self.offset
+    }
+    ///Forget the offset so that it is seeded again at the next update.
+    pub fn reseed(&mut self) {
+        self.offset = None;
+        self.disagreement_count = 0;
+    }
+}
+impl<GI: Getter<f32, E> + ?Sized, GA: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for SeededPosition<GI, GA, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.output
+    }
+}
+impl<GI: Getter<f32, E> + ?Sized, GA: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for SeededPosition<GI, GA, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let incremental = self.incremental.borrow().get();
+        let incremental = match incremental {
+            Ok(Some(incremental)) => incremental,
+            Ok(None) => {
+                self.output = Ok(None);
+                return Ok(());
+            }
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        let absolute = self.absolute.borrow().get();
+        let absolute = match absolute {
+            Ok(absolute) => absolute,
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        if let Some(absolute) = absolute {
+            match self.offset {
+                None => self.offset = Some(absolute.value - incremental.value),
+                Some(offset) => {
+                    if (incremental.value + offset - absolute.value).abs() > self.tolerance {
+                        self.disagreement_count = self.disagreement_count.saturating_add(1);
+                        if self.disagreement_count >= self.confirm_count {
+                            self.offset = Some(absolute.value - incremental.value);
+                            self.disagreement_count = 0;
+                        }
+                    } else {
+                        self.disagreement_count = 0;
+                    }
+                }
+            }
+        }
+        self.output = Ok(self
+            .offset
+            .map(|offset| Datum::new(incremental.time, incremental.value + offset)));
+        Ok(())
+    }
+}
+///A complementary filter fusing two measurements of the same thing: a `fast` one that responds
+///quickly but drifts, such as an angle integrated from a gyro, and a `slow` one that is noisy or
+///laggy but does not drift, such as a tilt angle from an accelerometer or an absolute encoder.
+///Changes in the fast input are passed straight through, while the output is pulled toward the
+///slow input with a time constant of `time_constant` seconds. Each update, the output becomes
+///α(previous output + change in fast input) + (1-α)(slow input), where α=τ/(τ+Δt). A larger
+///time constant trusts the fast input for longer. The output is seeded from the slow input, or
+///from the fast input if the slow one is not available yet. If either input returns `Ok(None)`,
+///the other is used alone.
+pub struct ComplementaryFilterStream<
+    GF: Getter<f32, E> + ?Sized,
+    GS: Getter<f32, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    fast: Reference<GF>,
+    slow: Reference<GS>,
+    time_constant: f32,
+    prev_fast: Option<f32>,
+    output: Output<f32, E>,
+}
+impl<GF: Getter<f32, E> + ?Sized, GS: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    ComplementaryFilterStream<GF, GS, E>
+{
+    ///Constructor for [`ComplementaryFilterStream`].
+    pub const fn new(fast: Reference<GF>, slow: Reference<GS>, time_constant: f32) -> Self {
+        Self {
+            fast: fast,
+            slow: slow,
+            time_constant: time_constant,
+            prev_fast: None,
+            output: Ok(None),
+        }
+    }
+    ///Get the time constant in seconds.
+    pub fn get_time_constant(&self) -> f32 {
+        self.time_constant
+    }
+    ///Set the time constant in seconds. This takes effect on the next update.
+    pub fn set_time_constant(&mut self, time_constant: f32) {
+        self.time_constant = time_constant;
+    }
+    ///Forget the output so that it is seeded again at the next update.
+    pub fn reset(&mut self) {
+        self.prev_fast = None;
+        self.output = Ok(None);
+    }
+}
+impl<GF: Getter<f32, E> + ?Sized, GS: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for ComplementaryFilterStream<GF, GS, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        self.output
+    }
+}
+impl<GF: Getter<f32, E> + ?Sized, GS: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ComplementaryFilterStream<GF, GS, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let fast = self.fast.borrow().get();
+        let fast = match fast {
+            Ok(fast) => fast,
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        let slow = self.slow.borrow().get();
+        let slow = match slow {
+            Ok(slow) => slow,
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        let prev_output = match self.output {
+            Ok(Some(prev_output)) => prev_output,
+            _ => {
+                self.output = Ok(slow.or(fast));
+                self.prev_fast = fast.map(|fast| fast.value);
+                return Ok(());
+            }
+        };
+        let mut predicted = prev_output;
+        if let Some(fast) = fast {
+            if let Some(prev_fast) = self.prev_fast {
+                predicted.value += fast.value - prev_fast;
+            }
+            predicted.time = predicted.time.max(fast.time);
+            self.prev_fast = Some(fast.value);
+        }
+        let slow = match slow {
+            Some(slow) => slow,
+            None => {
+                self.output = Ok(Some(predicted));
+                return Ok(());
+            }
+        };
+        let time = predicted.time.max(slow.time);
+        let delta_time = f32::from(Quantity::from(time - prev_output.time));
+        let alpha = if self.time_constant + delta_time > 0.0 {
+            self.time_constant / (self.time_constant + delta_time)
+        } else {
+            0.0
+        };
+        self.output = Ok(Some(Datum::new(
+            time,
+            alpha * predicted.value + (1.0 - alpha) * slow.value,
+        )));
+        Ok(())
+    }
+}
@@ -451,14 +451,8 @@ impl<G: Getter<f32, E>, E: Copy + Debug> Updatable<E> for FloatToQuantity<G, E>
 }
 impl<G: Getter<f32, E>, E: Copy + Debug> Getter<Quantity, E> for FloatToQuantity<G, E> {
     fn get(&self) -> Output<Quantity, E> {
-        match self.value {
-            Err(err) => Err(err),
-            Ok(None) => Ok(None),
-            Ok(Some(datum)) => Ok(Some(Datum::new(
-                datum.time,
-                Quantity::new(datum.value, self.unit),
-            ))),
-        }
+        self.value
+            .map_value(|value| Quantity::new(value, self.unit))
     }
 }
 ///Stream to convert a [`Quantity`] to a raw [`f32`].
@@ -483,11 +477,528 @@ impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for Quanti
 impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for QuantityToFloat<G, E> {
     fn update(&mut self) -> NothingOrError<E> {
         let gotten = self.input.borrow().get();
-        self.value = match gotten {
-            Err(error) => Err(error),
-            Ok(None) => Ok(None),
-            Ok(Some(datum)) => Ok(Some(Datum::new(datum.time, datum.value.value))),
+        self.value = gotten.map_value(|value| value.value);
+        Ok(())
+    }
+}
+///A stream extracting the position from a [`State`] getter as a [`Quantity`] in millimeters.
+pub struct PositionFromState<G: Getter<State, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Quantity, E>,
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> PositionFromState<G, E> {
+    ///Constructor for [`PositionFromState`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+        }
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E>
+    for PositionFromState<G, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        self.value
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E> for PositionFromState<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self
+            .input
+            .borrow()
+            .get()
+            .map_value(|state| state.get_position());
+        Ok(())
+    }
+}
+///A stream extracting the velocity from a [`State`] getter as a [`Quantity`] in millimeters per second.
+pub struct VelocityFromState<G: Getter<State, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Quantity, E>,
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> VelocityFromState<G, E> {
+    ///Constructor for [`VelocityFromState`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+        }
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E>
+    for VelocityFromState<G, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        self.value
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E> for VelocityFromState<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self
+            .input
+            .borrow()
+            .get()
+            .map_value(|state| state.get_velocity());
+        Ok(())
+    }
+}
+///A stream extracting the acceleration from a [`State`] getter as a [`Quantity`] in millimeters per second squared.
+pub struct AccelerationFromState<G: Getter<State, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Quantity, E>,
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> AccelerationFromState<G, E> {
+    ///Constructor for [`AccelerationFromState`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+        }
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E>
+    for AccelerationFromState<G, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        self.value
+    }
+}
+impl<G: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E> for AccelerationFromState<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self
+            .input
+            .borrow()
+            .get()
+            .map_value(|state| state.get_acceleration());
+        Ok(())
+    }
+}
+///A stream wrapping the output of an [`f32`] getter in a position [`Command`], keeping its
+///timestamp.
+pub struct PositionCommandStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Command, E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> PositionCommandStream<G, E> {
+    ///Constructor for [`PositionCommandStream`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<Command, E>
+    for PositionCommandStream<G, E>
+{
+    fn get(&self) -> Output<Command, E> {
+        self.value
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for PositionCommandStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self
+            .input
+            .borrow()
+            .get()
+            .map_value(|value| Command::new(PositionDerivative::Position, value));
+        Ok(())
+    }
+}
+///A stream wrapping the output of an [`f32`] getter in a velocity [`Command`], keeping its
+///timestamp.
+pub struct VelocityCommandStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Command, E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> VelocityCommandStream<G, E> {
+    ///Constructor for [`VelocityCommandStream`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<Command, E>
+    for VelocityCommandStream<G, E>
+{
+    fn get(&self) -> Output<Command, E> {
+        self.value
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for VelocityCommandStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self
+            .input
+            .borrow()
+            .get()
+            .map_value(|value| Command::new(PositionDerivative::Velocity, value));
+        Ok(())
+    }
+}
+///A stream wrapping the output of an [`f32`] getter in an acceleration [`Command`], keeping its
+///timestamp.
+pub struct AccelerationCommandStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Command, E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> AccelerationCommandStream<G, E> {
+    ///Constructor for [`AccelerationCommandStream`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            value: Ok(None),
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<Command, E>
+    for AccelerationCommandStream<G, E>
+{
+    fn get(&self) -> Output<Command, E> {
+        self.value
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for AccelerationCommandStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        self.value = self
+            .input
+            .borrow()
+            .get()
+            .map_value(|value| Command::new(PositionDerivative::Acceleration, value));
+        Ok(())
+    }
+}
+//Returns the newest of the timestamps, or None if any is more than max_skew older than it.
+fn zip_time(times: &[Time], max_skew: Option<Time>) -> Option<Time> {
+    let mut newest = times[0];
+    for time in times {
+        if *time > newest {
+            newest = *time;
+        }
+    }
+    if let Some(max_skew) = max_skew {
+        for time in times {
+            if newest - *time > max_skew {
+                return None;
+            }
+        }
+    }
+    Some(newest)
+}
+///A stream combining the outputs of two getters into a tuple so that they can be passed around as
+///one value. The timestamp is that of the newest input. If either input returns `Ok(None)`, this
+///does too. If `max_skew` is `Some`, this also returns `Ok(None)` when either input is older than
+///the other by more than it, so that only data from around the same time are combined.
+pub struct Zip2<A, B, GA: Getter<A, E> + ?Sized, GB: Getter<B, E> + ?Sized, E: Copy + Debug> {
+    first: Reference<GA>,
+    second: Reference<GB>,
+    max_skew: Option<Time>,
+    phantom_a: PhantomData<A>,
+    phantom_b: PhantomData<B>,
+    phantom_e: PhantomData<E>,
+}
+impl<A, B, GA: Getter<A, E> + ?Sized, GB: Getter<B, E> + ?Sized, E: Copy + Debug>
+    Zip2<A, B, GA, GB, E>
+{
+    ///Constructor for [`Zip2`].
+    pub const fn new(first: Reference<GA>, second: Reference<GB>, max_skew: Option<Time>) -> Self {
+        Self {
+            first: first,
+            second: second,
+            max_skew: max_skew,
+            phantom_a: PhantomData,
+            phantom_b: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<A, B, GA: Getter<A, E> + ?Sized, GB: Getter<B, E> + ?Sized, E: Copy + Debug> Getter<(A, B), E>
+    for Zip2<A, B, GA, GB, E>
+{
+    fn get(&self) -> Output<(A, B), E> {
+        let first = match self.first.borrow().get()? {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        let second = match self.second.borrow().get()? {
+            Some(second) => second,
+            None => return Ok(None),
+        };
+        let time = match zip_time(&[first.time, second.time], self.max_skew) {
+            Some(time) => time,
+            None => return Ok(None),
+        };
+        Ok(Some(Datum::new(time, (first.value, second.value))))
+    }
+}
+impl<A, B, GA: Getter<A, E> + ?Sized, GB: Getter<B, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for Zip2<A, B, GA, GB, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream combining the outputs of three getters into a tuple. This works the same way as
+///[`Zip2`].
+pub struct Zip3<
+    A,
+    B,
+    C,
+    GA: Getter<A, E> + ?Sized,
+    GB: Getter<B, E> + ?Sized,
+    GC: Getter<C, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    first: Reference<GA>,
+    second: Reference<GB>,
+    third: Reference<GC>,
+    max_skew: Option<Time>,
+    phantom_a: PhantomData<A>,
+    phantom_b: PhantomData<B>,
+    phantom_c: PhantomData<C>,
+    phantom_e: PhantomData<E>,
+}
+impl<
+        A,
+        B,
+        C,
+        GA: Getter<A, E> + ?Sized,
+        GB: Getter<B, E> + ?Sized,
+        GC: Getter<C, E> + ?Sized,
+        E: Copy + Debug,
+    > Zip3<A, B, C, GA, GB, GC, E>
+{
+    ///Constructor for [`Zip3`].
+    pub const fn new(
+        first: Reference<GA>,
+        second: Reference<GB>,
+        third: Reference<GC>,
+        max_skew: Option<Time>,
+    ) -> Self {
+        Self {
+            first: first,
+            second: second,
+            third: third,
+            max_skew: max_skew,
+            phantom_a: PhantomData,
+            phantom_b: PhantomData,
+            phantom_c: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<
+        A,
+        B,
+        C,
+        GA: Getter<A, E> + ?Sized,
+        GB: Getter<B, E> + ?Sized,
+        GC: Getter<C, E> + ?Sized,
+        E: Copy + Debug,
+    > Getter<(A, B, C), E> for Zip3<A, B, C, GA, GB, GC, E>
+{
+    fn get(&self) -> Output<(A, B, C), E> {
+        let first = match self.first.borrow().get()? {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        let second = match self.second.borrow().get()? {
+            Some(second) => second,
+            None => return Ok(None),
+        };
+        let third = match self.third.borrow().get()? {
+            Some(third) => third,
+            None => return Ok(None),
+        };
+        let time = match zip_time(&[first.time, second.time, third.time], self.max_skew) {
+            Some(time) => time,
+            None => return Ok(None),
+        };
+        Ok(Some(Datum::new(
+            time,
+            (first.value, second.value, third.value),
+        )))
+    }
+}
+impl<
+        A,
+        B,
+        C,
+        GA: Getter<A, E> + ?Sized,
+        GB: Getter<B, E> + ?Sized,
+        GC: Getter<C, E> + ?Sized,
+        E: Copy + Debug,
+    > Updatable<E> for Zip3<A, B, C, GA, GB, GC, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Holds a single sample from a batch for an [`Unbatcher`]. Use this as the input of the streams
+///that should process each sample. Its value is written by the [`Unbatcher`] and does not change
+///when this is updated.
+pub struct SampleSlot<T: Clone, E: Copy + Debug> {
+    value: Output<T, E>,
+}
+impl<T: Clone, E: Copy + Debug> SampleSlot<T, E> {
+    ///Constructor for [`SampleSlot`].
+    pub const fn new() -> Self {
+        Self { value: Ok(None) }
+    }
+}
+impl<T: Clone, E: Copy + Debug> Default for SampleSlot<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone, E: Copy + Debug> Getter<T, E> for SampleSlot<T, E> {
+    fn get(&self) -> Output<T, E> {
+        self.value.clone()
+    }
+}
+impl<T: Clone, E: Copy + Debug> Updatable<E> for SampleSlot<T, E> {
+    ///This does not need to be called.
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Flattens the batches from a [`BatchGetter`] into individual samples so that they can be processed
+///by ordinary streams. When updated, for each sample in its input's current batch, oldest first,
+///this writes the sample to a [`SampleSlot`] and updates each of the downstream
+///[`Updatable`]s in order. The streams processing the samples should use the slot as their input
+///and be passed to this as downstream updatables instead of being updated elsewhere. This way,
+///filters and integrators see every sample instead of only the latest one.
+pub struct Unbatcher<T: Clone, G: BatchGetter<T, E> + ?Sized, const N: usize, E: Copy + Debug> {
+    input: Reference<G>,
+    slot: Reference<SampleSlot<T, E>>,
+    downstream: [Reference<dyn Updatable<E>>; N],
+}
+impl<T: Clone, G: BatchGetter<T, E> + ?Sized, const N: usize, E: Copy + Debug>
+    Unbatcher<T, G, N, E>
+{
+    ///Constructor for [`Unbatcher`].
+    pub const fn new(
+        input: Reference<G>,
+        slot: Reference<SampleSlot<T, E>>,
+        downstream: [Reference<dyn Updatable<E>>; N],
+    ) -> Self {
+        Self {
+            input: input,
+            slot: slot,
+            downstream: downstream,
+        }
+    }
+}
+impl<T: Clone, G: BatchGetter<T, E> + ?Sized, const N: usize, E: Copy + Debug> Updatable<E>
+    for Unbatcher<T, G, N, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let batch_len = self.input.borrow().batch_len();
+        for i in 0..batch_len {
+            let sample = self.input.borrow().get_sample(i);
+            self.slot.borrow_mut().value = sample;
+            for updatable in &self.downstream {
+                updatable.borrow_mut().update()?;
+            }
+        }
+        Ok(())
+    }
+}
+///Approximates a continuous command from 0 to 1 on an actuator that can only be on or off, such as
+///a valve or relay, using time-proportioned output like slow PWM. Time is divided into periods of
+///length `period`, and the output is true for the input's fraction of each period, starting at the
+///beginning of the period. The input is only read at the start of each period so that the output
+///switches at most twice per period. Inputs outside of 0 to 1 are clamped. Returns `Ok(None)` if
+///the input does at the start of a period. `period` should be positive and several times longer
+///than the time between updates, since the output can only change when this is updated.
+pub struct DitherStream<G: Getter<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    time_getter: Reference<TG>,
+    period: Time,
+    period_start: Option<Time>,
+    duty: f32,
+    output: Output<bool, E>,
+}
+impl<G: Getter<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug>
+    DitherStream<G, TG, E>
+{
+    ///Constructor for [`DitherStream`].
+    pub const fn new(input: Reference<G>, time_getter: Reference<TG>, period: Time) -> Self {
+        Self {
+            input: input,
+            time_getter: time_getter,
+            period: period,
+            period_start: None,
+            duty: 0.0,
+            output: Ok(None),
+        }
+    }
+    ///Get the length of each period.
+    pub fn get_period(&self) -> Time {
+        self.period
+    }
+    ///Change the length of each period. This takes effect at the start of the next period.
+    pub fn set_period(&mut self, period: Time) {
+        self.period = period;
+    }
+    ///Get the fraction of the current period that the output is true for.
+    pub fn get_duty(&self) -> f32 {
+        self.duty
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Getter<bool, E>
+    for DitherStream<G, TG, E>
+{
+    fn get(&self) -> Output<bool, E> {
+        self.output
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, TG: TimeGetter<E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for DitherStream<G, TG, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let time = match self.time_getter.borrow().get() {
+            Ok(time) => time,
+            Err(error) => {
+                self.output = Err(error);
+                return Err(error);
+            }
+        };
+        let period_start = match self.period_start {
+            Some(period_start) if time >= period_start && time - period_start < self.period => {
+                period_start
+            }
+            _ => {
+                let input = self.input.borrow().get();
+                match input {
+                    Ok(Some(input)) => self.duty = input.value.clamp(0.0, 1.0),
+                    Ok(None) => {
+                        self.period_start = None;
+                        self.output = Ok(None);
+                        return Ok(());
+                    }
+                    Err(error) => {
+                        self.period_start = None;
+                        self.output = Err(error);
+                        return Err(error);
+                    }
+                }
+                //Periods stay aligned to the first one so that late updates do not stretch them.
+                let period_start = match self.period_start {
+                    Some(prev_start) if time >= prev_start && self.period > Time::default() => {
+                        prev_start + Time((time - prev_start).0 / self.period.0 * self.period.0)
+                    }
+                    _ => time,
+                };
+                self.period_start = Some(period_start);
+                period_start
+            }
         };
+        let elapsed = (time - period_start).0 as f32;
+        let on = elapsed < self.duty * self.period.0 as f32;
+        self.output = Ok(Some(Datum::new(time, on)));
         Ok(())
     }
 }
@@ -172,3 +172,134 @@ impl<T: Clone, GC: Getter<bool, E> + ?Sized, GI: Getter<T, E> + ?Sized, E: Copy
         Ok(())
     }
 }
+///Which input of a [`FallbackStream`] its output came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FallbackSource {
+    ///The primary input returned a value.
+    Primary,
+    ///The primary input returned an error or `Ok(None)`, and the secondary input returned a value.
+    Secondary,
+}
+///Returns the output of a primary getter, or the output of a secondary getter if the primary
+///returns an error or `Ok(None)`. This is useful for redundancy, such as falling back to a backup
+///sensor if the main encoder fails. The secondary getter can itself be a [`FallbackStream`] to
+///chain more than two sources. If neither input returns a value, the output of the secondary is
+///returned unless it is `Ok(None)` and the primary returned an error, in which case the primary's
+///error is returned. Use a [`FallbackSourceStream`] to see which input was used.
+pub struct FallbackStream<
+    T: Clone,
+    GP: Getter<T, E> + ?Sized,
+    GS: Getter<T, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    primary: Reference<GP>,
+    secondary: Reference<GS>,
+    value: Output<T, E>,
+    source: Option<FallbackSource>,
+}
+impl<T: Clone, GP: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E: Copy + Debug>
+    FallbackStream<T, GP, GS, E>
+{
+    ///Constructor for [`FallbackStream`].
+    pub const fn new(primary: Reference<GP>, secondary: Reference<GS>) -> Self {
+        Self {
+            primary: primary,
+            secondary: secondary,
+            value: Ok(None),
+            source: None,
+        }
+    }
+    ///Get which input the current output came from, or `None` if neither returned a value.
+    pub fn get_source(&self) -> Option<FallbackSource> {
+        self.source
+    }
+}
+impl<T: Clone, GP: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E: Copy + Debug> Getter<T, E>
+    for FallbackStream<T, GP, GS, E>
+{
+    fn get(&self) -> Output<T, E> {
+        self.value.clone()
+    }
+}
+impl<T: Clone, GP: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for FallbackStream<T, GP, GS, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let primary = self.primary.borrow().get();
+        let primary_error = match primary {
+            Ok(Some(datum)) => {
+                self.value = Ok(Some(datum));
+                self.source = Some(FallbackSource::Primary);
+                return Ok(());
+            }
+            Ok(None) => None,
+            Err(error) => Some(error),
+        };
+        let secondary = self.secondary.borrow().get();
+        match secondary {
+            Ok(Some(datum)) => {
+                self.value = Ok(Some(datum));
+                self.source = Some(FallbackSource::Secondary);
+                Ok(())
+            }
+            Ok(None) => {
+                self.source = None;
+                match primary_error {
+                    Some(error) => {
+                        self.value = Err(error);
+                        Err(error)
+                    }
+                    None => {
+                        self.value = Ok(None);
+                        Ok(())
+                    }
+                }
+            }
+            Err(error) => {
+                self.value = Err(error);
+                self.source = None;
+                Err(error)
+            }
+        }
+    }
+}
+///Reports which input of a [`FallbackStream`] its current output came from. Returns `Ok(None)`
+///when neither input returned a value. The timestamp is that of the [`FallbackStream`]'s output.
+pub struct FallbackSourceStream<
+    T: Clone,
+    GP: Getter<T, E> + ?Sized,
+    GS: Getter<T, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    stream: Reference<FallbackStream<T, GP, GS, E>>,
+}
+impl<T: Clone, GP: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E: Copy + Debug>
+    FallbackSourceStream<T, GP, GS, E>
+{
+    ///Constructor for [`FallbackSourceStream`].
+    pub const fn new(stream: Reference<FallbackStream<T, GP, GS, E>>) -> Self {
+        Self { stream: stream }
+    }
+}
+impl<T: Clone, GP: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E: Copy + Debug>
+    Getter<FallbackSource, E> for FallbackSourceStream<T, GP, GS, E>
+{
+    fn get(&self) -> Output<FallbackSource, E> {
+        let stream = self.stream.borrow();
+        let source = match stream.get_source() {
+            Some(source) => source,
+            None => return Ok(None),
+        };
+        match stream.get() {
+            Ok(Some(datum)) => Ok(Some(Datum::new(datum.time, source))),
+            _ => Ok(None),
+        }
+    }
+}
+impl<T: Clone, GP: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for FallbackSourceStream<T, GP, GS, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
@@ -172,6 +172,101 @@ impl<T: Sub<Output = T>, GM: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E
         Ok(())
     }
 }
+///A stream that adds all its [`Quantity`] inputs, checking that their units match. With dimension
+///checking on, returns [`Error::UnitInvalid`] instead of panicking if they do not. `Ok(None)` is
+///handled the same way as by [`SumStream`]. Multiplication and division always compose units
+///correctly, so [`ProductStream`] and [`QuotientStream`] can be used with [`Quantity`] directly.
+pub struct QuantitySumStream<const N: usize, E> {
+    addends: [Reference<dyn Getter<Quantity, E>>; N],
+}
+impl<const N: usize, E> QuantitySumStream<N, E> {
+    ///Constructor for [`QuantitySumStream`].
+    pub const fn new(addends: [Reference<dyn Getter<Quantity, E>>; N]) -> Self {
+        if N < 1 {
+            panic!("rrtk::streams::QuantitySumStream must have at least one input stream");
+        }
+        Self { addends: addends }
+    }
+}
+impl<const N: usize, E: Copy + Debug> Getter<Quantity, E> for QuantitySumStream<N, E> {
+    fn get(&self) -> Output<Quantity, E> {
+        let mut sum: Option<Datum<Quantity>> = None;
+        for i in &self.addends {
+            let addend = match i.borrow().get()? {
+                Some(addend) => addend,
+                None => continue,
+            };
+            sum = match sum {
+                None => Some(addend),
+                Some(sum) => {
+                    addend
+                        .value
+                        .unit
+                        .check(&sum.value.unit)
+                        .map_err(|mismatch| mismatch.with_operation("QuantitySumStream"))?;
+                    Some(sum + addend)
+                }
+            };
+        }
+        Ok(sum)
+    }
+}
+impl<const N: usize, E: Copy + Debug> Updatable<E> for QuantitySumStream<N, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that subtracts one of its [`Quantity`] inputs from the other, checking that their units
+///match. With dimension checking on, returns [`Error::UnitInvalid`] instead of panicking if they do
+///not. `Ok(None)` is handled the same way as by [`DifferenceStream`].
+pub struct QuantityDifferenceStream<
+    GM: Getter<Quantity, E> + ?Sized,
+    GS: Getter<Quantity, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    minuend: Reference<GM>,
+    subtrahend: Reference<GS>,
+    phantom_e: PhantomData<E>,
+}
+impl<GM: Getter<Quantity, E> + ?Sized, GS: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    QuantityDifferenceStream<GM, GS, E>
+{
+    ///Constructor for [`QuantityDifferenceStream`].
+    pub const fn new(minuend: Reference<GM>, subtrahend: Reference<GS>) -> Self {
+        Self {
+            minuend: minuend,
+            subtrahend: subtrahend,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<GM: Getter<Quantity, E> + ?Sized, GS: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    Getter<Quantity, E> for QuantityDifferenceStream<GM, GS, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        let minuend_output = match self.minuend.borrow().get()? {
+            Some(minuend_output) => minuend_output,
+            None => return Ok(None),
+        };
+        let subtrahend_output = match self.subtrahend.borrow().get()? {
+            Some(subtrahend_output) => subtrahend_output,
+            None => return Ok(Some(minuend_output)),
+        };
+        subtrahend_output
+            .value
+            .unit
+            .check(&minuend_output.value.unit)
+            .map_err(|mismatch| mismatch.with_operation("QuantityDifferenceStream"))?;
+        Ok(Some(minuend_output - subtrahend_output))
+    }
+}
+impl<GM: Getter<Quantity, E> + ?Sized, GS: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    Updatable<E> for QuantityDifferenceStream<GM, GS, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
 ///A stream that multiplies its inputs. If an input returns `Ok(None)`, it is excluded from the
 ///calculation, effectively treating it as though it had returned 1. If this is not the desired
 ///behavior, use [`rrtk::streams::converters::NoneToValue`](streams::converters::NoneToValue) or
@@ -334,6 +429,156 @@ impl<T: Div<Output = T>, GD: Getter<T, E> + ?Sized, GS: Getter<T, E> + ?Sized, E
         Ok(())
     }
 }
+///A stream that adds two [`State`] inputs using [`State`]'s [`Add`] implementation. This is useful
+///for applying corrections to a state in a stream pipeline. If one input returns `Ok(None)`, the
+///other input's output is returned. If both inputs return `Ok(None)`, returns `Ok(None)`.
+pub struct StateSumStream<
+    G1: Getter<State, E> + ?Sized,
+    G2: Getter<State, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    addend1: Reference<G1>,
+    addend2: Reference<G2>,
+    phantom_e: PhantomData<E>,
+}
+impl<G1: Getter<State, E> + ?Sized, G2: Getter<State, E> + ?Sized, E: Copy + Debug>
+    StateSumStream<G1, G2, E>
+{
+    ///Constructor for [`StateSumStream`].
+    pub const fn new(addend1: Reference<G1>, addend2: Reference<G2>) -> Self {
+        Self {
+            addend1: addend1,
+            addend2: addend2,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<G1: Getter<State, E> + ?Sized, G2: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<State, E>
+    for StateSumStream<G1, G2, E>
+{
+    fn get(&self) -> Output<State, E> {
+        let x = match self.addend1.borrow().get()? {
+            Some(x) => x,
+            None => return self.addend2.borrow().get(),
+        };
+        let y = match self.addend2.borrow().get()? {
+            Some(y) => y,
+            None => return Ok(Some(x)),
+        };
+        Ok(Some(x + y))
+    }
+}
+impl<G1: Getter<State, E> + ?Sized, G2: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for StateSumStream<G1, G2, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that subtracts one [`State`] input from another using [`State`]'s [`Sub`]
+///implementation. If the subtrahend returns `Ok(None)`, the minuend's output is returned directly.
+pub struct StateDifferenceStream<
+    GM: Getter<State, E> + ?Sized,
+    GS: Getter<State, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    minuend: Reference<GM>,
+    subtrahend: Reference<GS>,
+    phantom_e: PhantomData<E>,
+}
+impl<GM: Getter<State, E> + ?Sized, GS: Getter<State, E> + ?Sized, E: Copy + Debug>
+    StateDifferenceStream<GM, GS, E>
+{
+    ///Constructor for [`StateDifferenceStream`].
+    pub const fn new(minuend: Reference<GM>, subtrahend: Reference<GS>) -> Self {
+        Self {
+            minuend: minuend,
+            subtrahend: subtrahend,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<GM: Getter<State, E> + ?Sized, GS: Getter<State, E> + ?Sized, E: Copy + Debug> Getter<State, E>
+    for StateDifferenceStream<GM, GS, E>
+{
+    fn get(&self) -> Output<State, E> {
+        let minuend_output = match self.minuend.borrow().get()? {
+            Some(minuend_output) => minuend_output,
+            None => return Ok(None),
+        };
+        let subtrahend_output = match self.subtrahend.borrow().get()? {
+            Some(subtrahend_output) => subtrahend_output,
+            None => return Ok(Some(minuend_output)),
+        };
+        Ok(Some(minuend_output - subtrahend_output))
+    }
+}
+impl<GM: Getter<State, E> + ?Sized, GS: Getter<State, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for StateDifferenceStream<GM, GS, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that scales a [`State`] input by a dimensionless [`Quantity`] input using [`State`]'s
+///[`Mul<f32>`] implementation. With dimension checking on, returns [`Error::UnitInvalid`] if the
+///factor is not dimensionless. If the factor returns `Ok(None)`, the state is returned unscaled.
+pub struct StateScaleStream<
+    GS: Getter<State, E> + ?Sized,
+    GF: Getter<Quantity, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    state: Reference<GS>,
+    factor: Reference<GF>,
+    phantom_e: PhantomData<E>,
+}
+impl<GS: Getter<State, E> + ?Sized, GF: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    StateScaleStream<GS, GF, E>
+{
+    ///Constructor for [`StateScaleStream`].
+    pub const fn new(state: Reference<GS>, factor: Reference<GF>) -> Self {
+        Self {
+            state: state,
+            factor: factor,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<GS: Getter<State, E> + ?Sized, GF: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    Getter<State, E> for StateScaleStream<GS, GF, E>
+{
+    fn get(&self) -> Output<State, E> {
+        let state_output = match self.state.borrow().get()? {
+            Some(state_output) => state_output,
+            None => return Ok(None),
+        };
+        let factor_output = match self.factor.borrow().get()? {
+            Some(factor_output) => factor_output,
+            None => return Ok(Some(state_output)),
+        };
+        factor_output
+            .value
+            .unit
+            .check(&DIMENSIONLESS)
+            .map_err(|mismatch| mismatch.with_operation("StateScaleStream"))?;
+        let time = if state_output.time > factor_output.time {
+            state_output.time
+        } else {
+            factor_output.time
+        };
+        Ok(Some(Datum::new(
+            time,
+            state_output.value * factor_output.value.value,
+        )))
+    }
+}
+impl<GS: Getter<State, E> + ?Sized, GF: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for StateScaleStream<GS, GF, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
 ///A stream that exponentiates one of its inputs to the other. If the exponent input returns
 ///`Ok(None)`, the base's value is returned directly. Only available with `std`.
 #[cfg(feature = "internal_enhanced_float")]
@@ -394,6 +639,218 @@ impl<GB: Getter<f32, E> + ?Sized, GE: Getter<f32, E> + ?Sized, E: Copy + Debug>
         Ok(())
     }
 }
+///A stream that takes the square root of its input. The exponents of the input's unit are halved,
+///so with dimension checking on, this returns [`Error::UnitInvalid`] if any of them are odd.
+#[cfg(feature = "internal_enhanced_float")]
+pub struct SqrtStream<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    phantom_e: PhantomData<E>,
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> SqrtStream<G, E> {
+    ///Constructor for [`SqrtStream`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            phantom_e: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E> for SqrtStream<G, E> {
+    fn get(&self) -> Output<Quantity, E> {
+        let output = match self.input.borrow().get()? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+        let unit = output
+            .value
+            .unit
+            .checked_sqrt()
+            .map_err(|mismatch| mismatch.with_operation("SqrtStream"))?;
+        Ok(Some(Datum::new(
+            output.time,
+            Quantity::new(sqrtf(output.value.value), unit),
+        )))
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for SqrtStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that takes the sine of its input in radians. The input must be dimensionless, and with
+///dimension checking on, this returns [`Error::UnitInvalid`] if it is not.
+#[cfg(feature = "internal_enhanced_float")]
+pub struct SinStream<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    phantom_e: PhantomData<E>,
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> SinStream<G, E> {
+    ///Constructor for [`SinStream`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            phantom_e: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E> for SinStream<G, E> {
+    fn get(&self) -> Output<Quantity, E> {
+        let output = match self.input.borrow().get()? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+        output
+            .value
+            .unit
+            .check(&DIMENSIONLESS)
+            .map_err(|mismatch| mismatch.with_operation("SinStream"))?;
+        Ok(Some(Datum::new(
+            output.time,
+            Quantity::dimensionless(sinf(output.value.value)),
+        )))
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for SinStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that takes the cosine of its input in radians. The input must be dimensionless, and
+///with dimension checking on, this returns [`Error::UnitInvalid`] if it is not.
+#[cfg(feature = "internal_enhanced_float")]
+pub struct CosStream<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    phantom_e: PhantomData<E>,
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> CosStream<G, E> {
+    ///Constructor for [`CosStream`].
+    pub const fn new(input: Reference<G>) -> Self {
+        Self {
+            input: input,
+            phantom_e: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E> for CosStream<G, E> {
+    fn get(&self) -> Output<Quantity, E> {
+        let output = match self.input.borrow().get()? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+        output
+            .value
+            .unit
+            .check(&DIMENSIONLESS)
+            .map_err(|mismatch| mismatch.with_operation("CosStream"))?;
+        Ok(Some(Datum::new(
+            output.time,
+            Quantity::dimensionless(cosf(output.value.value)),
+        )))
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for CosStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that computes the four-quadrant arctangent of `y / x` in radians. Both inputs must have
+///the same unit, and with dimension checking on, this returns [`Error::UnitInvalid`] if they do
+///not. The output is
+///dimensionless. If either input returns `Ok(None)`, this returns `Ok(None)`.
+#[cfg(feature = "internal_enhanced_float")]
+pub struct Atan2Stream<
+    GY: Getter<Quantity, E> + ?Sized,
+    GX: Getter<Quantity, E> + ?Sized,
+    E: Copy + Debug,
+> {
+    y: Reference<GY>,
+    x: Reference<GX>,
+    phantom_e: PhantomData<E>,
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<GY: Getter<Quantity, E> + ?Sized, GX: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    Atan2Stream<GY, GX, E>
+{
+    ///Constructor for [`Atan2Stream`].
+    pub const fn new(y: Reference<GY>, x: Reference<GX>) -> Self {
+        Self {
+            y: y,
+            x: x,
+            phantom_e: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<GY: Getter<Quantity, E> + ?Sized, GX: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    Getter<Quantity, E> for Atan2Stream<GY, GX, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        let y = match self.y.borrow().get()? {
+            Some(y) => y,
+            None => return Ok(None),
+        };
+        let x = match self.x.borrow().get()? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        x.value
+            .unit
+            .check(&y.value.unit)
+            .map_err(|mismatch| mismatch.with_operation("Atan2Stream"))?;
+        let time = if y.time > x.time { y.time } else { x.time };
+        Ok(Some(Datum::new(
+            time,
+            Quantity::dimensionless(atan2f(y.value.value, x.value.value)),
+        )))
+    }
+}
+#[cfg(feature = "internal_enhanced_float")]
+impl<GY: Getter<Quantity, E> + ?Sized, GX: Getter<Quantity, E> + ?Sized, E: Copy + Debug>
+    Updatable<E> for Atan2Stream<GY, GX, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that wraps an angle in radians from its input into an [`AngleRange`](angles::AngleRange).
+pub struct NormalizeAngleStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    range: angles::AngleRange,
+    phantom_e: PhantomData<E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> NormalizeAngleStream<G, E> {
+    ///Constructor for [`NormalizeAngleStream`].
+    pub const fn new(input: Reference<G>, range: angles::AngleRange) -> Self {
+        Self {
+            input: input,
+            range: range,
+            phantom_e: PhantomData,
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for NormalizeAngleStream<G, E> {
+    fn get(&self) -> Output<f32, E> {
+        let output = match self.input.borrow().get()? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+        Ok(Some(Datum::new(output.time, self.range.wrap(output.value))))
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for NormalizeAngleStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
 ///A stream that computes the numerical derivative of its input.
 pub struct DerivativeStream<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> {
     input: Reference<G>,
@@ -451,6 +908,121 @@ impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for Derivati
         Ok(())
     }
 }
+///A stream estimating the derivative of its input by fitting a line to the last `N` samples with
+///least squares. Differentiating quantized encoder positions with [`DerivativeStream`] gives a
+///very noisy velocity since each step is only a few counts; fitting over a window averages out
+///the quantization at the cost of some lag, about half the window length. With regularly spaced
+///samples this is the same as a first-order Savitzky-Golay derivative filter, but actual
+///timestamps are used so irregular updates are handled correctly. Samples with the same timestamp
+///as the previous one are ignored. `N` must be at least 2. Until the window is full, the fit uses
+///every sample available, and the output is `Ok(None)` until there are two.
+pub struct VelocityEstimator<const N: usize, G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    value: Output<Quantity, E>,
+    times: [Time; N],
+    values: [f32; N],
+    unit: Unit,
+    len: usize,
+    next: usize,
+}
+impl<const N: usize, G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> VelocityEstimator<N, G, E> {
+    ///Constructor for [`VelocityEstimator`].
+    pub const fn new(input: Reference<G>) -> Self {
+        assert!(
+            N >= 2,
+            "rrtk::streams::math::VelocityEstimator needs a window of at least 2 samples"
+        );
+        Self {
+            input: input,
+            value: Ok(None),
+            times: [Time(0); N],
+            values: [0.0; N],
+            unit: DIMENSIONLESS,
+            len: 0,
+            next: 0,
+        }
+    }
+    fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
+impl<const N: usize, G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Getter<Quantity, E>
+    for VelocityEstimator<N, G, E>
+{
+    fn get(&self) -> Output<Quantity, E> {
+        self.value.clone()
+    }
+}
+impl<const N: usize, G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for VelocityEstimator<N, G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        let output = self.input.borrow().get();
+        let output = match output {
+            Ok(ok) => ok,
+            Err(error) => {
+                self.value = Err(error);
+                self.clear();
+                return Err(error);
+            }
+        };
+        let output = match output {
+            Some(some) => some,
+            None => {
+                self.value = Ok(None);
+                self.clear();
+                return Ok(());
+            }
+        };
+        if self.len > 0 {
+            let newest = (self.next + N - 1) % N;
+            if self.times[newest] == output.time {
+                return Ok(());
+            }
+            //Fitting samples with different units together would be meaningless.
+            if !self.unit.eq_assume_true(&output.value.unit) {
+                self.clear();
+            }
+        }
+        self.unit = output.value.unit;
+        self.times[self.next] = output.time;
+        self.values[self.next] = output.value.value;
+        self.next = (self.next + 1) % N;
+        if self.len < N {
+            self.len += 1;
+        }
+        if self.len < 2 {
+            self.value = Ok(None);
+            return Ok(());
+        }
+        //Times are taken relative to the newest sample to keep them small for f32 precision.
+        let relative_time =
+            |index: usize| f32::from(Quantity::from(self.times[index] - output.time));
+        let len = self.len as f32;
+        let mut time_mean = 0.0;
+        let mut value_mean = 0.0;
+        for index in 0..self.len {
+            time_mean += relative_time(index);
+            value_mean += self.values[index];
+        }
+        time_mean /= len;
+        value_mean /= len;
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        for index in 0..self.len {
+            let time_deviation = relative_time(index) - time_mean;
+            numerator += time_deviation * (self.values[index] - value_mean);
+            denominator += time_deviation * time_deviation;
+        }
+        let slope = numerator / denominator;
+        self.value = Ok(Some(Datum::new(
+            output.time,
+            Quantity::new(slope, self.unit / SECOND),
+        )));
+        Ok(())
+    }
+}
 ///A stream that computes the trapezoidal numerical integral of its input.
 pub struct IntegralStream<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> {
     input: Reference<G>,
@@ -512,3 +1084,268 @@ impl<G: Getter<Quantity, E> + ?Sized, E: Copy + Debug> Updatable<E> for Integral
         return Ok(());
     }
 }
+///A stream that evaluates a [`Polynomial`](crate::linalg::Polynomial) on its input, such as a
+///calibration curve fitted with [`Polynomial::fit`](crate::linalg::Polynomial::fit).
+pub struct PolynomialStream<const N: usize, G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    polynomial: linalg::Polynomial<N>,
+    phantom_e: PhantomData<E>,
+}
+impl<const N: usize, G: Getter<f32, E> + ?Sized, E: Copy + Debug> PolynomialStream<N, G, E> {
+    ///Constructor for [`PolynomialStream`].
+    pub const fn new(input: Reference<G>, polynomial: linalg::Polynomial<N>) -> Self {
+        Self {
+            input: input,
+            polynomial: polynomial,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Get the polynomial being evaluated.
+    pub fn get_polynomial(&self) -> linalg::Polynomial<N> {
+        self.polynomial
+    }
+    ///Replace the polynomial being evaluated, for example after recalibrating.
+    pub fn set_polynomial(&mut self, polynomial: linalg::Polynomial<N>) {
+        self.polynomial = polynomial;
+    }
+}
+impl<const N: usize, G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for PolynomialStream<N, G, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        let input = match self.input.borrow().get()? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+        Ok(Some(Datum::new(
+            input.time,
+            self.polynomial.evaluate(input.value),
+        )))
+    }
+}
+impl<const N: usize, G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for PolynomialStream<N, G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///What a [`RatioStream`] should do when the magnitude of its denominator is below its threshold.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SmallDenominatorAction<E: Copy + Debug> {
+    ///Return `Ok(None)`.
+    ReturnNone,
+    ///Return `Err(Error::Other(_))` with this value.
+    ReturnError(E),
+    ///Return this value.
+    Substitute(f32),
+    ///Divide by the threshold instead, keeping the sign of the denominator. Zero is treated as
+    ///positive.
+    ClampDenominator,
+}
+///A stream that divides one [`f32`] input by another without producing infinity or NaN. If the
+///magnitude of the denominator is less than `threshold`, the result is handled according to a
+///[`SmallDenominatorAction`]. This is useful for ratios of raw sensor values such as power from
+///current and voltage. Unlike [`QuotientStream`], if either input returns `Ok(None)`, this returns
+///`Ok(None)`.
+pub struct RatioStream<GN: Getter<f32, E> + ?Sized, GD: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    numerator: Reference<GN>,
+    denominator: Reference<GD>,
+    threshold: f32,
+    action: SmallDenominatorAction<E>,
+}
+impl<GN: Getter<f32, E> + ?Sized, GD: Getter<f32, E> + ?Sized, E: Copy + Debug>
+    RatioStream<GN, GD, E>
+{
+    ///Constructor for [`RatioStream`].
+    pub const fn new(
+        numerator: Reference<GN>,
+        denominator: Reference<GD>,
+        threshold: f32,
+        action: SmallDenominatorAction<E>,
+    ) -> Self {
+        Self {
+            numerator: numerator,
+            denominator: denominator,
+            threshold: threshold,
+            action: action,
+        }
+    }
+}
+impl<GN: Getter<f32, E> + ?Sized, GD: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E>
+    for RatioStream<GN, GD, E>
+{
+    fn get(&self) -> Output<f32, E> {
+        let numerator = match self.numerator.borrow().get()? {
+            Some(numerator) => numerator,
+            None => return Ok(None),
+        };
+        let denominator = match self.denominator.borrow().get()? {
+            Some(denominator) => denominator,
+            None => return Ok(None),
+        };
+        let time = if numerator.time > denominator.time {
+            numerator.time
+        } else {
+            denominator.time
+        };
+        let value = if denominator.value.abs() >= self.threshold && denominator.value != 0.0 {
+            numerator.value / denominator.value
+        } else {
+            match self.action {
+                SmallDenominatorAction::ReturnNone => return Ok(None),
+                SmallDenominatorAction::ReturnError(error) => return Err(Error::Other(error)),
+                SmallDenominatorAction::Substitute(value) => value,
+                SmallDenominatorAction::ClampDenominator => {
+                    numerator.value / (self.threshold * denominator.value.signum())
+                }
+            }
+        };
+        Ok(Some(Datum::new(time, value)))
+    }
+}
+impl<GN: Getter<f32, E> + ?Sized, GD: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for RatioStream<GN, GD, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that maps its input linearly from the range `input_min` to `input_max` onto 0 to 1, or
+///onto -1 to 1 if `bipolar` is true. Inputs outside of the range are clamped. This is useful for
+///turning raw readings such as from a flex sensor or potentiometer into control inputs. `input_min`
+///may be greater than `input_max` to reverse the direction.
+pub struct NormalizeStream<G: Getter<f32, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    input_min: f32,
+    input_max: f32,
+    bipolar: bool,
+    phantom_e: PhantomData<E>,
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> NormalizeStream<G, E> {
+    ///Constructor for [`NormalizeStream`]. Panics if `input_min` and `input_max` are equal.
+    pub const fn new(input: Reference<G>, input_min: f32, input_max: f32, bipolar: bool) -> Self {
+        assert!(
+            input_min != input_max,
+            "rrtk::streams::math::NormalizeStream input range must not be empty"
+        );
+        Self {
+            input: input,
+            input_min: input_min,
+            input_max: input_max,
+            bipolar: bipolar,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Normalize a single value.
+    pub fn normalize(&self, value: f32) -> f32 {
+        let fraction =
+            ((value - self.input_min) / (self.input_max - self.input_min)).clamp(0.0, 1.0);
+        if self.bipolar {
+            fraction * 2.0 - 1.0
+        } else {
+            fraction
+        }
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Getter<f32, E> for NormalizeStream<G, E> {
+    fn get(&self) -> Output<f32, E> {
+        let output = match self.input.borrow().get()? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+        Ok(Some(Datum::new(output.time, self.normalize(output.value))))
+    }
+}
+impl<G: Getter<f32, E> + ?Sized, E: Copy + Debug> Updatable<E> for NormalizeStream<G, E> {
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///A stream that limits its input to the range `min` to `max`. Use [`ClampSaturationStream`] to get
+///whether the input is currently being limited, for example to stop an upstream controller's
+///integral from winding up. `min` should not be greater than `max`.
+pub struct ClampStream<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
+    input: Reference<G>,
+    min: T,
+    max: T,
+    phantom_e: PhantomData<E>,
+}
+impl<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> ClampStream<T, G, E> {
+    ///Constructor for [`ClampStream`].
+    pub const fn new(input: Reference<G>, min: T, max: T) -> Self {
+        Self {
+            input: input,
+            min: min,
+            max: max,
+            phantom_e: PhantomData,
+        }
+    }
+    ///Limit a single value.
+    pub fn clamp(&self, value: T) -> T {
+        if value < self.min {
+            self.min.clone()
+        } else if value > self.max {
+            self.max.clone()
+        } else {
+            value
+        }
+    }
+    ///Returns true if a value is outside of the range and would be limited.
+    pub fn saturates(&self, value: &T) -> bool {
+        *value < self.min || *value > self.max
+    }
+    ///Get whether the input is currently outside of the range. The timestamp is that of the input.
+    pub fn get_saturation(&self) -> Output<bool, E> {
+        Ok(self
+            .input
+            .borrow()
+            .get()?
+            .map(|output| Datum::new(output.time, self.saturates(&output.value))))
+    }
+}
+impl<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> Getter<T, E>
+    for ClampStream<T, G, E>
+{
+    fn get(&self) -> Output<T, E> {
+        Ok(self
+            .input
+            .borrow()
+            .get()?
+            .map(|output| Datum::new(output.time, self.clamp(output.value))))
+    }
+}
+impl<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ClampStream<T, G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
+///Reports whether the input of a [`ClampStream`] is currently outside of its range and being
+///limited. This returns `Ok(None)` if the clamp stream's input does.
+pub struct ClampSaturationStream<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> {
+    clamp: Reference<ClampStream<T, G, E>>,
+}
+impl<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug>
+    ClampSaturationStream<T, G, E>
+{
+    ///Constructor for [`ClampSaturationStream`].
+    pub const fn new(clamp: Reference<ClampStream<T, G, E>>) -> Self {
+        Self { clamp: clamp }
+    }
+}
+impl<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> Getter<bool, E>
+    for ClampSaturationStream<T, G, E>
+{
+    fn get(&self) -> Output<bool, E> {
+        self.clamp.borrow().get_saturation()
+    }
+}
+impl<T: PartialOrd + Clone, G: Getter<T, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for ClampSaturationStream<T, G, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        Ok(())
+    }
+}
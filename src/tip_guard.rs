@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use crate::*;
+///What a [`TipGuard`] does while the robot is tipping.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TipGuardAction {
+    ///Set the drive output to 0.
+    CutPower,
+    ///Set the drive output to the negative of this gain times the tilt angle. Choose the sign of the
+    ///gain so that this drives the base of the robot back under its center of mass.
+    Correct(f32),
+}
+///Protects a tall robot from tipping over by sitting between a drive controller and the drive
+///output. The tilt getter returns a [`State`] whose position is the pitch or roll angle and whose
+///velocity is its rate of change, so guards for both axes can be chained by making one the output
+///of the other. Values set to this are forwarded to the drive output unless the robot is tipping,
+///which is when the magnitude of the angle exceeds `max_angle` or the angle is changing away from
+///level faster than `max_rate`. While tipping, the drive output is overridden according to the
+///[`TipGuardAction`] on every update. Once the magnitude of the angle falls below `recovery_angle`
+///and it is no longer changing too quickly, the most recent value set to this is forwarded again.
+///If the tilt getter returns `Ok(None)`, the robot is assumed not to be tipping. Updating this also
+///updates the drive output but not the tilt getter.
+pub struct TipGuard<G: Getter<State, E> + ?Sized, T: Settable<f32, E> + ?Sized, E: Copy + Debug> {
+    settable_data: SettableData<f32, E>,
+    tilt: Reference<G>,
+    drive: Reference<T>,
+    max_angle: f32,
+    max_rate: f32,
+    recovery_angle: f32,
+    action: TipGuardAction,
+    tipping: bool,
+}
+impl<G: Getter<State, E> + ?Sized, T: Settable<f32, E> + ?Sized, E: Copy + Debug>
+    TipGuard<G, T, E>
+{
+    ///Constructor for [`TipGuard`].
+    pub const fn new(
+        tilt: Reference<G>,
+        drive: Reference<T>,
+        max_angle: f32,
+        max_rate: f32,
+        recovery_angle: f32,
+        action: TipGuardAction,
+    ) -> Self {
+        Self {
+            settable_data: SettableData::new(),
+            tilt: tilt,
+            drive: drive,
+            max_angle: max_angle,
+            max_rate: max_rate,
+            recovery_angle: recovery_angle,
+            action: action,
+            tipping: false,
+        }
+    }
+    ///Returns true if the drive output is currently being overridden.
+    pub fn is_tipping(&self) -> bool {
+        self.tipping
+    }
+}
+impl<G: Getter<State, E> + ?Sized, T: Settable<f32, E> + ?Sized, E: Copy + Debug> Settable<f32, E>
+    for TipGuard<G, T, E>
+{
+    fn get_settable_data_ref(&self) -> &SettableData<f32, E> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, E> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: f32) -> NothingOrError<E> {
+        if self.tipping {
+            return Ok(());
+        }
+        self.drive.borrow_mut().set(value)
+    }
+}
+impl<G: Getter<State, E> + ?Sized, T: Settable<f32, E> + ?Sized, E: Copy + Debug> Updatable<E>
+    for TipGuard<G, T, E>
+{
+    fn update(&mut self) -> NothingOrError<E> {
+        self.update_following_data()?;
+        let tilt = self.tilt.borrow().get()?.map(|tilt| tilt.value);
+        let was_tipping = self.tipping;
+        self.tipping = match tilt {
+            Some(tilt) => {
+                //Only count the rate if it is taking the robot further from level.
+                let rate_away = tilt.velocity * tilt.position.signum();
+                let too_fast = rate_away > self.max_rate;
+                if was_tipping {
+                    too_fast || tilt.position.abs() > self.recovery_angle
+                } else {
+                    too_fast || tilt.position.abs() > self.max_angle
+                }
+            }
+            None => false,
+        };
+        if self.tipping {
+            let output = match (self.action, tilt) {
+                (TipGuardAction::Correct(gain), Some(tilt)) => -gain * tilt.position,
+                _ => 0.0,
+            };
+            self.drive.borrow_mut().set(output)?;
+        } else if was_tipping {
+            if let Some(request) = self.get_last_request() {
+                self.drive.borrow_mut().set(request)?;
+            }
+        }
+        self.drive.borrow_mut().update()
+    }
+}
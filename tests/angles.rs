@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use core::f32::consts::PI;
+use rrtk::angles::*;
+fn assert_close(a: f32, b: f32) {
+    assert!((a - b).abs() < 0.0001, "{} is not close to {}", a, b);
+}
+#[test]
+fn wrap_to_pi_test() {
+    assert_close(wrap_to_pi(0.0), 0.0);
+    assert_close(wrap_to_pi(PI / 2.0), PI / 2.0);
+    assert_close(wrap_to_pi(3.0 * PI / 2.0), -PI / 2.0);
+    assert_close(wrap_to_pi(-3.0 * PI / 2.0), PI / 2.0);
+    //Either end of the range is acceptable here because of floating point error.
+    assert_close(wrap_to_pi(5.0 * PI).abs(), PI);
+    assert_close(wrap_to_pi(PI), -PI);
+}
+#[test]
+fn wrap_to_2pi_test() {
+    assert_close(wrap_to_2pi(0.0), 0.0);
+    assert_close(wrap_to_2pi(-PI / 2.0), 3.0 * PI / 2.0);
+    assert_close(wrap_to_2pi(5.0 * PI / 2.0), PI / 2.0);
+    assert_close(wrap_to_2pi(-4.0 * PI), 0.0);
+    assert!(wrap_to_2pi(-1e-9) < 2.0 * PI);
+}
+#[test]
+fn shortest_arc_test() {
+    assert_close(shortest_arc(0.1, -0.1), -0.2);
+    assert_close(shortest_arc(PI - 0.1, -PI + 0.1), 0.2);
+    assert_close(shortest_arc(-PI + 0.1, PI - 0.1), -0.2);
+    assert_close(shortest_arc(0.0, 20.0 * PI + 0.5), 0.5);
+    assert_close(angular_distance(PI - 0.1, -PI + 0.1), 0.2);
+    assert_close(angular_distance(-PI + 0.1, PI - 0.1), 0.2);
+}
+#[test]
+fn angle_range() {
+    assert_close(AngleRange::NegativePiToPi.wrap(3.0 * PI / 2.0), -PI / 2.0);
+    assert_close(AngleRange::ZeroToTwoPi.wrap(-PI / 2.0), 3.0 * PI / 2.0);
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(any(feature = "std", feature = "libm"))]
+use core::f32::consts::FRAC_PI_2;
+use rrtk::arm::*;
+fn assert_close(a: (f32, f32), b: (f32, f32)) {
+    assert!(
+        (a.0 - b.0).abs() < 0.001 && (a.1 - b.1).abs() < 0.001,
+        "{:?} is not close to {:?}",
+        a,
+        b
+    );
+}
+#[test]
+fn arm_kinematics_2() {
+    let arm = ArmKinematics2::new(300.0, 200.0);
+    assert_close(arm.forward(0.0, 0.0), (500.0, 0.0));
+    assert_close(arm.forward(FRAC_PI_2, -FRAC_PI_2), (200.0, 300.0));
+    let up = arm.inverse(200.0, 300.0, ElbowConfiguration::Up).unwrap();
+    assert_close(up, (FRAC_PI_2, -FRAC_PI_2));
+    let down = arm.inverse(200.0, 300.0, ElbowConfiguration::Down).unwrap();
+    assert!(down.1 > 0.0);
+    assert_close(arm.forward(down.0, down.1), (200.0, 300.0));
+    //Straight out is at the edge of the reachable area.
+    assert_close(
+        arm.inverse(0.0, 500.0, ElbowConfiguration::Up).unwrap(),
+        (FRAC_PI_2, 0.0),
+    );
+    assert!(!arm.can_reach(400.0, 400.0));
+    assert_eq!(arm.inverse(400.0, 400.0, ElbowConfiguration::Up), None);
+    //Too close to the shoulder for the lower link to fold back to.
+    assert!(!arm.can_reach(50.0, 0.0));
+    assert_eq!(arm.inverse(50.0, 0.0, ElbowConfiguration::Down), None);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn arm_inverse_kinematics_stream() {
+    use rrtk::*;
+    let time = rc_ref_cell_reference(Time(5));
+    let target = rc_ref_cell_reference(ConstantGetter::<(f32, f32), _, ()>::new(
+        time.clone(),
+        (200.0, 300.0),
+    ));
+    let mut ik = ArmInverseKinematicsStream::new(
+        target.clone(),
+        ArmKinematics2::new(300.0, 200.0),
+        ElbowConfiguration::Up,
+    );
+    let angles = ik.get().unwrap().unwrap();
+    assert_eq!(angles.time, Time(5));
+    assert_close(angles.value, (FRAC_PI_2, -FRAC_PI_2));
+    ik.set_elbow_configuration(ElbowConfiguration::Down);
+    assert!(ik.get().unwrap().unwrap().value.1 > 0.0);
+    ik.set_elbow_configuration(ElbowConfiguration::Up);
+    let ik = rc_ref_cell_reference(ik);
+    let shoulder = ArmJointCommandStream::new(ik.clone(), ArmJoint::Shoulder);
+    let elbow = ArmJointCommandStream::new(ik.clone(), ArmJoint::Elbow);
+    match shoulder.get().unwrap().unwrap().value {
+        Command::Position(angle) => assert!((angle - FRAC_PI_2).abs() < 0.001),
+        command => panic!("{:?} is not a position command", command),
+    }
+    match elbow.get().unwrap().unwrap().value {
+        Command::Position(angle) => assert!((angle + FRAC_PI_2).abs() < 0.001),
+        command => panic!("{:?} is not a position command", command),
+    }
+    target.borrow_mut().set((1000.0, 0.0)).unwrap();
+    assert_eq!(shoulder.get(), Ok(None));
+}
+#[test]
+fn arm_gravity_model_2() {
+    let model = ArmGravityModel2::new(
+        ArmKinematics2::new(300.0, 200.0),
+        2.0,
+        150.0,
+        1.0,
+        100.0,
+        10.0,
+    );
+    //Straight out horizontally: the elbow holds 1 * 10 * 100 and the shoulder also holds
+    //2 * 10 * 150 for the first link and 1 * 10 * 300 for the second link's weight at the elbow.
+    assert_close(model.torques(0.0, 0.0), (7000.0, 1000.0));
+    //Straight up needs no torque at all.
+    assert_close(model.torques(FRAC_PI_2, 0.0), (0.0, 0.0));
+    //First link up and second link out horizontally.
+    assert_close(model.torques(FRAC_PI_2, -FRAC_PI_2), (1000.0, 1000.0));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn arm_gravity_feedforward() {
+    use rrtk::*;
+    let shoulder = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(
+        rc_ref_cell_reference(Time(2)),
+        0.0,
+    ));
+    let elbow = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(
+        rc_ref_cell_reference(Time(4)),
+        0.0,
+    ));
+    let model = ArmGravityModel2::new(
+        ArmKinematics2::new(300.0, 200.0),
+        2.0,
+        150.0,
+        1.0,
+        100.0,
+        10.0,
+    );
+    let mut feedforward = ArmGravityFeedforward::new(shoulder.clone(), elbow.clone(), model);
+    let mut heavier = model;
+    heavier.lower_mass = 2.0;
+    feedforward.set_model(heavier);
+    assert_eq!(feedforward.get_model(), heavier);
+    let feedforward = rc_ref_cell_reference(feedforward);
+    let shoulder_feedforward =
+        ArmJointFeedforwardStream::new(feedforward.clone(), ArmJoint::Shoulder);
+    let elbow_feedforward = ArmJointFeedforwardStream::new(feedforward.clone(), ArmJoint::Elbow);
+    assert_eq!(
+        shoulder_feedforward.get(),
+        Ok(Some(Datum::new(Time(4), 11000.0)))
+    );
+    assert_eq!(
+        elbow_feedforward.get(),
+        Ok(Some(Datum::new(Time(4), 2000.0)))
+    );
+    let none = rc_ref_cell_reference(NoneGetter);
+    let feedforward = rc_ref_cell_reference(ArmGravityFeedforward::<_, _, ()>::new(
+        shoulder.clone(),
+        none,
+        model,
+    ));
+    assert_eq!(
+        ArmJointFeedforwardStream::new(feedforward, ArmJoint::Shoulder).get(),
+        Ok(None)
+    );
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(feature = "alloc")]
+use core::cell::RefCell;
+use rrtk::commands::*;
+use rrtk::*;
+use std::rc::Rc;
+#[derive(Debug, PartialEq)]
+enum Event {
+    Start(u8),
+    Update(u8),
+    End(u8, bool),
+}
+struct LogTask {
+    id: u8,
+    updates: u8,
+    remaining: u8,
+    log: Rc<RefCell<Vec<Event>>>,
+}
+impl Task<()> for LogTask {
+    fn start(&mut self) -> NothingOrError<()> {
+        self.remaining = self.updates;
+        self.log.borrow_mut().push(Event::Start(self.id));
+        Ok(())
+    }
+    fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+    fn end(&mut self, interrupted: bool) -> NothingOrError<()> {
+        self.log.borrow_mut().push(Event::End(self.id, interrupted));
+        Ok(())
+    }
+}
+impl Updatable<()> for LogTask {
+    fn update(&mut self) -> NothingOrError<()> {
+        self.remaining -= 1;
+        self.log.borrow_mut().push(Event::Update(self.id));
+        Ok(())
+    }
+}
+fn task(id: u8, updates: u8, log: &Rc<RefCell<Vec<Event>>>) -> Reference<dyn Task<()>> {
+    to_dyn!(
+        Task<()>,
+        rc_ref_cell_reference(LogTask {
+            id: id,
+            updates: updates,
+            remaining: updates,
+            log: log.clone(),
+        })
+    )
+}
+fn run(task: &mut dyn Task<()>, max_updates: usize) -> usize {
+    task.start().unwrap();
+    for i in 0..max_updates {
+        if task.is_finished() {
+            task.end(false).unwrap();
+            return i;
+        }
+        task.update().unwrap();
+    }
+    task.end(true).unwrap();
+    max_updates
+}
+#[test]
+fn sequential() {
+    use Event::*;
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut sequential = Sequential::new([task(0, 1, &log), task(1, 2, &log)]);
+    assert_eq!(run(&mut sequential, 10), 3);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            Start(0),
+            Update(0),
+            End(0, false),
+            Start(1),
+            Update(1),
+            Update(1),
+            End(1, false)
+        ]
+    );
+    log.borrow_mut().clear();
+    assert_eq!(run(&mut sequential, 2), 2);
+    assert_eq!(sequential.get_current(), None);
+    assert_eq!(log.borrow().last(), Some(&End(1, true)));
+}
+#[test]
+fn parallel_and_race() {
+    use Event::*;
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut parallel = Parallel::new([task(0, 1, &log), task(1, 2, &log)]);
+    assert_eq!(run(&mut parallel, 10), 2);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            Start(0),
+            Start(1),
+            Update(0),
+            End(0, false),
+            Update(1),
+            Update(1),
+            End(1, false)
+        ]
+    );
+    log.borrow_mut().clear();
+    let mut race = Race::new([task(0, 1, &log), task(1, 2, &log)]);
+    assert_eq!(run(&mut race, 10), 1);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            Start(0),
+            Start(1),
+            Update(0),
+            End(0, false),
+            Update(1),
+            End(1, true)
+        ]
+    );
+}
+#[test]
+fn deadline() {
+    use Event::*;
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut deadline = Deadline::new(task(0, 2, &log), [task(1, 1, &log), task(2, 3, &log)]);
+    assert_eq!(run(&mut deadline, 10), 2);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            Start(0),
+            Start(1),
+            Start(2),
+            Update(0),
+            Update(1),
+            End(1, false),
+            Update(2),
+            Update(0),
+            Update(2),
+            End(0, false),
+            End(2, true)
+        ]
+    );
+}
+#[test]
+fn wait() {
+    let time = rc_ref_cell_reference(Time(0));
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut sequential = Sequential::new([
+        to_dyn!(
+            Task<()>,
+            rc_ref_cell_reference(Wait::new(time.clone(), Time(20)))
+        ),
+        task(0, 1, &log),
+    ]);
+    sequential.start().unwrap();
+    for i in 0..3 {
+        *time.borrow_mut() = Time(i * 10);
+        sequential.update().unwrap();
+    }
+    assert_eq!(sequential.get_current(), Some(1));
+    assert_eq!(*log.borrow(), vec![Event::Start(0)]);
+    sequential.update().unwrap();
+    assert!(sequential.is_finished());
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(feature = "std")]
+use rrtk::debugging::*;
+use rrtk::*;
+struct CountingGetter {
+    count: i64,
+}
+impl Getter<i64, ()> for CountingGetter {
+    fn get(&self) -> Output<i64, ()> {
+        Ok(Some(Datum::new(Time(self.count), self.count * 10)))
+    }
+}
+impl Updatable<()> for CountingGetter {
+    fn update(&mut self) -> NothingOrError<()> {
+        self.count += 1;
+        Ok(())
+    }
+}
+struct LogPipeline {
+    input: Reference<dyn Getter<i64, ()>>,
+    time: Reference<dyn TimeGetter<()>>,
+    log: Vec<(Time, i64)>,
+}
+impl Updatable<()> for LogPipeline {
+    fn update(&mut self) -> NothingOrError<()> {
+        let time = self.time.borrow().get()?;
+        let value = self.input.borrow().get()?.unwrap().value;
+        self.log.push((time, value));
+        Ok(())
+    }
+}
+#[test]
+fn recorder() {
+    let source = rc_ref_cell_reference(CountingGetter { count: 0 });
+    let clock = rc_ref_cell_reference(Time(0));
+    let pipeline = rc_ref_cell_reference(LogPipeline {
+        input: to_dyn!(Getter<i64, ()>, rc_ref_cell_reference(NoneGetter)),
+        time: to_dyn!(TimeGetter<()>, clock.clone()),
+        log: Vec::new(),
+    });
+    let mut recorder = Recorder::new(to_dyn!(Updatable<()>, pipeline.clone()));
+    let input = recorder.add_input(to_dyn!(Getter<i64, ()>, source.clone()));
+    let time = recorder.add_time(to_dyn!(TimeGetter<()>, clock.clone()));
+    pipeline.borrow_mut().input = to_dyn!(Getter<i64, ()>, input.clone());
+    pipeline.borrow_mut().time = to_dyn!(TimeGetter<()>, time.clone());
+    for i in 1..=3 {
+        *clock.borrow_mut() = Time(i * 100);
+        recorder.update().unwrap();
+    }
+    assert_eq!(recorder.cycles(), 3);
+    assert_eq!(input.borrow().log().len(), 3);
+    let recorded = pipeline.borrow().log.clone();
+    assert_eq!(
+        recorded,
+        vec![(Time(100), 10), (Time(200), 20), (Time(300), 30)]
+    );
+
+    //The live source keeps changing, but replay must use the recorded values.
+    source.borrow_mut().count = 1000;
+    *clock.borrow_mut() = Time(5000);
+    pipeline.borrow_mut().log.clear();
+    recorder.start_replay();
+    assert_eq!(recorder.replay_step(), Some(Ok(())));
+    assert_eq!(recorder.replay_position(), Some(1));
+    assert_eq!(pipeline.borrow().log, vec![(Time(100), 10)]);
+    while let Some(result) = recorder.replay_step() {
+        result.unwrap();
+    }
+    assert_eq!(pipeline.borrow().log, recorded);
+
+    recorder.stop_replay();
+    assert_eq!(recorder.replay_position(), None);
+    recorder.update().unwrap();
+    assert_eq!(recorder.cycles(), 4);
+    assert_eq!(*pipeline.borrow().log.last().unwrap(), (Time(5000), 10010));
+}
+#[test]
+fn recorder_late_input() {
+    let first = rc_ref_cell_reference(CountingGetter { count: 0 });
+    let second = rc_ref_cell_reference(CountingGetter { count: 0 });
+    let mut recorder = Recorder::new(to_dyn!(Updatable<()>, rc_ref_cell_reference(NoneGetter)));
+    let first_input = recorder.add_input(to_dyn!(Getter<i64, ()>, first.clone()));
+    recorder.update().unwrap();
+    recorder.update().unwrap();
+    let second_input = recorder.add_input(to_dyn!(Getter<i64, ()>, second.clone()));
+    recorder.update().unwrap();
+    assert_eq!(second_input.borrow().log().len(), 3);
+    recorder.start_replay();
+    for expected in [None, None, Some(10)] {
+        recorder.replay_step().unwrap().unwrap();
+        assert_eq!(
+            second_input
+                .borrow()
+                .get()
+                .unwrap()
+                .map(|datum| datum.value),
+            expected
+        );
+    }
+    assert_eq!(first_input.borrow().get().unwrap().unwrap().value, 30);
+}
+#[test]
+#[should_panic]
+fn recorder_record_while_replaying() {
+    let mut recorder =
+        Recorder::<()>::new(to_dyn!(Updatable<()>, rc_ref_cell_reference(NoneGetter)));
+    recorder.update().unwrap();
+    recorder.start_replay();
+    let _ = recorder.record();
+}
+#[test]
+fn recorder_update_while_replaying() {
+    let mut recorder =
+        Recorder::<()>::new(to_dyn!(Updatable<()>, rc_ref_cell_reference(NoneGetter)));
+    recorder.update().unwrap();
+    recorder.start_replay();
+    recorder.update().unwrap();
+    assert_eq!(recorder.cycles(), 1);
+    assert_eq!(recorder.replay_position(), Some(0));
+}
+#[test]
+fn inspector() {
+    let source = rc_ref_cell_reference(CountingGetter { count: 1 });
+    let mut inspector = Inspector::new();
+    let raw = inspector.tap("raw", to_dyn!(Getter<i64, ()>, source.clone()));
+    let factor = rc_ref_cell_reference(ConstantGetter::<i64, _, ()>::new(
+        rc_ref_cell_reference(Time(0)),
+        2,
+    ));
+    let scaled = rc_ref_cell_reference(streams::math::ProductStream::new([
+        to_dyn!(Getter<i64, ()>, raw.clone()),
+        to_dyn!(Getter<i64, ()>, factor),
+    ]));
+    let scaled = inspector.tap("scaled", to_dyn!(Getter<i64, ()>, scaled));
+    assert_eq!(inspector.get("raw"), Some(Ok(None)));
+    assert_eq!(inspector.get("missing"), None);
+    assert_eq!(scaled.borrow().get(), Ok(Some(Datum::new(Time(1), 20))));
+    let snapshot = inspector.snapshot();
+    assert_eq!(snapshot["raw"], Ok(Some(Datum::new(Time(1), 10))));
+    assert_eq!(snapshot["scaled"], Ok(Some(Datum::new(Time(1), 20))));
+    assert_eq!(inspector.names().collect::<Vec<_>>(), vec!["raw", "scaled"]);
+}
+#[test]
+fn golden_trace() {
+    let input = rc_ref_cell_reference(ScriptedInput::<f32, ()>::new(vec![
+        Some(Datum::new(Time(10), 1.0)),
+        None,
+        Some(Datum::new(Time(30), 3.0)),
+    ]));
+    let time = rc_ref_cell_reference(ScriptedTime::<()>::new(vec![Time(10), Time(20), Time(30)]));
+    assert_eq!(input.borrow().get(), Ok(None));
+    assert_eq!(time.borrow().get(), Err(Error::FromNone));
+    let factor = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(
+        to_dyn!(TimeGetter<()>, time.clone()),
+        2.0,
+    ));
+    let doubled = rc_ref_cell_reference(streams::math::Product2::new(
+        to_dyn!(Getter<f32, ()>, input.clone()),
+        to_dyn!(Getter<f32, ()>, factor),
+    ));
+    let inputs = [
+        to_dyn!(Replayable, input.clone()),
+        to_dyn!(Replayable, time.clone()),
+    ];
+    let trace = run_trace(
+        &inputs,
+        &to_dyn!(Updatable<()>, doubled.clone()),
+        &to_dyn!(Getter<f32, ()>, doubled.clone()),
+        3,
+    )
+    .unwrap();
+    assert_eq!(
+        trace,
+        vec![
+            Some(Datum::new(Time(10), 2.0)),
+            //With no input, the product is just the constant factor.
+            Some(Datum::new(Time(20), 2.0)),
+            Some(Datum::new(Time(30), 6.0)),
+        ]
+    );
+    let mut golden = GoldenTrace::new();
+    golden.push(Some(Datum::new(Time(10), 2.05)), 0.1);
+    golden.push(Some(Datum::new(Time(20), 2.0)), 0.0);
+    golden.push(Some(Datum::new(Time(30), 6.0)), 0.0);
+    assert_eq!(golden.compare(&trace), Ok(()));
+    assert_eq!(
+        GoldenTrace::from_outputs(&trace, 0.0).compare(&trace),
+        Ok(())
+    );
+
+    let mut golden = GoldenTrace::from_outputs(&trace[..2], 0.01);
+    golden.push(Some(Datum::new(Time(30), 5.0)), 0.5);
+    golden.push(None, 0.0);
+    let diff = golden.compare(&trace).unwrap_err();
+    assert_eq!(diff.expected_len, 4);
+    assert_eq!(diff.actual_len, 3);
+    assert_eq!(diff.mismatches.len(), 1);
+    assert_eq!(diff.mismatches[0].step, 2);
+    assert_eq!(
+        diff.to_string(),
+        "length: expected 4 steps, got 3\nstep 2: expected 5 at 30 ns (tolerance 0.5), got 6 at 30 ns\n"
+    );
+}
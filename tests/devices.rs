@@ -43,6 +43,201 @@ fn terminal() {
     term1.borrow_mut().update().unwrap(); //This should do nothing.
 }
 #[test]
+fn terminal_max_command_age() {
+    let clock = static_reference!(Time, Time(0));
+    let term1 = Terminal::<()>::new();
+    let term2 = Terminal::<()>::new();
+    connect(&term1, &term2);
+    term1
+        .borrow_mut()
+        .set(Datum::new(Time(0), Command::Velocity(1.0)))
+        .unwrap();
+    term2
+        .borrow_mut()
+        .set_max_command_age(Time(1_000_000_000), to_dyn!(TimeGetter<()>, clock.clone()));
+    *clock.borrow_mut() = Time(500_000_000);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Velocity(1.0))))
+    );
+    *clock.borrow_mut() = Time(2_000_000_000);
+    assert_eq!(
+        <Terminal<'_, ()> as Getter<Command, ()>>::get(&term2.borrow()),
+        Ok(None)
+    );
+    //The terminal without a maximum age still returns the command.
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Velocity(1.0))))
+    );
+    term1
+        .borrow_mut()
+        .set(Datum::new(Time(1_900_000_000), Command::Velocity(2.0)))
+        .unwrap();
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(
+            Time(1_900_000_000),
+            Command::Velocity(2.0)
+        )))
+    );
+    *clock.borrow_mut() = Time(10_000_000_000);
+    term2.borrow_mut().clear_max_command_age();
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(
+            Time(1_900_000_000),
+            Command::Velocity(2.0)
+        )))
+    );
+}
+#[test]
+fn terminal_command_policy() {
+    let term1 = Terminal::<()>::new();
+    let term2 = Terminal::<()>::new();
+    connect(&term1, &term2);
+    term1
+        .borrow_mut()
+        .set(Datum::new(Time(0), Command::Velocity(0.0)))
+        .unwrap();
+    term2
+        .borrow_mut()
+        .set(Datum::new(Time(1), Command::Position(5.0)))
+        .unwrap();
+    assert_eq!(
+        term2.borrow().get_command_policy(),
+        CommandPolicy::LatestWins
+    );
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), Command::Position(5.0))))
+    );
+    term2
+        .borrow_mut()
+        .set_command_policy(CommandPolicy::PriorityByDerivative([
+            PositionDerivative::Velocity,
+            PositionDerivative::Position,
+            PositionDerivative::Acceleration,
+        ]));
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Velocity(0.0))))
+    );
+    //The policy belongs to the connection, so both ends agree.
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Velocity(0.0))))
+    );
+    term2
+        .borrow_mut()
+        .set_command_policy(CommandPolicy::PriorityBySource);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), Command::Position(5.0))))
+    );
+    term1.borrow_mut().set_source_priority(1);
+    assert_eq!(term1.borrow().get_source_priority(), 1);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Velocity(0.0))))
+    );
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Velocity(0.0))))
+    );
+    //Connecting uses the policy of the first terminal.
+    let term3 = Terminal::<()>::new();
+    connect(&term3, &term2);
+    assert_eq!(
+        term2.borrow().get_command_policy(),
+        CommandPolicy::LatestWins
+    );
+    //term1 only had the policy because it was connected to term2, so it loses it on disconnecting.
+    assert_eq!(
+        term1.borrow().get_command_policy(),
+        CommandPolicy::LatestWins
+    );
+    term3
+        .borrow_mut()
+        .set_command_policy(CommandPolicy::PriorityBySource);
+    term3.borrow_mut().disconnect();
+    assert_eq!(
+        term3.borrow().get_command_policy(),
+        CommandPolicy::PriorityBySource
+    );
+    assert_eq!(
+        term2.borrow().get_command_policy(),
+        CommandPolicy::LatestWins
+    );
+}
+#[test]
+fn terminal_state_merger() {
+    let newest = NewestStateMerger;
+    let weighted = WeightedStateMerger::new(0.75);
+    let variance_weighted = VarianceWeightedStateMerger::new(1.0, 3.0);
+    let term1 = Terminal::<()>::new();
+    let term2 = Terminal::<()>::new();
+    connect(&term1, &term2);
+    term1
+        .borrow_mut()
+        .set(Datum::new(Time(0), State::new_raw(0.0, 4.0, 0.0)))
+        .unwrap();
+    term2
+        .borrow_mut()
+        .set(Datum::new(Time(1), State::new_raw(8.0, 0.0, 0.0)))
+        .unwrap();
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(4.0, 2.0, 0.0))))
+    );
+    term2.borrow_mut().set_state_merger(&newest);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(8.0, 0.0, 0.0))))
+    );
+    //The merger belongs to the connection, so both ends agree.
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(8.0, 0.0, 0.0))))
+    );
+    term2.borrow_mut().set_state_merger(&weighted);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(6.0, 1.0, 0.0))))
+    );
+    //The weight is given to the state of term2 since that is where the merger was set.
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(6.0, 1.0, 0.0))))
+    );
+    //term2's own state has a quarter of the total variance, so it gets three quarters of the weight.
+    term2.borrow_mut().set_state_merger(&variance_weighted);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(6.0, 1.0, 0.0))))
+    );
+    term2.borrow_mut().clear_state_merger();
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(4.0, 2.0, 0.0))))
+    );
+    //Connecting uses the merger of the first terminal with the same order of states.
+    term2.borrow_mut().set_state_merger(&weighted);
+    term1.borrow_mut().disconnect();
+    connect(&term2, &term1);
+    assert_eq!(
+        term1.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(6.0, 1.0, 0.0))))
+    );
+    //term1 only had the merger because it was connected to term2, so it does not keep it.
+    term2.borrow_mut().disconnect();
+    connect(&term1, &term2);
+    assert_eq!(
+        term2.borrow().get(),
+        Ok(Some(Datum::new(Time(1), State::new_raw(4.0, 2.0, 0.0))))
+    );
+}
+#[test]
 fn invert() {
     let mut invert = Invert::new();
     let terminal1 = Terminal::<()>::new();
@@ -419,6 +614,57 @@ fn differential() {
     );
 }
 #[test]
+fn differential_ratios() {
+    let mut differential = Differential::<()>::with_distrust(DifferentialDistrust::Sum);
+    differential.set_ratios(2.0, 1.0);
+    assert_eq!(differential.get_ratios(), (2.0, 1.0));
+    let terminal1 = Terminal::new();
+    let terminal2 = Terminal::new();
+    let terminal_sum = Terminal::new();
+    terminal1
+        .borrow_mut()
+        .set(Datum::new(Time(0), State::new_raw(2.0, 2.0, 2.0)))
+        .unwrap();
+    terminal2
+        .borrow_mut()
+        .set(Datum::new(Time(0), State::new_raw(3.0, 3.0, 3.0)))
+        .unwrap();
+    terminal_sum
+        .borrow_mut()
+        .set(Datum::new(Time(0), State::new_raw(4.0, 4.0, 4.0)))
+        .unwrap();
+    connect(differential.get_side_1(), &terminal1);
+    connect(differential.get_side_2(), &terminal2);
+    connect(differential.get_sum(), &terminal_sum);
+    differential.update().unwrap();
+    //The sum branch is estimated as 2 * 2 + 3 = 7 and averaged with its own terminal's 4.
+    assert_eq!(
+        <rrtk::Terminal<'_, ()> as rrtk::Getter<State, ()>>::get(&terminal_sum.borrow())
+            .unwrap()
+            .unwrap()
+            .value,
+        State::new_raw(5.5, 5.5, 5.5)
+    );
+    differential.set_distrust(DifferentialDistrust::Side2);
+    assert_eq!(differential.get_distrust(), DifferentialDistrust::Side2);
+    differential.update().unwrap();
+    //Side 2 is estimated from the sum branch, now 5.5, and side 1, still 2: 5.5 - 2 * 2 = 1.5.
+    //The terminal state's average with its own terminal is then (1.5 + 3) / 2 = 2.25.
+    assert_eq!(
+        <rrtk::Terminal<'_, ()> as rrtk::Getter<State, ()>>::get(&terminal2.borrow())
+            .unwrap()
+            .unwrap()
+            .value,
+        State::new_raw(2.25, 2.25, 2.25)
+    );
+}
+#[test]
+#[should_panic]
+fn differential_zero_ratio() {
+    let mut differential = Differential::<()>::new();
+    differential.set_ratios(1.0, 0.0);
+}
+#[test]
 fn differential_distrust_side_1() {
     let mut differential = Differential::<()>::with_distrust(DifferentialDistrust::Side1);
     let terminal1 = Terminal::new();
@@ -633,6 +879,80 @@ fn actuator_wrapper() {
     }
 }
 #[test]
+fn actuator_wrapper_disabled() {
+    static mut LAST_COMMAND: Option<Command> = None;
+    struct Actuator {
+        settable_data: SettableData<TerminalData, ()>,
+    }
+    impl Settable<TerminalData, ()> for Actuator {
+        fn get_settable_data_ref(&self) -> &SettableData<TerminalData, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<TerminalData, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: TerminalData) -> NothingOrError<()> {
+            unsafe {
+                LAST_COMMAND = value.command;
+            }
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Actuator {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let mut wrapper = ActuatorWrapper::new(Actuator {
+        settable_data: SettableData::new(),
+    });
+    let terminal = Terminal::new();
+    connect(wrapper.get_terminal(), &terminal);
+    terminal
+        .borrow_mut()
+        .set(Datum::new(
+            Time(1),
+            Command::new(PositionDerivative::Position, 5.0),
+        ))
+        .unwrap();
+    assert!(wrapper.is_enabled());
+    wrapper.disable();
+    assert!(!wrapper.is_enabled());
+    wrapper.update().unwrap();
+    #[allow(static_mut_refs)]
+    unsafe {
+        assert_eq!(
+            LAST_COMMAND,
+            Some(Command::new(PositionDerivative::Velocity, 0.0))
+        );
+    }
+    wrapper.enable();
+    wrapper.update().unwrap();
+    #[allow(static_mut_refs)]
+    unsafe {
+        assert_eq!(
+            LAST_COMMAND,
+            Some(Command::new(PositionDerivative::Position, 5.0))
+        );
+    }
+    //The stop command is sent while disabled even if the terminal has nothing.
+    let mut wrapper = ActuatorWrapper::new(Actuator {
+        settable_data: SettableData::new(),
+    });
+    wrapper.disable();
+    unsafe {
+        LAST_COMMAND = None;
+    }
+    wrapper.update().unwrap();
+    #[allow(static_mut_refs)]
+    unsafe {
+        assert_eq!(
+            LAST_COMMAND,
+            Some(Command::new(PositionDerivative::Velocity, 0.0))
+        );
+    }
+}
+#[test]
 fn getter_state_device_wrapper() {
     struct GetterState;
     impl Getter<State, ()> for GetterState {
@@ -743,3 +1063,382 @@ fn pid_wrapper() {
         assert_eq!(ASSERTS, 4);
     }
 }
+#[test]
+fn command_recorder() {
+    let mut recorder = CommandRecorder::<3, ()>::new();
+    let terminal1 = Terminal::new();
+    let terminal2 = Terminal::new();
+    connect(recorder.get_terminal_1(), &terminal1);
+    connect(recorder.get_terminal_2(), &terminal2);
+    assert!(recorder.is_empty());
+    for i in 1..5 {
+        terminal1
+            .borrow_mut()
+            .set(Datum::new(Time(i), Command::Position(i as f32)))
+            .unwrap();
+        recorder.update().unwrap();
+        //Updating again without a new command should not record anything.
+        recorder.update().unwrap();
+    }
+    assert_eq!(recorder.len(), 3);
+    assert_eq!(
+        recorder.get_recorded(0),
+        Some(Datum::new(Time(4), Command::Position(4.0)))
+    );
+    assert_eq!(
+        recorder.get_recorded(2),
+        Some(Datum::new(Time(2), Command::Position(2.0)))
+    );
+    assert_eq!(recorder.get_recorded(3), None);
+    assert_eq!(
+        History::<Command, ()>::get(&recorder, Time(3)),
+        Some(Datum::new(Time(3), Command::Position(3.0)))
+    );
+    assert_eq!(History::<Command, ()>::get(&recorder, Time(1)), None);
+    assert_eq!(
+        <rrtk::Terminal<'_, ()> as rrtk::Getter<Command, ()>>::get(&terminal2.borrow())
+            .unwrap()
+            .unwrap()
+            .value,
+        Command::Position(4.0)
+    );
+    assert!(recorder.return_to_previous(1, Time(5)).unwrap());
+    assert!(!recorder.return_to_previous(3, Time(5)).unwrap());
+    recorder.update().unwrap();
+    assert_eq!(
+        recorder.get_recorded(0),
+        Some(Datum::new(Time(5), Command::Position(3.0)))
+    );
+    assert_eq!(
+        <rrtk::Terminal<'_, ()> as rrtk::Getter<Command, ()>>::get(&terminal2.borrow())
+            .unwrap()
+            .unwrap()
+            .value,
+        Command::Position(3.0)
+    );
+    recorder.clear();
+    assert!(recorder.is_empty());
+}
+#[test]
+fn solenoid() {
+    use rrtk::devices::pneumatics::*;
+    static mut FORWARD: Option<bool> = None;
+    static mut REVERSE: Option<bool> = None;
+    struct Valve {
+        settable_data: SettableData<bool, ()>,
+        forward: bool,
+    }
+    impl Valve {
+        const fn new(forward: bool) -> Self {
+            Self {
+                settable_data: SettableData::new(),
+                forward: forward,
+            }
+        }
+    }
+    impl Settable<bool, ()> for Valve {
+        fn get_settable_data_ref(&self) -> &SettableData<bool, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<bool, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: bool) -> NothingOrError<()> {
+            unsafe {
+                if self.forward {
+                    //Both sides must never be open at once.
+                    assert!(!(value && REVERSE == Some(true)));
+                    FORWARD = Some(value);
+                } else {
+                    assert!(!(value && FORWARD == Some(true)));
+                    REVERSE = Some(value);
+                }
+            }
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Valve {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let time = static_reference!(Time, Time(0));
+    let mut solenoid = Solenoid::new(Valve::new(true), time.clone(), Time(100));
+    assert_eq!(solenoid.get_output(), None);
+    solenoid.set(true).unwrap();
+    assert_eq!(solenoid.get_output(), Some(true));
+    assert_eq!(unsafe { FORWARD }, Some(true));
+    //Too soon to change again, so the change is held.
+    *time.borrow_mut() = Time(50);
+    solenoid.set(false).unwrap();
+    assert!(solenoid.is_pending());
+    assert_eq!(unsafe { FORWARD }, Some(true));
+    *time.borrow_mut() = Time(100);
+    solenoid.update().unwrap();
+    assert!(!solenoid.is_pending());
+    assert_eq!(solenoid.get_output(), Some(false));
+    assert_eq!(unsafe { FORWARD }, Some(false));
+    //A request cancelled before it is applied does nothing.
+    *time.borrow_mut() = Time(150);
+    solenoid.set(true).unwrap();
+    solenoid.set(false).unwrap();
+    assert!(!solenoid.is_pending());
+
+    let mut double =
+        DoubleSolenoid::new(Valve::new(true), Valve::new(false), time.clone(), Time(0));
+    double.set(DoubleSolenoidState::Forward).unwrap();
+    assert_eq!(unsafe { (FORWARD, REVERSE) }, (Some(true), Some(false)));
+    double.set(DoubleSolenoidState::Reverse).unwrap();
+    assert_eq!(unsafe { (FORWARD, REVERSE) }, (Some(false), Some(true)));
+    double.set(DoubleSolenoidState::Off).unwrap();
+    assert_eq!(unsafe { (FORWARD, REVERSE) }, (Some(false), Some(false)));
+    assert_eq!(double.get_output(), Some(DoubleSolenoidState::Off));
+    double.update().unwrap();
+}
+#[test]
+fn servo_wrapper() {
+    static mut OUTPUT: Option<f32> = None;
+    struct Servo {
+        settable_data: SettableData<f32, ()>,
+    }
+    impl Settable<f32, ()> for Servo {
+        fn get_settable_data_ref(&self) -> &SettableData<f32, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: f32) -> NothingOrError<()> {
+            unsafe {
+                OUTPUT = Some(value);
+            }
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Servo {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let time = static_reference!(Time, Time(0));
+    let mut wrapper = ServoWrapper::new(
+        Servo {
+            settable_data: SettableData::new(),
+        },
+        time.clone(),
+        0.0,
+        100.0,
+        1000.0,
+        2000.0,
+        25.0,
+    );
+    assert_eq!(wrapper.to_output(25.0), 1250.0);
+    let term = Terminal::new();
+    connect(wrapper.get_terminal(), &term);
+    term.borrow_mut()
+        .set(Datum::new(Time(0), Command::Position(150.0)))
+        .unwrap();
+    wrapper.update().unwrap();
+    assert_eq!(wrapper.get_target(), Some(100.0));
+    assert_eq!(unsafe { OUTPUT }, Some(2000.0));
+    assert_eq!(
+        term.borrow().get(),
+        Ok(Some(Datum::new(Time(0), State::new_raw(100.0, 0.0, 0.0))))
+    );
+    *time.borrow_mut() = Time(1_000_000_000);
+    term.borrow_mut()
+        .set(Datum::new(Time(1_000_000_000), Command::Position(50.0)))
+        .unwrap();
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { OUTPUT }, Some(1500.0));
+    //The modeled servo can only move at its maximum speed.
+    assert_eq!(
+        term.borrow().get(),
+        Ok(Some(Datum::new(
+            Time(1_000_000_000),
+            State::new_raw(75.0, -25.0, 0.0)
+        )))
+    );
+    //Disabling stops new positions from being sent, but the model keeps moving.
+    wrapper.set_enabled(false);
+    term.borrow_mut()
+        .set(Datum::new(Time(1_000_000_000), Command::Position(0.0)))
+        .unwrap();
+    *time.borrow_mut() = Time(2_000_000_000);
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { OUTPUT }, Some(1500.0));
+    assert_eq!(
+        term.borrow().get(),
+        Ok(Some(Datum::new(
+            Time(2_000_000_000),
+            State::new_raw(50.0, -25.0, 0.0)
+        )))
+    );
+    //A time getter going backwards does not move the model.
+    *time.borrow_mut() = Time(1_500_000_000);
+    wrapper.update().unwrap();
+    assert_eq!(
+        term.borrow().get(),
+        Ok(Some(Datum::new(
+            Time(1_500_000_000),
+            State::new_raw(50.0, -25.0, 0.0)
+        )))
+    );
+}
+#[test]
+#[should_panic]
+fn servo_wrapper_negative_speed() {
+    let time = static_reference!(Time, Time(0));
+    let _ = ServoWrapper::new(
+        ConstantGetter::<f32, Time, ()>::new(time.clone(), 0.0),
+        time,
+        0.0,
+        100.0,
+        1000.0,
+        2000.0,
+        -25.0,
+    );
+}
+#[test]
+fn hardware_error_default() {
+    use rrtk::devices::pneumatics::*;
+    struct DisconnectedValve {
+        settable_data: SettableData<bool, HardwareError>,
+    }
+    impl Settable<bool, HardwareError> for DisconnectedValve {
+        fn get_settable_data_ref(&self) -> &SettableData<bool, HardwareError> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<bool, HardwareError> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, _: bool) -> NothingOrError<HardwareError> {
+            Err(Error::Other(HardwareError::Disconnected))
+        }
+    }
+    impl Updatable<HardwareError> for DisconnectedValve {
+        fn update(&mut self) -> NothingOrError<HardwareError> {
+            Ok(())
+        }
+    }
+    //The error type does not need to be named.
+    let mut solenoid: Solenoid<_, Time> = Solenoid::new(
+        DisconnectedValve {
+            settable_data: SettableData::new(),
+        },
+        static_reference!(Time, Time(0)),
+        Time(0),
+    );
+    assert_eq!(
+        solenoid.set(true),
+        Err(Error::Other(HardwareError::Disconnected))
+    );
+}
+#[test]
+fn stream_bridge() {
+    let state = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(3)), State::new_raw(1.0, 2.0, 0.0))
+    );
+    let command = static_reference!(
+        ConstantGetter<Command, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(3)), Command::Velocity(0.0))
+    );
+    let mut bridge = StreamBridge::new(state.clone(), command.clone());
+    let term = Terminal::new();
+    connect(bridge.get_terminal(), &term);
+    term.borrow_mut()
+        .set(Datum::new(Time(2), Command::Position(5.0)))
+        .unwrap();
+    bridge.update().unwrap();
+    assert_eq!(
+        command.borrow().get(),
+        Ok(Some(Datum::new(Time(3), Command::Position(5.0))))
+    );
+    assert_eq!(
+        term.borrow().get(),
+        Ok(Some(Datum::new(Time(3), State::new_raw(1.0, 2.0, 0.0))))
+    );
+}
+#[test]
+fn velocity_limited_position_wrapper() {
+    static mut LAST_COMMAND: Option<Command> = None;
+    struct Motor {
+        settable_data: SettableData<Command, ()>,
+    }
+    impl Settable<Command, ()> for Motor {
+        fn get_settable_data_ref(&self) -> &SettableData<Command, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<Command, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: Command) -> NothingOrError<()> {
+            unsafe {
+                LAST_COMMAND = Some(value);
+            }
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Motor {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let mut wrapper = VelocityLimitedPositionWrapper::new(
+        Motor {
+            settable_data: SettableData::new(),
+        },
+        2.0,
+        10.0,
+    );
+    assert_eq!(
+        wrapper.convert(Command::Velocity(-20.0), None),
+        Command::Velocity(-10.0)
+    );
+    let term = Terminal::new();
+    connect(wrapper.get_terminal(), &term);
+    term.borrow_mut()
+        .set(Datum::new(Time(1), Command::Position(100.0)))
+        .unwrap();
+    //Without a state, the motor is stopped.
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { LAST_COMMAND }, Some(Command::Velocity(0.0)));
+    term.borrow_mut()
+        .set(Datum::new(Time(1), State::new_raw(0.0, 0.0, 0.0)))
+        .unwrap();
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { LAST_COMMAND }, Some(Command::Velocity(10.0)));
+    term.borrow_mut()
+        .set(Datum::new(Time(2), State::new_raw(97.0, 0.0, 0.0)))
+        .unwrap();
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { LAST_COMMAND }, Some(Command::Velocity(6.0)));
+    wrapper.disable();
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { LAST_COMMAND }, Some(Command::Velocity(0.0)));
+    //The stop command is sent while disabled even if there is no command at all.
+    let mut wrapper = VelocityLimitedPositionWrapper::new(
+        Motor {
+            settable_data: SettableData::new(),
+        },
+        2.0,
+        10.0,
+    );
+    wrapper.disable();
+    unsafe {
+        LAST_COMMAND = None;
+    }
+    wrapper.update().unwrap();
+    assert_eq!(unsafe { LAST_COMMAND }, Some(Command::Velocity(0.0)));
+}
+#[test]
+#[should_panic]
+fn velocity_limited_position_wrapper_nan_speed() {
+    let time = static_reference!(Time, Time(0));
+    let _ = VelocityLimitedPositionWrapper::new(
+        ConstantGetter::<Command, Time, ()>::new(time, Command::Velocity(0.0)),
+        2.0,
+        f32::NAN,
+    );
+}
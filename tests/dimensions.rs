@@ -34,7 +34,10 @@ fn time_try_from_quantity_success() {
 fn time_try_from_quantity_failure() {
     let x = Quantity::new(5.0, MILLIMETER);
     let x = Time::try_from(x);
-    assert_eq!(x, Err(()));
+    assert_eq!(
+        x,
+        Err(UnitMismatch::new(SECOND, MILLIMETER).with_operation("Time::try_from"))
+    );
 }
 #[test]
 fn quantity_from_time() {
@@ -141,7 +144,11 @@ fn dimensionless_integer_try_from_quantity_success() {
 fn dimensionless_integer_try_from_quantity_failure() {
     let x = Quantity::new(5.0, MILLIMETER);
     let x = DimensionlessInteger::try_from(x);
-    assert_eq!(x, Err(()));
+    assert_eq!(
+        x,
+        Err(UnitMismatch::new(DIMENSIONLESS, MILLIMETER)
+            .with_operation("DimensionlessInteger::try_from"))
+    );
 }
 #[test]
 fn quantity_from_dimensionless_integer() {
@@ -512,6 +519,40 @@ fn quantity_from_command() {
     );
 }
 #[test]
+fn command_checked_constructors() {
+    assert_eq!(
+        Command::from_position(Quantity::new(5.0, MILLIMETER)),
+        Ok(Command::Position(5.0))
+    );
+    assert_eq!(
+        Command::from_velocity(Quantity::new(5.0, MILLIMETER_PER_SECOND)),
+        Ok(Command::Velocity(5.0))
+    );
+    assert_eq!(
+        Command::from_acceleration(Quantity::new(5.0, MILLIMETER_PER_SECOND_SQUARED)),
+        Ok(Command::Acceleration(5.0))
+    );
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn command_checked_constructors_failure() {
+    assert_eq!(
+        Command::from_velocity(Quantity::new(5.0, MILLIMETER)),
+        Err(UnitMismatch::new(MILLIMETER_PER_SECOND, MILLIMETER).with_operation("Command::try_new"))
+    );
+    assert_eq!(
+        Command::try_new(
+            PositionDerivative::Position,
+            Quantity::new(5.0, MILLIMETER_PER_SECOND_SQUARED)
+        ),
+        Err(UnitMismatch::new(MILLIMETER, MILLIMETER_PER_SECOND_SQUARED)
+            .with_operation("Command::try_new"))
+    );
+}
+#[test]
 fn f32_from_quantity() {
     assert_eq!(5.0, f32::from(Quantity::new(5.0, MILLIMETER_PER_SECOND)));
 }
@@ -701,3 +742,159 @@ fn quantity_partial_ord_failure() {
     let _ = Quantity::new(5.0, MILLIMETER_PER_SECOND)
         < Quantity::new(8.0, MILLIMETER_PER_SECOND_SQUARED);
 }
+#[test]
+fn unit_sqrt() {
+    MILLIMETER_SQUARED.sqrt().assert_eq_assume_ok(&MILLIMETER);
+    DIMENSIONLESS.sqrt().assert_eq_assume_ok(&DIMENSIONLESS);
+}
+#[test]
+#[should_panic]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn unit_sqrt_failure() {
+    let _ = MILLIMETER.sqrt();
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn unit_checked_sqrt() {
+    MILLIMETER_SQUARED
+        .checked_sqrt()
+        .unwrap()
+        .assert_eq_assume_ok(&MILLIMETER);
+    assert_eq!(
+        MILLIMETER_PER_SECOND.checked_sqrt(),
+        Err(UnitMismatch::new(DIMENSIONLESS, MILLIMETER_PER_SECOND).with_operation("square root"))
+    );
+    assert_eq!(
+        Unit::new(3, -2).checked_sqrt(),
+        Err(UnitMismatch::new(Unit::new(2, -2), Unit::new(3, -2)).with_operation("square root"))
+    );
+}
+#[test]
+#[cfg(feature = "uom")]
+fn uom_conversions() {
+    use uom::si::f32::{Length, Time as UomTime, Velocity};
+    use uom::si::{length, time, velocity};
+    let length = Length::new::<length::meter>(1.5);
+    let quantity = Quantity::from(length);
+    assert!((quantity.value - 1500.0).abs() < 0.001);
+    quantity.unit.assert_eq_assume_ok(&MILLIMETER);
+    let back = Length::try_from(quantity).unwrap();
+    assert!((back.get::<length::meter>() - 1.5).abs() < 0.000001);
+    let velocity = Velocity::try_from(Quantity::new(250.0, MILLIMETER_PER_SECOND)).unwrap();
+    assert!((velocity.get::<velocity::meter_per_second>() - 0.25).abs() < 0.000001);
+    assert!((Time::from(UomTime::new::<time::millisecond>(20.0)).0 - 20_000_000).abs() < 10);
+    assert!((UomTime::from(Time(2_000_000_000)).get::<time::second>() - 2.0).abs() < 0.000001);
+}
+#[test]
+#[cfg(all(
+    feature = "uom",
+    any(
+        feature = "dim_check_release",
+        all(debug_assertions, feature = "dim_check_debug")
+    )
+))]
+fn uom_conversions_wrong_unit() {
+    use uom::si::f32::Length;
+    assert_eq!(
+        Length::try_from(Quantity::new(1.0, SECOND)),
+        Err(UnitMismatch::new(MILLIMETER, SECOND).with_operation("Length::try_from"))
+    );
+}
+#[test]
+fn quantity_checked_ops() {
+    let x = Quantity::new(3.0, MILLIMETER);
+    let y = Quantity::new(2.0, MILLIMETER);
+    assert_eq!(x.checked_add(y), Ok(Quantity::new(5.0, MILLIMETER)));
+    assert_eq!(x.checked_sub(y), Ok(Quantity::new(1.0, MILLIMETER)));
+    assert_eq!(x.expect_unit(MILLIMETER), Ok(x));
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn quantity_checked_ops_mismatch() {
+    let x = Quantity::new(3.0, MILLIMETER);
+    let y = Quantity::new(2.0, SECOND);
+    assert_eq!(
+        x.checked_add(y),
+        Err(UnitMismatch::new(MILLIMETER, SECOND).with_operation("addition"))
+    );
+    assert_eq!(
+        x.checked_sub(y),
+        Err(UnitMismatch::new(MILLIMETER, SECOND).with_operation("subtraction"))
+    );
+    assert_eq!(
+        x.expect_unit(SECOND),
+        Err(UnitMismatch::new(SECOND, MILLIMETER))
+    );
+    let error: Error<()> = x.checked_add(y).unwrap_err().into();
+    assert_eq!(
+        error,
+        Error::UnitInvalid(UnitMismatch::new(MILLIMETER, SECOND).with_operation("addition"))
+    );
+}
+#[test]
+fn quantity_parse() {
+    let x: Quantity = "123.5 mm/s".parse().unwrap();
+    assert_eq!(x, Quantity::new(123.5, MILLIMETER_PER_SECOND));
+    assert_eq!(
+        "-2 mm*s / s^2".parse::<Quantity>(),
+        Ok(Quantity::new(-2.0, MILLIMETER_PER_SECOND))
+    );
+    assert_eq!("3".parse::<Quantity>(), Ok(Quantity::dimensionless(3.0)));
+    assert_eq!(
+        "abc mm".parse::<Quantity>(),
+        Err(ParseQuantityError::InvalidNumber)
+    );
+    assert_eq!(
+        "1 furlong".parse::<Quantity>(),
+        Err(ParseQuantityError::InvalidUnit)
+    );
+    assert_eq!("1500 ns".parse::<Time>(), Ok(Time(1500)));
+    assert_eq!("1.5 s".parse::<Time>(), Ok(Time(1_500_000_000)));
+    assert_eq!(Time(1500).to_string(), "1500 ns");
+    assert_eq!(
+        "-4".parse::<DimensionlessInteger>(),
+        Ok(DimensionlessInteger(-4))
+    );
+    assert_eq!(DimensionlessInteger(-4).to_string(), "-4");
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn quantity_display() {
+    assert_eq!(
+        Quantity::new(123.4, MILLIMETER_PER_SECOND).to_string(),
+        "123.4 mm/s"
+    );
+    assert_eq!(
+        format!("{:.1}", Quantity::new(2.25, MILLIMETER_PER_SECOND_SQUARED)),
+        "2.2 mm/s^2"
+    );
+    assert_eq!(Quantity::dimensionless(3.0).to_string(), "3");
+    assert_eq!(INVERSE_SECOND.to_string(), "1/s");
+    assert_eq!(INVERSE_MILLIMETER_SQUARED_SECOND.to_string(), "1/mm^2/s");
+    assert_eq!(Unit::new(2, 1).to_string(), "mm^2*s");
+    for unit in [
+        INVERSE_MILLIMETER_SQUARED_SECOND,
+        SECOND_CUBED_PER_MILLIMETER,
+        MILLIMETER_PER_SECOND_CUBED,
+        DIMENSIONLESS,
+    ] {
+        assert_eq!(unit.to_string().parse::<Unit>(), Ok(unit));
+    }
+    assert_eq!(
+        "1 s".parse::<DimensionlessInteger>(),
+        Err(ParseQuantityError::InvalidNumber)
+    );
+    assert_eq!("1 mm".parse::<Time>(), Err(ParseQuantityError::InvalidUnit));
+}
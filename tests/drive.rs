@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(feature = "std")]
+use rrtk::drive::*;
+use rrtk::*;
+use std::cell::Cell;
+use std::rc::Rc;
+struct Encoder {
+    time: Reference<Time>,
+    position: f32,
+}
+impl Getter<State, ()> for Encoder {
+    fn get(&self) -> Output<State, ()> {
+        Ok(Some(Datum::new(
+            *self.time.borrow(),
+            State::new_raw(self.position, 0.0, 0.0),
+        )))
+    }
+}
+impl Updatable<()> for Encoder {
+    fn update(&mut self) -> NothingOrError<()> {
+        Ok(())
+    }
+}
+type MotorOutput = Rc<Cell<f32>>;
+struct Motor {
+    settable_data: SettableData<f32, ()>,
+    output: MotorOutput,
+}
+impl Settable<f32, ()> for Motor {
+    fn get_settable_data_ref(&self) -> &SettableData<f32, ()> {
+        &self.settable_data
+    }
+    fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, ()> {
+        &mut self.settable_data
+    }
+    fn impl_set(&mut self, value: f32) -> NothingOrError<()> {
+        self.output.set(value);
+        Ok(())
+    }
+}
+impl Updatable<()> for Motor {
+    fn update(&mut self) -> NothingOrError<()> {
+        Ok(())
+    }
+}
+const K_VALUES: PositionDerivativeDependentPIDKValues = PositionDerivativeDependentPIDKValues::new(
+    PIDKValues::new(1.0, 0.0, 0.0),
+    PIDKValues::new(1.0, 0.0, 0.0),
+    PIDKValues::new(1.0, 0.0, 0.0),
+);
+fn setup() -> (
+    Reference<Time>,
+    DifferentialDriveHardware<Encoder, Motor, Time>,
+    MotorOutput,
+    MotorOutput,
+) {
+    let time = rc_ref_cell_reference(Time(0));
+    let left_encoder = rc_ref_cell_reference(Encoder {
+        time: time.clone(),
+        position: 100.0,
+    });
+    let right_encoder = rc_ref_cell_reference(Encoder {
+        time: time.clone(),
+        position: 200.0,
+    });
+    let left_output = Rc::new(Cell::new(0.0));
+    let right_output = Rc::new(Cell::new(0.0));
+    let left_motor = Motor {
+        settable_data: SettableData::new(),
+        output: left_output.clone(),
+    };
+    let right_motor = Motor {
+        settable_data: SettableData::new(),
+        output: right_output.clone(),
+    };
+    let hardware = DifferentialDriveHardware {
+        left_encoder: left_encoder,
+        right_encoder: right_encoder,
+        left_motor: left_motor,
+        right_motor: right_motor,
+        time_getter: time.clone(),
+    };
+    (time, hardware, left_output, right_output)
+}
+#[test]
+fn drive_straight() {
+    let (time, hardware, left_output, right_output) = setup();
+    let mut drive = DifferentialDriveMove::drive_straight(
+        hardware,
+        Quantity::new(10.0, MILLIMETER),
+        Quantity::new(5.0, MILLIMETER_PER_SECOND),
+        Quantity::new(5.0, MILLIMETER_PER_SECOND_SQUARED),
+        K_VALUES,
+    )
+    .unwrap();
+    assert_eq!(drive.get(), Ok(None));
+    drive.update().unwrap();
+    assert!(!drive.done());
+    //The profile takes 3 seconds.
+    *time.borrow_mut() = Time(3_000_000_000);
+    drive.update().unwrap();
+    assert!(drive.done());
+    assert_eq!(drive.get(), Ok(Some(Datum::new(Time(3_000_000_000), true))));
+    //The encoders have not moved, so each side should be 10 mm from its end position.
+    assert_eq!(left_output.get(), 10.0);
+    assert_eq!(right_output.get(), 10.0);
+}
+#[test]
+fn turn_in_place() {
+    let (time, hardware, left_output, right_output) = setup();
+    let mut drive = DifferentialDriveMove::turn_in_place(
+        hardware,
+        core::f32::consts::PI,
+        Quantity::new(20.0 / core::f32::consts::PI, MILLIMETER),
+        Quantity::new(5.0, MILLIMETER_PER_SECOND),
+        Quantity::new(5.0, MILLIMETER_PER_SECOND_SQUARED),
+        K_VALUES,
+    )
+    .unwrap();
+    drive.update().unwrap();
+    *time.borrow_mut() = Time(3_000_000_000);
+    drive.update().unwrap();
+    assert!(drive.done());
+    assert!((left_output.get() + 10.0).abs() < 0.001);
+    assert!((right_output.get() - 10.0).abs() < 0.001);
+}
+#[test]
+fn drive_straight_infeasible() {
+    let (_, hardware, _, _) = setup();
+    assert!(matches!(
+        DifferentialDriveMove::drive_straight(
+            hardware,
+            Quantity::new(10.0, MILLIMETER),
+            Quantity::new(5.0, MILLIMETER_PER_SECOND),
+            Quantity::new(0.0, MILLIMETER_PER_SECOND_SQUARED),
+            K_VALUES,
+        ),
+        Err(MotionProfileError::ZeroAcceleration)
+    ));
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn drive_straight_unit_invalid() {
+    let (_, hardware, _, _) = setup();
+    assert!(matches!(
+        DifferentialDriveMove::drive_straight(
+            hardware,
+            Quantity::new(10.0, MILLIMETER_PER_SECOND),
+            Quantity::new(5.0, MILLIMETER_PER_SECOND),
+            Quantity::new(1.0, MILLIMETER_PER_SECOND_SQUARED),
+            K_VALUES,
+        ),
+        Err(MotionProfileError::UnitInvalid(_))
+    ));
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use rrtk::events::*;
+use rrtk::*;
+#[test]
+fn event_log() {
+    let mut log = EventLog::<3>::new();
+    assert!(log.is_empty());
+    assert_eq!(log.get(0), None);
+    log.log(Time(1), Severity::Info, 1000, 1.0);
+    log.log(Time(2), Severity::Warning, 1001, 2.0);
+    assert_eq!(log.len(), 2);
+    assert_eq!(
+        log.get(0),
+        Some(Event::new(Time(2), Severity::Warning, 1001, 2.0))
+    );
+    assert_eq!(
+        log.get(1),
+        Some(Event::new(Time(1), Severity::Info, 1000, 1.0))
+    );
+    assert_eq!(log.get(2), None);
+    log.log(Time(3), Severity::Error, 1002, 3.0);
+    log.push(Event::new(Time(4), Severity::Critical, 1003, 4.0));
+    assert_eq!(log.len(), 3);
+    assert_eq!(log.get_overwritten(), 1);
+    let times: Vec<Time> = log.snapshot().map(|event| event.time).collect();
+    assert_eq!(times, [Time(2), Time(3), Time(4)]);
+    assert_eq!(log.len(), 3);
+    let codes: Vec<u16> = log.drain().map(|event| event.code).collect();
+    assert_eq!(codes, [1001, 1002, 1003]);
+    assert!(log.is_empty());
+    assert_eq!(log.get_overwritten(), 0);
+    assert_eq!(log.snapshot().next(), None);
+    assert!(Severity::Critical > Severity::Warning);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn enable_line_event_log() {
+    let time = rc_ref_cell_reference(Time(0));
+    let log = rc_ref_cell_reference(EventLog::<4>::new());
+    let mut line = EnableLine::<()>::new();
+    line.set_event_log(to_dyn!(EventSink, log.clone()));
+    line.set_watchdog(Time(1_000_000_000), to_dyn!(TimeGetter<()>, time.clone()));
+    //Being tripped before the first feed is not logged.
+    line.update().unwrap();
+    assert!(log.borrow().is_empty());
+    line.feed().unwrap();
+    line.update().unwrap();
+    assert!(log.borrow().is_empty());
+    *time.borrow_mut() = Time(1_500_000_000);
+    line.update().unwrap();
+    line.update().unwrap();
+    assert_eq!(log.borrow().len(), 1);
+    assert_eq!(
+        log.borrow().get(0),
+        Some(Event::new(
+            Time(1_500_000_000),
+            Severity::Error,
+            WATCHDOG_TRIPPED,
+            1.0
+        ))
+    );
+    *time.borrow_mut() = Time(2_000_000_000);
+    line.feed().unwrap();
+    line.update().unwrap();
+    assert_eq!(log.borrow().len(), 1);
+    line.reset_watchdog();
+    line.feed().unwrap();
+    line.update().unwrap();
+    assert_eq!(
+        log.borrow().get(0),
+        Some(Event::new(
+            Time(2_000_000_000),
+            Severity::Info,
+            WATCHDOG_RECOVERED,
+            0.0
+        ))
+    );
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn following_error_monitor_event_log() {
+    struct Target;
+    impl History<Command, ()> for Target {
+        fn get(&self, time: Time) -> Option<Datum<Command>> {
+            Some(Datum::new(time, Command::Position(10.0)))
+        }
+    }
+    impl Updatable<()> for Target {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let time = rc_ref_cell_reference(Time(0));
+    let state = rc_ref_cell_reference(ConstantGetter::<State, Time, ()>::new(
+        time.clone(),
+        State::new_raw(4.0, 0.0, 0.0),
+    ));
+    let log = rc_ref_cell_reference(EventLog::<4>::new());
+    let mut monitor = streams::control::FollowingErrorMonitor::new(
+        rc_ref_cell_reference(Target),
+        state,
+        time.clone(),
+        1.0,
+        Time(2),
+        Command::Velocity(0.0),
+    );
+    monitor.set_event_log(to_dyn!(EventSink, log.clone()));
+    monitor.update().unwrap();
+    assert!(log.borrow().is_empty());
+    *time.borrow_mut() = Time(2);
+    monitor.update().unwrap();
+    *time.borrow_mut() = Time(3);
+    monitor.update().unwrap();
+    assert_eq!(log.borrow().len(), 1);
+    assert_eq!(
+        log.borrow().get(0),
+        Some(Event::new(
+            Time(2),
+            Severity::Error,
+            FOLLOWING_ERROR_FAULT,
+            6.0
+        ))
+    );
+}
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(any(feature = "std", feature = "libm"))]
+use core::f32::consts::FRAC_PI_2;
+use rrtk::frames::*;
+use rrtk::*;
+fn assert_transform_close(a: Transform2d, b: Transform2d) {
+    assert!(
+        (a.x - b.x).abs() < 0.001 && (a.y - b.y).abs() < 0.001 && (a.angle - b.angle).abs() < 0.001,
+        "{:?} is not close to {:?}",
+        a,
+        b
+    );
+}
+#[test]
+fn transform_2d() {
+    let transform = Transform2d::new(1.0, 2.0, FRAC_PI_2);
+    let (x, y) = transform.transform_point(1.0, 0.0);
+    assert!((x - 1.0).abs() < 0.001 && (y - 3.0).abs() < 0.001);
+    assert_transform_close(transform * transform.inverse(), Transform2d::identity());
+    assert_transform_close(transform.inverse() * transform, Transform2d::identity());
+}
+#[test]
+fn frame_registry() {
+    struct Odometry;
+    impl Getter<Transform2d, ()> for Odometry {
+        fn get(&self) -> Output<Transform2d, ()> {
+            Ok(Some(Datum::new(
+                Time(0),
+                Transform2d::new(1000.0, 500.0, FRAC_PI_2),
+            )))
+        }
+    }
+    impl Updatable<()> for Odometry {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let mut registry = FrameRegistry::<4, ()>::new();
+    let field = registry.add_root("field").unwrap();
+    let robot = registry
+        .add_dynamic(
+            "robot",
+            field,
+            to_dyn!(Getter<Transform2d, ()>, static_reference!(Odometry, Odometry)),
+        )
+        .unwrap();
+    let turret = registry
+        .add_static("turret", robot, Transform2d::new(100.0, 0.0, 0.0))
+        .unwrap();
+    assert_eq!(registry.find("turret"), Some(turret));
+    assert_eq!(registry.find("arm"), None);
+    assert_eq!(registry.add_root("field"), Err(FrameError::DuplicateName));
+    //The turret is 100 mm in front of the robot, which is facing the +y direction of the field.
+    assert_transform_close(
+        registry.lookup(turret, field, Time(0)).unwrap(),
+        Transform2d::new(1000.0, 600.0, FRAC_PI_2),
+    );
+    assert_transform_close(
+        registry.lookup(field, turret, Time(0)).unwrap(),
+        Transform2d::new(1000.0, 600.0, FRAC_PI_2).inverse(),
+    );
+    assert_transform_close(
+        registry.lookup(turret, robot, Time(0)).unwrap(),
+        Transform2d::new(100.0, 0.0, 0.0),
+    );
+    let other = registry.add_root("other").unwrap();
+    assert_eq!(
+        registry.lookup(turret, other, Time(0)),
+        Err(FrameError::NotConnected)
+    );
+    assert_eq!(registry.add_root("extra"), Err(FrameError::Full));
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use rrtk::linalg::*;
+fn assert_matrix_close<const R: usize, const C: usize>(a: Matrix<R, C>, b: Matrix<R, C>) {
+    for i in 0..R {
+        for j in 0..C {
+            assert!(
+                (a.get(i, j) - b.get(i, j)).abs() < 0.0001,
+                "{:?} is not close to {:?}",
+                a,
+                b
+            );
+        }
+    }
+}
+#[test]
+fn matrix_ops() {
+    let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = Matrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+    assert_eq!(a * b, Matrix::new([[58.0, 64.0], [139.0, 154.0]]));
+    assert_eq!(
+        a.transpose(),
+        Matrix::new([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]])
+    );
+    assert_eq!(a + a, Matrix::new([[2.0, 4.0, 6.0], [8.0, 10.0, 12.0]]));
+    assert_eq!(a - a, Matrix::zeros());
+    assert_eq!(a * 2.0, a + a);
+    assert_eq!((a * 2.0) / 2.0, a);
+    assert_eq!(-a, a * -1.0);
+    let mut c = a;
+    c.set(0, 0, 10.0);
+    assert_eq!(c.get(0, 0), 10.0);
+    assert_eq!(
+        Matrix::<2, 2>::identity(),
+        Matrix::new([[1.0, 0.0], [0.0, 1.0]])
+    );
+}
+#[test]
+fn vector() {
+    let v = Vector::from_elements([1.0, 2.0, 3.0]);
+    let w = Vector::from_elements([4.0, 5.0, 6.0]);
+    assert_eq!(v.dot(&w), 32.0);
+    assert_eq!(v.transpose() * w, Matrix::new([[32.0]]));
+    assert_eq!(Matrix::<3, 3>::identity() * v, v);
+}
+#[test]
+fn solve() {
+    //The first pivot is zero, so this requires row swapping.
+    let a = Matrix::new([[0.0, 2.0, 1.0], [1.0, 1.0, 1.0], [2.0, 1.0, 3.0]]);
+    let x = Vector::from_elements([1.0, -2.0, 3.0]);
+    let b = a * x;
+    assert_matrix_close(a.solve(&b).unwrap(), x);
+    assert_matrix_close(a * a.inverse().unwrap(), Matrix::identity());
+    let singular = Matrix::new([[1.0, 2.0], [2.0, 4.0]]);
+    assert_eq!(singular.inverse(), None);
+    //Whether a matrix is singular does not depend on its scale.
+    let small = Matrix::new([[1e-8, 0.0], [0.0, 2e-8]]);
+    assert_matrix_close(small * small.inverse().unwrap(), Matrix::identity());
+    let large_singular = Matrix::new([[1e8, 2e8], [2e8, 4e8 + 64.0]]);
+    assert_eq!(large_singular.inverse(), None);
+    assert_eq!(Matrix::<2, 2>::zeros().inverse(), None);
+}
+#[test]
+fn polynomial() {
+    let polynomial = Polynomial::new([1.0, -2.0, 0.5]);
+    assert_eq!(polynomial.evaluate(0.0), 1.0);
+    assert_eq!(polynomial.evaluate(2.0), -1.0);
+    let points: [(f32, f32); 6] = core::array::from_fn(|i| {
+        let x = i as f32;
+        (x, polynomial.evaluate(x))
+    });
+    let fitted = Polynomial::<3>::fit(&points).unwrap();
+    for (fitted, actual) in fitted.coefficients.iter().zip(polynomial.coefficients) {
+        assert!((fitted - actual).abs() < 0.001);
+    }
+    //A line through noisy points is the least-squares fit.
+    let line = Polynomial::<2>::fit(&[(0.0, 0.0), (1.0, 2.0), (2.0, 2.0)]).unwrap();
+    assert!((line.coefficients[0] - 1.0 / 3.0).abs() < 0.001);
+    assert!((line.coefficients[1] - 1.0).abs() < 0.001);
+    //Points far from x = 0 are fitted as well as those near it.
+    let offset: [(f32, f32); 6] = core::array::from_fn(|i| {
+        let x = 1000.0 + i as f32 * 0.5;
+        (x, 0.25 * (x - 1001.0) * (x - 1001.0) + 3.0)
+    });
+    let fitted = Polynomial::<3>::fit(&offset).unwrap();
+    for (x, y) in offset {
+        assert!((fitted.evaluate(x) - y).abs() < 0.01);
+    }
+    //Two points cannot determine a quadratic.
+    assert_eq!(Polynomial::<3>::fit(&[(0.0, 0.0), (1.0, 1.0)]), None);
+}
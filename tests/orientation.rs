@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(any(feature = "std", feature = "libm"))]
+use core::f32::consts::{FRAC_PI_2, PI};
+use rrtk::linalg::*;
+use rrtk::orientation::*;
+use rrtk::*;
+fn assert_vector_close(a: Vector<3>, b: Vector<3>) {
+    for i in 0..3 {
+        assert!(
+            (a.get(i, 0) - b.get(i, 0)).abs() < 0.001,
+            "{:?} is not close to {:?}",
+            a,
+            b
+        );
+    }
+}
+fn assert_quaternion_close(a: UnitQuaternion, b: UnitQuaternion) {
+    let (a, b) = (a.components(), b.components());
+    //q and -q are the same rotation.
+    let sign = if a.iter().zip(b.iter()).map(|(a, b)| a * b).sum::<f32>() < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    for i in 0..4 {
+        assert!(
+            (a[i] - sign * b[i]).abs() < 0.001,
+            "{:?} is not close to {:?}",
+            a,
+            b
+        );
+    }
+}
+const X: Vector<3> = Vector::from_elements([1.0, 0.0, 0.0]);
+const Y: Vector<3> = Vector::from_elements([0.0, 1.0, 0.0]);
+const Z: Vector<3> = Vector::from_elements([0.0, 0.0, 1.0]);
+#[test]
+fn unit_quaternion() {
+    let yaw = UnitQuaternion::from_axis_angle(Z * 2.0, FRAC_PI_2);
+    assert!((yaw.angle() - FRAC_PI_2).abs() < 0.001);
+    assert_vector_close(yaw.rotate_vector(X), Y);
+    assert_vector_close(yaw.inverse().rotate_vector(Y), X);
+    assert_quaternion_close(yaw * yaw.inverse(), UnitQuaternion::identity());
+    let pitch = UnitQuaternion::from_axis_angle(Y, FRAC_PI_2);
+    //Yaw and then pitch. Pitching about y does not move the result of the yaw.
+    assert_vector_close((pitch * yaw).rotate_vector(X), Y);
+    //Pitch and then yaw.
+    assert_vector_close((yaw * pitch).rotate_vector(X), -Z);
+    assert_eq!(
+        UnitQuaternion::new(2.0, 0.0, 0.0, 0.0),
+        UnitQuaternion::identity()
+    );
+    assert_eq!(
+        UnitQuaternion::from_axis_angle(Vector::zeros(), 1.0),
+        UnitQuaternion::identity()
+    );
+}
+#[test]
+fn unit_quaternion_integrate() {
+    let mut orientation = UnitQuaternion::identity();
+    //Spin at π/2 radians per second about z for a second in 100 steps.
+    for _ in 0..100 {
+        orientation = orientation.integrate(Z * FRAC_PI_2, Time(10_000_000));
+    }
+    assert_quaternion_close(orientation, UnitQuaternion::from_axis_angle(Z, FRAC_PI_2));
+    //Rates are in the body frame, so rolling now turns about what started as the y axis.
+    let orientation = orientation.integrate(X * PI, Time(500_000_000));
+    assert_vector_close(orientation.rotate_vector(Z), X);
+}
+#[test]
+fn rotation3() {
+    let yaw = Rotation3::from_axis_angle(Z, FRAC_PI_2);
+    let pitch = Rotation3::from_axis_angle(Y, FRAC_PI_2);
+    assert_vector_close(yaw.rotate_vector(X), Y);
+    assert_vector_close(yaw.inverse().rotate_vector(Y), X);
+    assert_vector_close((pitch * yaw).rotate_vector(X), Y);
+    assert_vector_close((yaw * pitch).rotate_vector(X), -Z);
+    assert_eq!(Rotation3::default(), Rotation3::identity());
+    for quaternion in [
+        UnitQuaternion::from_axis_angle(Z, FRAC_PI_2),
+        UnitQuaternion::from_axis_angle(Vector::from_elements([1.0, -2.0, 0.5]), 2.5),
+        UnitQuaternion::from_axis_angle(X, PI),
+        UnitQuaternion::from_axis_angle(Y, PI),
+        UnitQuaternion::from_axis_angle(Z, PI),
+    ] {
+        let rotation = Rotation3::from(quaternion);
+        assert_quaternion_close(UnitQuaternion::from(rotation), quaternion);
+        let v = Vector::from_elements([0.3, -1.0, 2.0]);
+        assert_vector_close(rotation.rotate_vector(v), quaternion.rotate_vector(v));
+    }
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use rrtk::packing::*;
+use rrtk::*;
+#[test]
+fn quantization_range() {
+    let range = QuantizationRange::new(-10.0, 10.0);
+    assert_eq!(range.quantize(-10.0, 8), 0);
+    assert_eq!(range.quantize(10.0, 8), 255);
+    assert_eq!(range.quantize(-20.0, 8), 0);
+    assert_eq!(range.quantize(20.0, 8), 255);
+    assert_eq!(range.quantize(f32::NAN, 8), 0);
+    assert_eq!(range.dequantize(0, 8), -10.0);
+    assert_eq!(range.dequantize(255, 8), 10.0);
+    assert_eq!(range.resolution(8), 20.0 / 255.0);
+    assert_eq!(range.quantize(10.0, 32), u32::MAX as u64);
+}
+#[test]
+#[should_panic]
+fn quantization_range_backwards() {
+    let _ = QuantizationRange::new(10.0, -10.0);
+}
+#[test]
+fn state_packer_round_trip() {
+    let packer = StatePacker::new(
+        QuantizationRange::new(-1000.0, 1000.0),
+        24,
+        QuantizationRange::new(-500.0, 500.0),
+        20,
+        QuantizationRange::new(-250.0, 250.0),
+        20,
+    );
+    for state in [
+        State::new_raw(0.0, 0.0, 0.0),
+        State::new_raw(123.456, -78.9, 10.0),
+        State::new_raw(-1000.0, 500.0, -250.0),
+        State::new_raw(999.9, -499.9, 249.9),
+    ] {
+        let decoded = packer.decode(packer.encode(state));
+        assert!(
+            (decoded.position - state.position).abs()
+                <= QuantizationRange::new(-1000.0, 1000.0).resolution(24)
+        );
+        assert!(
+            (decoded.velocity - state.velocity).abs()
+                <= QuantizationRange::new(-500.0, 500.0).resolution(20)
+        );
+        assert!(
+            (decoded.acceleration - state.acceleration).abs()
+                <= QuantizationRange::new(-250.0, 250.0).resolution(20)
+        );
+    }
+    let clamped = packer.decode(packer.encode(State::new_raw(5000.0, -5000.0, 0.0)));
+    assert_eq!(clamped.position, 1000.0);
+    assert_eq!(clamped.velocity, -500.0);
+}
+#[test]
+#[should_panic]
+fn state_packer_too_wide() {
+    let range = QuantizationRange::new(0.0, 1.0);
+    let _ = StatePacker::new(range, 32, range, 32, range, 1);
+}
+#[test]
+fn command_packer_round_trip() {
+    let packer = CommandPacker::new(
+        QuantizationRange::new(-1000.0, 1000.0),
+        QuantizationRange::new(-500.0, 500.0),
+        QuantizationRange::new(-250.0, 250.0),
+        16,
+    );
+    for command in [
+        Command::new(PositionDerivative::Position, 123.4),
+        Command::new(PositionDerivative::Velocity, -45.6),
+        Command::new(PositionDerivative::Acceleration, 7.8),
+    ] {
+        let decoded = packer.decode(packer.encode(command)).unwrap();
+        assert_eq!(
+            PositionDerivative::from(decoded),
+            PositionDerivative::from(command)
+        );
+        assert!((f32::from(decoded) - f32::from(command)).abs() <= 2000.0 / 65535.0);
+    }
+    let mut bytes = packer.encode(Command::new(PositionDerivative::Position, 0.0));
+    bytes[0] = 3;
+    assert_eq!(
+        packer.decode(bytes),
+        Err(CommandDecodeError::UnknownPositionDerivative(3))
+    );
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(feature = "alloc")]
+use core::cell::RefCell;
+use rrtk::pipeline::*;
+use rrtk::*;
+use std::rc::Rc;
+struct LogUpdatable {
+    id: u8,
+    log: Rc<RefCell<Vec<u8>>>,
+    fail: bool,
+}
+impl Updatable<()> for LogUpdatable {
+    fn update(&mut self) -> NothingOrError<()> {
+        self.log.borrow_mut().push(self.id);
+        if self.fail {
+            return Err(Error::Other(()));
+        }
+        Ok(())
+    }
+}
+fn node(id: u8, log: &Rc<RefCell<Vec<u8>>>, fail: bool) -> Reference<dyn Updatable<()>> {
+    to_dyn!(
+        Updatable<()>,
+        rc_ref_cell_reference(LogUpdatable {
+            id: id,
+            log: log.clone(),
+            fail: fail,
+        })
+    )
+}
+#[test]
+fn update_pipeline() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut builder = UpdatePipelineBuilder::<4, ()>::new();
+    let output = builder.add(node(0, &log, false)).unwrap();
+    let middle = builder.add(node(1, &log, false)).unwrap();
+    let input_a = builder.add(node(2, &log, false)).unwrap();
+    let input_b = builder.add(node(3, &log, false)).unwrap();
+    assert_eq!(builder.add(node(4, &log, false)), Err(PipelineError::Full));
+    builder.add_dependency(output, middle).unwrap();
+    builder.add_dependency(middle, input_a).unwrap();
+    builder.add_dependency(middle, input_b).unwrap();
+    let mut pipeline = builder.build().unwrap();
+    assert_eq!(
+        pipeline.order().collect::<Vec<_>>(),
+        vec![input_a, input_b, middle, output]
+    );
+    pipeline.update().unwrap();
+    pipeline.update().unwrap();
+    assert_eq!(*log.borrow(), vec![2, 3, 1, 0, 2, 3, 1, 0]);
+    assert_eq!(pipeline.last_failed(), None);
+}
+#[test]
+fn update_pipeline_failure() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut builder = UpdatePipelineBuilder::<3, ()>::new();
+    let output = builder.add(node(0, &log, false)).unwrap();
+    let failing = builder.add(node(1, &log, true)).unwrap();
+    builder.add_dependency(output, failing).unwrap();
+    let mut pipeline = builder.build().unwrap();
+    assert_eq!(pipeline.run(), Err((failing, Error::Other(()))));
+    assert_eq!(pipeline.last_failed(), Some(failing));
+    assert_eq!(*log.borrow(), vec![1]);
+}
+#[test]
+fn update_pipeline_cycle() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut builder = UpdatePipelineBuilder::<3, ()>::new();
+    let a = builder.add(node(0, &log, false)).unwrap();
+    let b = builder.add(node(1, &log, false)).unwrap();
+    let c = builder.add(node(2, &log, false)).unwrap();
+    builder.add_dependency(a, b).unwrap();
+    builder.add_dependency(b, c).unwrap();
+    builder.add_dependency(c, a).unwrap();
+    assert!(matches!(builder.build(), Err(PipelineError::Cycle)));
+    let mut other = UpdatePipelineBuilder::<3, ()>::new();
+    let a = other.add(node(0, &log, false)).unwrap();
+    assert_eq!(other.add_dependency(a, c), Err(PipelineError::UnknownNode));
+}
+#[test]
+fn phased_runner() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut runner = PhasedRunner::<5, ()>::new();
+    runner
+        .add(UpdatePhase::Outputs, node(3, &log, false))
+        .unwrap();
+    runner
+        .add(UpdatePhase::Controllers, node(2, &log, false))
+        .unwrap();
+    runner
+        .add(UpdatePhase::Sensors, node(0, &log, false))
+        .unwrap();
+    runner
+        .add(UpdatePhase::Sensors, node(1, &log, false))
+        .unwrap();
+    assert_eq!(runner.phase_len(UpdatePhase::Sensors), 2);
+    assert_eq!(runner.phase_len(UpdatePhase::Estimators), 0);
+    runner.update().unwrap();
+    assert_eq!(*log.borrow(), vec![0, 1, 2, 3]);
+    runner
+        .add(UpdatePhase::Estimators, node(4, &log, true))
+        .unwrap();
+    assert_eq!(
+        runner.add(UpdatePhase::Outputs, node(5, &log, false)),
+        Err(PipelineError::Full)
+    );
+    log.borrow_mut().clear();
+    assert_eq!(
+        runner.run(),
+        Err((UpdatePhase::Estimators, Error::Other(())))
+    );
+    assert_eq!(*log.borrow(), vec![0, 1, 4]);
+}
+#[test]
+fn chunked_computation() {
+    let time = rc_ref_cell_reference(Time(0));
+    //Sum the numbers from 1 to 10, at most 4 per update.
+    let mut sum = ChunkedComputation::<_, _, _, _, ()>::new(
+        time.clone(),
+        (0u32, 0u32),
+        |(i, total): &mut (u32, u32)| {
+            *i += 1;
+            *total += *i;
+            if *i == 10 {
+                Some(*total)
+            } else {
+                None
+            }
+        },
+        4,
+    );
+    sum.update().unwrap();
+    assert_eq!(sum.get_steps(), 4);
+    assert_eq!(sum.get(), Ok(None));
+    *time.borrow_mut() = Time(1);
+    sum.update().unwrap();
+    assert!(!sum.is_finished());
+    *time.borrow_mut() = Time(2);
+    sum.update().unwrap();
+    assert!(sum.is_finished());
+    assert_eq!(sum.get_steps(), 10);
+    assert_eq!(sum.get(), Ok(Some(Datum::new(Time(2), 55))));
+    sum.update().unwrap();
+    assert_eq!(sum.get_steps(), 10);
+    sum.restart((0, 0));
+    assert_eq!(sum.get(), Ok(None));
+}
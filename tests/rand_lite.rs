@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use rrtk::rand_lite::*;
+#[test]
+fn prng() {
+    //Reference values for SplitMix64 seeded with 0.
+    let mut prng = Prng::new(0);
+    assert_eq!(prng.next_u64(), 0xe220a8397b1dcdaf);
+    assert_eq!(prng.next_u64(), 0x6e789e6aa1b965f4);
+    let mut a = Prng::new(1234);
+    let mut b = a;
+    let mut c = Prng::new(1235);
+    let mut any_different = false;
+    for _ in 0..1000 {
+        let value = a.next_f32();
+        assert!((0.0..1.0).contains(&value));
+        assert_eq!(value, b.next_f32());
+        any_different |= value != c.next_f32();
+        let value = a.range_f32(-2.0, 3.0);
+        assert!((-2.0..3.0).contains(&value));
+        b.range_f32(-2.0, 3.0);
+        c.next_f32();
+    }
+    assert!(any_different);
+    assert_eq!(a.next_u32(), b.next_u32());
+    let mut sum = 0.0;
+    let mut sum_of_squares = 0.0;
+    for _ in 0..10000 {
+        let value = a.next_normal();
+        assert!(value.abs() <= 6.0);
+        sum += value;
+        sum_of_squares += value * value;
+    }
+    let mean = sum / 10000.0;
+    let variance = sum_of_squares / 10000.0 - mean * mean;
+    assert!(mean.abs() < 0.05);
+    assert!((variance - 1.0).abs() < 0.1);
+}
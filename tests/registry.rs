@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use rrtk::registry::*;
+use rrtk::*;
+#[test]
+fn registry() {
+    let speed = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 5.0)
+    );
+    let mut registry = Registry::<2, ()>::new();
+    assert!(registry.is_empty());
+    registry
+        .register(
+            "speed",
+            RegistryHandle::F32Getter(to_dyn!(Getter<f32, ()>, speed.clone())),
+        )
+        .unwrap();
+    registry
+        .register(
+            "speed_setpoint",
+            RegistryHandle::F32Settable(to_dyn!(Settable<f32, ()>, speed.clone())),
+        )
+        .unwrap();
+    assert_eq!(
+        registry.register(
+            "speed",
+            RegistryHandle::F32Getter(to_dyn!(Getter<f32, ()>, speed.clone())),
+        ),
+        Err(RegistryError::DuplicateName)
+    );
+    assert_eq!(
+        registry.register(
+            "other",
+            RegistryHandle::F32Getter(to_dyn!(Getter<f32, ()>, speed.clone())),
+        ),
+        Err(RegistryError::Full)
+    );
+    assert_eq!(registry.len(), 2);
+    registry
+        .get("speed_setpoint")
+        .unwrap()
+        .as_f32_settable()
+        .unwrap()
+        .borrow_mut()
+        .set(7.0)
+        .unwrap();
+    let getter = registry.get("speed").unwrap().as_f32_getter().unwrap();
+    assert_eq!(getter.borrow().get(), Ok(Some(Datum::new(Time(0), 7.0))));
+    assert!(registry.get("speed").unwrap().as_f32_settable().is_none());
+    assert!(registry.get("missing").is_none());
+    assert!(registry.remove("speed").is_some());
+    assert!(registry.get("speed").is_none());
+    assert_eq!(
+        registry.iter().map(|(name, _)| name).next(),
+        Some("speed_setpoint")
+    );
+}
+#[test]
+#[cfg(feature = "std")]
+fn hash_registry() {
+    let speed = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 5.0)
+    );
+    let mut registry = HashRegistry::<()>::new();
+    registry
+        .register(
+            "speed",
+            RegistryHandle::F32Getter(to_dyn!(Getter<f32, ()>, speed.clone())),
+        )
+        .unwrap();
+    assert_eq!(
+        registry.register(
+            "speed",
+            RegistryHandle::F32Getter(to_dyn!(Getter<f32, ()>, speed)),
+        ),
+        Err(RegistryError::DuplicateName)
+    );
+    let getter = registry.get("speed").unwrap().as_f32_getter().unwrap();
+    assert_eq!(getter.borrow().get(), Ok(Some(Datum::new(Time(0), 5.0))));
+    assert_eq!(registry.len(), 1);
+    assert!(registry.remove("speed").is_some());
+    assert!(registry.is_empty());
+}
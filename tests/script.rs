@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+#![cfg(feature = "alloc")]
+use rrtk::registry::*;
+use rrtk::script::*;
+use rrtk::*;
+#[test]
+fn script_interpreter() {
+    let time = rc_ref_cell_reference(Time(0));
+    let speed = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let done = rc_ref_cell_reference(ConstantGetter::<bool, _, ()>::new(time.clone(), false));
+    let mut registry = Registry::<2, ()>::new();
+    registry
+        .register(
+            "speed",
+            RegistryHandle::F32Settable(to_dyn!(Settable<f32, ()>, speed.clone())),
+        )
+        .unwrap();
+    registry
+        .register(
+            "done",
+            RegistryHandle::BoolGetter(to_dyn!(Getter<bool, ()>, done.clone())),
+        )
+        .unwrap();
+    let registry = rc_ref_cell_reference(registry);
+    const PROGRAM: [ScriptOp; 5] = [
+        ScriptOp::SetF32("speed", 1.0),
+        ScriptOp::WaitFor(Time(10)),
+        ScriptOp::WaitUntil("done"),
+        ScriptOp::SetF32("speed", 0.0),
+        ScriptOp::End,
+    ];
+    let mut interpreter = ScriptInterpreter::new(&PROGRAM, registry, time.clone());
+    interpreter.update().unwrap();
+    assert_eq!(speed.borrow().get().unwrap().unwrap().value, 1.0);
+    assert_eq!(interpreter.get_program_counter(), 1);
+    *time.borrow_mut() = Time(10);
+    interpreter.update().unwrap();
+    assert_eq!(interpreter.get_program_counter(), 2);
+    done.borrow_mut().set(true).unwrap();
+    interpreter.update().unwrap();
+    assert_eq!(speed.borrow().get().unwrap().unwrap().value, 0.0);
+    assert!(interpreter.is_finished());
+    assert_eq!(interpreter.get_error(), None);
+}
+#[test]
+fn script_interpreter_errors() {
+    let time = rc_ref_cell_reference(Time(0));
+    let done = rc_ref_cell_reference(ConstantGetter::<bool, _, ()>::new(time.clone(), true));
+    let mut registry = Registry::<1, ()>::new();
+    registry
+        .register(
+            "done",
+            RegistryHandle::BoolGetter(to_dyn!(Getter<bool, ()>, done)),
+        )
+        .unwrap();
+    let registry = rc_ref_cell_reference(registry);
+    const UNKNOWN: [ScriptOp; 1] = [ScriptOp::SetF32("speed", 1.0)];
+    let mut interpreter = ScriptInterpreter::new(&UNKNOWN, registry.clone(), time.clone());
+    interpreter.update().unwrap();
+    assert!(interpreter.is_finished());
+    assert_eq!(
+        interpreter.get_error(),
+        Some(ScriptError::UnknownName("speed"))
+    );
+    const WRONG_TYPE: [ScriptOp; 1] = [ScriptOp::SetBool("done", false)];
+    let mut interpreter = ScriptInterpreter::new(&WRONG_TYPE, registry.clone(), time.clone());
+    interpreter.update().unwrap();
+    assert_eq!(
+        interpreter.get_error(),
+        Some(ScriptError::WrongType("done"))
+    );
+    //A loop without waits stops for the update once it has run as many operations as the program
+    //is long.
+    const LOOP: [ScriptOp; 2] = [ScriptOp::JumpIf("done", 1), ScriptOp::Jump(0)];
+    let mut interpreter = ScriptInterpreter::new(&LOOP, registry.clone(), time.clone());
+    interpreter.update().unwrap();
+    assert!(!interpreter.is_finished());
+    const INVALID_JUMP: [ScriptOp; 1] = [ScriptOp::Jump(5)];
+    let mut interpreter = ScriptInterpreter::new(&INVALID_JUMP, registry, time);
+    interpreter.update().unwrap();
+    assert_eq!(interpreter.get_error(), Some(ScriptError::InvalidJump(5)));
+}
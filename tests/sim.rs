@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+use rrtk::sim::*;
+use rrtk::*;
+#[test]
+fn step_response_analyzer() {
+    let mut analyzer = StepResponseAnalyzer::new(0.0, 10.0, 0.05);
+    assert_eq!(analyzer.metrics(), None);
+    for (time, value) in [
+        (0, 0.0),
+        (1, 2.0),
+        (2, 6.0),
+        (3, 9.5),
+        (4, 11.0),
+        (5, 10.8),
+        (6, 10.2),
+        (7, 10.1),
+    ] {
+        analyzer.push(Datum::new(Time(time), value));
+    }
+    let metrics = analyzer.metrics().unwrap();
+    assert_eq!(metrics.rise_time, Some(Time(2)));
+    assert!((metrics.overshoot - 0.1).abs() < 0.0001);
+    assert_eq!(metrics.settling_time, Some(Time(6)));
+    assert!((metrics.steady_state_error + 0.1).abs() < 0.0001);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn test_bench_pid_first_order_plant() {
+    let time = rc_ref_cell_reference(Time(0));
+    let plant = rc_ref_cell_reference(FirstOrderPlant::new(time.clone(), 1.0, 0.5, 0.0));
+    let pid = rc_ref_cell_reference(streams::control::PIDControllerStream::new(
+        plant.clone(),
+        10.0,
+        PIDKValues::new(2.0, 4.0, 0.0),
+    ));
+    plant
+        .borrow_mut()
+        .follow(to_dyn!(Getter<f32, ()>, pid.clone()));
+    let mut bench = TestBench::new(
+        time,
+        [
+            to_dyn!(Updatable<()>, plant.clone()),
+            to_dyn!(Updatable<()>, pid.clone()),
+        ],
+        to_dyn!(Getter<f32, ()>, plant.clone()),
+    );
+    let metrics = bench
+        .run(
+            Time(10_000_000_000),
+            Time(1_000_000),
+            StepResponseAnalyzer::new(0.0, 10.0, 0.02),
+        )
+        .unwrap()
+        .unwrap();
+    assert!(metrics.rise_time.unwrap() < Time(2_000_000_000));
+    assert!(metrics.overshoot < 0.2);
+    assert!(metrics.settling_time.unwrap() < Time(5_000_000_000));
+    assert!(metrics.steady_state_error.abs() < 0.01);
+}
+#[test]
+#[should_panic]
+fn test_bench_zero_time_step() {
+    let output = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 0.0)
+    );
+    let mut bench = TestBench::new(
+        static_reference!(Time, Time(0)),
+        [],
+        to_dyn!(Getter<f32, ()>, output),
+    );
+    let _ = bench.run(
+        Time(1_000_000_000),
+        Time(0),
+        StepResponseAnalyzer::new(0.0, 1.0, 0.02),
+    );
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn simulated_motor() {
+    let time = rc_ref_cell_reference(Time(0));
+    let motor = MotorModel::new(600.0, 3.0, 200.0, 12.0);
+    let mut sim = SimulatedMotor::<_, ()>::new(time.clone(), motor, 0.001);
+    assert_eq!(sim.get(), Ok(None));
+    sim.set(12.0).unwrap();
+    sim.update().unwrap();
+    assert_eq!(sim.get(), Ok(Some(Datum::new(Time(0), 0.0))));
+    assert_eq!(sim.get_current(), 200.0);
+    *time.borrow_mut() = Time(1_000_000);
+    sim.update().unwrap();
+    //3 N*m / 0.001 kg*m^2 * 0.001 s
+    let velocity = sim.get().unwrap().unwrap().value;
+    assert!((velocity - 3.0).abs() < 0.001);
+    for i in 2..2000 {
+        *time.borrow_mut() = Time(i * 1_000_000);
+        sim.update().unwrap();
+    }
+    let velocity = sim.get().unwrap().unwrap().value;
+    assert!((velocity - 600.0).abs() < 1.0);
+    assert!(sim.get_current().abs() < 1.0);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn fixed_step_runner() {
+    fn simulate() -> (f32, Time) {
+        let time = rc_ref_cell_reference(Time(0));
+        let plant = rc_ref_cell_reference(FirstOrderPlant::new(time.clone(), 1.0, 0.5, 0.0));
+        let pid = rc_ref_cell_reference(streams::control::PIDControllerStream::new(
+            plant.clone(),
+            10.0,
+            PIDKValues::new(2.0, 4.0, 0.0),
+        ));
+        plant
+            .borrow_mut()
+            .follow(to_dyn!(Getter<f32, ()>, pid.clone()));
+        let mut runner = FixedStepRunner::new(
+            time.clone(),
+            Time(1_000_000),
+            [
+                to_dyn!(Updatable<()>, plant.clone()),
+                to_dyn!(Updatable<()>, pid.clone()),
+            ],
+        );
+        runner.run(500).unwrap();
+        runner.update().unwrap();
+        assert_eq!(runner.get_step_count(), 501);
+        assert_eq!(runner.get_time_step(), Time(1_000_000));
+        let output = plant.borrow().get().unwrap().unwrap().value;
+        let end_time = *time.borrow();
+        (output, end_time)
+    }
+    let (output, end_time) = simulate();
+    assert_eq!(end_time, Time(501_000_000));
+    let (repeat_output, _) = simulate();
+    assert_eq!(output.to_bits(), repeat_output.to_bits());
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn delayed_getter() {
+    let time = rc_ref_cell_reference(Time(0));
+    let input = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 1.0));
+    let mut delayed = DelayedGetter::new(input.clone(), time.clone(), Time(2));
+    assert_eq!(delayed.get_latency(), Time(2));
+    delayed.update().unwrap();
+    assert_eq!(delayed.get(), Ok(None));
+    *time.borrow_mut() = Time(1);
+    input.borrow_mut().set(2.0).unwrap();
+    delayed.update().unwrap();
+    assert_eq!(delayed.get(), Ok(None));
+    *time.borrow_mut() = Time(2);
+    input.borrow_mut().set(3.0).unwrap();
+    delayed.update().unwrap();
+    assert_eq!(delayed.get(), Ok(Some(Datum::new(Time(2), 1.0))));
+    *time.borrow_mut() = Time(3);
+    delayed.update().unwrap();
+    assert_eq!(delayed.get(), Ok(Some(Datum::new(Time(3), 2.0))));
+    //After a late update, the newest value that is due is returned.
+    *time.borrow_mut() = Time(10);
+    input.borrow_mut().set(4.0).unwrap();
+    delayed.update().unwrap();
+    assert_eq!(delayed.get(), Ok(Some(Datum::new(Time(5), 3.0))));
+    delayed.set_latency(Time(0));
+    delayed.update().unwrap();
+    assert_eq!(delayed.get(), Ok(Some(Datum::new(Time(10), 4.0))));
+}
+#[test]
+fn noisy_getter() {
+    let input = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(5)), 10.0)
+    );
+    let mut noisy = NoisyGetter::new(input.clone(), NoiseModel::Uniform { amplitude: 0.5 }, 1);
+    let mut same_seed = NoisyGetter::new(input.clone(), NoiseModel::Uniform { amplitude: 0.5 }, 1);
+    let mut other_seed = NoisyGetter::new(input.clone(), NoiseModel::Uniform { amplitude: 0.5 }, 2);
+    assert_eq!(noisy.get(), Ok(None));
+    let mut any_different = false;
+    for _ in 0..100 {
+        noisy.update().unwrap();
+        same_seed.update().unwrap();
+        other_seed.update().unwrap();
+        let output = noisy.get().unwrap().unwrap();
+        assert_eq!(output.time, Time(5));
+        assert!((output.value - 10.0).abs() <= 0.5);
+        assert_eq!(noisy.get(), same_seed.get());
+        any_different |= noisy.get() != other_seed.get();
+    }
+    assert!(any_different);
+    noisy.set_noise_model(NoiseModel::Gaussian {
+        standard_deviation: 1.0,
+    });
+    let mut sum = 0.0;
+    let mut sum_of_squares = 0.0;
+    for _ in 0..10000 {
+        noisy.update().unwrap();
+        let noise = noisy.get().unwrap().unwrap().value - 10.0;
+        assert!(noise.abs() <= 6.0);
+        sum += noise;
+        sum_of_squares += noise * noise;
+    }
+    let mean = sum / 10000.0;
+    let variance = sum_of_squares / 10000.0 - mean * mean;
+    assert!(mean.abs() < 0.05);
+    assert!((variance - 1.0).abs() < 0.1);
+}
@@ -79,6 +79,86 @@ fn expirer() {
     }
 }
 #[test]
+fn stale_data_policy_stream() {
+    struct Input;
+    impl Getter<f32, ()> for Input {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(Datum::new(Time(0), 1.0)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    struct Derivative;
+    impl Getter<f32, ()> for Derivative {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(Datum::new(Time(0), 2.0)))
+        }
+    }
+    impl Updatable<()> for Derivative {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let input = static_reference!(Input, Input);
+    let time = static_reference!(Time, Time(0));
+    let mut pass_through = StaleDataPolicyStream::new(
+        input.clone(),
+        time.clone(),
+        Time(1_000_000_000),
+        StaleDataPolicy::PassThrough,
+    );
+    let mut hold = StaleDataPolicyStream::new(
+        input.clone(),
+        time.clone(),
+        Time(1_000_000_000),
+        StaleDataPolicy::HoldLastGood(Time(1_000_000_000)),
+    );
+    let mut extrapolate = StaleDataPolicyStream::new(
+        input.clone(),
+        time.clone(),
+        Time(1_000_000_000),
+        StaleDataPolicy::Extrapolate(to_dyn!(
+            Getter<f32, ()>,
+            static_reference!(Derivative, Derivative)
+        )),
+    );
+    let mut error = StaleDataPolicyStream::new(
+        input,
+        time.clone(),
+        Time(1_000_000_000),
+        StaleDataPolicy::ReturnError(()),
+    );
+    let fresh = Ok(Some(Datum::new(Time(0), 1.0)));
+    pass_through.update().unwrap();
+    hold.update().unwrap();
+    extrapolate.update().unwrap();
+    error.update().unwrap();
+    assert!(!pass_through.is_stale());
+    assert_eq!(pass_through.get(), fresh);
+    assert_eq!(hold.get(), fresh);
+    assert_eq!(extrapolate.get(), fresh);
+    assert_eq!(error.get(), fresh);
+    *time.borrow_mut() = Time(1_500_000_000);
+    pass_through.update().unwrap();
+    hold.update().unwrap();
+    extrapolate.update().unwrap();
+    error.update().unwrap();
+    assert!(pass_through.is_stale());
+    assert_eq!(pass_through.get(), fresh);
+    assert_eq!(hold.get(), fresh);
+    assert_eq!(
+        extrapolate.get(),
+        Ok(Some(Datum::new(Time(1_500_000_000), 4.0)))
+    );
+    assert_eq!(error.get(), Err(Error::Other(())));
+    *time.borrow_mut() = Time(2_500_000_000);
+    hold.update().unwrap();
+    assert_eq!(hold.get(), Ok(None));
+}
+#[test]
 fn expirer_none() {
     struct DummyStream;
     impl Getter<f32, ()> for DummyStream {
@@ -724,6 +804,138 @@ fn difference_stream() {
     }
 }
 #[test]
+fn quantity_sum_stream() {
+    let a = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(1)),
+            Quantity::new(2.0, MILLIMETER)
+        )
+    );
+    let b = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(2)),
+            Quantity::new(3.0, MILLIMETER)
+        )
+    );
+    let sum = QuantitySumStream::new([
+        to_dyn!(Getter<Quantity, ()>, a.clone()),
+        to_dyn!(Getter<Quantity, ()>, b.clone()),
+    ]);
+    let output = sum.get().unwrap().unwrap();
+    assert_eq!(output.time, Time(2));
+    assert_eq!(output.value.value, 5.0);
+    output.value.unit.assert_eq_assume_ok(&MILLIMETER);
+    let difference = QuantityDifferenceStream::new(a, b);
+    let output = difference.get().unwrap().unwrap();
+    assert_eq!(output.time, Time(2));
+    assert_eq!(output.value.value, -1.0);
+    output.value.unit.assert_eq_assume_ok(&MILLIMETER);
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn quantity_sum_stream_unit_invalid() {
+    let a = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(1)),
+            Quantity::new(2.0, MILLIMETER)
+        )
+    );
+    let b = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(1)), Quantity::new(3.0, SECOND))
+    );
+    let sum = QuantitySumStream::new([
+        to_dyn!(Getter<Quantity, ()>, a.clone()),
+        to_dyn!(Getter<Quantity, ()>, b.clone()),
+    ]);
+    assert_eq!(
+        sum.get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(MILLIMETER, SECOND).with_operation("QuantitySumStream")
+        ))
+    );
+    let difference = QuantityDifferenceStream::new(a, b);
+    assert_eq!(
+        difference.get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(MILLIMETER, SECOND).with_operation("QuantityDifferenceStream")
+        ))
+    );
+}
+#[test]
+fn state_stream_algebra() {
+    let a = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(1)),
+            State::new_raw(1.0, 2.0, 3.0)
+        )
+    );
+    let b = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(2)),
+            State::new_raw(0.5, 0.5, 0.5)
+        )
+    );
+    let factor = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(3)),
+            Quantity::new(2.0, DIMENSIONLESS)
+        )
+    );
+    let sum = StateSumStream::new(a.clone(), b.clone());
+    assert_eq!(
+        sum.get(),
+        Ok(Some(Datum::new(Time(2), State::new_raw(1.5, 2.5, 3.5))))
+    );
+    let difference = StateDifferenceStream::new(a.clone(), b);
+    assert_eq!(
+        difference.get(),
+        Ok(Some(Datum::new(Time(2), State::new_raw(0.5, 1.5, 2.5))))
+    );
+    let scale = StateScaleStream::new(a, factor);
+    assert_eq!(
+        scale.get(),
+        Ok(Some(Datum::new(Time(3), State::new_raw(2.0, 4.0, 6.0))))
+    );
+}
+#[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn state_scale_stream_unit_invalid() {
+    let state = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(1)),
+            State::new_raw(1.0, 2.0, 3.0)
+        )
+    );
+    let factor = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(1)),
+            Quantity::new(2.0, MILLIMETER)
+        )
+    );
+    let scale = StateScaleStream::new(state, factor);
+    assert_eq!(
+        scale.get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(DIMENSIONLESS, MILLIMETER).with_operation("StateScaleStream")
+        ))
+    );
+}
+#[test]
 fn product_stream() {
     #[derive(Clone, Copy, Debug)]
     struct Nothing;
@@ -1200,6 +1412,142 @@ fn exponent_stream() {
     }
 }
 #[test]
+#[cfg(any(feature = "std", feature = "libm"))]
+fn trig_and_sqrt_streams() {
+    struct Input {
+        value: Quantity,
+    }
+    impl Getter<Quantity, ()> for Input {
+        fn get(&self) -> Output<Quantity, ()> {
+            Ok(Some(Datum::new(Time(1), self.value)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let area = static_reference!(
+        Input,
+        Input {
+            value: Quantity::new(9.0, MILLIMETER_SQUARED)
+        }
+    );
+    let sqrt = SqrtStream::new(area).get().unwrap().unwrap().value;
+    assert_eq!(sqrt.value, 3.0);
+    sqrt.unit.assert_eq_assume_ok(&MILLIMETER);
+    let angle = static_reference!(
+        Input,
+        Input {
+            value: Quantity::dimensionless(0.0)
+        }
+    );
+    let sin = SinStream::new(angle.clone());
+    let cos = CosStream::new(angle);
+    assert_eq!(sin.get().unwrap().unwrap().value.value, 0.0);
+    assert_eq!(cos.get().unwrap().unwrap().value.value, 1.0);
+    let y = static_reference!(
+        Input,
+        Input {
+            value: Quantity::new(1.0, MILLIMETER)
+        }
+    );
+    let x = static_reference!(
+        Input,
+        Input {
+            value: Quantity::new(-1.0, MILLIMETER)
+        }
+    );
+    let atan2 = Atan2Stream::new(y, x);
+    let output = atan2.get().unwrap().unwrap();
+    assert_eq!(output.time, Time(1));
+    assert!((output.value.value - 3.0 * core::f32::consts::FRAC_PI_4).abs() < 0.0001);
+    output.value.unit.assert_eq_assume_ok(&DIMENSIONLESS);
+}
+#[test]
+#[cfg(all(
+    any(feature = "std", feature = "libm"),
+    any(
+        feature = "dim_check_release",
+        all(debug_assertions, feature = "dim_check_debug")
+    )
+))]
+fn trigonometry_stream_dimension_failure() {
+    struct Input {
+        value: Quantity,
+    }
+    impl Getter<Quantity, ()> for Input {
+        fn get(&self) -> Output<Quantity, ()> {
+            Ok(Some(Datum::new(Time(0), self.value)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let length = static_reference!(
+        Input,
+        Input {
+            value: Quantity::new(1.0, MILLIMETER)
+        }
+    );
+    assert_eq!(
+        SinStream::new(length.clone()).get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(DIMENSIONLESS, MILLIMETER).with_operation("SinStream")
+        ))
+    );
+    assert_eq!(
+        CosStream::new(length.clone()).get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(DIMENSIONLESS, MILLIMETER).with_operation("CosStream")
+        ))
+    );
+    assert_eq!(
+        SqrtStream::new(length.clone()).get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(DIMENSIONLESS, MILLIMETER).with_operation("SqrtStream")
+        ))
+    );
+    let time = static_reference!(
+        Input,
+        Input {
+            value: Quantity::new(1.0, SECOND)
+        }
+    );
+    assert_eq!(
+        Atan2Stream::new(length, time).get(),
+        Err(Error::UnitInvalid(
+            UnitMismatch::new(MILLIMETER, SECOND).with_operation("Atan2Stream")
+        ))
+    );
+}
+#[test]
+fn normalize_angle_stream() {
+    struct Input;
+    impl Getter<f32, ()> for Input {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(Datum::new(Time(2), -core::f32::consts::FRAC_PI_2)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let input = static_reference!(Input, Input);
+    let stream = NormalizeAngleStream::new(input.clone(), angles::AngleRange::ZeroToTwoPi);
+    let output = stream.get().unwrap().unwrap();
+    assert_eq!(output.time, Time(2));
+    assert!((output.value - 3.0 * core::f32::consts::FRAC_PI_2).abs() < 0.0001);
+    let stream = NormalizeAngleStream::new(input, angles::AngleRange::NegativePiToPi);
+    assert_eq!(
+        stream.get().unwrap().unwrap().value,
+        -core::f32::consts::FRAC_PI_2
+    );
+}
+#[test]
 fn derivative_stream() {
     #[derive(Clone, Copy, Debug)]
     struct DummyError;
@@ -1241,6 +1589,53 @@ fn derivative_stream() {
     }
 }
 #[test]
+fn velocity_estimator() {
+    struct QuantizedEncoder {
+        index: i64,
+    }
+    impl Getter<Quantity, ()> for QuantizedEncoder {
+        fn get(&self) -> Output<Quantity, ()> {
+            //7.3 mm/s sampled every 100 ms and rounded down to whole millimeters.
+            let position = (self.index as f32 * 0.73) as i64 as f32;
+            Ok(Some(Datum::new(
+                Time(self.index * 100_000_000),
+                Quantity::new(position, MILLIMETER),
+            )))
+        }
+    }
+    impl Updatable<()> for QuantizedEncoder {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.index += 1;
+            Ok(())
+        }
+    }
+    let input = static_reference!(QuantizedEncoder, QuantizedEncoder { index: 0 });
+    let mut estimator = VelocityEstimator::<10, _, _>::new(input.clone());
+    let mut derivative = DerivativeStream::new(input.clone());
+    estimator.update().unwrap();
+    assert_eq!(estimator.get(), Ok(None));
+    //A repeated timestamp is ignored.
+    estimator.update().unwrap();
+    assert_eq!(estimator.get(), Ok(None));
+    let mut worst_derivative_error: f32 = 0.0;
+    for _ in 0..50 {
+        input.borrow_mut().update().unwrap();
+        estimator.update().unwrap();
+        derivative.update().unwrap();
+        if let Ok(Some(datum)) = derivative.get() {
+            worst_derivative_error = worst_derivative_error.max((datum.value.value - 7.3).abs());
+        }
+    }
+    let velocity = estimator.get().unwrap().unwrap();
+    assert_eq!(velocity.time, Time(5_000_000_000));
+    velocity
+        .value
+        .unit
+        .assert_eq_assume_ok(&MILLIMETER_PER_SECOND);
+    assert!((velocity.value.value - 7.3).abs() < 0.5);
+    assert!(worst_derivative_error > 2.0);
+}
+#[test]
 fn integral_stream() {
     #[derive(Clone, Copy, Debug)]
     struct DummyError;
@@ -1321,6 +1716,283 @@ fn pid_controller_stream() {
         assert_eq!(stream.get().unwrap().unwrap().value, 4.04);
     }
 }
+#[test]
+fn zoned_pid_controller_stream() {
+    let kvalues = ZonedPIDKValues::new(
+        PIDKValues::new(4.0, 2.0, 0.0),
+        PIDKValues::new(1.0, 0.0, 0.0),
+        1.0,
+        3.0,
+    );
+    assert_eq!(
+        kvalues.get_k_values(PositionDerivative::Position, 0.5),
+        PIDKValues::new(4.0, 2.0, 0.0)
+    );
+    assert_eq!(
+        kvalues.get_k_values(PositionDerivative::Position, -5.0),
+        PIDKValues::new(1.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        kvalues.get_k_values(PositionDerivative::Position, 2.0),
+        PIDKValues::new(2.5, 1.0, 0.0)
+    );
+    assert_eq!(
+        kvalues.get_k_values(PositionDerivative::Position, -2.0),
+        PIDKValues::new(2.5, 1.0, 0.0)
+    );
+    struct Input {
+        time: Time,
+        value: f32,
+    }
+    impl Getter<f32, ()> for Input {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(Datum::new(self.time, self.value)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let input = static_reference!(
+        Input,
+        Input {
+            time: Time(0),
+            value: 0.0
+        }
+    );
+    let mut stream = PIDControllerStream::new(input.clone(), 10.0, kvalues);
+    stream.update().unwrap();
+    //Large error
+    assert_eq!(stream.get().unwrap().unwrap().value, 10.0);
+    input.borrow_mut().time = Time(1_000_000_000);
+    input.borrow_mut().value = 9.5;
+    stream.update().unwrap();
+    //Small error: 4 * 0.5 + 2 * (10 + 0.5) / 2
+    assert_eq!(stream.get().unwrap().unwrap().value, 12.5);
+    //The same k-values work with CommandPID.
+    let state = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), State::new_raw(0.0, 0.0, 0.0))
+    );
+    let mut pid = CommandPID::new(state.clone(), Command::Position(10.0), kvalues);
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 10.0);
+    let mut pid = CommandPID::new(state, Command::Position(0.5), kvalues);
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 2.0);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn pid_controller_stream_set_k_values() {
+    let time = rc_ref_cell_reference(Time(0));
+    let input = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let mut stream = PIDControllerStream::new(input.clone(), 2.0, PIDKValues::new(1.0, 1.0, 0.0));
+    stream.update().unwrap();
+    assert_eq!(stream.get().unwrap().unwrap().value, 2.0);
+    *time.borrow_mut() = Time(1_000_000_000);
+    stream.update().unwrap();
+    //2 + 1 * 2
+    assert_eq!(stream.get().unwrap().unwrap().value, 4.0);
+    //The accumulated integral is halved so the integral term stays at 2.
+    stream.set_k_values(PIDKValues::new(1.0, 2.0, 0.0));
+    assert_eq!(*stream.get_k_values(), PIDKValues::new(1.0, 2.0, 0.0));
+    *time.borrow_mut() = Time(2_000_000_000);
+    stream.update().unwrap();
+    //2 + 2 * (1 + 2)
+    assert_eq!(stream.get().unwrap().unwrap().value, 8.0);
+    stream.set(PIDKValues::new(1.0, 1.0, 0.0)).unwrap();
+    assert_eq!(
+        stream.get_last_request(),
+        Some(PIDKValues::new(1.0, 1.0, 0.0))
+    );
+    *time.borrow_mut() = Time(3_000_000_000);
+    stream.update().unwrap();
+    //2 + 1 * (6 + 2)
+    assert_eq!(stream.get().unwrap().unwrap().value, 10.0);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn pid_controller_stream_anti_windup() {
+    //Runs a pure integral controller with a setpoint of 10 from a process of 0 for three seconds,
+    //then a process of 20 for two more, returning the output each second.
+    fn run(output_limits: Option<OutputLimits>, anti_windup: Option<AntiWindup>) -> Vec<f32> {
+        let time = rc_ref_cell_reference(Time(0));
+        let input = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+        let mut stream =
+            PIDControllerStream::new(input.clone(), 10.0, PIDKValues::new(0.0, 1.0, 0.0));
+        stream.set_output_limits(output_limits);
+        stream.set_anti_windup(anti_windup);
+        assert_eq!(stream.get_anti_windup(), anti_windup);
+        let mut outputs = Vec::new();
+        for i in 0..6 {
+            *time.borrow_mut() = Time(i * 1_000_000_000);
+            if i == 4 {
+                input.borrow_mut().set(20.0).unwrap();
+            }
+            stream.update().unwrap();
+            outputs.push(stream.get().unwrap().unwrap().value);
+        }
+        outputs
+    }
+    let limits = Some(OutputLimits::new(-5.0, 5.0));
+    assert_eq!(run(None, None), [0.0, 10.0, 20.0, 30.0, 30.0, 20.0]);
+    //Without anti-windup, the output stays at the limit long after the error reverses.
+    assert_eq!(run(limits, None), [0.0, 5.0, 5.0, 5.0, 5.0, 5.0]);
+    assert_eq!(
+        run(
+            None,
+            Some(AntiWindup::ClampIntegral(OutputLimits::new(-15.0, 15.0)))
+        ),
+        [0.0, 10.0, 15.0, 15.0, 15.0, 5.0]
+    );
+    assert_eq!(
+        run(limits, Some(AntiWindup::ConditionalIntegration)),
+        [0.0, 5.0, 5.0, 5.0, 5.0, 0.0]
+    );
+    assert_eq!(
+        run(limits, Some(AntiWindup::BackCalculation(1.0))),
+        [0.0, 5.0, 5.0, 5.0, 5.0, -5.0]
+    );
+    //Back-calculation and conditional integration need output limits to do anything.
+    assert_eq!(
+        run(None, Some(AntiWindup::BackCalculation(1.0))),
+        run(None, None)
+    );
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn command_pid_anti_windup() {
+    let time = rc_ref_cell_reference(Time(0));
+    let input = rc_ref_cell_reference(ConstantGetter::<State, _, ()>::new(
+        time.clone(),
+        State::default(),
+    ));
+    let kvals = PositionDerivativeDependentPIDKValues::new(
+        PIDKValues::new(0.0, 1.0, 0.0),
+        PIDKValues::new(0.0, 1.0, 0.0),
+        PIDKValues::new(0.0, 1.0, 0.0),
+    );
+    let mut pid = CommandPID::new(
+        input.clone(),
+        Command::new(PositionDerivative::Position, 10.0),
+        kvals,
+    );
+    pid.set_anti_windup(Some(AntiWindup::ClampIntegral(OutputLimits::new(
+        -15.0, 15.0,
+    ))));
+    let mut outputs = Vec::new();
+    for i in 0..4 {
+        *time.borrow_mut() = Time(i * 1_000_000_000);
+        pid.update().unwrap();
+        outputs.push(pid.get().unwrap().unwrap().value);
+    }
+    assert_eq!(outputs, [0.0, 10.0, 15.0, 15.0]);
+    pid.set_output_limits(
+        PositionDerivative::Position,
+        Some(OutputLimits::new(-5.0, 5.0)),
+    );
+    pid.set_anti_windup(Some(AntiWindup::BackCalculation(1.0)));
+    assert_eq!(
+        pid.get_anti_windup(),
+        Some(AntiWindup::BackCalculation(1.0))
+    );
+    *time.borrow_mut() = Time(4_000_000_000);
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 5.0);
+    //The integral was bled back to where the output is at the limit, so reversing the error
+    //brings the output down right away.
+    input
+        .borrow_mut()
+        .set(State::new_raw(20.0, 0.0, 0.0))
+        .unwrap();
+    *time.borrow_mut() = Time(5_000_000_000);
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 5.0);
+    *time.borrow_mut() = Time(6_000_000_000);
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, -5.0);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn command_pid_anti_windup_velocity() {
+    fn run(anti_windup: Option<AntiWindup>) -> Vec<Option<f32>> {
+        let time = rc_ref_cell_reference(Time(0));
+        let input = rc_ref_cell_reference(ConstantGetter::<State, _, ()>::new(
+            time.clone(),
+            State::default(),
+        ));
+        let kvals = PositionDerivativeDependentPIDKValues::new(
+            PIDKValues::new(0.0, 1.0, 0.0),
+            PIDKValues::new(0.0, 1.0, 0.0),
+            PIDKValues::new(0.0, 1.0, 0.0),
+        );
+        let mut pid = CommandPID::new(
+            input.clone(),
+            Command::new(PositionDerivative::Velocity, 10.0),
+            kvals,
+        );
+        pid.set_output_limits(
+            PositionDerivative::Velocity,
+            Some(OutputLimits::new(-5.0, 5.0)),
+        );
+        pid.set_anti_windup(anti_windup);
+        let mut outputs = Vec::new();
+        for i in 0..8 {
+            if i == 4 {
+                input
+                    .borrow_mut()
+                    .set(State::new_raw(0.0, 20.0, 0.0))
+                    .unwrap();
+            }
+            *time.borrow_mut() = Time(i * 1_000_000_000);
+            pid.update().unwrap();
+            outputs.push(pid.get().unwrap().map(|datum| datum.value));
+        }
+        outputs
+    }
+    //Without anti-windup, the integral built up while the integrated output was saturated keeps
+    //it saturated long after the error reverses.
+    assert_eq!(
+        run(None),
+        [
+            None,
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0)
+        ]
+    );
+    assert_eq!(
+        run(Some(AntiWindup::ConditionalIntegration)),
+        [
+            None,
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(0.0),
+            Some(-5.0)
+        ]
+    );
+    assert_eq!(
+        run(Some(AntiWindup::BackCalculation(1.0))),
+        [
+            None,
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(5.0),
+            Some(0.0),
+            Some(-5.0),
+            Some(-5.0)
+        ]
+    );
+}
 //See note on exponent_stream test
 #[test]
 #[cfg(any(feature = "std", feature = "libm"))]
@@ -1483,6 +2155,58 @@ fn ewma_stream_quantity() {
     }
 }
 #[test]
+#[cfg(any(feature = "std", feature = "libm"))]
+fn adaptive_ewma_stream() {
+    struct DummyStream {
+        index: usize,
+    }
+    impl DummyStream {
+        const fn new() -> Self {
+            Self { index: 0 }
+        }
+    }
+    impl Getter<f32, ()> for DummyStream {
+        fn get(&self) -> Output<f32, ()> {
+            //Noisy but steady around 100 for 20 samples, then a step to 200.
+            let value = if self.index > 20 {
+                200.0
+            } else if self.index.is_multiple_of(2) {
+                101.0
+            } else {
+                99.0
+            };
+            Ok(Some(Datum::new(
+                Time(self.index as i64 * 1_000_000_000),
+                value,
+            )))
+        }
+    }
+    impl Updatable<()> for DummyStream {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.index += 1;
+            Ok(())
+        }
+    }
+    let input = static_reference!(DummyStream, DummyStream::new());
+    let mut stream = AdaptiveEWMAStream::new(input.clone(), 0.05, 0.9, 0.2, 3.0);
+    assert_eq!(stream.get(), Ok(None));
+    for _ in 0..20 {
+        input.borrow_mut().update().unwrap();
+        stream.update().unwrap();
+    }
+    //The noise is well under the change threshold, so the stream should be smoothing heavily.
+    assert!(stream.smoothing_constant() < 0.5);
+    assert!(stream.variance() > 0.0);
+    let value = stream.get().unwrap().unwrap().value;
+    assert!((value - 100.0).abs() < 1.0);
+    input.borrow_mut().update().unwrap();
+    stream.update().unwrap();
+    //The step is far beyond the change threshold, so the stream should track it quickly.
+    assert_eq!(stream.smoothing_constant(), 0.9);
+    let value = stream.get().unwrap().unwrap().value;
+    assert!(value > 180.0);
+}
+#[test]
 #[cfg(feature = "alloc")]
 fn moving_average_stream() {
     #[derive(Clone, Copy, Debug)]
@@ -2154,6 +2878,59 @@ fn freeze_stream() {
     }
 }
 #[test]
+#[cfg(feature = "alloc")]
+fn fallback_stream() {
+    struct Sensor {
+        output: Output<f32, u8>,
+    }
+    impl Getter<f32, u8> for Sensor {
+        fn get(&self) -> Output<f32, u8> {
+            self.output
+        }
+    }
+    impl Updatable<u8> for Sensor {
+        fn update(&mut self) -> NothingOrError<u8> {
+            Ok(())
+        }
+    }
+    let primary = static_reference!(
+        Sensor,
+        Sensor {
+            output: Ok(Some(Datum::new(Time(1), 1.0)))
+        }
+    );
+    let secondary = static_reference!(
+        Sensor,
+        Sensor {
+            output: Ok(Some(Datum::new(Time(1), 2.0)))
+        }
+    );
+    let fallback = rc_ref_cell_reference(FallbackStream::new(primary.clone(), secondary.clone()));
+    let source = FallbackSourceStream::new(fallback.clone());
+    assert_eq!(source.get(), Ok(None));
+    fallback.borrow_mut().update().unwrap();
+    assert_eq!(fallback.borrow().get(), Ok(Some(Datum::new(Time(1), 1.0))));
+    assert_eq!(
+        source.get(),
+        Ok(Some(Datum::new(Time(1), FallbackSource::Primary)))
+    );
+    primary.borrow_mut().output = Err(Error::Other(1));
+    fallback.borrow_mut().update().unwrap();
+    assert_eq!(fallback.borrow().get(), Ok(Some(Datum::new(Time(1), 2.0))));
+    assert_eq!(
+        source.get(),
+        Ok(Some(Datum::new(Time(1), FallbackSource::Secondary)))
+    );
+    secondary.borrow_mut().output = Ok(None);
+    assert_eq!(fallback.borrow_mut().update(), Err(Error::Other(1)));
+    assert_eq!(source.get(), Ok(None));
+    primary.borrow_mut().output = Ok(None);
+    fallback.borrow_mut().update().unwrap();
+    assert_eq!(fallback.borrow().get(), Ok(None));
+    secondary.borrow_mut().output = Err(Error::Other(2));
+    assert_eq!(fallback.borrow_mut().update(), Err(Error::Other(2)));
+}
+#[test]
 fn command_pid() {
     struct Input {
         time: Time,
@@ -2242,3 +3019,1052 @@ fn command_pid() {
         }
     }
 }
+#[test]
+fn command_pid_backlash() {
+    struct Input {
+        time: Time,
+        state: State,
+    }
+    impl Getter<State, ()> for Input {
+        fn get(&self) -> Output<State, ()> {
+            Ok(Some(Datum::new(self.time, self.state)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.time += Time(1_000_000_000);
+            Ok(())
+        }
+    }
+    let kvals = PositionDerivativeDependentPIDKValues::new(
+        PIDKValues::new(1.0, 1.0, 1.0),
+        PIDKValues::new(1.0, 1.0, 1.0),
+        PIDKValues::new(1.0, 1.0, 1.0),
+    );
+    let input = static_reference!(
+        Input,
+        Input {
+            time: Time(0),
+            state: State::new_raw(5.0, 1.0, 0.0),
+        }
+    );
+    let mut pid = CommandPID::new(
+        input.clone(),
+        Command::new(PositionDerivative::Position, 0.0),
+        kvals,
+    );
+    pid.set_backlash(Some(1.0));
+    assert_eq!(pid.get_backlash(), Some(1.0));
+    pid.update().unwrap();
+    assert!(!pid.is_in_backlash());
+    assert_eq!(pid.get().unwrap().unwrap().value, -5.0);
+    //The mechanism reverses, so it is in the backlash band and only the proportional term is used.
+    input.borrow_mut().update().unwrap();
+    input.borrow_mut().state = State::new_raw(4.5, -1.0, 0.0);
+    pid.update().unwrap();
+    assert!(pid.is_in_backlash());
+    assert_eq!(pid.get().unwrap().unwrap().value, -4.5);
+    //Once it has moved more than the backlash width, integration resumes.
+    input.borrow_mut().update().unwrap();
+    input.borrow_mut().state = State::new_raw(3.0, -1.0, 0.0);
+    pid.update().unwrap();
+    assert!(!pid.is_in_backlash());
+    assert_eq!(pid.get().unwrap().unwrap().value, -5.25);
+    //Moving in the negative direction first is not a reversal either.
+    let input = static_reference!(
+        Input,
+        Input {
+            time: Time(0),
+            state: State::new_raw(0.0, -1.0, 0.0),
+        }
+    );
+    let mut pid = CommandPID::new(
+        input.clone(),
+        Command::new(PositionDerivative::Position, 0.0),
+        kvals,
+    );
+    pid.set_backlash(Some(1.0));
+    pid.update().unwrap();
+    assert!(!pid.is_in_backlash());
+    input.borrow_mut().update().unwrap();
+    input.borrow_mut().state = State::new_raw(-0.5, -1.0, 0.0);
+    pid.update().unwrap();
+    assert!(!pid.is_in_backlash());
+}
+#[test]
+fn command_pid_output_limits() {
+    struct Input {
+        time: Time,
+    }
+    impl Getter<State, ()> for Input {
+        fn get(&self) -> Output<State, ()> {
+            Ok(Some(Datum::new(self.time, State::default())))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.time += Time(1_000_000_000);
+            Ok(())
+        }
+    }
+    let kvals = PositionDerivativeDependentPIDKValues::new(
+        PIDKValues::new(1.0, 0.01, 0.1),
+        PIDKValues::new(1.0, 0.01, 0.1),
+        PIDKValues::new(1.0, 0.01, 0.1),
+    );
+    let input = static_reference!(Input, Input { time: Time(0) });
+    let mut pid = CommandPID::new(
+        input.clone(),
+        Command::new(PositionDerivative::Position, 5.0),
+        kvals,
+    );
+    pid.set_output_limits(
+        PositionDerivative::Position,
+        Some(OutputLimits::new(-3.0, 3.0)),
+    );
+    pid.set_output_limits(
+        PositionDerivative::Velocity,
+        Some(OutputLimits::new(-8.0, 8.0)),
+    );
+    assert_eq!(
+        pid.get_output_limits(PositionDerivative::Velocity),
+        Some(OutputLimits::new(-8.0, 8.0))
+    );
+    assert_eq!(
+        pid.get_output_limits(PositionDerivative::Acceleration),
+        None
+    );
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 3.0);
+
+    pid.set(Command::new(PositionDerivative::Velocity, 5.0))
+        .unwrap();
+    input.borrow_mut().update().unwrap();
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap(), None);
+    input.borrow_mut().update().unwrap();
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 5.025);
+    input.borrow_mut().update().unwrap();
+    pid.update().unwrap();
+    //Would be 10.1 without the limits.
+    assert_eq!(pid.get().unwrap().unwrap().value, 8.0);
+    input.borrow_mut().update().unwrap();
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 8.0);
+}
+#[test]
+fn speed_scheduled_pid_k_values() {
+    struct Velocity {
+        value: f32,
+    }
+    impl Getter<f32, ()> for Velocity {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(Datum::new(Time(0), self.value)))
+        }
+    }
+    impl Updatable<()> for Velocity {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    struct Input;
+    impl Getter<State, ()> for Input {
+        fn get(&self) -> Output<State, ()> {
+            Ok(Some(Datum::new(Time(0), State::default())))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let low = PositionDerivativeDependentPIDKValues::new(
+        PIDKValues::new(1.0, 0.0, 0.0),
+        PIDKValues::new(1.0, 0.0, 0.0),
+        PIDKValues::new(1.0, 0.0, 0.0),
+    );
+    let high = PositionDerivativeDependentPIDKValues::new(
+        PIDKValues::new(3.0, 0.2, 0.4),
+        PIDKValues::new(3.0, 0.2, 0.4),
+        PIDKValues::new(3.0, 0.2, 0.4),
+    );
+    let velocity = static_reference!(Velocity, Velocity { value: 0.0 });
+    let kvals = SpeedScheduledPIDKValues::new(velocity.clone(), low, high, 1.0, 3.0);
+    assert_eq!(
+        kvals.get_k_values(PositionDerivative::Position, 0.0),
+        PIDKValues::new(1.0, 0.0, 0.0)
+    );
+    velocity.borrow_mut().value = -2.0;
+    assert_eq!(
+        kvals.get_k_values(PositionDerivative::Velocity, 0.0),
+        PIDKValues::new(2.0, 0.1, 0.2)
+    );
+    velocity.borrow_mut().value = 5.0;
+    assert_eq!(
+        kvals.get_k_values(PositionDerivative::Acceleration, 0.0),
+        PIDKValues::new(3.0, 0.2, 0.4)
+    );
+    let input = static_reference!(Input, Input);
+    let mut pid = CommandPID::new(
+        input,
+        Command::new(PositionDerivative::Position, 5.0),
+        kvals,
+    );
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, 15.0);
+}
+#[test]
+fn vision_gate() {
+    struct Input {
+        datum: Datum<f32>,
+    }
+    impl Getter<f32, ()> for Input {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(self.datum))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let measurement = static_reference!(
+        Input,
+        Input {
+            datum: Datum::new(Time(0), 100.0)
+        }
+    );
+    let odometry = static_reference!(
+        Input,
+        Input {
+            datum: Datum::new(Time(0), 0.0)
+        }
+    );
+    let mut gate = VisionGate::new(measurement.clone(), odometry.clone(), 3.0, 1.0, 0.1, 2);
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(Some(Datum::new(Time(0), 100.0))));
+    //No new measurement.
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(None));
+    //Consistent with odometry.
+    odometry.borrow_mut().datum = Datum::new(Time(1), 10.0);
+    measurement.borrow_mut().datum = Datum::new(Time(1), 111.0);
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(Some(Datum::new(Time(1), 111.0))));
+    //An outlier is rejected.
+    measurement.borrow_mut().datum = Datum::new(Time(2), 200.0);
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(None));
+    //A second outlier agreeing with the first causes the gate to switch hypotheses.
+    measurement.borrow_mut().datum = Datum::new(Time(3), 200.5);
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(Some(Datum::new(Time(3), 200.5))));
+    measurement.borrow_mut().datum = Datum::new(Time(4), 111.0);
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(None));
+    //After a reset, the same measurement is accepted.
+    gate.reset();
+    gate.update().unwrap();
+    assert_eq!(gate.get(), Ok(Some(Datum::new(Time(4), 111.0))));
+}
+#[test]
+fn field_from_state() {
+    let input = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(5)),
+            State::new_raw(1.0, 2.0, 3.0)
+        )
+    );
+    let mut position = PositionFromState::new(input.clone());
+    let mut velocity = VelocityFromState::new(input.clone());
+    let mut acceleration = AccelerationFromState::new(input.clone());
+    assert_eq!(position.get(), Ok(None));
+    position.update().unwrap();
+    velocity.update().unwrap();
+    acceleration.update().unwrap();
+    let position = position.get().unwrap().unwrap();
+    let velocity = velocity.get().unwrap().unwrap();
+    let acceleration = acceleration.get().unwrap().unwrap();
+    assert_eq!(position.time, Time(5));
+    assert_eq!(position.value.value, 1.0);
+    position.value.unit.assert_eq_assume_ok(&MILLIMETER);
+    assert_eq!(velocity.value.value, 2.0);
+    velocity
+        .value
+        .unit
+        .assert_eq_assume_ok(&MILLIMETER_PER_SECOND);
+    assert_eq!(acceleration.value.value, 3.0);
+    acceleration
+        .value
+        .unit
+        .assert_eq_assume_ok(&MILLIMETER_PER_SECOND_SQUARED);
+}
+#[test]
+fn command_streams() {
+    let input = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(5)), 2.0)
+    );
+    let mut position = PositionCommandStream::new(input.clone());
+    let mut velocity = VelocityCommandStream::new(input.clone());
+    let mut acceleration = AccelerationCommandStream::new(input.clone());
+    assert_eq!(position.get(), Ok(None));
+    position.update().unwrap();
+    velocity.update().unwrap();
+    acceleration.update().unwrap();
+    assert_eq!(
+        position.get(),
+        Ok(Some(Datum::new(Time(5), Command::Position(2.0))))
+    );
+    assert_eq!(
+        velocity.get(),
+        Ok(Some(Datum::new(Time(5), Command::Velocity(2.0))))
+    );
+    assert_eq!(
+        acceleration.get(),
+        Ok(Some(Datum::new(Time(5), Command::Acceleration(2.0))))
+    );
+}
+#[test]
+fn zip_streams() {
+    let first = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(1_000)), 1.0)
+    );
+    let second = static_reference!(
+        ConstantGetter<bool, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(3_000)), true)
+    );
+    let third = static_reference!(
+        ConstantGetter<i32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(2_000)), 3)
+    );
+    let zip = Zip2::new(first.clone(), second.clone(), None);
+    assert_eq!(zip.get(), Ok(Some(Datum::new(Time(3_000), (1.0, true)))));
+    let zip = Zip2::new(first.clone(), second.clone(), Some(Time(1_000)));
+    assert_eq!(zip.get(), Ok(None));
+    let zip = Zip3::new(
+        first.clone(),
+        second.clone(),
+        third.clone(),
+        Some(Time(2_000)),
+    );
+    assert_eq!(zip.get(), Ok(Some(Datum::new(Time(3_000), (1.0, true, 3)))));
+    let none = static_reference!(NoneGetter, NoneGetter);
+    let zip = Zip2::<f32, i32, _, _, ()>::new(first.clone(), none, None);
+    assert_eq!(zip.get(), Ok(None));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn unbatcher() {
+    struct Fifo;
+    impl BatchGetter<Quantity, ()> for Fifo {
+        fn batch_len(&self) -> usize {
+            3
+        }
+        fn get_sample(&self, index: usize) -> Output<Quantity, ()> {
+            if index >= 3 {
+                return Ok(None);
+            }
+            Ok(Some(Datum::new(
+                Time(index as i64 * 1_000_000_000),
+                Quantity::new(2.0, MILLIMETER_PER_SECOND),
+            )))
+        }
+    }
+    impl Updatable<()> for Fifo {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let fifo = static_reference!(Fifo, Fifo);
+    let slot = static_reference!(SampleSlot<Quantity, ()>, SampleSlot::new());
+    let integral = rc_ref_cell_reference(IntegralStream::new(slot.clone()));
+    let mut unbatcher = Unbatcher::new(
+        fifo,
+        slot.clone(),
+        [to_dyn!(Updatable<()>, integral.clone())],
+    );
+    unbatcher.update().unwrap();
+    let output = integral.borrow().get().unwrap().unwrap();
+    assert_eq!(output.time, Time(2_000_000_000));
+    assert_eq!(output.value.value, 4.0);
+    output.value.unit.assert_eq_assume_ok(&MILLIMETER);
+    assert_eq!(
+        slot.borrow().get().unwrap().unwrap().time,
+        Time(2_000_000_000)
+    );
+}
+#[test]
+fn frequency_stream() {
+    struct Input {
+        time: Time,
+    }
+    impl Getter<f32, ()> for Input {
+        fn get(&self) -> Output<f32, ()> {
+            Ok(Some(Datum::new(self.time, 0.0)))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let input = static_reference!(Input, Input { time: Time(0) });
+    let time = static_reference!(Time, Time(0));
+    let mut stream = FrequencyStream::new(input.clone(), time.clone(), 0.5);
+    stream.update().unwrap();
+    assert_eq!(stream.get(), Ok(None));
+    for i in 1..=3 {
+        input.borrow_mut().time = Time(i * 100_000_000);
+        *time.borrow_mut() = Time(i * 100_000_000);
+        stream.update().unwrap();
+        let output = stream.get().unwrap().unwrap();
+        assert_eq!(output.time, Time(i * 100_000_000));
+        assert!((output.value - 10.0).abs() < 0.001);
+    }
+    //A slower sample is smoothed.
+    input.borrow_mut().time = Time(500_000_000);
+    *time.borrow_mut() = Time(500_000_000);
+    stream.update().unwrap();
+    assert!((stream.get().unwrap().unwrap().value - 7.5).abs() < 0.001);
+    //No new data means that the rate falls.
+    *time.borrow_mut() = Time(1_500_000_000);
+    stream.update().unwrap();
+    assert!((stream.get().unwrap().unwrap().value - 1.0).abs() < 0.001);
+}
+#[test]
+fn state_validator() {
+    struct Encoder {
+        datum: Datum<State>,
+    }
+    impl Getter<State, u8> for Encoder {
+        fn get(&self) -> Output<State, u8> {
+            Ok(Some(self.datum))
+        }
+    }
+    impl Updatable<u8> for Encoder {
+        fn update(&mut self) -> NothingOrError<u8> {
+            Ok(())
+        }
+    }
+    let encoder = static_reference!(
+        Encoder,
+        Encoder {
+            datum: Datum::new(Time(0), State::new_raw(f32::NAN, 0.0, 0.0))
+        }
+    );
+    let mut clamp = StateValidator::new(encoder.clone(), 1.0, InvalidStateAction::Clamp);
+    let mut reject = StateValidator::new(encoder.clone(), 1.0, InvalidStateAction::Reject);
+    let mut error = StateValidator::new(encoder.clone(), 1.0, InvalidStateAction::ReturnError(7));
+    //Nothing to fix a non-finite state with yet.
+    clamp.update().unwrap();
+    assert_eq!(clamp.get(), Ok(None));
+    reject.update().unwrap();
+    assert_eq!(reject.get(), Ok(None));
+    assert_eq!(error.update(), Err(Error::Other(7)));
+
+    encoder.borrow_mut().datum = Datum::new(Time(0), State::new_raw(0.0, 10.0, 0.0));
+    for validator in [&mut clamp, &mut reject, &mut error] {
+        validator.update().unwrap();
+        assert_eq!(
+            validator.get(),
+            Ok(Some(Datum::new(Time(0), State::new_raw(0.0, 10.0, 0.0))))
+        );
+    }
+
+    //Moving 10 mm at 10 mm/s in one second is consistent.
+    encoder.borrow_mut().datum = Datum::new(Time(1_000_000_000), State::new_raw(10.5, 10.0, 0.0));
+    for validator in [&mut clamp, &mut reject, &mut error] {
+        validator.update().unwrap();
+        assert_eq!(validator.get().unwrap().unwrap().value.position, 10.5);
+    }
+
+    //Jumping 50 mm is not.
+    encoder.borrow_mut().datum = Datum::new(
+        Time(2_000_000_000),
+        State::new_raw(70.5, 10.0, f32::INFINITY),
+    );
+    clamp.update().unwrap();
+    assert_eq!(
+        clamp.get(),
+        Ok(Some(Datum::new(
+            Time(2_000_000_000),
+            State::new_raw(21.5, 10.0, 0.0)
+        )))
+    );
+    reject.update().unwrap();
+    assert_eq!(reject.get(), Ok(None));
+    assert_eq!(error.update(), Err(Error::Other(7)));
+}
+#[test]
+fn finite_guard_stream() {
+    struct Input {
+        datum: Datum<f32>,
+    }
+    impl Getter<f32, u8> for Input {
+        fn get(&self) -> Output<f32, u8> {
+            Ok(Some(self.datum))
+        }
+    }
+    impl Updatable<u8> for Input {
+        fn update(&mut self) -> NothingOrError<u8> {
+            Ok(())
+        }
+    }
+    let input = static_reference!(
+        Input,
+        Input {
+            datum: Datum::new(Time(0), f32::NAN)
+        }
+    );
+    let mut error = FiniteGuardStream::new(input.clone(), NonFiniteAction::ReturnError(3));
+    let mut hold = FiniteGuardStream::new(input.clone(), NonFiniteAction::HoldLastGood);
+    let mut substitute = FiniteGuardStream::new(input.clone(), NonFiniteAction::Substitute(0.0));
+    assert_eq!(error.update(), Err(Error::Other(3)));
+    hold.update().unwrap();
+    assert_eq!(hold.get(), Ok(None));
+    substitute.update().unwrap();
+    assert_eq!(substitute.get(), Ok(Some(Datum::new(Time(0), 0.0))));
+
+    input.borrow_mut().datum = Datum::new(Time(1), 5.0);
+    for stream in [&mut error, &mut hold, &mut substitute] {
+        stream.update().unwrap();
+        assert_eq!(stream.get(), Ok(Some(Datum::new(Time(1), 5.0))));
+    }
+
+    input.borrow_mut().datum = Datum::new(Time(2), f32::NEG_INFINITY);
+    assert_eq!(error.update(), Err(Error::Other(3)));
+    hold.update().unwrap();
+    assert_eq!(hold.get(), Ok(Some(Datum::new(Time(1), 5.0))));
+    substitute.update().unwrap();
+    assert_eq!(substitute.get(), Ok(Some(Datum::new(Time(2), 0.0))));
+
+    let quantity_input = static_reference!(
+        ConstantGetter<Quantity, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(4)),
+            Quantity::new(f32::NAN, MILLIMETER)
+        )
+    );
+    let mut quantity_guard = FiniteGuardStream::new(
+        quantity_input,
+        NonFiniteAction::Substitute(Quantity::new(1.0, MILLIMETER)),
+    );
+    quantity_guard.update().unwrap();
+    assert_eq!(quantity_guard.get().unwrap().unwrap().value.value, 1.0);
+}
+#[test]
+fn friction_compensator() {
+    let control = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 1.0)
+    );
+    let velocity = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 10.0)
+    );
+    let compensator = FrictionCompensator::new(control, velocity.clone(), 0.5, 0.1, 1.0);
+    assert_eq!(compensator.get(), Ok(Some(Datum::new(Time(0), 2.5))));
+    velocity.borrow_mut().set(-10.0).unwrap();
+    assert_eq!(compensator.get(), Ok(Some(Datum::new(Time(0), -0.5))));
+    //Within the deadband, only the viscous term is applied.
+    velocity.borrow_mut().set(0.5).unwrap();
+    assert_eq!(compensator.get(), Ok(Some(Datum::new(Time(0), 1.05))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn following_error_monitor() {
+    struct Target;
+    impl History<Command, ()> for Target {
+        fn get(&self, time: Time) -> Option<Datum<Command>> {
+            Some(Datum::new(time, Command::Position(10.0)))
+        }
+    }
+    impl Updatable<()> for Target {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let time = rc_ref_cell_reference(Time(0));
+    let state = rc_ref_cell_reference(ConstantGetter::<State, Time, ()>::new(
+        time.clone(),
+        State::new_raw(0.0, 0.0, 0.0),
+    ));
+    let monitor = rc_ref_cell_reference(FollowingErrorMonitor::new(
+        rc_ref_cell_reference(Target),
+        state.clone(),
+        time.clone(),
+        1.0,
+        Time(2),
+        Command::Velocity(0.0),
+    ));
+    let fault = FollowingErrorFaultStream::new(monitor.clone());
+    monitor.borrow_mut().update().unwrap();
+    assert_eq!(
+        monitor.borrow().get(),
+        Ok(Some(Datum::new(Time(0), Command::Position(10.0))))
+    );
+    assert_eq!(fault.get(), Ok(Some(Datum::new(Time(0), false))));
+    *time.borrow_mut() = Time(1);
+    monitor.borrow_mut().update().unwrap();
+    assert_eq!(
+        monitor.borrow().get(),
+        Ok(Some(Datum::new(Time(1), Command::Position(10.0))))
+    );
+    *time.borrow_mut() = Time(2);
+    monitor.borrow_mut().update().unwrap();
+    assert_eq!(
+        monitor.borrow().get(),
+        Ok(Some(Datum::new(Time(2), Command::Velocity(0.0))))
+    );
+    assert_eq!(fault.get(), Ok(Some(Datum::new(Time(2), true))));
+    //The fault latches even once the error is small again.
+    state
+        .borrow_mut()
+        .set(State::new_raw(10.0, 0.0, 0.0))
+        .unwrap();
+    *time.borrow_mut() = Time(3);
+    monitor.borrow_mut().update().unwrap();
+    assert_eq!(
+        monitor.borrow().get(),
+        Ok(Some(Datum::new(Time(3), Command::Velocity(0.0))))
+    );
+    monitor.borrow_mut().reset();
+    monitor.borrow_mut().update().unwrap();
+    assert_eq!(
+        monitor.borrow().get(),
+        Ok(Some(Datum::new(Time(3), Command::Position(10.0))))
+    );
+    assert_eq!(fault.get(), Ok(Some(Datum::new(Time(3), false))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn memoized_getter() {
+    use core::cell::Cell;
+    use std::rc::Rc;
+    let calls = Rc::new(Cell::new(0));
+    let calls_clone = calls.clone();
+    let input = rc_ref_cell_reference(FnGetter::new(move || -> Output<u32, ()> {
+        calls_clone.set(calls_clone.get() + 1);
+        Ok(Some(Datum::new(Time(0), calls_clone.get())))
+    }));
+    let mut memoized = MemoizedGetter::new(input);
+    assert!(memoized.is_invalidated());
+    assert_eq!(memoized.get(), Ok(None));
+    memoized.update().unwrap();
+    assert_eq!(memoized.get(), Ok(Some(Datum::new(Time(0), 1))));
+    assert_eq!(memoized.get(), Ok(Some(Datum::new(Time(0), 1))));
+    memoized.update().unwrap();
+    assert_eq!(calls.get(), 1);
+    memoized.invalidate();
+    //The old value is kept until the next update.
+    assert_eq!(memoized.get(), Ok(Some(Datum::new(Time(0), 1))));
+    memoized.update().unwrap();
+    assert_eq!(memoized.get(), Ok(Some(Datum::new(Time(0), 2))));
+    assert_eq!(calls.get(), 2);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn broadcast() {
+    struct Counter {
+        count: u32,
+    }
+    impl Getter<u32, ()> for Counter {
+        fn get(&self) -> Output<u32, ()> {
+            Ok(Some(Datum::new(Time(0), self.count)))
+        }
+    }
+    impl Updatable<()> for Counter {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.count += 1;
+            Ok(())
+        }
+    }
+    let time = rc_ref_cell_reference(Time(0));
+    let broadcast = rc_ref_cell_reference(Broadcast::new(Counter { count: 0 }, time.clone()));
+    let mut reader_1 = BroadcastReader::new(broadcast.clone());
+    let mut reader_2 = reader_1.clone();
+    assert_eq!(reader_1.get(), Ok(None));
+    reader_1.update().unwrap();
+    reader_2.update().unwrap();
+    broadcast.borrow_mut().update().unwrap();
+    assert_eq!(broadcast.borrow().get_source().count, 1);
+    assert_eq!(reader_1.get(), Ok(Some(Datum::new(Time(0), 1))));
+    assert_eq!(reader_2.get(), Ok(Some(Datum::new(Time(0), 1))));
+    *time.borrow_mut() = Time(10);
+    reader_2.update().unwrap();
+    reader_1.update().unwrap();
+    assert_eq!(broadcast.borrow().get_last_update(), Some(Time(10)));
+    assert_eq!(reader_1.get(), Ok(Some(Datum::new(Time(0), 2))));
+}
+#[test]
+fn polynomial_stream() {
+    let input = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(4)), 3.0)
+    );
+    let mut stream = PolynomialStream::new(input, rrtk::linalg::Polynomial::new([1.0, 2.0]));
+    assert_eq!(stream.get(), Ok(Some(Datum::new(Time(4), 7.0))));
+    stream.set_polynomial(rrtk::linalg::Polynomial::new([0.0, 0.0]));
+    assert_eq!(stream.get(), Ok(Some(Datum::new(Time(4), 0.0))));
+}
+#[test]
+fn motor_feedforward() {
+    let torque = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 1.5)
+    );
+    let velocity = static_reference!(NoneGetter, NoneGetter);
+    let feedforward = MotorFeedforward::new(
+        torque.clone(),
+        velocity,
+        MotorModel::new(600.0, 3.0, 200.0, 12.0),
+    );
+    assert_eq!(feedforward.get(), Ok(Some(Datum::new(Time(0), 6.0))));
+    let velocity = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(1)), 300.0)
+    );
+    let feedforward =
+        MotorFeedforward::new(torque, velocity, MotorModel::new(600.0, 3.0, 200.0, 12.0));
+    assert_eq!(feedforward.get(), Ok(Some(Datum::new(Time(1), 12.0))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn slip_limiter() {
+    let time = rc_ref_cell_reference(Time(0));
+    let drive = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 1.0));
+    let wheel = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 10.0));
+    let reference = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 10.0));
+    let mut limiter = SlipLimiter::new(drive, wheel.clone(), reference, 0.2, 1.0, 2.0, 1.0);
+    limiter.update().unwrap();
+    assert_eq!(limiter.get(), Ok(Some(Datum::new(Time(0), 1.0))));
+    assert!(!limiter.is_slipping());
+    //The wheel spins at twice the speed of the ground.
+    wheel.borrow_mut().set(20.0).unwrap();
+    *time.borrow_mut() = Time(250_000_000);
+    limiter.update().unwrap();
+    assert_eq!(limiter.get_slip_ratio(), 0.5);
+    assert!(limiter.is_slipping());
+    assert_eq!(limiter.get(), Ok(Some(Datum::new(Time(250_000_000), 0.5))));
+    *time.borrow_mut() = Time(500_000_000);
+    limiter.update().unwrap();
+    assert_eq!(limiter.get(), Ok(Some(Datum::new(Time(500_000_000), 0.0))));
+    //Traction is regained and power comes back gradually.
+    wheel.borrow_mut().set(10.0).unwrap();
+    *time.borrow_mut() = Time(750_000_000);
+    limiter.update().unwrap();
+    assert_eq!(limiter.get(), Ok(Some(Datum::new(Time(750_000_000), 0.25))));
+    //Below the minimum speed, slip is ignored.
+    wheel.borrow_mut().set(0.5).unwrap();
+    *time.borrow_mut() = Time(1_000_000_000);
+    limiter.update().unwrap();
+    assert!(!limiter.is_slipping());
+    assert_eq!(limiter.get_scale(), 0.5);
+    limiter.reset();
+    assert_eq!(limiter.get_scale(), 1.0);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn heading_hold() {
+    let time = rc_ref_cell_reference(Time(0));
+    let rotation = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.5));
+    let heading = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 1.0));
+    let mut hold = HeadingHold::new(
+        rotation.clone(),
+        heading.clone(),
+        PIDKValues::new(2.0, 0.0, 0.0),
+        0.05,
+    );
+    //The driver is turning, so the input passes through.
+    hold.update().unwrap();
+    assert_eq!(hold.get(), Ok(Some(Datum::new(Time(0), 0.5))));
+    assert_eq!(hold.get_target(), None);
+    //The driver lets go and the heading is latched.
+    rotation.borrow_mut().set(0.01).unwrap();
+    hold.update().unwrap();
+    assert_eq!(hold.get_target(), Some(1.0));
+    assert_eq!(hold.get(), Ok(Some(Datum::new(Time(0), 0.0))));
+    //A disturbance turns the robot and is corrected.
+    *time.borrow_mut() = Time(1_000_000_000);
+    heading.borrow_mut().set(0.75).unwrap();
+    hold.update().unwrap();
+    assert_eq!(hold.get(), Ok(Some(Datum::new(Time(1_000_000_000), 0.5))));
+    //The driver turns again.
+    rotation.borrow_mut().set(-0.3).unwrap();
+    hold.update().unwrap();
+    assert_eq!(hold.get(), Ok(Some(Datum::new(Time(1_000_000_000), -0.3))));
+    assert_eq!(hold.get_target(), None);
+}
+#[test]
+fn seeded_position() {
+    let incremental = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 0.0)
+    );
+    let absolute = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 5.0)
+    );
+    let mut seeded = SeededPosition::new(incremental.clone(), absolute.clone(), 1.0, 2);
+    assert_eq!(seeded.get(), Ok(None));
+    seeded.update().unwrap();
+    assert_eq!(seeded.get_offset(), Some(5.0));
+    assert_eq!(seeded.get(), Ok(Some(Datum::new(Time(0), 5.0))));
+    //Small disagreements are ignored.
+    incremental.borrow_mut().set(2.0).unwrap();
+    absolute.borrow_mut().set(7.5).unwrap();
+    seeded.update().unwrap();
+    assert_eq!(seeded.get(), Ok(Some(Datum::new(Time(0), 7.0))));
+    //A single large disagreement is ignored too.
+    absolute.borrow_mut().set(10.0).unwrap();
+    seeded.update().unwrap();
+    assert_eq!(seeded.get(), Ok(Some(Datum::new(Time(0), 7.0))));
+    absolute.borrow_mut().set(7.0).unwrap();
+    seeded.update().unwrap();
+    absolute.borrow_mut().set(10.0).unwrap();
+    seeded.update().unwrap();
+    assert_eq!(seeded.get(), Ok(Some(Datum::new(Time(0), 7.0))));
+    //A lasting one causes a reseed.
+    seeded.update().unwrap();
+    assert_eq!(seeded.get_offset(), Some(8.0));
+    assert_eq!(seeded.get(), Ok(Some(Datum::new(Time(0), 10.0))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn complementary_filter_stream() {
+    let time = rc_ref_cell_reference(Time(0));
+    let fast = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let slow = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let mut filter = ComplementaryFilterStream::new(fast.clone(), slow.clone(), 1.0);
+    assert_eq!(filter.get(), Ok(None));
+    filter.update().unwrap();
+    assert_eq!(filter.get(), Ok(Some(Datum::new(Time(0), 0.0))));
+    //α = 1 / (1 + 1) = 0.5
+    *time.borrow_mut() = Time(1_000_000_000);
+    fast.borrow_mut().set(2.0).unwrap();
+    filter.update().unwrap();
+    assert_eq!(filter.get(), Ok(Some(Datum::new(Time(1_000_000_000), 1.0))));
+    //The drift in the fast input is pulled back toward the slow input.
+    *time.borrow_mut() = Time(2_000_000_000);
+    filter.update().unwrap();
+    assert_eq!(filter.get(), Ok(Some(Datum::new(Time(2_000_000_000), 0.5))));
+    //With no new data, the output does not change.
+    filter.update().unwrap();
+    assert_eq!(filter.get(), Ok(Some(Datum::new(Time(2_000_000_000), 0.5))));
+    filter.set_time_constant(0.0);
+    assert_eq!(filter.get_time_constant(), 0.0);
+    *time.borrow_mut() = Time(3_000_000_000);
+    fast.borrow_mut().set(4.0).unwrap();
+    slow.borrow_mut().set(3.0).unwrap();
+    filter.update().unwrap();
+    assert_eq!(filter.get(), Ok(Some(Datum::new(Time(3_000_000_000), 3.0))));
+    filter.reset();
+    assert_eq!(filter.get(), Ok(None));
+    slow.borrow_mut().set(-1.0).unwrap();
+    filter.update().unwrap();
+    assert_eq!(
+        filter.get(),
+        Ok(Some(Datum::new(Time(3_000_000_000), -1.0)))
+    );
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn dynamic_setpoint_pid_controller_stream() {
+    let time = rc_ref_cell_reference(Time(0));
+    let input = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let setpoint = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 5.0));
+    let mut stream = DynamicSetpointPIDControllerStream::new(
+        input.clone(),
+        setpoint.clone(),
+        PIDKValues::new(1.0, 0.01, 0.1),
+    );
+    stream.update().unwrap();
+    assert_eq!(stream.get(), Ok(Some(Datum::new(Time(0), 5.0))));
+    //A step in the setpoint does not kick the derivative term.
+    *time.borrow_mut() = Time(2_000_000_000);
+    setpoint.borrow_mut().set(10.0).unwrap();
+    stream.update().unwrap();
+    //P: 10, I: 0.01 * 2 * (5 + 10) / 2 = 0.15, D: 0
+    assert_eq!(
+        stream.get(),
+        Ok(Some(Datum::new(Time(2_000_000_000), 10.15)))
+    );
+    //The process moving does show up in the derivative term.
+    *time.borrow_mut() = Time(4_000_000_000);
+    input.borrow_mut().set(4.0).unwrap();
+    stream.update().unwrap();
+    //P: 6, I: 0.15 + 0.01 * 2 * (10 + 6) / 2 = 0.31, D: 0.1 * -2
+    let output = stream.get().unwrap().unwrap().value;
+    assert!((output - 6.11).abs() < 0.0001);
+    let mut stream = DynamicSetpointPIDControllerStream::new(
+        input,
+        rc_ref_cell_reference(NoneGetter),
+        PIDKValues::new(1.0, 0.01, 0.1),
+    );
+    stream.update().unwrap();
+    assert_eq!(stream.get(), Ok(None));
+}
+#[test]
+fn command_pid_bumpless_retune() {
+    struct Input {
+        time: Time,
+    }
+    impl Getter<State, ()> for Input {
+        fn get(&self) -> Output<State, ()> {
+            Ok(Some(Datum::new(self.time, State::new_raw(5.0, 0.0, 0.0))))
+        }
+    }
+    impl Updatable<()> for Input {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.time += Time(1_000_000_000);
+            Ok(())
+        }
+    }
+    let integral_only = |ki| {
+        PositionDerivativeDependentPIDKValues::new(
+            PIDKValues::new(0.0, ki, 0.0),
+            PIDKValues::new(0.0, ki, 0.0),
+            PIDKValues::new(0.0, ki, 0.0),
+        )
+    };
+    let input = static_reference!(Input, Input { time: Time(0) });
+    let mut pid = CommandPID::new(
+        input.clone(),
+        Command::new(PositionDerivative::Position, 0.0),
+        integral_only(1.0),
+    );
+    for _ in 0..3 {
+        pid.update().unwrap();
+        input.borrow_mut().update().unwrap();
+    }
+    assert_eq!(pid.get().unwrap().unwrap().value, -10.0);
+    pid.set_k_values(integral_only(2.0));
+    assert_eq!(pid.get_k_values().position.ki, 2.0);
+    //The stored integral is halved, so only the new error is weighted by the doubled gain.
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, -20.0);
+    //Coming back from a zero integral gain, the integral term was zero, so it restarts from zero.
+    pid.set_k_values(integral_only(0.0));
+    pid.set_k_values(integral_only(2.0));
+    input.borrow_mut().update().unwrap();
+    pid.update().unwrap();
+    assert_eq!(pid.get().unwrap().unwrap().value, -10.0);
+}
+#[test]
+fn ratio_stream() {
+    let numerator = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 6.0)
+    );
+    let denominator = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(1)), 2.0)
+    );
+    let ratio = |action| RatioStream::new(numerator.clone(), denominator.clone(), 0.5, action);
+    assert_eq!(
+        ratio(SmallDenominatorAction::ReturnNone).get(),
+        Ok(Some(Datum::new(Time(1), 3.0)))
+    );
+    denominator.borrow_mut().set(-0.1).unwrap();
+    assert_eq!(ratio(SmallDenominatorAction::ReturnNone).get(), Ok(None));
+    assert_eq!(
+        ratio(SmallDenominatorAction::ReturnError(())).get(),
+        Err(Error::Other(()))
+    );
+    assert_eq!(
+        ratio(SmallDenominatorAction::Substitute(0.0)).get(),
+        Ok(Some(Datum::new(Time(1), 0.0)))
+    );
+    assert_eq!(
+        ratio(SmallDenominatorAction::ClampDenominator).get(),
+        Ok(Some(Datum::new(Time(1), -12.0)))
+    );
+    denominator.borrow_mut().set(0.0).unwrap();
+    assert_eq!(
+        ratio(SmallDenominatorAction::ClampDenominator).get(),
+        Ok(Some(Datum::new(Time(1), 12.0)))
+    );
+}
+#[test]
+fn normalize_stream() {
+    let input = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 300.0)
+    );
+    let unipolar = NormalizeStream::new(input.clone(), 200.0, 600.0, false);
+    let bipolar = NormalizeStream::new(input.clone(), 200.0, 600.0, true);
+    let reversed = NormalizeStream::new(input.clone(), 600.0, 200.0, false);
+    assert_eq!(unipolar.get(), Ok(Some(Datum::new(Time(0), 0.25))));
+    assert_eq!(bipolar.get(), Ok(Some(Datum::new(Time(0), -0.5))));
+    assert_eq!(reversed.get(), Ok(Some(Datum::new(Time(0), 0.75))));
+    input.borrow_mut().set(1000.0).unwrap();
+    assert_eq!(unipolar.get(), Ok(Some(Datum::new(Time(0), 1.0))));
+    assert_eq!(bipolar.normalize(0.0), -1.0);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn clamp_stream() {
+    let input = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(
+        rc_ref_cell_reference(Time(3)),
+        0.5,
+    ));
+    let clamp = rc_ref_cell_reference(ClampStream::new(input.clone(), -1.0, 1.0));
+    let saturation = ClampSaturationStream::new(clamp.clone());
+    assert_eq!(clamp.borrow().get(), Ok(Some(Datum::new(Time(3), 0.5))));
+    assert_eq!(saturation.get(), Ok(Some(Datum::new(Time(3), false))));
+    input.borrow_mut().set(1.5).unwrap();
+    assert_eq!(clamp.borrow().get(), Ok(Some(Datum::new(Time(3), 1.0))));
+    assert_eq!(saturation.get(), Ok(Some(Datum::new(Time(3), true))));
+    input.borrow_mut().set(-4.0).unwrap();
+    assert_eq!(clamp.borrow().get(), Ok(Some(Datum::new(Time(3), -1.0))));
+    assert_eq!(saturation.get(), Ok(Some(Datum::new(Time(3), true))));
+    //Exactly at a limit is not saturating.
+    assert!(!clamp.borrow().saturates(&1.0));
+    let clamp = rc_ref_cell_reference(ClampStream::<i32, _, ()>::new(
+        rc_ref_cell_reference(NoneGetter),
+        0,
+        10,
+    ));
+    assert_eq!(clamp.borrow().get(), Ok(None));
+    assert_eq!(ClampSaturationStream::new(clamp).get(), Ok(None));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn dither_stream() {
+    let time = rc_ref_cell_reference(Time(0));
+    let input = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.3));
+    let mut dither = DitherStream::new(input.clone(), time.clone(), Time(10));
+    assert_eq!(dither.get(), Ok(None));
+    let mut check = |t: i64, expected: bool| {
+        *time.borrow_mut() = Time(t);
+        dither.update().unwrap();
+        assert_eq!(
+            dither.get(),
+            Ok(Some(Datum::new(Time(t), expected))),
+            "{}",
+            t
+        );
+        dither.get_duty()
+    };
+    check(0, true);
+    check(2, true);
+    check(3, false);
+    //The input is only read at the start of each period.
+    input.borrow_mut().set(0.5).unwrap();
+    assert_eq!(check(9, false), 0.3);
+    assert_eq!(check(10, true), 0.5);
+    check(14, true);
+    check(15, false);
+    //A late update still lines up with the periods.
+    input.borrow_mut().set(0.6).unwrap();
+    check(27, false);
+    check(31, true);
+    //Inputs are clamped.
+    input.borrow_mut().set(1.5).unwrap();
+    check(40, true);
+    assert_eq!(check(49, true), 1.0);
+}
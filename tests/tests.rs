@@ -143,6 +143,44 @@ fn datum_replace_if_none_or_older_than_option() {
     assert_eq!(x, y);
 }
 #[test]
+fn datum_map() {
+    let mut x = Datum::new(Time(1), 2);
+    assert_eq!(x.map(|value| value * 3), Datum::new(Time(1), 6));
+    assert_eq!(x.as_ref(), Datum::new(Time(1), &2));
+    *x.as_mut().value += 1;
+    assert_eq!(x, Datum::new(Time(1), 3));
+    assert_eq!(
+        Some(x).map_value(|value| value + 1),
+        Some(Datum::new(Time(1), 4))
+    );
+    assert_eq!(None::<Datum<i32>>.map_value(|value| value + 1), None);
+}
+#[test]
+fn output_ext() {
+    let some: Output<i32, ()> = Ok(Some(Datum::new(Time(1), 2)));
+    let none: Output<i32, ()> = Ok(None);
+    let error: Output<i32, ()> = Err(Error::Other(()));
+    assert_eq!(
+        some.map_value(|value| value > 0),
+        Ok(Some(Datum::new(Time(1), true)))
+    );
+    assert_eq!(none.map_value(|value| value > 0), Ok(None));
+    assert_eq!(error.map_value(|value| value > 0), Err(Error::Other(())));
+    assert_eq!(some.ok_or_from_none(), Ok(Datum::new(Time(1), 2)));
+    assert_eq!(none.ok_or_from_none(), Err(Error::FromNone));
+    assert_eq!(
+        some.unwrap_or_value(5, &Time(10)),
+        Ok(Datum::new(Time(1), 2))
+    );
+    assert_eq!(
+        none.unwrap_or_value(5, &Time(10)),
+        Ok(Datum::new(Time(10), 5))
+    );
+    assert_eq!(error.unwrap_or_value(5, &Time(10)), Err(Error::Other(())));
+    assert_eq!(some.value(), Ok(Some(2)));
+    assert_eq!(none.value(), Ok(None));
+}
+#[test]
 fn datum_not() {
     assert_eq!(!Datum::new(Time(0), false), Datum::new(Time(0), true));
 }
@@ -615,6 +653,229 @@ fn motion_profile_piece() {
     );
 }
 #[test]
+#[cfg(any(
+    feature = "dim_check_release",
+    all(debug_assertions, feature = "dim_check_debug")
+))]
+fn motion_profile_try_new_unit_invalid() {
+    assert_eq!(
+        MotionProfile::try_new(
+            State::new_raw(0.0, 0.0, 0.0),
+            State::new_raw(3.0, 0.0, 0.0),
+            Quantity::new(0.1, MILLIMETER_PER_SECOND),
+            Quantity::new(0.01, MILLIMETER_PER_SECOND),
+        ),
+        Err(MotionProfileError::UnitInvalid(
+            UnitMismatch::new(MILLIMETER_PER_SECOND_SQUARED, MILLIMETER_PER_SECOND)
+                .with_operation("MotionProfile::try_new")
+        ))
+    );
+}
+#[test]
+fn motion_profile_replan() {
+    let mut motion_profile = MotionProfile::new(
+        State::new_raw(0.0, 0.0, 0.0),
+        State::new_raw(3.0, 0.0, 0.0),
+        Quantity::new(0.1, MILLIMETER_PER_SECOND),
+        Quantity::new(0.01, MILLIMETER_PER_SECOND_SQUARED),
+    );
+    assert_eq!(motion_profile.start_time(), Time(0));
+    let current = motion_profile.get_state(Time(20_000_000_000)).unwrap();
+    assert!((current.position - 1.5).abs() < 0.0001);
+    assert!((current.velocity - 0.1).abs() < 0.0001);
+    let current = State::new_raw(1.5, 0.1, 0.0);
+    //Too close to stop in time, so nothing changes.
+    let unchanged = motion_profile.clone();
+    assert_eq!(
+        motion_profile.replan(current, State::new_raw(1.6, 0.0, 0.0), Time(20_000_000_000)),
+        Err(MotionProfileError::DistanceTooShort)
+    );
+    assert_eq!(motion_profile, unchanged);
+    motion_profile
+        .replan(current, State::new_raw(2.5, 0.0, 0.0), Time(20_000_000_000))
+        .unwrap();
+    assert_eq!(motion_profile.start_time(), Time(20_000_000_000));
+    //Converting the phase times from seconds loses a little precision.
+    assert!((motion_profile.duration() - Time(15_000_000_000)).0.abs() < 1000);
+    let [t1, t2, t3] = motion_profile.phase_boundaries();
+    assert_eq!(t1, Time(20_000_000_000));
+    assert_eq!(t2, Time(25_000_000_000));
+    assert!((t3 - Time(35_000_000_000)).0.abs() < 1000);
+    assert_eq!(
+        motion_profile.get_piece(Time(10_000_000_000)),
+        MotionProfilePiece::BeforeStart
+    );
+    assert_eq!(
+        motion_profile.get_velocity(Time(20_000_000_000)),
+        Some(Quantity::new(0.1, MILLIMETER_PER_SECOND))
+    );
+    assert_eq!(
+        motion_profile.get_position(Time(25_000_000_000)),
+        Some(Quantity::new(2.0, MILLIMETER))
+    );
+    assert_eq!(
+        motion_profile.get_piece(Time(30_000_000_000)),
+        MotionProfilePiece::EndAcceleration
+    );
+    assert_eq!(
+        History::<Command, ()>::get(&motion_profile, Time(40_000_000_000)),
+        Some(Datum::new(Time(40_000_000_000), Command::Position(2.5)))
+    );
+    let samples: Vec<Time> = motion_profile
+        .sample_iter(Time(5_000_000_000))
+        .map(|(time, _)| time)
+        .collect();
+    assert_eq!(samples.first(), Some(&Time(20_000_000_000)));
+    assert_eq!(samples.last(), Some(&t3));
+}
+#[test]
+#[cfg(any(feature = "std", feature = "libm"))]
+fn s_curve_motion_profile() {
+    fn assert_close(state: State, position: f32, velocity: f32, acceleration: f32) {
+        assert!((state.position - position).abs() < 0.001, "{:?}", state);
+        assert!((state.velocity - velocity).abs() < 0.001, "{:?}", state);
+        assert!(
+            (state.acceleration - acceleration).abs() < 0.001,
+            "{:?}",
+            state
+        );
+    }
+    //Reaches both the maximum velocity and the maximum acceleration.
+    let profile = SCurveMotionProfile::new(
+        Quantity::new(0.0, MILLIMETER),
+        Quantity::new(10.0, MILLIMETER),
+        Quantity::new(2.0, MILLIMETER_PER_SECOND),
+        Quantity::new(2.0, MILLIMETER_PER_SECOND_SQUARED),
+        Quantity::new(4.0, MILLIMETER_PER_SECOND_CUBED),
+    );
+    assert_eq!(profile.duration(), Time(6_500_000_000));
+    assert_eq!(
+        profile.phase_boundaries(),
+        [
+            Time(500_000_000),
+            Time(1_000_000_000),
+            Time(1_500_000_000),
+            Time(5_000_000_000),
+            Time(5_500_000_000),
+            Time(6_000_000_000),
+            Time(6_500_000_000)
+        ]
+    );
+    assert_eq!(profile.get_state(Time(-1)), None);
+    assert_close(profile.get_state(Time(0)).unwrap(), 0.0, 0.0, 0.0);
+    assert_close(
+        profile.get_state(Time(500_000_000)).unwrap(),
+        1.0 / 12.0,
+        0.5,
+        2.0,
+    );
+    assert_close(
+        profile.get_state(Time(3_250_000_000)).unwrap(),
+        5.0,
+        2.0,
+        0.0,
+    );
+    assert_close(
+        profile.get_state(Time(6_500_000_000)).unwrap(),
+        10.0,
+        0.0,
+        0.0,
+    );
+    assert_eq!(
+        profile.get_jerk(Time(100_000_000)),
+        Some(Quantity::new(4.0, MILLIMETER_PER_SECOND_CUBED))
+    );
+    assert_eq!(
+        profile.get_piece(Time(1_200_000_000)),
+        MotionProfilePiece::InitialAcceleration
+    );
+    assert_eq!(
+        profile.get_piece(Time(6_000_000_000)),
+        MotionProfilePiece::EndAcceleration
+    );
+    assert_eq!(
+        History::<Command, ()>::get(&profile, Time(2_000_000_000)),
+        Some(Datum::new(Time(2_000_000_000), Command::Velocity(2.0)))
+    );
+    assert_eq!(
+        History::<Command, ()>::get(&profile, Time(7_000_000_000)),
+        Some(Datum::new(Time(7_000_000_000), Command::Position(10.0)))
+    );
+    //Reaches the maximum acceleration but not the maximum velocity, going backward.
+    let profile = SCurveMotionProfile::new(
+        Quantity::new(10.0, MILLIMETER),
+        Quantity::new(0.0, MILLIMETER),
+        Quantity::new(10.0, MILLIMETER_PER_SECOND),
+        Quantity::new(2.0, MILLIMETER_PER_SECOND_SQUARED),
+        Quantity::new(4.0, MILLIMETER_PER_SECOND_CUBED),
+    );
+    assert_eq!(profile.duration(), Time(5_000_000_000));
+    assert_eq!(
+        profile.peak_velocity(),
+        Quantity::new(-4.0, MILLIMETER_PER_SECOND)
+    );
+    assert_close(
+        profile.get_state(Time(2_500_000_000)).unwrap(),
+        5.0,
+        -4.0,
+        0.0,
+    );
+    assert_close(
+        profile.get_state(Time(5_000_000_000)).unwrap(),
+        0.0,
+        0.0,
+        0.0,
+    );
+    //Reaches neither.
+    let profile = SCurveMotionProfile::new(
+        Quantity::new(0.0, MILLIMETER),
+        Quantity::new(0.5, MILLIMETER),
+        Quantity::new(10.0, MILLIMETER_PER_SECOND),
+        Quantity::new(10.0, MILLIMETER_PER_SECOND_SQUARED),
+        Quantity::new(2.0, MILLIMETER_PER_SECOND_CUBED),
+    );
+    assert!((profile.duration() - Time(2_000_000_000)).0.abs() < 1000);
+    assert_close(
+        profile.get_state(Time(1_000_000_000)).unwrap(),
+        0.25,
+        0.5,
+        0.0,
+    );
+    assert_eq!(
+        SCurveMotionProfile::try_new(
+            Quantity::new(0.0, MILLIMETER),
+            Quantity::new(1.0, MILLIMETER),
+            Quantity::new(1.0, MILLIMETER_PER_SECOND),
+            Quantity::new(1.0, MILLIMETER_PER_SECOND_SQUARED),
+            Quantity::new(0.0, MILLIMETER_PER_SECOND_CUBED),
+        ),
+        Err(MotionProfileError::ZeroJerk)
+    );
+}
+#[test]
+#[cfg(all(
+    any(feature = "std", feature = "libm"),
+    any(
+        feature = "dim_check_release",
+        all(debug_assertions, feature = "dim_check_debug")
+    )
+))]
+fn s_curve_motion_profile_unit_invalid() {
+    assert_eq!(
+        SCurveMotionProfile::try_new(
+            Quantity::new(0.0, MILLIMETER),
+            Quantity::new(1.0, MILLIMETER),
+            Quantity::new(1.0, MILLIMETER_PER_SECOND),
+            Quantity::new(1.0, MILLIMETER_PER_SECOND_SQUARED),
+            Quantity::new(1.0, MILLIMETER_PER_SECOND_SQUARED),
+        ),
+        Err(MotionProfileError::UnitInvalid(
+            UnitMismatch::new(MILLIMETER_PER_SECOND_CUBED, MILLIMETER_PER_SECOND_SQUARED)
+                .with_operation("SCurveMotionProfile::try_new")
+        ))
+    );
+}
+#[test]
 fn command() {
     let command = Command::new(PositionDerivative::Position, 5.0);
     assert_eq!(command.get_position(), Some(Quantity::new(5.0, MILLIMETER)));
@@ -987,3 +1248,870 @@ fn none_getter() {
     <NoneGetter as Updatable<()>>::update(&mut getter).unwrap();
     assert_eq!(<NoneGetter as Getter<(), ()>>::get(&getter), Ok(None));
 }
+#[test]
+fn tee_settable() {
+    struct Sink {
+        settable_data: SettableData<f32, ()>,
+        value: f32,
+        fail: bool,
+    }
+    impl Sink {
+        const fn new(fail: bool) -> Self {
+            Self {
+                settable_data: SettableData::new(),
+                value: 0.0,
+                fail: fail,
+            }
+        }
+    }
+    impl Settable<f32, ()> for Sink {
+        fn get_settable_data_ref(&self) -> &SettableData<f32, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: f32) -> NothingOrError<()> {
+            if self.fail {
+                return Err(Error::Other(()));
+            }
+            self.value = value;
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Sink {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let motor = static_reference!(Sink, Sink::new(false));
+    let logger = static_reference!(Sink, Sink::new(false));
+    let mut tee = TeeSettable::new(motor.clone(), logger.clone(), TeeErrorPolicy::FailFast);
+    tee.set(5.0).unwrap();
+    assert_eq!(motor.borrow().value, 5.0);
+    assert_eq!(logger.borrow().value, 5.0);
+    tee.update().unwrap();
+
+    let broken_motor = static_reference!(Sink, Sink::new(true));
+    let logger = static_reference!(Sink, Sink::new(false));
+    let mut tee = TeeSettable::new(
+        broken_motor.clone(),
+        logger.clone(),
+        TeeErrorPolicy::FailFast,
+    );
+    assert_eq!(tee.set(1.0), Err(Error::Other(())));
+    assert_eq!(logger.borrow().value, 0.0);
+    let mut tee = TeeSettable::new(broken_motor, logger.clone(), TeeErrorPolicy::ForwardToBoth);
+    assert_eq!(tee.set(2.0), Err(Error::Other(())));
+    assert_eq!(logger.borrow().value, 2.0);
+
+    let motor = static_reference!(Sink, Sink::new(false));
+    let broken_logger = static_reference!(Sink, Sink::new(true));
+    let mut tee = TeeSettable::new(motor.clone(), broken_logger, TeeErrorPolicy::IgnoreSecond);
+    tee.set(3.0).unwrap();
+    assert_eq!(motor.borrow().value, 3.0);
+}
+#[test]
+fn settable_group() {
+    struct Sink {
+        settable_data: SettableData<f32, ()>,
+        value: f32,
+        updates: u8,
+    }
+    impl Sink {
+        const fn new() -> Self {
+            Self {
+                settable_data: SettableData::new(),
+                value: 0.0,
+                updates: 0,
+            }
+        }
+    }
+    impl Settable<f32, ()> for Sink {
+        fn get_settable_data_ref(&self) -> &SettableData<f32, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: f32) -> NothingOrError<()> {
+            self.value = value;
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Sink {
+        fn update(&mut self) -> NothingOrError<()> {
+            self.updates += 1;
+            Ok(())
+        }
+    }
+    let angle = static_reference!(Sink, Sink::new());
+    let speed = static_reference!(Sink, Sink::new());
+    let mut group = SettableGroup::new([
+        to_dyn!(Settable<f32, ()>, angle.clone()),
+        to_dyn!(Settable<f32, ()>, speed.clone()),
+    ]);
+    group.stage(0, 1.5);
+    assert!(group.has_pending());
+    assert_eq!(angle.borrow().value, 0.0);
+    group.stage(1, 3.0);
+    group.commit().unwrap();
+    assert!(!group.has_pending());
+    assert_eq!(angle.borrow().value, 1.5);
+    assert_eq!(speed.borrow().value, 3.0);
+
+    group.set([2.0, 4.0]).unwrap();
+    assert_eq!(angle.borrow().value, 1.5);
+    assert_eq!(speed.borrow().value, 3.0);
+    group.update().unwrap();
+    assert_eq!(angle.borrow().value, 2.0);
+    assert_eq!(speed.borrow().value, 4.0);
+    assert_eq!(angle.borrow().updates, 1);
+    assert_eq!(speed.borrow().updates, 1);
+
+    group.stage(1, 5.0);
+    group.discard();
+    group.update().unwrap();
+    assert_eq!(speed.borrow().value, 4.0);
+}
+#[test]
+fn inverted_and_scaled_settable() {
+    struct Sink {
+        settable_data: SettableData<f32, ()>,
+        value: f32,
+    }
+    impl Sink {
+        const fn new() -> Self {
+            Self {
+                settable_data: SettableData::new(),
+                value: 0.0,
+            }
+        }
+    }
+    impl Settable<f32, ()> for Sink {
+        fn get_settable_data_ref(&self) -> &SettableData<f32, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: f32) -> NothingOrError<()> {
+            self.value = value;
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Sink {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let motor = static_reference!(Sink, Sink::new());
+    let mut inverted = InvertedSettable::new(motor.clone());
+    inverted.set(0.5).unwrap();
+    assert_eq!(motor.borrow().value, -0.5);
+    assert_eq!(inverted.get_last_request(), Some(0.5));
+
+    let motor = static_reference!(Sink, Sink::new());
+    let mut scaled = ScaledSettable::new(motor.clone(), 2.0);
+    scaled.set(3.0).unwrap();
+    assert_eq!(motor.borrow().value, 6.0);
+    scaled.set_scale(-1.0);
+    assert_eq!(scaled.scale(), -1.0);
+    scaled.set(3.0).unwrap();
+    assert_eq!(motor.borrow().value, -3.0);
+
+    let command = static_reference!(
+        ConstantGetter<f32, Time, ()>,
+        ConstantGetter::new(static_reference!(Time, Time(0)), 4.0)
+    );
+    scaled.follow(to_dyn!(Getter<f32, ()>, command));
+    scaled.update().unwrap();
+    assert_eq!(motor.borrow().value, -4.0);
+
+    let voltage = OutputMode::Voltage {
+        nominal_voltage: 12.0,
+    };
+    let velocity = OutputMode::Velocity { max_velocity: 50.0 };
+    assert_eq!(voltage.full_output(), 12.0);
+    assert_eq!(voltage.convert(6.0, velocity), 25.0);
+    let motor = static_reference!(Sink, Sink::new());
+    let mut output_mode =
+        OutputModeSettable::new(motor.clone(), voltage, OutputMode::PercentOutput);
+    output_mode.set(3.0).unwrap();
+    assert_eq!(motor.borrow().value, 0.25);
+    output_mode.set_output_mode(velocity);
+    assert_eq!(output_mode.get_output_mode(), velocity);
+    output_mode.set(-6.0).unwrap();
+    assert_eq!(motor.borrow().value, -25.0);
+    output_mode.set_input_mode(OutputMode::PercentOutput);
+    assert_eq!(output_mode.get_input_mode(), OutputMode::PercentOutput);
+    output_mode.set(0.5).unwrap();
+    assert_eq!(motor.borrow().value, 25.0);
+}
+#[test]
+fn looping_history() {
+    struct TimeHistory;
+    impl History<i64, ()> for TimeHistory {
+        fn get(&self, time: Time) -> Option<Datum<i64>> {
+            Some(Datum::new(time, time.0))
+        }
+    }
+    impl Updatable<()> for TimeHistory {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let limited = LoopingHistory::new(TimeHistory, Time(10), Time(5), Some(2));
+    assert_eq!(limited.get(Time(3)), Some(Datum::new(Time(3), 3)));
+    assert_eq!(limited.get(Time(10)), Some(Datum::new(Time(10), 10)));
+    assert_eq!(limited.get(Time(14)), Some(Datum::new(Time(14), 14)));
+    assert_eq!(limited.get(Time(15)), Some(Datum::new(Time(15), 10)));
+    assert_eq!(limited.get(Time(19)), Some(Datum::new(Time(19), 14)));
+    assert_eq!(limited.get(Time(20)), Some(Datum::new(Time(20), 15)));
+    assert_eq!(limited.get(Time(100)), Some(Datum::new(Time(100), 15)));
+    let forever = LoopingHistory::new(TimeHistory, Time(10), Time(5), None);
+    assert_eq!(forever.get(Time(27)), Some(Datum::new(Time(27), 12)));
+    assert_eq!(forever.get(Time(1000)), Some(Datum::new(Time(1000), 10)));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn history_sequence() {
+    struct TimeHistory(i64);
+    impl History<i64, ()> for TimeHistory {
+        fn get(&self, time: Time) -> Option<Datum<i64>> {
+            if time < Time(0) {
+                return None;
+            }
+            Some(Datum::new(time, self.0 + time.0))
+        }
+    }
+    impl Updatable<()> for TimeHistory {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let mut sequence = HistorySequence::new();
+    assert!(sequence.is_empty());
+    assert_eq!(sequence.get(Time(0)), None);
+    sequence.push(TimeHistory(100), Time(10));
+    sequence.push(TimeHistory(200), Time(5));
+    sequence.push(TimeHistory(300), Time(20));
+    assert_eq!(sequence.len(), 3);
+    assert_eq!(sequence.duration(), Time(35));
+    assert_eq!(sequence.get_segment_start(2), Some(Time(15)));
+    assert_eq!(sequence.get_segment_start(3), None);
+    assert_eq!(sequence.get_segment(Time(12)), Some((1, Time(10))));
+    assert_eq!(sequence.get(Time(-1)), None);
+    assert_eq!(sequence.get(Time(3)), Some(Datum::new(Time(3), 103)));
+    assert_eq!(sequence.get(Time(10)), Some(Datum::new(Time(10), 200)));
+    assert_eq!(sequence.get(Time(16)), Some(Datum::new(Time(16), 301)));
+    //The last segment keeps going after it ends.
+    assert_eq!(sequence.get(Time(50)), Some(Datum::new(Time(50), 335)));
+    sequence.update().unwrap();
+
+    let there = MotionProfile::new(
+        State::new_raw(0.0, 0.0, 0.0),
+        State::new_raw(3.0, 0.0, 0.0),
+        Quantity::new(0.1, MILLIMETER_PER_SECOND),
+        Quantity::new(0.01, MILLIMETER_PER_SECOND_SQUARED),
+    );
+    let back = MotionProfile::new(
+        State::new_raw(3.0, 0.0, 0.0),
+        State::new_raw(0.0, 0.0, 0.0),
+        Quantity::new(0.1, MILLIMETER_PER_SECOND),
+        Quantity::new(0.01, MILLIMETER_PER_SECOND_SQUARED),
+    );
+    let mut path = HistorySequence::<Command, ()>::new();
+    let there_duration = there.duration();
+    path.push_finite(there.clone());
+    path.push_finite(back.clone());
+    assert_eq!(path.get_segment_start(1), Some(there_duration));
+    //A replanned profile does not finish until its start time plus its duration.
+    let mut replanned = back.clone();
+    replanned
+        .replan(
+            State::new_raw(3.0, 0.0, 0.0),
+            State::new_raw(0.0, 0.0, 0.0),
+            Time(1_000_000_000),
+        )
+        .unwrap();
+    assert_eq!(
+        FiniteHistory::<Command, ()>::end_time(&replanned),
+        Time(1_000_000_000) + back.duration()
+    );
+    assert_eq!(
+        path.get(Time(5_000_000_000)),
+        History::<Command, ()>::get(&there, Time(5_000_000_000))
+    );
+    let datum = path.get(there_duration + Time(5_000_000_000)).unwrap();
+    assert_eq!(datum.time, there_duration + Time(5_000_000_000));
+    assert_eq!(
+        Some(datum.value),
+        History::<Command, ()>::get(&back, Time(5_000_000_000)).map(|datum| datum.value)
+    );
+    assert_eq!(
+        path.get(Time(1_000_000_000_000)).unwrap().value,
+        Command::Position(0.0)
+    );
+}
+#[test]
+fn setpoint_history() {
+    let knots = [(Time(0), 0.0), (Time(10), 10.0), (Time(20), 0.0)];
+    let hold = SetpointHistory::new(
+        knots,
+        SetpointInterpolation::Hold,
+        PositionDerivative::Position,
+    );
+    assert_eq!(History::<f32, ()>::get(&hold, Time(-1)), None);
+    assert_eq!(
+        History::<f32, ()>::get(&hold, Time(5)),
+        Some(Datum::new(Time(5), 0.0))
+    );
+    assert_eq!(
+        History::<f32, ()>::get(&hold, Time(30)),
+        Some(Datum::new(Time(30), 0.0))
+    );
+    let linear = SetpointHistory::new(
+        knots,
+        SetpointInterpolation::Linear,
+        PositionDerivative::Position,
+    );
+    assert_eq!(linear.get_value(Time(5)), Some(5.0));
+    assert_eq!(linear.get_value(Time(15)), Some(5.0));
+    assert_eq!(
+        History::<Command, ()>::get(&linear, Time(10)),
+        Some(Datum::new(Time(10), Command::Position(10.0)))
+    );
+    let cubic = SetpointHistory::new(
+        knots,
+        SetpointInterpolation::Cubic,
+        PositionDerivative::Position,
+    );
+    //The cubic passes through the knots but overshoots a straight line between them.
+    assert_eq!(cubic.get_value(Time(10)), Some(10.0));
+    let value = cubic.get_value(Time(5)).unwrap();
+    assert!(value > 5.0 && value < 10.0);
+    let symmetric = cubic.get_value(Time(15)).unwrap();
+    assert!((value - symmetric).abs() < 0.0001);
+}
+#[test]
+#[should_panic]
+fn setpoint_history_unsorted() {
+    SetpointHistory::new(
+        [(Time(10), 0.0), (Time(0), 1.0)],
+        SetpointInterpolation::Linear,
+        PositionDerivative::Position,
+    );
+}
+#[test]
+fn closure_adapters() {
+    let getter = FnGetter::new(|| -> Output<f32, ()> { Ok(Some(Datum::new(Time(1), 2.0))) });
+    assert_eq!(getter.get(), Ok(Some(Datum::new(Time(1), 2.0))));
+    let time_getter = FnTimeGetter::new(|| -> TimeOutput<()> { Ok(Time(3)) });
+    assert_eq!(time_getter.get(), Ok(Time(3)));
+    let mut last_set = None;
+    {
+        let mut settable = FnSettable::new(|value: f32| -> NothingOrError<()> {
+            last_set = Some(value);
+            Ok(())
+        });
+        settable.set(4.0).unwrap();
+        assert_eq!(settable.get_last_request(), Some(4.0));
+        settable.follow(to_dyn!(
+            Getter<f32, ()>,
+            static_reference!(
+                ConstantGetter<f32, Time, ()>,
+                ConstantGetter::new(static_reference!(Time, Time(0)), 5.0)
+            )
+        ));
+        settable.update().unwrap();
+    }
+    assert_eq!(last_set, Some(5.0));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn iterator_bridge() {
+    let source = (0..10).map(|i| Datum::new(Time(i), i as f32));
+    let getter = rc_ref_cell_reference(IterGetter::<f32, _, ()>::new(source));
+    assert_eq!(getter.borrow().get(), Ok(None));
+    let values: Vec<f32> = GetterIterator::new(getter.clone(), Some(3), None)
+        .map(|datum| datum.value)
+        .collect();
+    assert_eq!(values, [0.0, 1.0, 2.0]);
+    let times: Vec<Time> = GetterIterator::new(getter.clone(), None, Some(Time(5)))
+        .map(|datum| datum.time)
+        .collect();
+    assert_eq!(times, [Time(3), Time(4), Time(5)]);
+    //Iteration ends on an error.
+    struct Failing;
+    impl Getter<f32, ()> for Failing {
+        fn get(&self) -> Output<f32, ()> {
+            Err(Error::Other(()))
+        }
+    }
+    impl Updatable<()> for Failing {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let mut iterator = GetterIterator::new(rc_ref_cell_reference(Failing), None, None);
+    assert_eq!(iterator.next(), None);
+    assert_eq!(iterator.get_error(), Some(Error::Other(())));
+}
+#[test]
+fn motor_model() {
+    let motor = MotorModel::new(600.0, 3.0, 200.0, 12.0);
+    assert_eq!(motor.resistance(), 0.06);
+    assert_eq!(motor.torque_constant(), 0.015);
+    assert_eq!(motor.velocity_constant(), 50.0);
+    assert_eq!(motor.voltage(3.0, 0.0), 12.0);
+    assert_eq!(motor.voltage(0.0, 600.0), 12.0);
+    assert_eq!(motor.duty_cycle(1.5, 300.0), 1.0);
+    assert_eq!(motor.torque(12.0, 0.0), 3.0);
+    assert_eq!(motor.current(12.0, 300.0), 100.0);
+    assert_eq!(motor.velocity(12.0, 0.0), 600.0);
+    let voltage = motor.voltage(1.0, 200.0);
+    assert!((motor.torque(voltage, 200.0) - 1.0).abs() < 0.0001);
+    assert!((motor.velocity(voltage, 1.0) - 200.0).abs() < 0.01);
+}
+#[test]
+fn tip_guard() {
+    struct Sink {
+        settable_data: SettableData<f32, ()>,
+        value: f32,
+    }
+    impl Settable<f32, ()> for Sink {
+        fn get_settable_data_ref(&self) -> &SettableData<f32, ()> {
+            &self.settable_data
+        }
+        fn get_settable_data_mut(&mut self) -> &mut SettableData<f32, ()> {
+            &mut self.settable_data
+        }
+        fn impl_set(&mut self, value: f32) -> NothingOrError<()> {
+            self.value = value;
+            Ok(())
+        }
+    }
+    impl Updatable<()> for Sink {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let motor = static_reference!(
+        Sink,
+        Sink {
+            settable_data: SettableData::new(),
+            value: 0.0,
+        }
+    );
+    let tilt = static_reference!(
+        ConstantGetter<State, Time, ()>,
+        ConstantGetter::new(
+            static_reference!(Time, Time(0)),
+            State::new_raw(0.0, 0.0, 0.0)
+        )
+    );
+    let mut guard = TipGuard::new(
+        tilt.clone(),
+        motor.clone(),
+        0.3,
+        1.0,
+        0.1,
+        TipGuardAction::Correct(2.0),
+    );
+    guard.set(0.5).unwrap();
+    guard.update().unwrap();
+    assert!(!guard.is_tipping());
+    assert_eq!(motor.borrow().value, 0.5);
+    //Past the maximum angle
+    tilt.borrow_mut()
+        .set(State::new_raw(0.4, 0.0, 0.0))
+        .unwrap();
+    guard.update().unwrap();
+    assert!(guard.is_tipping());
+    assert_eq!(motor.borrow().value, -0.8);
+    guard.set(1.0).unwrap();
+    assert_eq!(motor.borrow().value, -0.8);
+    //Still past the recovery angle
+    tilt.borrow_mut()
+        .set(State::new_raw(0.2, -0.5, 0.0))
+        .unwrap();
+    guard.update().unwrap();
+    assert!(guard.is_tipping());
+    assert_eq!(motor.borrow().value, -0.4);
+    tilt.borrow_mut()
+        .set(State::new_raw(0.05, -0.5, 0.0))
+        .unwrap();
+    guard.update().unwrap();
+    assert!(!guard.is_tipping());
+    assert_eq!(motor.borrow().value, 1.0);
+    //Tipping quickly, even though the angle is small
+    tilt.borrow_mut()
+        .set(State::new_raw(-0.1, -2.0, 0.0))
+        .unwrap();
+    guard.update().unwrap();
+    assert!(guard.is_tipping());
+    //Moving quickly back toward level is fine.
+    let mut guard = TipGuard::new(
+        tilt.clone(),
+        motor.clone(),
+        0.3,
+        1.0,
+        0.1,
+        TipGuardAction::CutPower,
+    );
+    tilt.borrow_mut()
+        .set(State::new_raw(-0.1, 2.0, 0.0))
+        .unwrap();
+    guard.update().unwrap();
+    assert!(!guard.is_tipping());
+    tilt.borrow_mut()
+        .set(State::new_raw(0.5, 0.0, 0.0))
+        .unwrap();
+    guard.update().unwrap();
+    assert_eq!(motor.borrow().value, 0.0);
+}
+#[test]
+fn motion_profile_visualization() {
+    let motion_profile = MotionProfile::new(
+        State::new_raw(0.0, 0.0, 0.0),
+        State::new_raw(3.0, 0.0, 0.0),
+        Quantity::new(0.1, MILLIMETER_PER_SECOND),
+        Quantity::new(0.01, MILLIMETER_PER_SECOND_SQUARED),
+    );
+    let [t1, t2, t3] = motion_profile.phase_boundaries();
+    assert_eq!(t1, Time(10_000_000_000));
+    assert!((t2 - Time(30_000_000_000)).0.abs() < 1_000_000);
+    assert_eq!(t3, motion_profile.duration());
+    assert!((motion_profile.peak_velocity().value - 0.1).abs() < 0.0001);
+    motion_profile
+        .peak_velocity()
+        .unit
+        .assert_eq_assume_ok(&MILLIMETER_PER_SECOND);
+    assert_eq!(motion_profile.get_state(Time(-1)), None);
+    let samples = motion_profile.sample_iter(Time(15_000_000_000));
+    let mut times = [Time(0); 4];
+    let mut count = 0;
+    let mut last_state = State::default();
+    for (i, (time, state)) in samples.enumerate() {
+        times[i] = time;
+        count += 1;
+        last_state = state;
+    }
+    assert_eq!(count, 4);
+    assert_eq!(
+        times[..3],
+        [Time(0), Time(15_000_000_000), Time(30_000_000_000)]
+    );
+    assert_eq!(times[3], t3);
+    assert!((last_state.position - 3.0).abs() < 0.001);
+    assert!(last_state.velocity.abs() < 0.001);
+    let middle = motion_profile.get_state(Time(15_000_000_000)).unwrap();
+    assert!((middle.position - 1.0).abs() < 0.001);
+    assert!((middle.velocity - 0.1).abs() < 0.001);
+    assert_eq!(middle.acceleration, 0.0);
+}
+#[test]
+fn command_from_state_history() {
+    struct Trajectory;
+    impl History<State, ()> for Trajectory {
+        fn get(&self, time: Time) -> Option<Datum<State>> {
+            if time < Time(0) {
+                return None;
+            }
+            if time < Time(10) {
+                Some(Datum::new(time, State::new_raw(1.0, 2.0, 0.0)))
+            } else {
+                Some(Datum::new(time, State::new_raw(3.0, 0.0, 0.0)))
+            }
+        }
+    }
+    impl Updatable<()> for Trajectory {
+        fn update(&mut self) -> NothingOrError<()> {
+            Ok(())
+        }
+    }
+    let mut history =
+        CommandFromStateHistory::new(Trajectory, StateCommandPreference::HighestNonzero);
+    assert_eq!(History::<Command, ()>::get(&history, Time(-1)), None);
+    assert_eq!(
+        history.get(Time(5)),
+        Some(Datum::new(Time(5), Command::Velocity(2.0)))
+    );
+    assert_eq!(
+        history.get(Time(15)),
+        Some(Datum::new(Time(15), Command::Position(3.0)))
+    );
+    history.set_preference(StateCommandPreference::Always(PositionDerivative::Position));
+    assert_eq!(
+        history.get_preference(),
+        StateCommandPreference::Always(PositionDerivative::Position)
+    );
+    assert_eq!(
+        history.get(Time(5)),
+        Some(Datum::new(Time(5), Command::Position(1.0)))
+    );
+    history.update().unwrap();
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn delayed_action_queue() {
+    let time = rc_ref_cell_reference(Time(0));
+    let latch = rc_ref_cell_reference(ConstantGetter::<bool, _, ()>::new(time.clone(), false));
+    let mut queue = DelayedActionQueue::<_, _, _, 2, _>::new(latch.clone(), time.clone());
+    assert!(queue.is_empty());
+    queue.schedule(Time(20), false).unwrap();
+    queue.schedule_after(Time(10), true).unwrap();
+    assert_eq!(queue.schedule(Time(30), true), Err(true));
+    assert_eq!(
+        queue.schedule_after(Time(30), true),
+        Err(ScheduleError::Full(true))
+    );
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.next_time(), Some(Time(10)));
+    queue.update().unwrap();
+    assert!(!latch.borrow().get().unwrap().unwrap().value);
+    *time.borrow_mut() = Time(15);
+    queue.update().unwrap();
+    assert!(latch.borrow().get().unwrap().unwrap().value);
+    assert_eq!(queue.len(), 1);
+    //Actions due at the same time are applied in the order they were scheduled.
+    queue.schedule(Time(20), true).unwrap();
+    *time.borrow_mut() = Time(25);
+    queue.update().unwrap();
+    assert!(queue.is_empty());
+    assert!(latch.borrow().get().unwrap().unwrap().value);
+    queue.schedule(Time(100), false).unwrap();
+    queue.clear();
+    assert_eq!(queue.next_time(), None);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn setpoint_ramp() {
+    let time = rc_ref_cell_reference(Time(0));
+    let mut ramp = SetpointRamp::<_, ()>::new(time.clone(), 0.0, 10.0, None);
+    assert_eq!(ramp.get(), Ok(None));
+    assert!(ramp.is_at_target());
+    ramp.set(12.0).unwrap();
+    assert!(!ramp.is_at_target());
+    ramp.update().unwrap();
+    assert_eq!(ramp.get(), Ok(Some(Datum::new(Time(0), 0.0))));
+    let mut expected = [5.0, 10.0, 12.0, 12.0].into_iter();
+    for i in 1..5 {
+        *time.borrow_mut() = Time(i * 500_000_000);
+        ramp.update().unwrap();
+        assert_eq!(ramp.get().unwrap().unwrap().value, expected.next().unwrap());
+    }
+    assert!(ramp.is_at_target());
+    ramp.set(0.0).unwrap();
+    *time.borrow_mut() = Time(2_500_000_000);
+    ramp.update().unwrap();
+    assert_eq!(ramp.get().unwrap().unwrap().value, 7.0);
+    assert_eq!(ramp.get_velocity(), -10.0);
+    assert!(!ramp.is_at_target());
+    ramp.reset(3.0);
+    assert!(ramp.is_at_target());
+    assert_eq!(ramp.get_target(), 3.0);
+    assert_eq!(ramp.get().unwrap().unwrap().value, 3.0);
+
+    let time = rc_ref_cell_reference(Time(0));
+    let mut ramp = SetpointRamp::<_, ()>::new(time.clone(), 0.0, 10.0, Some(20.0));
+    ramp.set(10.0).unwrap();
+    ramp.update().unwrap();
+    let mut expected = [1.25, 3.75, 6.25, 8.75, 10.0].into_iter();
+    for i in 1..6 {
+        *time.borrow_mut() = Time(i * 250_000_000);
+        ramp.update().unwrap();
+        assert_eq!(ramp.get().unwrap().unwrap().value, expected.next().unwrap());
+    }
+    assert_eq!(ramp.get_velocity(), 0.0);
+
+    //Stopping right at a target that is too close would take more than the maximum acceleration,
+    //so the value overshoots instead.
+    let time = rc_ref_cell_reference(Time(0));
+    let mut ramp = SetpointRamp::<_, ()>::new(time.clone(), 0.0, 20.0, Some(20.0));
+    ramp.set(100.0).unwrap();
+    ramp.update().unwrap();
+    for i in 1..5 {
+        *time.borrow_mut() = Time(i * 250_000_000);
+        ramp.update().unwrap();
+    }
+    assert_eq!(ramp.get().unwrap().unwrap().value, 12.5);
+    assert_eq!(ramp.get_velocity(), 20.0);
+    ramp.set(13.0).unwrap();
+    *time.borrow_mut() = Time(1_250_000_000);
+    ramp.update().unwrap();
+    assert_eq!(ramp.get().unwrap().unwrap().value, 16.25);
+    assert_eq!(ramp.get_velocity(), 15.0);
+    for i in 6..40 {
+        *time.borrow_mut() = Time(i * 250_000_000);
+        ramp.update().unwrap();
+    }
+    assert!(ramp.is_at_target());
+}
+#[test]
+#[should_panic]
+#[cfg(feature = "alloc")]
+fn setpoint_ramp_zero_acceleration() {
+    let _ = SetpointRamp::<_, ()>::new(rc_ref_cell_reference(Time(0)), 0.0, 10.0, Some(0.0));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn one_shot_after() {
+    let time = rc_ref_cell_reference(Time(100));
+    let mut trigger = OneShotAfter::<_, ()>::new(time.clone(), Time(20));
+    assert_eq!(trigger.get(), Ok(None));
+    let mut fired = Vec::new();
+    for i in 0..5 {
+        *time.borrow_mut() = Time(100 + i * 10);
+        trigger.update().unwrap();
+        fired.push(trigger.get().unwrap().unwrap().value);
+    }
+    assert_eq!(fired, [false, false, true, false, false]);
+    assert!(trigger.has_fired());
+    trigger.reset();
+    assert!(!trigger.has_fired());
+    trigger.update().unwrap();
+    assert_eq!(trigger.get(), Ok(Some(Datum::new(Time(140), false))));
+    *time.borrow_mut() = Time(160);
+    trigger.update().unwrap();
+    assert_eq!(trigger.get(), Ok(Some(Datum::new(Time(160), true))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn periodic_trigger() {
+    let time = rc_ref_cell_reference(Time(0));
+    let mut trigger = PeriodicTrigger::<_, ()>::new(time.clone(), Time(20));
+    let mut fired = Vec::new();
+    for i in [0, 10, 20, 30, 45, 50, 60, 130, 135, 140] {
+        *time.borrow_mut() = Time(i);
+        trigger.update().unwrap();
+        fired.push(trigger.get().unwrap().unwrap().value);
+    }
+    //Fires at 20, 45 (for 40), and 60. At 130, the periods at 80, 100, and 120 only fire once, and
+    //the next one is at 140.
+    assert_eq!(
+        fired,
+        [false, false, true, false, true, false, true, true, false, true]
+    );
+    trigger.reset();
+    assert_eq!(trigger.get(), Ok(None));
+    trigger.update().unwrap();
+    *time.borrow_mut() = Time(160);
+    trigger.update().unwrap();
+    assert_eq!(trigger.get(), Ok(Some(Datum::new(Time(160), true))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn uptime_getter_and_cycle_counter() {
+    let time = rc_ref_cell_reference(Time(1000));
+    let uptime = UptimeGetter::<_, ()>::new(time.clone()).unwrap();
+    let uptime = rc_ref_cell_reference(uptime);
+    let mut counter = CycleCounter::new(uptime.clone());
+    assert_eq!(counter.get(), Ok(None));
+    for i in 1..=3 {
+        *time.borrow_mut() = Time(1000 + i * 10);
+        counter.update().unwrap();
+    }
+    assert_eq!(counter.get(), Ok(Some(Datum::new(Time(30), 3))));
+    assert_eq!(uptime.borrow().get_start(), Time(1000));
+    uptime.borrow_mut().reset().unwrap();
+    counter.reset();
+    assert_eq!(counter.get_count(), 0);
+    assert_eq!(TimeGetter::get(&*uptime.borrow()), Ok(Time(0)));
+    *time.borrow_mut() = Time(1045);
+    counter.update().unwrap();
+    assert_eq!(counter.get(), Ok(Some(Datum::new(Time(15), 1))));
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn soft_start() {
+    let time = rc_ref_cell_reference(Time(0));
+    let motor = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let mut soft_start = SoftStart::new(motor.clone(), time.clone(), Time(100));
+    let mut outputs = Vec::new();
+    soft_start.set(0.8).unwrap();
+    for i in 0..4 {
+        *time.borrow_mut() = Time(i * 50);
+        soft_start.update().unwrap();
+        outputs.push(motor.borrow().get().unwrap().unwrap().value);
+    }
+    assert_eq!(outputs, [0.0, 0.4, 0.8, 0.8]);
+    assert!(!soft_start.is_ramping());
+    //Going through zero starts a new ramp.
+    soft_start.set(0.0).unwrap();
+    soft_start.update().unwrap();
+    assert_eq!(motor.borrow().get().unwrap().unwrap().value, 0.0);
+    soft_start.set(-0.8).unwrap();
+    soft_start.update().unwrap();
+    *time.borrow_mut() = Time(175);
+    soft_start.update().unwrap();
+    assert!(soft_start.is_ramping());
+    assert_eq!(motor.borrow().get().unwrap().unwrap().value, -0.2);
+    //So does enabling.
+    soft_start.disable();
+    soft_start.update().unwrap();
+    assert_eq!(motor.borrow().get().unwrap().unwrap().value, 0.0);
+    soft_start.enable();
+    soft_start.update().unwrap();
+    assert_eq!(motor.borrow().get().unwrap().unwrap().value, 0.0);
+    *time.borrow_mut() = Time(225);
+    soft_start.update().unwrap();
+    assert_eq!(motor.borrow().get().unwrap().unwrap().value, -0.4);
+}
+#[test]
+#[cfg(feature = "alloc")]
+fn enable_line() {
+    let time = rc_ref_cell_reference(Time(0));
+    let line = rc_ref_cell_reference(EnableLine::<()>::new());
+    let motor1 = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let motor2 = rc_ref_cell_reference(ConstantGetter::<f32, _, ()>::new(time.clone(), 0.0));
+    let mut gate1 = EnableLineGate::new(motor1.clone(), line.clone(), 0.0);
+    let mut gate2 = EnableLineGate::new(motor2.clone(), line.clone(), 0.0);
+    gate1.set(0.5).unwrap();
+    gate2.set(-0.25).unwrap();
+    gate1.update().unwrap();
+    gate2.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.5);
+    assert_eq!(motor2.borrow().get().unwrap().unwrap().value, -0.25);
+    line.borrow_mut().disable();
+    gate1.update().unwrap();
+    gate2.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.0);
+    assert_eq!(motor2.borrow().get().unwrap().unwrap().value, 0.0);
+    line.borrow_mut().enable();
+    //The request from before disabling must not be sent again.
+    gate1.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.0);
+    gate1.set(0.5).unwrap();
+    gate1.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.5);
+
+    line.borrow_mut()
+        .set_watchdog(Time(100), to_dyn!(TimeGetter<()>, time.clone()));
+    assert!(line.borrow().is_tripped());
+    line.borrow_mut().feed().unwrap();
+    *time.borrow_mut() = Time(100);
+    assert!(line.borrow().is_live());
+    gate1.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.5);
+    *time.borrow_mut() = Time(101);
+    assert!(line.borrow().is_enabled());
+    assert!(!line.borrow().is_live());
+    gate1.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.0);
+    //Feeding does not clear the trip until the watchdog is reset.
+    line.borrow_mut().feed().unwrap();
+    assert!(line.borrow().is_tripped());
+    line.borrow_mut().reset_watchdog();
+    assert!(line.borrow().is_tripped());
+    line.borrow_mut().feed().unwrap();
+    assert!(line.borrow().is_live());
+    gate1.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.0);
+    gate1.set(0.5).unwrap();
+    gate1.update().unwrap();
+    assert_eq!(motor1.borrow().get().unwrap().unwrap().value, 0.5);
+    line.borrow_mut().clear_watchdog();
+    assert!(!line.borrow().is_tripped());
+}